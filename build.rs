@@ -0,0 +1,5 @@
+fn main() {
+    // sets up the linker flags Node.js addons need on macOS and Windows
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
@@ -0,0 +1,442 @@
+//! Architecture rules checking the project references against layers defined by patterns, like
+//! ArchUnit at the project level.
+
+use crate::{
+    graph::DependencyGraph,
+    validation::{referenced_project, validate, Rule, Severity, Violation},
+    workspace::Workspace,
+    Project,
+};
+
+/// The rule id used for violations about forbidden project references.
+pub const ARCHITECTURE_RULE_ID: &str = "DL0201";
+
+/// Represents a pattern matching projects by name or by path.
+///
+/// `*` matches any sequence of characters and `?` matches a single character, ignoring case.
+/// Patterns containing a `/` are matched against the project path with forward slashes (ex:
+/// `*/src/Web/*`), other patterns are matched against the project name (ex: `*.Domain`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pattern {
+    value: String,
+}
+
+/// Represents a constraint forbidding the projects matching `from` to reference the projects
+/// matching `to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Constraint {
+    from: Pattern,
+    to: Pattern,
+}
+
+/// A validation rule reporting the project references that break architecture constraints.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{
+///     architecture::ArchitectureRule,
+///     validation::{validate, Rule},
+/// };
+///
+/// let mut rule = ArchitectureRule::new();
+///
+/// // the domain must not reference the web layer
+/// rule.forbid("*.Domain", "*.Web");
+///
+/// // each layer may only reference the layers below it
+/// rule.add_layers(&["*.Web", "*.Application", "*.Infrastructure"]);
+///
+/// let rules: Vec<Box<dyn Rule>> = vec![Box::new(rule)];
+/// let violations = validate(&[], &rules);
+///
+/// assert!(violations.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArchitectureRule {
+    constraints: Vec<Constraint>,
+    severity: Severity,
+}
+
+impl Pattern {
+    /// Creates a new `Pattern`.
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+        }
+    }
+
+    /// Returns the pattern as written.
+    pub fn value(&self) -> &String {
+        &self.value
+    }
+
+    /// Returns `true` if the pattern matches the whole `text`, ignoring case.
+    pub fn matches(&self, text: &str) -> bool {
+        let pattern: Vec<char> = self.value.to_lowercase().chars().collect();
+        let text: Vec<char> = text.to_lowercase().chars().collect();
+
+        let (mut p, mut t) = (0, 0);
+        // the position after the last `*` and the text position it is currently matching
+        let mut backtrack: Option<(usize, usize)> = None;
+
+        while t < text.len() {
+            match pattern.get(p) {
+                Some('*') => {
+                    backtrack = Some((p + 1, t));
+                    p += 1;
+                }
+                Some(c) if *c == '?' || *c == text[t] => {
+                    p += 1;
+                    t += 1;
+                }
+                _ => match backtrack {
+                    Some((star, matched)) => {
+                        p = star;
+                        t = matched + 1;
+                        backtrack = Some((star, matched + 1));
+                    }
+                    None => return false,
+                },
+            }
+        }
+
+        pattern[p..].iter().all(|c| *c == '*')
+    }
+
+    /// Returns `true` if the pattern matches the project.
+    pub fn matches_project(&self, project: &Project) -> bool {
+        if self.value.contains('/') {
+            self.matches(&project.path().to_string_lossy().replace('\\', "/"))
+        } else {
+            self.matches(project.name())
+        }
+    }
+}
+
+impl Constraint {
+    /// Creates a new `Constraint`.
+    pub fn new(from: Pattern, to: Pattern) -> Self {
+        Self { from, to }
+    }
+
+    /// Returns the pattern of the referencing projects.
+    pub fn from(&self) -> &Pattern {
+        &self.from
+    }
+
+    /// Returns the pattern of the projects that must not be referenced.
+    pub fn to(&self) -> &Pattern {
+        &self.to
+    }
+}
+
+impl ArchitectureRule {
+    /// Creates a new `ArchitectureRule` without constraints, reporting errors.
+    pub fn new() -> Self {
+        Self {
+            constraints: vec![],
+            severity: Severity::Error,
+        }
+    }
+
+    /// Returns the constraints of the rule.
+    pub fn constraints(&self) -> &Vec<Constraint> {
+        &self.constraints
+    }
+
+    /// Forbids the projects matching `from` to reference the projects matching `to`.
+    pub fn forbid(&mut self, from: &str, to: &str) {
+        self.constraints
+            .push(Constraint::new(Pattern::new(from), Pattern::new(to)));
+    }
+
+    /// Adds layers, ordered from the top to the bottom, where each layer may only reference the
+    /// layers below it.
+    pub fn add_layers(&mut self, layers: &[&str]) {
+        for (index, lower) in layers.iter().enumerate() {
+            for upper in &layers[..index] {
+                self.forbid(lower, upper);
+            }
+        }
+    }
+
+    /// Returns the severity of the violations.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Sets the severity of the violations.
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.severity = severity;
+    }
+}
+
+impl Default for ArchitectureRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for ArchitectureRule {
+    fn id(&self) -> &'static str {
+        ARCHITECTURE_RULE_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Project reference breaks an architecture constraint"
+    }
+
+    fn check(&self, projects: &[Project]) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for project in projects {
+            for reference in project.project_references() {
+                let Some(target) = referenced_project(projects, project, reference) else {
+                    continue;
+                };
+
+                let constraint = self.constraints.iter().find(|constraint| {
+                    constraint.from.matches_project(project)
+                        && constraint.to.matches_project(target)
+                });
+
+                if let Some(constraint) = constraint {
+                    violations.push(Violation::new(
+                        self.id().to_string(),
+                        self.severity,
+                        format!(
+                            "project '{}' must not reference '{}' ('{}' must not reference '{}')",
+                            project.name(),
+                            target.name(),
+                            constraint.from.value,
+                            constraint.to.value
+                        ),
+                        project.path().clone(),
+                    ));
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+/// Returns the shortest chain of project references from each project matching `from` to each
+/// project matching `to`, as project names. Returns an empty list if no project matching `from`
+/// depends on a project matching `to`, directly or transitively.
+pub fn find_dependencies(workspace: &Workspace, from: &str, to: &str) -> Vec<Vec<String>> {
+    let (from, to) = (Pattern::new(from), Pattern::new(to));
+    let graph = DependencyGraph::from_workspace(workspace, false);
+
+    let matching = |pattern: &Pattern| -> Vec<usize> {
+        (0..graph.nodes().len())
+            .filter(|index| {
+                graph
+                    .node(*index)
+                    .path()
+                    .and_then(|path| workspace.project_by_path(path))
+                    .is_some_and(|project| pattern.matches_project(project))
+            })
+            .collect()
+    };
+
+    let targets = matching(&to);
+    let mut chains = vec![];
+
+    for source in matching(&from) {
+        for target in targets.iter().filter(|target| **target != source) {
+            if let Some(path) = graph.why(source, *target) {
+                chains.push(
+                    path.iter()
+                        .map(|index| graph.node(*index).name().clone())
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    chains
+}
+
+/// Asserts that no project matching `from` depends on a project matching `to`, directly or
+/// transitively, so architecture rules can be written as Rust tests next to the code.
+///
+/// # Panics
+///
+/// Panics with the offending chains of references if a project matching `from` depends on a
+/// project matching `to`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{architecture::assert_no_dependency, workspace::Workspace};
+///
+/// #[test]
+/// fn domain_does_not_depend_on_infrastructure() {
+///     let workspace = Workspace::load("path/to/repository").unwrap();
+///
+///     assert_no_dependency(&workspace, "*.Domain", "*.Infrastructure");
+/// }
+/// ```
+#[track_caller]
+pub fn assert_no_dependency(workspace: &Workspace, from: &str, to: &str) {
+    let chains = find_dependencies(workspace, from, to);
+
+    if !chains.is_empty() {
+        let chains: Vec<String> = chains
+            .iter()
+            .map(|chain| format!("  {}", chain.join(" -> ")))
+            .collect();
+
+        panic!(
+            "'{}' must not depend on '{}', but:\n{}",
+            from,
+            to,
+            chains.join("\n")
+        );
+    }
+}
+
+/// Asserts that the workspace does not contain projects referencing each other in a cycle.
+///
+/// # Panics
+///
+/// Panics with the projects of every cycle found.
+#[track_caller]
+pub fn assert_no_cycles(workspace: &Workspace) {
+    let graph = DependencyGraph::from_workspace(workspace, false);
+    let cycles: Vec<String> = graph
+        .cyclic_components()
+        .iter()
+        .map(|component| {
+            let names: Vec<&str> = component
+                .iter()
+                .map(|index| graph.node(*index).name().as_str())
+                .collect();
+
+            format!("  {}", names.join(", "))
+        })
+        .collect();
+
+    if !cycles.is_empty() {
+        panic!(
+            "the workspace must not contain cycles, but these projects reference each other:\n{}",
+            cycles.join("\n")
+        );
+    }
+}
+
+/// Asserts that the workspace does not break any constraint of an [`ArchitectureRule`].
+///
+/// # Panics
+///
+/// Panics with the message of every violation found.
+#[track_caller]
+pub fn assert_rule(workspace: &Workspace, rule: &ArchitectureRule) {
+    let rules: Vec<Box<dyn Rule>> = vec![Box::new(rule.clone())];
+    let messages: Vec<String> = validate(workspace.projects(), &rules)
+        .iter()
+        .map(|violation| format!("  {}", violation.message()))
+        .collect();
+
+    if !messages.is_empty() {
+        panic!(
+            "the workspace breaks architecture constraints:\n{}",
+            messages.join("\n")
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{graph::test::project, validation::Rule, workspace::Workspace};
+
+    use super::{assert_no_dependency, find_dependencies, ArchitectureRule, Pattern};
+
+    #[test]
+    pub fn match_patterns() {
+        assert!(Pattern::new("*.Domain").matches("Shop.domain"));
+        assert!(Pattern::new("Shop.*.Api").matches("Shop.Orders.Api"));
+        assert!(Pattern::new("Shop.?").matches("Shop.A"));
+        assert!(Pattern::new("*").matches(""));
+        assert!(!Pattern::new("*.Domain").matches("Shop.Domain.Tests"));
+        assert!(!Pattern::new("Shop.?").matches("Shop.AB"));
+    }
+
+    #[test]
+    pub fn report_forbidden_references() {
+        // given
+        let projects = vec![
+            project(
+                "/repo/src/Shop.Web/Shop.Web.csproj",
+                &["../Shop.Domain/Shop.Domain.csproj"],
+                &[],
+            ),
+            project(
+                "/repo/src/Shop.Domain/Shop.Domain.csproj",
+                &[
+                    "../Shop.Web/Shop.Web.csproj",
+                    "../Shop.Data/Shop.Data.csproj",
+                ],
+                &[],
+            ),
+            project("/repo/src/Shop.Data/Shop.Data.csproj", &[], &[]),
+        ];
+
+        let mut rule = ArchitectureRule::new();
+        rule.forbid("*.Domain", "*/src/Shop.Web/*");
+        rule.add_layers(&["*.Data", "*.Domain"]);
+
+        // when
+        let violations = rule.check(&projects);
+
+        // then
+        let messages: Vec<&String> = violations.iter().map(|v| v.message()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "project 'Shop.Domain' must not reference 'Shop.Web' ('*.Domain' must not reference '*/src/Shop.Web/*')",
+                "project 'Shop.Domain' must not reference 'Shop.Data' ('*.Domain' must not reference '*.Data')",
+            ]
+        );
+    }
+
+    #[test]
+    pub fn find_transitive_dependencies() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/Web/Web.csproj", &["../App/App.csproj"], &[]),
+                project("/repo/App/App.csproj", &["../Domain/Domain.csproj"], &[]),
+                project("/repo/Domain/Domain.csproj", &[], &[]),
+            ],
+        );
+
+        // when
+        let chains = find_dependencies(&workspace, "Web", "Dom*");
+
+        // then
+        assert_eq!(chains, vec![vec!["Web", "App", "Domain"]]);
+        assert_no_dependency(&workspace, "Domain", "*");
+    }
+
+    #[test]
+    #[should_panic(expected = "'App' must not depend on 'Domain', but:\n  App -> Domain")]
+    pub fn assert_no_dependency_panics() {
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/App/App.csproj", &["../Domain/Domain.csproj"], &[]),
+                project("/repo/Domain/Domain.csproj", &[], &[]),
+            ],
+        );
+
+        assert_no_dependency(&workspace, "App", "Domain");
+    }
+}
@@ -0,0 +1,243 @@
+//! Loading of the projects stored in `.zip`, `.tar` and `.tar.gz` archives, such as source
+//! snapshots or build artifacts, without extracting them.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek},
+    path::{Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use thiserror::Error;
+use zip::{result::ZipError, ZipArchive};
+
+use crate::{
+    condition::Properties, intern::Interner, parser, parser::ParseError, search, vfs::OsFileSystem,
+    workspace::Workspace, Project,
+};
+
+/// Loads the projects of an archive file, choosing the format from its extension (`.zip`,
+/// `.tar`, `.tar.gz` or `.tgz`).
+///
+/// The projects keep their path inside the archive and the workspace root is `.`. Project
+/// files in the directories skipped by [`search::search_projects`] (ex: `bin`, `obj`) are
+/// ignored.
+///
+/// # Errors
+///
+/// This function will return an error if the archive could not be read, if its format is not
+/// supported or if one of the projects could not be parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::archive;
+///
+/// let workspace = archive::load("snapshot.tar.gz")?;
+///
+/// for project in workspace.projects() {
+///     println!("{}: {:?}", project.name(), project.path());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn load<P>(path: P) -> Result<Workspace, ArchiveError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let file = BufReader::new(File::open(path)?);
+
+    if name.ends_with(".zip") {
+        read_zip(file)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        read_tar_gz(file)
+    } else if name.ends_with(".tar") {
+        read_tar(file)
+    } else {
+        Err(ArchiveError::UnsupportedFormat(path.to_path_buf()))
+    }
+}
+
+/// Loads the projects of a `.zip` archive.
+///
+/// See [`load`] for more details.
+pub fn read_zip<R>(reader: R) -> Result<Workspace, ArchiveError>
+where
+    R: Read + Seek,
+{
+    let mut archive = ZipArchive::new(reader)?;
+    let strings = Interner::new();
+    let mut projects = vec![];
+
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+
+        let Some(path) = entry.enclosed_name() else {
+            continue;
+        };
+
+        if entry.is_file() && search::is_project_file(&path) {
+            projects.push(parse_entry(entry, path, &strings)?);
+        }
+    }
+
+    Ok(workspace(projects))
+}
+
+/// Loads the projects of a `.tar` archive.
+///
+/// See [`load`] for more details.
+pub fn read_tar<R>(reader: R) -> Result<Workspace, ArchiveError>
+where
+    R: Read,
+{
+    let mut archive = tar::Archive::new(reader);
+    let strings = Interner::new();
+    let mut projects = vec![];
+
+    for entry in archive.entries()? {
+        let entry = entry?;
+        let path = entry.path()?.to_path_buf();
+
+        if entry.header().entry_type().is_file() && search::is_project_file(&path) {
+            projects.push(parse_entry(entry, path, &strings)?);
+        }
+    }
+
+    Ok(workspace(projects))
+}
+
+/// Loads the projects of a `.tar.gz` archive.
+///
+/// See [`load`] for more details.
+pub fn read_tar_gz<R>(reader: R) -> Result<Workspace, ArchiveError>
+where
+    R: Read,
+{
+    read_tar(GzDecoder::new(reader))
+}
+
+fn parse_entry<R>(entry: R, path: PathBuf, strings: &Interner) -> Result<Project, ArchiveError>
+where
+    R: Read,
+{
+    parser::parse_in(entry, &path, &Properties::new(), &OsFileSystem, strings)
+        .map_err(|source| ArchiveError::ParseError { path, source })
+}
+
+fn workspace(mut projects: Vec<Project>) -> Workspace {
+    projects.sort_by(|a, b| a.path().cmp(b.path()));
+
+    Workspace::from_projects(".", projects)
+}
+
+/// Represents errors that can occur while loading the projects of an archive.
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    /// An I/O error occurred while reading the archive.
+    #[error("there was an error while reading the archive")]
+    IoError(#[from] io::Error),
+    /// The zip archive is invalid.
+    #[error("the zip archive could not be read")]
+    ZipError(#[from] ZipError),
+    /// The extension of the archive is not a supported format.
+    #[error("the archive {0:?} is not a .zip, .tar, .tar.gz or .tgz file")]
+    UnsupportedFormat(PathBuf),
+    /// A project of the archive could not be parsed.
+    #[error("the project {path:?} could not be parsed")]
+    ParseError {
+        path: PathBuf,
+        #[source]
+        source: ParseError,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::GzEncoder, Compression};
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::{read_tar_gz, read_zip};
+
+    const API: &str = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="..\Domain\Domain.csproj" />
+  </ItemGroup>
+</Project>"#;
+
+    const DOMAIN: &str = r#"<Project Sdk="Microsoft.NET.Sdk" />"#;
+
+    #[test]
+    pub fn read_projects_from_zip() {
+        // given
+        let mut writer = ZipWriter::new(Cursor::new(vec![]));
+
+        for (path, content) in [
+            ("repo/src/Api/Api.csproj", API),
+            ("repo/src/Domain/Domain.csproj", DOMAIN),
+            ("repo/src/Api/obj/Api.csproj", DOMAIN),
+            ("repo/README.md", "# repo"),
+        ] {
+            writer
+                .start_file(path, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+
+        let archive = writer.finish().unwrap();
+
+        // when
+        let workspace = read_zip(archive).unwrap();
+
+        // then
+        let ids: Vec<String> = workspace
+            .projects()
+            .iter()
+            .map(|project| workspace.relative_path(project.path()))
+            .collect();
+
+        assert_eq!(
+            ids,
+            vec!["repo/src/Api/Api.csproj", "repo/src/Domain/Domain.csproj"]
+        );
+
+        let api = &workspace.projects()[0];
+        assert!(workspace
+            .resolve_reference(api, &api.project_references()[0])
+            .is_some());
+    }
+
+    #[test]
+    pub fn read_projects_from_tar_gz() {
+        // given
+        let mut builder = tar::Builder::new(GzEncoder::new(vec![], Compression::default()));
+
+        for (path, content) in [("Api/Api.csproj", API), ("Domain/Domain.csproj", DOMAIN)] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+
+        // when
+        let workspace = read_tar_gz(Cursor::new(archive)).unwrap();
+
+        // then
+        let names: Vec<&String> = workspace.projects().iter().map(|p| p.name()).collect();
+        assert_eq!(names, vec!["Api", "Domain"]);
+    }
+}
@@ -0,0 +1,210 @@
+//! The properties of the assembly built by a project (`AssemblyName`, `AssemblyVersion`,
+//! `FileVersion` and `InformationalVersion`), and a report on the consistency of their versions
+//! across projects.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::Project;
+
+/// Represents the assembly properties of a project.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssemblyMetadata {
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    name: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    version: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    file_version: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    informational_version: Option<String>,
+}
+
+impl AssemblyMetadata {
+    /// Creates a new `AssemblyMetadata` without any property.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the name of the assembly (`AssemblyName`), if the project sets it.
+    pub fn name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    /// Sets the name of the assembly.
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    /// Returns the version of the assembly (`AssemblyVersion`), if any.
+    pub fn version(&self) -> Option<&String> {
+        self.version.as_ref()
+    }
+
+    /// Sets the version of the assembly.
+    pub fn set_version(&mut self, version: Option<String>) {
+        self.version = version;
+    }
+
+    /// Returns the version of the file (`FileVersion`), if any.
+    pub fn file_version(&self) -> Option<&String> {
+        self.file_version.as_ref()
+    }
+
+    /// Sets the version of the file.
+    pub fn set_file_version(&mut self, file_version: Option<String>) {
+        self.file_version = file_version;
+    }
+
+    /// Returns the informational version of the assembly (`InformationalVersion`), if any.
+    pub fn informational_version(&self) -> Option<&String> {
+        self.informational_version.as_ref()
+    }
+
+    /// Sets the informational version of the assembly.
+    pub fn set_informational_version(&mut self, informational_version: Option<String>) {
+        self.informational_version = informational_version;
+    }
+
+    /// Returns `true` if the project does not set any assembly property.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.version.is_none()
+            && self.file_version.is_none()
+            && self.informational_version.is_none()
+    }
+}
+
+/// Represents the versions stamped by the projects of a workspace, returned by
+/// [`crate::workspace::Workspace::version_stamping_report`].
+///
+/// Each map is keyed by version and holds the projects setting it.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionStamping {
+    assembly_versions: BTreeMap<String, Vec<PathBuf>>,
+    file_versions: BTreeMap<String, Vec<PathBuf>>,
+    informational_versions: BTreeMap<String, Vec<PathBuf>>,
+    unstamped: Vec<PathBuf>,
+}
+
+impl VersionStamping {
+    /// Creates the report of the versions stamped by `projects`.
+    pub fn new(projects: &[Project]) -> Self {
+        let mut report = Self::default();
+
+        for project in projects {
+            let assembly = project.assembly();
+
+            if assembly.version.is_none()
+                && assembly.file_version.is_none()
+                && assembly.informational_version.is_none()
+            {
+                report.unstamped.push(project.path().clone());
+                continue;
+            }
+
+            let versions = [
+                (&mut report.assembly_versions, assembly.version()),
+                (&mut report.file_versions, assembly.file_version()),
+                (
+                    &mut report.informational_versions,
+                    assembly.informational_version(),
+                ),
+            ];
+
+            for (map, version) in versions {
+                if let Some(version) = version {
+                    map.entry(version.clone())
+                        .or_default()
+                        .push(project.path().clone());
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Returns the projects by `AssemblyVersion`.
+    pub fn assembly_versions(&self) -> &BTreeMap<String, Vec<PathBuf>> {
+        &self.assembly_versions
+    }
+
+    /// Returns the projects by `FileVersion`.
+    pub fn file_versions(&self) -> &BTreeMap<String, Vec<PathBuf>> {
+        &self.file_versions
+    }
+
+    /// Returns the projects by `InformationalVersion`.
+    pub fn informational_versions(&self) -> &BTreeMap<String, Vec<PathBuf>> {
+        &self.informational_versions
+    }
+
+    /// Returns the projects that do not set any version, which get the default versions of the
+    /// SDK or the ones set by an imported file.
+    pub fn unstamped(&self) -> &Vec<PathBuf> {
+        &self.unstamped
+    }
+
+    /// Returns `true` if the projects that set each version all set the same one.
+    pub fn is_consistent(&self) -> bool {
+        self.assembly_versions.len() <= 1
+            && self.file_versions.len() <= 1
+            && self.informational_versions.len() <= 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Cursor, path::PathBuf};
+
+    use crate::parser::parse;
+
+    use super::VersionStamping;
+
+    #[test]
+    pub fn report_version_stamping() {
+        // given
+        let stamped = |version: &str| {
+            format!(
+                r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <AssemblyVersion>{version}</AssemblyVersion>
+    <FileVersion>{version}</FileVersion>
+    <InformationalVersion>{version}-beta</InformationalVersion>
+  </PropertyGroup>
+</Project>"#
+            )
+        };
+
+        let projects = vec![
+            parse(Cursor::new(stamped("1.0.0.0")), "Api.csproj").unwrap(),
+            parse(Cursor::new(stamped("1.1.0.0")), "Core.csproj").unwrap(),
+            parse(Cursor::new("<Project></Project>"), "Tools.csproj").unwrap(),
+        ];
+
+        // when
+        let report = VersionStamping::new(&projects);
+
+        // then
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report.assembly_versions()["1.1.0.0"],
+            vec![PathBuf::from("Core.csproj")]
+        );
+        assert_eq!(report.informational_versions().len(), 2);
+        assert_eq!(report.unstamped(), &vec![PathBuf::from("Tools.csproj")]);
+    }
+}
@@ -0,0 +1,384 @@
+//! Reading of the restore graph of a project (`obj/project.assets.json`) and comparison with the
+//! package references declared in the project file.
+
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    parser::ParseError,
+    version::{NuGetVersion, VersionRange},
+    Project,
+};
+
+/// The path of the restore graph, relative to the directory of the project.
+const ASSETS_FILE_PATH: &str = "obj/project.assets.json";
+
+/// Represents the restore graph written by `dotnet restore` in `project.assets.json`.
+///
+/// Only the packages are kept: project references are read from the project files.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ProjectAssets {
+    /// The resolved version of every package, by target framework and package name.
+    resolved: BTreeMap<String, BTreeMap<String, String>>,
+    /// The version range of the packages the restore was asked for, by target framework and
+    /// package name, including those added by imported files and the SDK.
+    requested: BTreeMap<String, BTreeMap<String, String>>,
+    /// The dependencies of every package, with their version ranges, by target framework and
+    /// package name.
+    #[serde(default)]
+    dependencies: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+impl ProjectAssets {
+    /// Parses the content of a `project.assets.json` file.
+    ///
+    /// The targets of a runtime identifier (ex: `net8.0/win-x64`) are ignored, since they
+    /// resolve the same packages as the target framework.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be read or is not a JSON
+    /// object.
+    pub fn parse<R>(reader: R) -> Result<Self, ParseError>
+    where
+        R: Read,
+    {
+        let document: Value =
+            serde_json::from_reader(reader).map_err(|_| ParseError::DeserializationError)?;
+
+        if !document.is_object() {
+            return Err(ParseError::DeserializationError);
+        }
+
+        let mut assets = Self::default();
+
+        for (target, libraries) in objects(&document, "targets") {
+            if target.contains('/') {
+                continue;
+            }
+
+            let packages = assets.resolved.entry(target.clone()).or_default();
+            let dependencies = assets.dependencies.entry(target.clone()).or_default();
+
+            for (library, metadata) in libraries.as_object().into_iter().flatten() {
+                let is_package = metadata.get("type").and_then(Value::as_str) == Some("package");
+
+                if let (true, Some((name, version))) = (is_package, library.split_once('/')) {
+                    packages.insert(name.to_string(), version.to_string());
+
+                    let ranges: BTreeMap<String, String> = objects(metadata, "dependencies")
+                        .filter_map(|(dependency, range)| {
+                            Some((dependency.clone(), range.as_str()?.to_string()))
+                        })
+                        .collect();
+
+                    if !ranges.is_empty() {
+                        dependencies.insert(name.to_string(), ranges);
+                    }
+                }
+            }
+        }
+
+        let frameworks = document.get("project").unwrap_or(&Value::Null);
+
+        for (framework, metadata) in objects(frameworks, "frameworks") {
+            let packages = assets.requested.entry(framework.clone()).or_default();
+
+            for (name, dependency) in objects(metadata, "dependencies") {
+                if let Some(version) = dependency.get("version").and_then(Value::as_str) {
+                    packages.insert(name.clone(), version.to_string());
+                }
+            }
+        }
+
+        Ok(assets)
+    }
+
+    /// Reads a `project.assets.json` file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn load<P>(path: P) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse(File::open(path)?)
+    }
+
+    /// Returns the path of the restore graph of the project, if it was restored.
+    pub fn find(project: &Project) -> Option<PathBuf> {
+        let path = project
+            .path()
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(ASSETS_FILE_PATH);
+
+        path.is_file().then_some(path)
+    }
+
+    /// Reads the restore graph of the project (see [`ProjectAssets::find`]), or returns `None`
+    /// if it was not restored.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn for_project(project: &Project) -> Result<Option<Self>, ParseError> {
+        Self::find(project).map(Self::load).transpose()
+    }
+
+    /// Returns the target frameworks of the restore graph.
+    pub fn target_frameworks(&self) -> impl Iterator<Item = &String> {
+        self.resolved.keys()
+    }
+
+    /// Returns the resolved version of every package for a target framework, by package name.
+    pub fn resolved(&self, target_framework: &str) -> Option<&BTreeMap<String, String>> {
+        self.resolved.get(target_framework)
+    }
+
+    /// Returns the version range of every package requested for a target framework, by package
+    /// name.
+    pub fn requested(&self, target_framework: &str) -> Option<&BTreeMap<String, String>> {
+        self.requested.get(target_framework)
+    }
+
+    /// Returns the version range of every dependency of a resolved package for a target
+    /// framework, by dependency name. Returns `None` for packages without dependencies.
+    pub fn dependencies(
+        &self,
+        target_framework: &str,
+        package: &str,
+    ) -> Option<&BTreeMap<String, String>> {
+        self.dependencies.get(target_framework)?.get(package)
+    }
+}
+
+/// Represents how a package differs between the project file and its restore graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DiscrepancyKind {
+    /// The package is requested by an imported file or the SDK, not by the project file.
+    Implicit,
+    /// The package is only restored as a dependency of other packages or projects.
+    Transitive,
+    /// The restored version is higher than the declared one.
+    Upgraded,
+    /// The restored version is lower than the declared one (NU1605).
+    Downgraded,
+}
+
+/// Represents a package that differs between the project file and its restore graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestoreDiscrepancy {
+    target_framework: String,
+    package: String,
+    kind: DiscrepancyKind,
+    declared: Option<String>,
+    restored: String,
+}
+
+impl RestoreDiscrepancy {
+    /// Returns the target framework of the restore graph.
+    pub fn target_framework(&self) -> &String {
+        &self.target_framework
+    }
+
+    /// Returns the name of the package.
+    pub fn package(&self) -> &String {
+        &self.package
+    }
+
+    /// Returns how the package differs.
+    pub fn kind(&self) -> DiscrepancyKind {
+        self.kind
+    }
+
+    /// Returns the version declared in the project file, if the package is declared there.
+    pub fn declared(&self) -> Option<&String> {
+        self.declared.as_ref()
+    }
+
+    /// Returns the restored version.
+    pub fn restored(&self) -> &String {
+        &self.restored
+    }
+}
+
+/// Compares the package references of the project with its restore graph, for every target
+/// framework, and returns the restored packages that are not declared and the declared packages
+/// restored with another version.
+///
+/// Packages are compared ignoring case. Declared versions that are not valid versions or ranges
+/// (ex: an unexpanded `$(SerilogVersion)`) are not compared.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{assets::{discrepancies, ProjectAssets}, Project};
+///
+/// let project = Project::new("src/Api/Api.csproj")?;
+///
+/// if let Some(assets) = ProjectAssets::for_project(&project)? {
+///     for discrepancy in discrepancies(&project, &assets) {
+///         println!("{}: {:?}", discrepancy.package(), discrepancy.kind());
+///     }
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn discrepancies(project: &Project, assets: &ProjectAssets) -> Vec<RestoreDiscrepancy> {
+    let mut discrepancies = vec![];
+
+    for (target_framework, resolved) in &assets.resolved {
+        let requested = assets.requested(target_framework);
+
+        for (package, restored) in resolved {
+            let declared = project
+                .package_references_for(target_framework)
+                .find(|reference| reference.name().eq_ignore_ascii_case(package));
+
+            let kind = match declared {
+                Some(declared) => compare(declared.version(), restored),
+                None if requested.is_some_and(|requested| contains(requested, package)) => {
+                    Some(DiscrepancyKind::Implicit)
+                }
+                None => Some(DiscrepancyKind::Transitive),
+            };
+
+            if let Some(kind) = kind {
+                discrepancies.push(RestoreDiscrepancy {
+                    target_framework: target_framework.clone(),
+                    package: package.clone(),
+                    kind,
+                    declared: declared.map(|declared| declared.version().to_string()),
+                    restored: restored.clone(),
+                });
+            }
+        }
+    }
+
+    discrepancies
+}
+
+/// Returns how the restored version differs from the lower bound of the declared range.
+fn compare(declared: &str, restored: &str) -> Option<DiscrepancyKind> {
+    let range: VersionRange = declared.parse().ok()?;
+    let restored: NuGetVersion = restored.parse().ok()?;
+
+    match restored.cmp(range.min()?) {
+        Ordering::Less => Some(DiscrepancyKind::Downgraded),
+        Ordering::Greater => Some(DiscrepancyKind::Upgraded),
+        Ordering::Equal => None,
+    }
+}
+
+fn contains(packages: &BTreeMap<String, String>, name: &str) -> bool {
+    packages
+        .keys()
+        .any(|package| package.eq_ignore_ascii_case(name))
+}
+
+/// Returns the entries of the object `key` of `value`, or nothing if it is not an object.
+fn objects<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    value
+        .get(key)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::parser::parse;
+
+    use super::{discrepancies, DiscrepancyKind, ProjectAssets};
+
+    #[test]
+    pub fn compare_declared_and_restored_packages() {
+        // given
+        let project = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.0.0" />
+    <PackageReference Include="Polly" Version="8.4.1" />
+    <PackageReference Include="Dapper" Version="2.1.35" />
+  </ItemGroup>
+</Project>"#;
+        let assets = r#"{
+  "version": 3,
+  "targets": {
+    "net8.0": {
+      "Dapper/2.1.35": { "type": "package" },
+      "Polly/8.2.0": { "type": "package" },
+      "Polly.Core/8.2.0": { "type": "package" },
+      "serilog/3.1.1": { "type": "package" },
+      "StyleCop.Analyzers/1.1.118": { "type": "package" },
+      "Core/1.0.0": { "type": "project" }
+    },
+    "net8.0/win-x64": {
+      "Dapper/2.1.35": { "type": "package" }
+    }
+  },
+  "project": {
+    "frameworks": {
+      "net8.0": {
+        "dependencies": {
+          "Dapper": { "target": "Package", "version": "[2.1.35, )" },
+          "StyleCop.Analyzers": { "target": "Package", "version": "[1.1.118, )" }
+        }
+      }
+    }
+  }
+}"#;
+
+        let project = parse(Cursor::new(project), "Api.csproj").unwrap();
+        let assets = ProjectAssets::parse(Cursor::new(assets)).unwrap();
+
+        // when
+        let discrepancies = discrepancies(&project, &assets);
+
+        // then
+        let summary: Vec<(&str, DiscrepancyKind, Option<&str>, &str)> = discrepancies
+            .iter()
+            .map(|d| {
+                (
+                    d.package().as_str(),
+                    d.kind(),
+                    d.declared().map(String::as_str),
+                    d.restored().as_str(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Polly", DiscrepancyKind::Downgraded, Some("8.4.1"), "8.2.0"),
+                ("Polly.Core", DiscrepancyKind::Transitive, None, "8.2.0"),
+                (
+                    "StyleCop.Analyzers",
+                    DiscrepancyKind::Implicit,
+                    None,
+                    "1.1.118"
+                ),
+                ("serilog", DiscrepancyKind::Upgraded, Some("3.0.0"), "3.1.1"),
+            ]
+        );
+        assert_eq!(
+            assets.target_frameworks().collect::<Vec<_>>(),
+            vec!["net8.0"]
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Returns the files changed in a git revision range, relative to `directory`.
+pub fn git_diff(directory: &Path, range: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(["diff", "--name-only", "--relative", range])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| directory.join(line))
+        .collect())
+}
@@ -0,0 +1,483 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    sync::Mutex,
+};
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use dotnet_lens::{
+    export::json,
+    graph::DependencyGraph,
+    report::Report,
+    validation::{default_rules, validate},
+    workspace::Workspace,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+/// Answers JSON-RPC 2.0 requests on the standard input, one message per line, until the input
+/// is closed or a `shutdown` request is received.
+pub fn serve_stdio(workspace: Workspace) -> Result<(), Box<dyn Error>> {
+    let state = Mutex::new(workspace);
+
+    serve_connection(&state, io::stdin().lock(), io::stdout().lock())?;
+
+    Ok(())
+}
+
+/// Answers JSON-RPC 2.0 requests on a Unix socket, one message per line, until a `shutdown`
+/// request is received on any connection. Every connection is served by its own thread and
+/// shares the same workspace.
+///
+/// A socket file left by a daemon that did not stop cleanly is replaced, and the socket file is
+/// removed when the daemon stops.
+#[cfg(unix)]
+pub fn serve_socket(workspace: Workspace, path: &Path) -> Result<(), Box<dyn Error>> {
+    let socket = SocketFile::bind(path)?;
+    let state = Arc::new(Mutex::new(workspace));
+    let stopping = Arc::new(AtomicBool::new(false));
+
+    for stream in socket.listener.incoming() {
+        if stopping.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let stream = stream?;
+        let state = Arc::clone(&state);
+        let stopping = Arc::clone(&stopping);
+        let path = socket.path.clone();
+
+        thread::spawn(move || {
+            let reader = BufReader::new(&stream);
+
+            match serve_connection(&state, reader, &stream) {
+                Ok(true) => {
+                    stopping.store(true, Ordering::SeqCst);
+
+                    // wakes the listener up, so it sees that the daemon is stopping
+                    let _ = UnixStream::connect(&path);
+                }
+                Ok(false) => {}
+                Err(error) => eprintln!("error: {}", error),
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns an error, since Unix sockets are only available on Unix platforms.
+#[cfg(not(unix))]
+pub fn serve_socket(_workspace: Workspace, _path: &Path) -> Result<(), Box<dyn Error>> {
+    Err("Unix sockets are not supported on this platform".into())
+}
+
+/// A listening Unix socket, whose file is removed when it is dropped.
+#[cfg(unix)]
+struct SocketFile {
+    listener: UnixListener,
+    path: PathBuf,
+}
+
+#[cfg(unix)]
+impl SocketFile {
+    fn bind(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if path.exists() {
+            // a socket nobody listens to is left by a daemon that did not stop cleanly
+            if UnixStream::connect(path).is_ok() {
+                return Err(format!("a daemon is already listening on {}", path.display()).into());
+            }
+
+            fs::remove_file(path)?;
+        }
+
+        Ok(Self {
+            listener: UnixListener::bind(path)?,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Answers the requests of a connection until it is closed, returning `true` if it was closed
+/// by a `shutdown` request.
+fn serve_connection<R, W>(
+    state: &Mutex<Workspace>,
+    reader: R,
+    mut writer: W,
+) -> Result<bool, Box<dyn Error>>
+where
+    R: BufRead,
+    W: Write,
+{
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut workspace = state.lock().map_err(|_| "the workspace lock is poisoned")?;
+        let (response, shutdown) = handle(&mut workspace, &line);
+        drop(workspace);
+
+        if let Some(response) = response {
+            writeln!(writer, "{}", response)?;
+            writer.flush()?;
+        }
+
+        if shutdown {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectParams {
+    project: String,
+    #[serde(default)]
+    transitive: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphParams {
+    #[serde(default)]
+    include_packages: bool,
+    focus: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RefreshParams {
+    paths: Option<Vec<String>>,
+}
+
+/// Handles a JSON-RPC message, returning the response (`None` for notifications) and whether
+/// the server should shut down.
+fn handle(workspace: &mut Workspace, message: &str) -> (Option<String>, bool) {
+    let request: Request = match serde_json::from_str(message) {
+        Ok(request) => request,
+        Err(error) => {
+            let code = if serde_json::from_str::<Value>(message).is_ok() {
+                INVALID_REQUEST
+            } else {
+                PARSE_ERROR
+            };
+
+            return (Some(error_response(Value::Null, code, error)), false);
+        }
+    };
+
+    if request.jsonrpc != "2.0" {
+        return (
+            Some(error_response(
+                request.id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "jsonrpc must be \"2.0\"",
+            )),
+            false,
+        );
+    }
+
+    let shutdown = request.method == "shutdown";
+    let result = dispatch(workspace, &request.method, request.params);
+
+    let Some(id) = request.id else {
+        return (None, shutdown);
+    };
+
+    let response = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }).to_string(),
+        Err((code, message)) => error_response(id, code, message),
+    };
+
+    (Some(response), shutdown)
+}
+
+fn dispatch(
+    workspace: &mut Workspace,
+    method: &str,
+    params: Value,
+) -> Result<Value, (i64, String)> {
+    match method {
+        "projects" => to_value(workspace.projects()),
+        "dependencies" | "dependents" => {
+            let params: ProjectParams = parse_params(params)?;
+            let graph = DependencyGraph::from_workspace(workspace, false);
+            let index = find_project(workspace, &graph, &params.project)?;
+
+            let next = |index: usize| -> Vec<usize> {
+                if method == "dependencies" {
+                    graph.dependencies(index).collect()
+                } else {
+                    graph.dependents(index).collect()
+                }
+            };
+
+            let mut found: Vec<usize> = next(index);
+
+            if params.transitive {
+                let mut stack = found.clone();
+
+                while let Some(current) = stack.pop() {
+                    for other in next(current) {
+                        if !found.contains(&other) {
+                            found.push(other);
+                            stack.push(other);
+                        }
+                    }
+                }
+            }
+
+            found.sort();
+
+            Ok(found
+                .iter()
+                .map(|index| Value::String(graph.node(*index).id().clone()))
+                .collect())
+        }
+        "graph" => {
+            let params: GraphParams = parse_params(params)?;
+            let mut graph = DependencyGraph::from_workspace(workspace, params.include_packages);
+
+            if let Some(focus) = params.focus {
+                let index = find_project(workspace, &graph, &focus)?;
+                graph = graph.focus(index);
+            }
+
+            let document = json::to_string(&graph).map_err(server_error)?;
+            serde_json::from_str(&document).map_err(server_error)
+        }
+        "validate" => to_value(&Report::from(validate(
+            workspace.projects(),
+            &default_rules(),
+        ))),
+        "refresh" => {
+            let params: RefreshParams = parse_params(params)?;
+
+            let changed = match params.paths {
+                Some(paths) => {
+                    let paths: Vec<_> = paths
+                        .iter()
+                        .map(|path| workspace.root().join(path))
+                        .collect();
+
+                    workspace.refresh(&paths).map_err(server_error)?.len()
+                }
+                None => {
+                    *workspace = Workspace::load(workspace.root()).map_err(server_error)?;
+                    workspace.projects().len()
+                }
+            };
+
+            Ok(json!({ "changed": changed }))
+        }
+        "shutdown" => Ok(Value::Null),
+        _ => Err((METHOD_NOT_FOUND, format!("method '{}' not found", method))),
+    }
+}
+
+fn parse_params<T>(params: Value) -> Result<T, (i64, String)>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    // params may be omitted when every parameter is optional
+    let params = if params.is_null() { json!({}) } else { params };
+
+    serde_json::from_value(params).map_err(|error| (INVALID_PARAMS, error.to_string()))
+}
+
+fn find_project(
+    workspace: &Workspace,
+    graph: &DependencyGraph,
+    name: &str,
+) -> Result<usize, (i64, String)> {
+    workspace
+        .project(name)
+        .and_then(|project| graph.find(&workspace.relative_path(project.path())))
+        .ok_or((INVALID_PARAMS, format!("project '{}' was not found", name)))
+}
+
+fn to_value<T>(value: &T) -> Result<Value, (i64, String)>
+where
+    T: serde::Serialize + ?Sized,
+{
+    serde_json::to_value(value).map_err(server_error)
+}
+
+fn server_error<E>(error: E) -> (i64, String)
+where
+    E: std::fmt::Display,
+{
+    (SERVER_ERROR, error.to_string())
+}
+
+fn error_response<M>(id: Value, code: i64, message: M) -> String
+where
+    M: std::fmt::Display,
+{
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.to_string() }
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use dotnet_lens::{parser::parse, workspace::Workspace};
+    use serde_json::{json, Value};
+
+    use super::handle;
+
+    fn workspace() -> Workspace {
+        let api = r#"<Project><ItemGroup><ProjectReference Include="../Domain/Domain.csproj" /></ItemGroup></Project>"#;
+        let web = r#"<Project><ItemGroup><ProjectReference Include="../Api/Api.csproj" /></ItemGroup></Project>"#;
+
+        Workspace::from_projects(
+            "/repo",
+            vec![
+                parse(Cursor::new(api), "/repo/Api/Api.csproj").unwrap(),
+                parse(
+                    Cursor::new("<Project></Project>"),
+                    "/repo/Domain/Domain.csproj",
+                )
+                .unwrap(),
+                parse(Cursor::new(web), "/repo/Web/Web.csproj").unwrap(),
+            ],
+        )
+    }
+
+    fn call(workspace: &mut Workspace, message: &str) -> Value {
+        let (response, _) = handle(workspace, message);
+
+        serde_json::from_str(&response.unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn answer_transitive_dependents() {
+        // given
+        let mut workspace = workspace();
+
+        // when
+        let response = call(
+            &mut workspace,
+            r#"{"jsonrpc":"2.0","id":1,"method":"dependents","params":{"project":"Domain","transitive":true}}"#,
+        );
+
+        // then
+        assert_eq!(
+            response,
+            json!({"jsonrpc":"2.0","id":1,"result":["Api/Api.csproj","Web/Web.csproj"]})
+        );
+    }
+
+    #[test]
+    pub fn report_errors() {
+        let mut workspace = workspace();
+
+        let response = call(
+            &mut workspace,
+            r#"{"jsonrpc":"2.0","id":2,"method":"unknown"}"#,
+        );
+        assert_eq!(response["error"]["code"], -32601);
+
+        let response = call(&mut workspace, "{not json");
+        assert_eq!(response["error"]["code"], -32700);
+
+        let response = call(
+            &mut workspace,
+            r#"{"jsonrpc":"2.0","id":3,"method":"dependents","params":{"project":"Missing"}}"#,
+        );
+        assert_eq!(response["error"]["code"], -32602);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    pub fn stop_the_socket_daemon_on_shutdown() {
+        use std::{
+            io::{BufRead, BufReader, Write},
+            os::unix::net::{UnixListener, UnixStream},
+            thread,
+            time::Duration,
+        };
+
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dotnet-lens.sock");
+
+        // a socket file left by a daemon that did not stop cleanly
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let daemon = {
+            let path = path.clone();
+            thread::spawn(move || super::serve_socket(workspace(), &path).unwrap())
+        };
+
+        // when
+        let mut stream = loop {
+            match UnixStream::connect(&path) {
+                Ok(stream) => break stream,
+                Err(_) => thread::sleep(Duration::from_millis(10)),
+            }
+        };
+        writeln!(stream, r#"{{"jsonrpc":"2.0","id":1,"method":"shutdown"}}"#).unwrap();
+
+        let mut response = String::new();
+        BufReader::new(&stream).read_line(&mut response).unwrap();
+
+        // then
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["result"], Value::Null);
+
+        daemon.join().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    pub fn notifications_have_no_response() {
+        let (response, shutdown) =
+            handle(&mut workspace(), r#"{"jsonrpc":"2.0","method":"shutdown"}"#);
+
+        assert!(response.is_none());
+        assert!(shutdown);
+    }
+}
@@ -0,0 +1,1172 @@
+//! The `dotnet-lens` command line tool.
+//!
+//! Exposes the library through subcommands (see `dotnet-lens --help` for the list), with a
+//! `--watch` mode for `graph` and `check`. Requires the `cli` feature.
+//!
+//! The `tui` subcommand opens an interactive dependency explorer and requires the `tui` feature.
+
+mod affected;
+mod daemon;
+#[cfg(feature = "tui")]
+mod tui;
+mod watch;
+
+use std::{
+    env,
+    error::Error,
+    fs::{self, File},
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use dotnet_lens::{
+    cache::ParseCache,
+    condition::Properties,
+    export::{
+        tree::{self, TreeOptions},
+        Clusters, ExportOptions, Exporters,
+    },
+    filter::{Matcher, ProjectFilter},
+    framework::FrameworkRules,
+    graph::{DependencyGraph, Direction, EdgeClass},
+    metrics, migration,
+    nuget::NugetClient,
+    nuget_config::NugetConfig,
+    nullable::{self, NullableStatus},
+    osv::OsvClient,
+    output::{dotnet_list, github, sarif},
+    parser::parse_as_with_properties,
+    query::Query,
+    report::Report,
+    sbom::{cyclonedx, spdx, SbomOptions},
+    solution::Solution,
+    strictness::StrictnessReport,
+    upgrade,
+    validation::{default_rules, validate, Severity},
+    workloads,
+    workspace::Workspace,
+    Project, ProjectLanguage,
+};
+
+#[derive(Parser)]
+#[command(name = "dotnet-lens", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Lists the projects with their project and package references.
+    List {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// The output format of the projects. `ndjson` prints one project per line as soon as
+        /// it is parsed, and `dotnet-json` prints the packages like `dotnet list package
+        /// --include-transitive --format json`.
+        #[arg(long, value_enum, default_value_t = ListFormat::Text)]
+        format: ListFormat,
+        /// Only lists the projects matching the query (ex: `--filter "kind == test && tfm
+        /// startswith 'net4'"`).
+        #[arg(long, value_name = "QUERY")]
+        filter: Option<Query>,
+    },
+    /// Prints the dependency graph between the projects.
+    Graph {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// The output format of the graph: `dot`, `mermaid`, `json`, `graphml` or `tree`. The
+        /// `cyclonedx`, `spdx`, `sarif`, `github` and `dotnet-json` formats describe the whole
+        /// workspace instead of the graph.
+        #[arg(long, default_value = "dot", value_parser = parse_graph_format)]
+        format: String,
+        /// Adds the referenced packages to the graph.
+        #[arg(long)]
+        include_packages: bool,
+        /// Prints the graph as it is built for this target framework (ex: `net48`), instead of
+        /// the union of every target framework.
+        #[arg(long, value_name = "TFM")]
+        framework: Option<String>,
+        /// Removes the test projects (the projects referencing `Microsoft.NET.Test.Sdk`).
+        #[arg(long)]
+        exclude_tests: bool,
+        /// Also removes the packages only referenced by test projects.
+        #[arg(long, requires = "exclude_tests")]
+        exclude_test_dependencies: bool,
+        /// Only keeps the references of these classes, from the `PrivateAssets`, `IncludeAssets`
+        /// and `ExcludeAssets` of the packages (ex: `--edge-class runtime`). Can be repeated.
+        #[arg(long, value_enum, value_name = "CLASS")]
+        edge_class: Vec<GraphEdgeClass>,
+        /// Only prints the given project with its dependencies and dependents.
+        #[arg(long, value_name = "PROJECT")]
+        focus: Option<String>,
+        /// Only keeps the nodes within this number of references of the focused project.
+        #[arg(long, requires = "focus")]
+        depth: Option<usize>,
+        /// The references followed from the focused project: `upstream` for its dependencies,
+        /// `downstream` for its dependents.
+        #[arg(long, value_enum, default_value_t = GraphDirection::Both, requires = "focus")]
+        direction: GraphDirection,
+        /// Groups the projects by top-level directory, by solution folder or by their first tag
+        /// (`dot` and `mermaid` formats only).
+        #[arg(long, value_enum)]
+        group_by: Option<GroupBy>,
+        /// The solution used to group by solution folder. Defaults to the solution of
+        /// `--in-solution`, or else to the only `.sln` file in the workspace directory.
+        #[arg(long, value_name = "PATH")]
+        solution: Option<PathBuf>,
+        /// Prints the graph again every time a project changes.
+        #[arg(long)]
+        watch: bool,
+    },
+    /// Prints the projects as a tree of their project and package references.
+    Tree {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// Disables the colors, which are also disabled when the `NO_COLOR` environment
+        /// variable is set or the output is not a terminal.
+        #[arg(long)]
+        no_color: bool,
+        /// Highlights the packages with newer versions available on the feed.
+        #[arg(long)]
+        outdated: bool,
+        /// Highlights the packages with known vulnerabilities in the OSV database.
+        #[arg(long)]
+        vulnerable: bool,
+        /// The name of a source from NuGet.config or the URL of a V3 service index.
+        #[arg(long)]
+        source: Option<String>,
+    },
+    /// Validates the projects and reports the violations found.
+    Check {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// The output format of the violations.
+        #[arg(long, value_enum, default_value_t = CheckFormat::Text)]
+        format: CheckFormat,
+        /// The minimum severity that makes the command fail.
+        #[arg(long, value_enum, default_value_t = FailOn::Error)]
+        fail_on: FailOn,
+        /// Validates the projects again every time a project changes.
+        #[arg(long)]
+        watch: bool,
+    },
+    /// Lists the packages with newer versions available on the feed.
+    Outdated {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// Considers prerelease versions.
+        #[arg(long)]
+        prerelease: bool,
+        /// The name of a source from NuGet.config or the URL of a V3 service index.
+        #[arg(long)]
+        source: Option<String>,
+    },
+    /// Prints a software bill of materials of the referenced packages.
+    Sbom {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// The format of the SBOM.
+        #[arg(long, value_enum, default_value_t = SbomFormat::Cyclonedx)]
+        format: SbomFormat,
+        /// Writes the SBOM to a file instead of the standard output.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Derives the serial number from the workspace content and omits the timestamp, so the
+        /// same workspace always produces the same document.
+        #[arg(long)]
+        stable: bool,
+    },
+    /// Prints the projects impacted by changed files, including every project that depends on
+    /// them, to select what to build and test (ex: `dotnet-lens affected . src/Core/Order.cs`).
+    Affected {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// The changed files, after the directory searched for projects.
+        #[arg(required_unless_present = "git_diff")]
+        files: Vec<PathBuf>,
+        /// Reads the changed files from `git diff` over a revision range (ex: `main...HEAD`).
+        #[arg(long, value_name = "RANGE")]
+        git_diff: Option<String>,
+    },
+    /// Prints the .NET SDK workloads required by the projects, one per line, to provision build
+    /// machines (ex: `dotnet workload install $(dotnet-lens workloads)`).
+    Workloads {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// Also prints the projects requiring each workload.
+        #[arg(long)]
+        projects: bool,
+    },
+    /// Prints, for each project, whether it and the projects it references are ready for an
+    /// upgrade to a target framework, and the projects to upgrade first in dependency order.
+    Upgrade {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// The target framework of the upgrade (ex: `net9.0`).
+        target_framework: String,
+        /// Only prints the projects that are not ready.
+        #[arg(long)]
+        blocked: bool,
+    },
+    /// Prints what ties the projects to .NET Framework (legacy project format, `packages.config`,
+    /// `TargetFrameworkVersion`, GAC and COM references), to plan their migration.
+    Migration {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+    },
+    /// Prints the projects treating warnings as errors, and the projects suppressing each warning
+    /// (`NoWarn`) or setting each analysis level (`AnalysisLevel`).
+    Strictness {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+    },
+    /// Prints how many C# projects enable nullable reference types, partially enable, disable or
+    /// do not set them, by directory.
+    Nullable {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// The number of directories, from the root, the projects are grouped by.
+        #[arg(long, default_value_t = 2)]
+        depth: usize,
+    },
+    /// Prints, as CSV, the number of references in the shortest chain of project references
+    /// between every pair of projects, with an empty cell when a project cannot reach the other.
+    Distances {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// Only prints the distances between these projects, in this order. Can be repeated.
+        #[arg(long = "project")]
+        projects: Vec<String>,
+    },
+    /// Keeps the workspace in memory and answers JSON-RPC 2.0 requests (`projects`,
+    /// `dependencies`, `dependents`, `graph`, `validate`, `refresh` and `shutdown`), one message
+    /// per line.
+    Serve {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+        /// Listens on a Unix socket instead of the standard input and output.
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
+    /// Opens an interactive explorer of the projects, packages and their dependencies.
+    #[cfg(feature = "tui")]
+    Tui {
+        #[command(flatten)]
+        workspace: WorkspaceArgs,
+    },
+}
+
+#[derive(Args)]
+struct WorkspaceArgs {
+    /// The directory searched for projects, or `-` to read a single project from the standard
+    /// input.
+    #[arg(default_value = ".")]
+    path: PathBuf,
+    /// The name of the project read from the standard input.
+    #[arg(long, default_value = "stdin")]
+    assume_name: String,
+    /// The extension of the project read from the standard input, which sets its language.
+    #[arg(long, default_value = "csproj", value_parser = ["csproj", "fsproj", "vbproj", "dcproj", "sfproj", "wixproj"])]
+    assume_extension: String,
+    /// The directory where the parsed projects are cached between runs, so only the changed
+    /// project files are parsed again.
+    #[arg(long, value_name = "PATH")]
+    cache_dir: Option<PathBuf>,
+    /// A property used to evaluate the conditions of the projects, like the `-p` option of
+    /// `dotnet build` (ex: `-p Configuration=Release`). Can be repeated.
+    #[arg(
+        short = 'p',
+        long = "property",
+        value_name = "NAME=VALUE",
+        value_parser = parse_property
+    )]
+    properties: Vec<(String, String)>,
+    /// Only keeps the projects whose name matches the pattern, a glob (ex: `Shop.*`) or a regular
+    /// expression prefixed with `regex:`. Can be repeated.
+    #[arg(long = "project-name", value_name = "PATTERN", value_parser = Matcher::parse)]
+    project_names: Vec<Matcher>,
+    /// Only keeps the projects whose path, relative to the root, matches the pattern (ex:
+    /// `src/*`). Can be repeated.
+    #[arg(long = "project-path", value_name = "PATTERN", value_parser = Matcher::parse)]
+    project_paths: Vec<Matcher>,
+    /// Only keeps the package references whose name matches the pattern (ex:
+    /// `Microsoft.Extensions.*`). Can be repeated.
+    #[arg(long = "package", value_name = "PATTERN", value_parser = Matcher::parse)]
+    packages: Vec<Matcher>,
+    /// Only keeps the projects of this solution (`.sln`), for repositories hosting several
+    /// independent solutions.
+    #[arg(long, value_name = "PATH")]
+    in_solution: Option<PathBuf>,
+}
+
+impl WorkspaceArgs {
+    fn is_stdin(&self) -> bool {
+        self.path.as_os_str() == "-"
+    }
+
+    fn filter(&self) -> ProjectFilter {
+        let mut filter = ProjectFilter::new();
+
+        self.project_names
+            .iter()
+            .for_each(|matcher| filter.add_name(matcher.clone()));
+        self.project_paths
+            .iter()
+            .for_each(|matcher| filter.add_path(matcher.clone()));
+        self.packages
+            .iter()
+            .for_each(|matcher| filter.add_package(matcher.clone()));
+
+        filter
+    }
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum ListFormat {
+    Text,
+    Json,
+    Ndjson,
+    DotnetJson,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum GraphDirection {
+    Upstream,
+    Downstream,
+    Both,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum GroupBy {
+    Directory,
+    SolutionFolder,
+    Tag,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum GraphEdgeClass {
+    Runtime,
+    CompileOnly,
+    Analyzer,
+    Build,
+}
+
+/// The transformations of the graph printed by the `graph` subcommand.
+struct GraphView<'a> {
+    include_packages: bool,
+    target_framework: Option<String>,
+    // `Some` with whether the dependencies only used by the tests are removed too
+    exclude_tests: Option<bool>,
+    edge_classes: Vec<EdgeClass>,
+    focus: Option<(&'a str, Option<usize>, Direction)>,
+}
+
+/// The grouping of the graph nodes, with the solution already loaded.
+enum Grouping {
+    Directory,
+    SolutionFolder(Solution),
+    Tag,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum SbomFormat {
+    Cyclonedx,
+    Spdx,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum CheckFormat {
+    Text,
+    Json,
+    Sarif,
+    Github,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum FailOn {
+    Error,
+    Warning,
+    Note,
+    Never,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli.command) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {}", error);
+
+            let mut source = error.source();
+            while let Some(cause) = source {
+                eprintln!("  caused by: {}", cause);
+                source = cause.source();
+            }
+
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(command: Command) -> Result<ExitCode, Box<dyn Error>> {
+    let mut stdout = io::stdout().lock();
+
+    match command {
+        Command::List {
+            workspace,
+            format,
+            filter,
+        } => {
+            let matches = |project: &Project| filter.as_ref().is_none_or(|q| q.matches(project));
+
+            match format {
+                ListFormat::Text => list(&mut stdout, &load(&workspace)?, matches)?,
+                ListFormat::Json => {
+                    let workspace = load(&workspace)?;
+                    let projects: Vec<&Project> = workspace
+                        .projects()
+                        .iter()
+                        .filter(|project| matches(project))
+                        .collect();
+
+                    serde_json::to_writer_pretty(&mut stdout, &projects)?;
+                    writeln!(stdout)?;
+                }
+                ListFormat::DotnetJson => {
+                    let workspace = load(&workspace)?;
+                    let projects: Vec<Project> = workspace
+                        .projects()
+                        .iter()
+                        .filter(|project| matches(project))
+                        .cloned()
+                        .collect();
+
+                    dotnet_list::write(&mut stdout, &projects)?;
+                    writeln!(stdout)?;
+                }
+                ListFormat::Ndjson if workspace.is_stdin() => {
+                    let workspace = load(&workspace)?;
+                    let project = &workspace.projects()[0];
+
+                    if matches(project) {
+                        serde_json::to_writer(&mut stdout, project)?;
+                        writeln!(stdout)?;
+                    }
+                }
+                ListFormat::Ndjson => {
+                    let mut failed = false;
+
+                    for project in Workspace::stream(workspace.path.canonicalize()?)? {
+                        match project {
+                            Ok(project) if !matches(&project) => (),
+                            Ok(project) => {
+                                serde_json::to_writer(&mut stdout, &project)?;
+                                writeln!(stdout)?;
+                            }
+                            Err(error) => {
+                                eprintln!("error: {}", error);
+                                failed = true;
+                            }
+                        }
+                    }
+
+                    if failed {
+                        return Ok(ExitCode::from(2));
+                    }
+                }
+            }
+        }
+        Command::Graph {
+            workspace,
+            format,
+            include_packages,
+            framework,
+            exclude_tests,
+            exclude_test_dependencies,
+            edge_class,
+            focus,
+            depth,
+            direction,
+            group_by,
+            solution,
+            watch,
+        } => {
+            let grouping = match group_by {
+                None => None,
+                Some(GroupBy::Directory) => Some(Grouping::Directory),
+                Some(GroupBy::Tag) => Some(Grouping::Tag),
+                Some(GroupBy::SolutionFolder) => {
+                    let path = match solution.or_else(|| workspace.in_solution.clone()) {
+                        Some(path) => path,
+                        None => find_solution(&workspace.path)?,
+                    };
+
+                    // like the workspace, so the paths of the solution's projects match
+                    Some(Grouping::SolutionFolder(Solution::load(
+                        path.canonicalize()?,
+                    )?))
+                }
+            };
+
+            let workspace = load(&workspace)?;
+
+            let direction = match direction {
+                GraphDirection::Upstream => Direction::Upstream,
+                GraphDirection::Downstream => Direction::Downstream,
+                GraphDirection::Both => Direction::Both,
+            };
+
+            let view = GraphView {
+                include_packages,
+                target_framework: framework,
+                exclude_tests: exclude_tests.then_some(exclude_test_dependencies),
+                edge_classes: edge_class
+                    .into_iter()
+                    .map(|class| match class {
+                        GraphEdgeClass::Runtime => EdgeClass::Runtime,
+                        GraphEdgeClass::CompileOnly => EdgeClass::CompileOnly,
+                        GraphEdgeClass::Analyzer => EdgeClass::Analyzer,
+                        GraphEdgeClass::Build => EdgeClass::Build,
+                    })
+                    .collect(),
+                focus: focus.as_deref().map(|focus| (focus, depth, direction)),
+            };
+
+            let mut emit = |workspace: &Workspace| {
+                write_graph(&mut stdout, workspace, &format, &view, grouping.as_ref())
+            };
+
+            if watch {
+                watch::watch(workspace, emit)?;
+            } else {
+                emit(&workspace)?;
+            }
+        }
+        Command::Tree {
+            workspace: args,
+            no_color,
+            outdated,
+            vulnerable,
+            source,
+        } => {
+            let workspace = load(&args)?;
+            let mut findings = vec![];
+
+            if outdated {
+                let client = nuget_client(&args.path, source)?;
+
+                findings.extend(
+                    client
+                        .outdated(workspace.projects(), false)?
+                        .iter()
+                        .map(|outdated| outdated.to_finding()),
+                );
+            }
+
+            if vulnerable {
+                findings.extend(
+                    OsvClient::new()
+                        .scan(workspace.projects())?
+                        .iter()
+                        .map(|vulnerable| vulnerable.to_finding()),
+                );
+            }
+
+            let color = !no_color && env::var_os("NO_COLOR").is_none() && stdout.is_terminal();
+            let graph = DependencyGraph::from_workspace(&workspace, true);
+
+            tree::write(&mut stdout, &graph, &TreeOptions::new(color, findings))?;
+        }
+        Command::Check {
+            workspace,
+            format,
+            fail_on,
+            watch,
+        } => {
+            let workspace = load(&workspace)?;
+
+            if watch {
+                watch::watch(workspace, |workspace| {
+                    write_check(&mut stdout, workspace, format).map(|_| ())
+                })?;
+
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            let report = write_check(&mut stdout, &workspace, format)?;
+
+            let failed = match fail_on {
+                FailOn::Error => report.has_violations_at_least(Severity::Error),
+                FailOn::Warning => report.has_violations_at_least(Severity::Warning),
+                FailOn::Note => report.has_violations_at_least(Severity::Note),
+                FailOn::Never => false,
+            };
+
+            if failed {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Outdated {
+            workspace,
+            prerelease,
+            source,
+        } => {
+            let client = nuget_client(&workspace.path, source)?;
+            let workspace = load(&workspace)?;
+
+            for outdated in client.outdated(workspace.projects(), prerelease)? {
+                writeln!(
+                    stdout,
+                    "{}: {} {} -> {}",
+                    workspace.relative_path(outdated.project()),
+                    outdated.name(),
+                    outdated.current(),
+                    outdated.latest()
+                )?;
+            }
+        }
+        Command::Sbom {
+            workspace,
+            format,
+            output,
+            stable,
+        } => {
+            let workspace = load(&workspace)?;
+
+            let options = if stable {
+                SbomOptions::stable(&workspace)
+            } else {
+                SbomOptions::generated()
+            };
+
+            let mut writer: Box<dyn Write> = match output {
+                Some(path) => Box::new(File::create(path)?),
+                None => Box::new(&mut stdout),
+            };
+
+            match format {
+                SbomFormat::Cyclonedx => cyclonedx::write(&mut writer, &workspace, &options)?,
+                SbomFormat::Spdx => spdx::write(&mut writer, &workspace, &options)?,
+            }
+
+            writeln!(writer)?;
+        }
+        Command::Affected {
+            workspace,
+            files,
+            git_diff,
+        } => {
+            let workspace = load(&workspace)?;
+
+            let mut changed_files = files
+                .iter()
+                .map(std::path::absolute)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(range) = git_diff {
+                changed_files.extend(affected::git_diff(workspace.root(), &range)?);
+            }
+
+            for project in workspace.affected_by(&changed_files) {
+                writeln!(stdout, "{}", workspace.relative_path(project.path()))?;
+            }
+        }
+        Command::Workloads {
+            workspace,
+            projects,
+        } => {
+            let workspace = load(&workspace)?;
+
+            for workload in workloads::required_workloads(&workspace) {
+                writeln!(stdout, "{}", workload.id())?;
+
+                if projects {
+                    for path in workload.projects() {
+                        writeln!(stdout, "  {}", workspace.relative_path(path))?;
+                    }
+                }
+            }
+        }
+        Command::Upgrade {
+            workspace,
+            target_framework,
+            blocked,
+        } => {
+            let workspace = load(&workspace)?;
+            let rules = FrameworkRules::new();
+
+            for readiness in upgrade::upgrade_readiness(&workspace, &target_framework, &rules) {
+                if blocked && readiness.is_ready() {
+                    continue;
+                }
+
+                let status = if readiness.is_ready() {
+                    "ready"
+                } else {
+                    "blocked"
+                };
+                writeln!(
+                    stdout,
+                    "{} ({status})",
+                    workspace.relative_path(readiness.project())
+                )?;
+
+                for path in readiness.blocking() {
+                    writeln!(stdout, "  {}", workspace.relative_path(path))?;
+                }
+            }
+        }
+        Command::Migration { workspace } => {
+            let workspace = load(&workspace)?;
+
+            for inventory in migration::migration_inventory(&workspace) {
+                writeln!(stdout, "{}", workspace.relative_path(inventory.project()))?;
+
+                if inventory.is_legacy_format() {
+                    writeln!(stdout, "  legacy project format")?;
+                }
+
+                if let Some(path) = inventory.packages_config() {
+                    writeln!(stdout, "  {}", workspace.relative_path(path))?;
+                }
+
+                if let Some(version) = inventory.target_framework_version() {
+                    writeln!(stdout, "  TargetFrameworkVersion {version}")?;
+                }
+
+                for name in inventory.gac_references() {
+                    writeln!(stdout, "  GAC reference {name}")?;
+                }
+
+                for name in inventory.com_references() {
+                    writeln!(stdout, "  COM reference {name}")?;
+                }
+            }
+        }
+        Command::Strictness { workspace } => {
+            let workspace = load(&workspace)?;
+            let report = StrictnessReport::from_workspace(&workspace);
+
+            let sections = [(
+                "TreatWarningsAsErrors".to_string(),
+                report.treating_warnings_as_errors(),
+            )]
+            .into_iter()
+            .chain(
+                report
+                    .suppressed_warnings()
+                    .iter()
+                    .map(|(code, projects)| (format!("NoWarn {code}"), projects)),
+            )
+            .chain(
+                report
+                    .analysis_levels()
+                    .iter()
+                    .map(|(level, projects)| (format!("AnalysisLevel {level}"), projects)),
+            );
+
+            for (title, projects) in sections {
+                writeln!(stdout, "{title}: {}/{}", projects.len(), report.projects())?;
+
+                for path in projects {
+                    writeln!(stdout, "  {}", workspace.relative_path(path))?;
+                }
+            }
+        }
+        Command::Nullable { workspace, depth } => {
+            let workspace = load(&workspace)?;
+
+            for group in nullable::nullable_adoption(&workspace, depth) {
+                writeln!(
+                    stdout,
+                    "{}: {} enabled, {} partial, {} disabled, {} unset ({:.0}%)",
+                    group.directory(),
+                    group.count(NullableStatus::Enabled),
+                    group.count(NullableStatus::Partial),
+                    group.count(NullableStatus::Disabled),
+                    group.count(NullableStatus::Unset),
+                    group.adoption() * 100.0
+                )?;
+            }
+        }
+        Command::Distances {
+            workspace,
+            projects,
+        } => {
+            let workspace = load(&workspace)?;
+            let graph = DependencyGraph::from_workspace(&workspace, false);
+
+            let indexes = projects
+                .iter()
+                .map(|name| {
+                    workspace
+                        .project(name)
+                        .and_then(|project| graph.find(&workspace.relative_path(project.path())))
+                        .ok_or(format!("project '{}' was not found", name))
+                })
+                .collect::<Result<Vec<usize>, String>>()?;
+
+            metrics::distance_matrix(&graph, &indexes).write_csv(&mut stdout)?;
+        }
+        Command::Serve { workspace, socket } => {
+            drop(stdout);
+
+            let workspace = load(&workspace)?;
+
+            match socket {
+                Some(path) => daemon::serve_socket(workspace, &path)?,
+                None => daemon::serve_stdio(workspace)?,
+            }
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui { workspace } => {
+            drop(stdout);
+
+            tui::run(&load(&workspace)?)?;
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Loads the workspace (see [`load_all`]) and keeps the projects and packages matching the
+/// filter options.
+fn load(args: &WorkspaceArgs) -> Result<Workspace, Box<dyn Error>> {
+    let mut workspace = load_all(args)?;
+
+    if let Some(path) = &args.in_solution {
+        workspace = workspace.solution_slice(&Solution::load(path.canonicalize()?)?);
+    }
+
+    let filter = args.filter();
+
+    if filter.is_empty() {
+        return Ok(workspace);
+    }
+
+    Ok(workspace.filter(&filter))
+}
+
+/// Loads the workspace from its canonical path, so the project paths match the absolute paths
+/// reported by the file watcher, or a workspace with the single project of the standard input.
+fn load_all(args: &WorkspaceArgs) -> Result<Workspace, Box<dyn Error>> {
+    let mut properties = Properties::new();
+
+    for (name, value) in &args.properties {
+        properties.set(name, value);
+    }
+
+    if args.is_stdin() {
+        let language = ProjectLanguage::from_extension(args.assume_extension.as_ref())
+            .ok_or("the assumed extension is not a project extension")?;
+        let project =
+            parse_as_with_properties(io::stdin().lock(), &args.assume_name, language, &properties)?;
+
+        return Ok(Workspace::from_projects(".", vec![project]));
+    }
+
+    match &args.cache_dir {
+        Some(directory) => Ok(ParseCache::with_properties(directory.clone(), properties)
+            .load(args.path.canonicalize()?)?),
+        None if !args.properties.is_empty() => Ok(Workspace::load_with_properties(
+            args.path.canonicalize()?,
+            &properties,
+        )?),
+        None => Ok(Workspace::load(args.path.canonicalize()?)?),
+    }
+}
+
+/// Parses a `NAME=VALUE` property assignment.
+fn parse_property(assignment: &str) -> Result<(String, String), String> {
+    match assignment.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("'{}' is not a NAME=VALUE property", assignment)),
+    }
+}
+
+fn write_graph<W>(
+    mut writer: W,
+    workspace: &Workspace,
+    format: &str,
+    view: &GraphView,
+    grouping: Option<&Grouping>,
+) -> Result<(), Box<dyn Error>>
+where
+    W: Write,
+{
+    let mut graph = match &view.target_framework {
+        Some(framework) => {
+            DependencyGraph::from_workspace_for(workspace, view.include_packages, framework)
+        }
+        None => DependencyGraph::from_workspace(workspace, view.include_packages),
+    };
+
+    if let Some(exclusive_dependencies) = view.exclude_tests {
+        graph = graph.without_tests(workspace, exclusive_dependencies);
+    }
+
+    if !view.edge_classes.is_empty() {
+        graph = graph.with_edge_classes(&view.edge_classes);
+    }
+
+    if let Some((focus, depth, direction)) = view.focus {
+        let index = workspace
+            .project(focus)
+            .and_then(|project| graph.find(&workspace.relative_path(project.path())))
+            .ok_or(format!("project '{}' was not found", focus))?;
+
+        graph = match (depth, direction) {
+            (None, Direction::Both) => graph.focus(index),
+            (depth, direction) => graph.subgraph(index, depth.unwrap_or(usize::MAX), direction),
+        };
+    }
+
+    let mut options = ExportOptions::new();
+    options.set_clusters(grouping.map(|grouping| match grouping {
+        Grouping::Directory => Clusters::by_directory(&graph, 1),
+        Grouping::SolutionFolder(solution) => Clusters::by_solution_folder(&graph, solution),
+        Grouping::Tag => Clusters::by_tag(&graph, workspace),
+    }));
+
+    let exporters = Exporters::builtin(&options);
+    let exporter = exporters
+        .get(format)
+        .ok_or(format!("the graph format '{}' is not supported", format))?;
+
+    exporter.export(&mut writer, &graph, workspace)?;
+
+    Ok(())
+}
+
+/// Parses the name of one of the built-in graph exporters.
+fn parse_graph_format(format: &str) -> Result<String, String> {
+    let exporters = Exporters::builtin(&ExportOptions::new());
+
+    match exporters.get(format) {
+        Some(exporter) => Ok(exporter.name().to_string()),
+        None => Err(format!(
+            "'{}' is not one of the graph formats: {}",
+            format,
+            exporters.names().collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Returns the only solution file in `directory`.
+fn find_solution(directory: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let mut solutions = vec![];
+
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+
+        if path.extension().is_some_and(|extension| extension == "sln") {
+            solutions.push(path);
+        }
+    }
+
+    match solutions.len() {
+        1 => Ok(solutions.remove(0)),
+        0 => Err(format!("no solution was found in {:?}", directory).into()),
+        _ => Err(format!(
+            "more than one solution was found in {:?}, use --solution to choose one",
+            directory
+        )
+        .into()),
+    }
+}
+
+fn write_check<W>(
+    mut writer: W,
+    workspace: &Workspace,
+    format: CheckFormat,
+) -> Result<Report, Box<dyn Error>>
+where
+    W: Write,
+{
+    let rules = default_rules();
+    let report = Report::from(validate(workspace.projects(), &rules));
+
+    match format {
+        CheckFormat::Text => {
+            for violation in report.violations() {
+                writeln!(
+                    writer,
+                    "{:?} {} {}: {}",
+                    violation.severity(),
+                    violation.rule_id(),
+                    workspace.relative_path(violation.path()),
+                    violation.message()
+                )?;
+            }
+
+            let summary = report.summary();
+            writeln!(
+                writer,
+                "{} errors, {} warnings, {} notes",
+                summary.errors(),
+                summary.warnings(),
+                summary.notes()
+            )?;
+        }
+        CheckFormat::Json => writeln!(writer, "{}", report.to_json()?)?,
+        CheckFormat::Sarif => {
+            sarif::write(&mut writer, report.violations(), &rules)?;
+            writeln!(writer)?;
+        }
+        CheckFormat::Github => github::write(&mut writer, report.violations())?,
+    }
+
+    Ok(report)
+}
+
+fn list<W, F>(mut writer: W, workspace: &Workspace, matches: F) -> io::Result<()>
+where
+    W: Write,
+    F: Fn(&Project) -> bool,
+{
+    for project in workspace
+        .projects()
+        .iter()
+        .filter(|project| matches(project))
+    {
+        writeln!(
+            writer,
+            "{} ({}) {}",
+            project.name(),
+            workspace.relative_path(project.path()),
+            project.target_framework().map_or("", |tfm| tfm)
+        )?;
+
+        for reference in project.project_references() {
+            writeln!(writer, "  -> {}", reference.name())?;
+        }
+
+        for package_reference in project.package_references() {
+            writeln!(
+                writer,
+                "  {} {}",
+                package_reference.name(),
+                package_reference.version()
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn nuget_client(path: &PathBuf, source: Option<String>) -> Result<NugetClient, Box<dyn Error>> {
+    let Some(source) = source else {
+        return Ok(NugetClient::new());
+    };
+
+    if let Some(config_path) = NugetConfig::find(path) {
+        let config = NugetConfig::load(config_path)?;
+
+        if let Some(package_source) = config.source(&source) {
+            return Ok(NugetClient::from_source(package_source));
+        }
+    }
+
+    Ok(NugetClient::with_service_index(source))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use clap::Parser;
+
+    use super::{load, Cli, Command};
+
+    #[test]
+    pub fn evaluate_properties_with_a_cache() {
+        // given
+        let root = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+
+        fs::create_dir(root.path().join("Api")).unwrap();
+        fs::write(
+            root.path().join("Api/Api.csproj"),
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup Condition="'$(Configuration)' == 'Release'">
+    <PackageReference Include="Serilog" Version="3.1.1" />
+  </ItemGroup>
+</Project>"#,
+        )
+        .unwrap();
+
+        let packages = |configuration: &str| {
+            let cli = Cli::try_parse_from([
+                "dotnet-lens".as_ref(),
+                "list".as_ref(),
+                root.path().as_os_str(),
+                "--cache-dir".as_ref(),
+                cache.path().as_os_str(),
+                "-p".as_ref(),
+                format!("Configuration={configuration}").as_ref(),
+            ])
+            .unwrap();
+
+            let Command::List { workspace, .. } = cli.command else {
+                unreachable!()
+            };
+
+            load(&workspace).unwrap().projects()[0]
+                .package_references()
+                .len()
+        };
+
+        // when
+        let release = packages("Release");
+        let debug = packages("Debug");
+
+        // then
+        assert_eq!(release, 1);
+        assert_eq!(debug, 0);
+    }
+
+    #[test]
+    pub fn read_the_workspace_options_of_affected() {
+        // when
+        let cli = Cli::try_parse_from([
+            "dotnet-lens",
+            "affected",
+            "repos/shop",
+            "src/Core/Order.cs",
+            "src/Api/Program.cs",
+            "--project-name",
+            "Shop.*",
+            "-p",
+            "Configuration=Release",
+        ])
+        .unwrap();
+
+        // then
+        let Command::Affected {
+            workspace, files, ..
+        } = cli.command
+        else {
+            unreachable!()
+        };
+
+        assert_eq!(workspace.path, PathBuf::from("repos/shop"));
+        assert_eq!(workspace.project_names.len(), 1);
+        assert_eq!(
+            workspace.properties,
+            vec![("Configuration".to_string(), "Release".to_string())]
+        );
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("src/Core/Order.cs"),
+                PathBuf::from("src/Api/Program.cs")
+            ]
+        );
+    }
+}
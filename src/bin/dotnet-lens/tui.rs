@@ -0,0 +1,404 @@
+use std::{error::Error, io};
+
+use dotnet_lens::{
+    graph::{DependencyGraph, NodeKind},
+    workspace::Workspace,
+};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+/// Opens the interactive dependency explorer until the user quits.
+///
+/// Keys: `j`/`k` or arrows move, `Tab` switches panes, `Enter` jumps to the selected
+/// dependency or dependent, `Backspace` goes back, `/` searches and `q` quits.
+pub fn run(workspace: &Workspace) -> Result<(), Box<dyn Error>> {
+    let mut app = App::new(DependencyGraph::from_workspace(workspace, true));
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+
+    result.map_err(|error| error.into())
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Pane {
+    Nodes,
+    Dependencies,
+    Dependents,
+}
+
+struct App {
+    graph: DependencyGraph,
+    query: String,
+    searching: bool,
+    pane: Pane,
+    /// The indexes of the graph nodes matching the query, best matches first.
+    matches: Vec<usize>,
+    nodes: ListState,
+    dependencies: ListState,
+    dependents: ListState,
+    history: Vec<usize>,
+    quit: bool,
+}
+
+impl App {
+    fn new(graph: DependencyGraph) -> Self {
+        let mut app = Self {
+            graph,
+            query: String::new(),
+            searching: false,
+            pane: Pane::Nodes,
+            matches: vec![],
+            nodes: ListState::default(),
+            dependencies: ListState::default(),
+            dependents: ListState::default(),
+            history: vec![],
+            quit: false,
+        };
+
+        app.filter();
+
+        app
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index of the graph node selected in the node list.
+    fn selected(&self) -> Option<usize> {
+        self.nodes
+            .selected()
+            .and_then(|i| self.matches.get(i).copied())
+    }
+
+    fn dependencies_of_selected(&self) -> Vec<usize> {
+        self.selected()
+            .map(|index| self.graph.dependencies(index).collect())
+            .unwrap_or_default()
+    }
+
+    fn dependents_of_selected(&self) -> Vec<usize> {
+        self.selected()
+            .map(|index| self.graph.dependents(index).collect())
+            .unwrap_or_default()
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .graph
+            .nodes()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| {
+                fuzzy_score(&self.query, node.name()).map(|score| (score, index))
+            })
+            .collect();
+
+        // projects before packages, then best matches first
+        scored.sort_by_key(|(score, index)| {
+            (
+                self.graph.node(*index).kind() == NodeKind::Package,
+                -score,
+                *index,
+            )
+        });
+
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.nodes.select((!self.matches.is_empty()).then_some(0));
+        self.reset_side_panes();
+    }
+
+    fn reset_side_panes(&mut self) {
+        self.dependencies
+            .select((!self.dependencies_of_selected().is_empty()).then_some(0));
+        self.dependents
+            .select((!self.dependents_of_selected().is_empty()).then_some(0));
+    }
+
+    /// Selects the node at `index` in the node list, clearing the search if it hides the node.
+    fn jump(&mut self, index: usize) {
+        if !self.matches.contains(&index) {
+            self.query.clear();
+            self.filter();
+        }
+
+        let position = self.matches.iter().position(|i| *i == index);
+        self.nodes.select(position);
+        self.pane = Pane::Nodes;
+        self.reset_side_panes();
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if self.searching {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => self.searching = false,
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.filter();
+                }
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.filter();
+                }
+                _ => (),
+            }
+
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('/') => {
+                self.searching = true;
+                self.pane = Pane::Nodes;
+            }
+            KeyCode::Tab => {
+                self.pane = match self.pane {
+                    Pane::Nodes => Pane::Dependencies,
+                    Pane::Dependencies => Pane::Dependents,
+                    Pane::Dependents => Pane::Nodes,
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Enter => {
+                let target = match self.pane {
+                    Pane::Nodes => None,
+                    Pane::Dependencies => self
+                        .dependencies
+                        .selected()
+                        .and_then(|i| self.dependencies_of_selected().get(i).copied()),
+                    Pane::Dependents => self
+                        .dependents
+                        .selected()
+                        .and_then(|i| self.dependents_of_selected().get(i).copied()),
+                };
+
+                if let (Some(target), Some(current)) = (target, self.selected()) {
+                    self.history.push(current);
+                    self.jump(target);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(previous) = self.history.pop() {
+                    self.jump(previous);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        let (state, len) = match self.pane {
+            Pane::Nodes => (&mut self.nodes, self.matches.len()),
+            Pane::Dependencies => {
+                let len = self.dependencies_of_selected().len();
+                (&mut self.dependencies, len)
+            }
+            Pane::Dependents => {
+                let len = self.dependents_of_selected().len();
+                (&mut self.dependents, len)
+            }
+        };
+
+        if len == 0 {
+            return;
+        }
+
+        let current = state.selected().unwrap_or(0) as isize;
+        state.select(Some((current + offset).clamp(0, len as isize - 1) as usize));
+
+        if self.pane == Pane::Nodes {
+            self.reset_side_panes();
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(main);
+        let [top, bottom] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(right);
+
+        let title = if self.query.is_empty() {
+            " Projects and packages ".to_string()
+        } else {
+            format!(" Projects and packages (/{}) ", self.query)
+        };
+
+        let nodes = self.list(&self.matches, title, Pane::Nodes);
+        frame.render_stateful_widget(nodes, left, &mut self.nodes);
+
+        let dependencies = self.list(
+            &self.dependencies_of_selected(),
+            " Dependencies ".to_string(),
+            Pane::Dependencies,
+        );
+        frame.render_stateful_widget(dependencies, top, &mut self.dependencies);
+
+        let dependents = self.list(
+            &self.dependents_of_selected(),
+            " Dependents ".to_string(),
+            Pane::Dependents,
+        );
+        frame.render_stateful_widget(dependents, bottom, &mut self.dependents);
+
+        let help = if self.searching {
+            format!("search: {}_  (enter/esc to finish)", self.query)
+        } else {
+            "j/k move  tab switch pane  enter jump  backspace back  / search  q quit".to_string()
+        };
+        frame.render_widget(
+            Paragraph::new(help).style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn list(&self, indexes: &[usize], title: String, pane: Pane) -> List<'static> {
+        let items: Vec<ListItem> = indexes
+            .iter()
+            .map(|index| {
+                let node = self.graph.node(*index);
+
+                let line = match node.kind() {
+                    NodeKind::Project => Line::from(Span::styled(
+                        node.name().clone(),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                    NodeKind::Package => Line::from(vec![
+                        Span::raw(node.name().clone()),
+                        Span::styled(
+                            format!(" {}", node.version().map_or("", |v| v)),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ]),
+                };
+
+                ListItem::new(line)
+            })
+            .collect();
+
+        let border = if self.pane == pane {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+
+        List::new(items)
+            .block(Block::bordered().title(title).border_style(border))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    }
+}
+
+/// Scores how well `text` matches `query` as a case-insensitive subsequence, or returns `None`
+/// if it does not match. Consecutive characters and matches at word starts score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let Some(first) = query.first() else {
+        return Some(0);
+    };
+
+    // tries every occurrence of the first character, so "api" matches the "Api" of
+    // "Company.Api" instead of the first "a"
+    text.iter()
+        .enumerate()
+        .filter(|(_, c)| *c == first)
+        .filter_map(|(start, _)| score_from(&query, &text, start))
+        .max()
+}
+
+fn score_from(query: &[char], text: &[char], start: usize) -> Option<i64> {
+    let mut score = 0;
+    let mut position = start;
+    let mut previous: Option<usize> = None;
+
+    for c in query {
+        let found = text[position..].iter().position(|t| t == c)? + position;
+
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || matches!(text[found - 1], '.' | '-' | '_' | ' ') {
+            score += 3;
+        }
+
+        previous = Some(found);
+        position = found + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use std::io::Cursor;
+
+    use dotnet_lens::{graph::DependencyGraph, parser::parse, workspace::Workspace};
+
+    use super::{fuzzy_score, App};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    pub fn fuzzy_score_prefers_consecutive_matches() {
+        assert!(fuzzy_score("xyz", "Company.Api").is_none());
+        assert!(fuzzy_score("api", "Company.Api") > fuzzy_score("api", "Application"));
+        assert_eq!(fuzzy_score("", "Company.Api"), Some(0));
+    }
+
+    #[test]
+    pub fn jump_to_dependency_and_back() {
+        // given
+        let api = r#"<Project><ItemGroup><ProjectReference Include="../Domain/Domain.csproj" /></ItemGroup></Project>"#;
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                parse(Cursor::new(api), "/repo/Api/Api.csproj").unwrap(),
+                parse(
+                    Cursor::new("<Project></Project>"),
+                    "/repo/Domain/Domain.csproj",
+                )
+                .unwrap(),
+            ],
+        );
+
+        let mut app = App::new(DependencyGraph::from_workspace(&workspace, true));
+
+        // when
+        app.handle_key(key(KeyCode::Tab));
+        app.handle_key(key(KeyCode::Enter));
+
+        // then
+        assert_eq!(app.graph.node(app.selected().unwrap()).name(), "Domain");
+
+        app.handle_key(key(KeyCode::Backspace));
+        assert_eq!(app.graph.node(app.selected().unwrap()).name(), "Api");
+    }
+}
@@ -0,0 +1,36 @@
+use std::error::Error;
+
+use dotnet_lens::{
+    watch::{WatchError, WorkspaceWatcher},
+    workspace::Workspace,
+};
+
+/// Calls `emit` with the workspace, then again every time one of its projects changes, until the
+/// watcher fails.
+///
+/// Errors while parsing a changed project (ex: a file saved halfway through an edit) are
+/// printed and do not stop the watch.
+pub fn watch<F>(workspace: Workspace, mut emit: F) -> Result<(), Box<dyn Error>>
+where
+    F: FnMut(&Workspace) -> Result<(), Box<dyn Error>>,
+{
+    emit(&workspace)?;
+
+    let mut watcher = WorkspaceWatcher::new(workspace)?;
+
+    loop {
+        match watcher.wait() {
+            Ok(update) => {
+                for failure in update.failures() {
+                    eprintln!("error: {}", failure);
+                }
+
+                if !update.events().is_empty() {
+                    emit(watcher.workspace())?;
+                }
+            }
+            Err(WatchError::WorkspaceError(error)) => eprintln!("error: {}", error),
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
@@ -0,0 +1,588 @@
+//! A compact binary encoding of workspaces and dependency graphs, so a model loaded once (ex: by
+//! a CI job) can be persisted and reloaded without searching and parsing the projects again.
+//!
+//! The encoding uses [bincode](https://docs.rs/bincode) behind a small header made of the
+//! [`MAGIC`] bytes and the [`BINARY_FORMAT_VERSION`], and decoding stops once
+//! [`MAX_DECODED_SIZE`] bytes were read, so a corrupted length cannot allocate unbounded memory.
+//! Unlike the JSON snapshots (see
+//! [`Workspace::to_snapshot`]), the encoding is not meant to be stable across versions of the
+//! library: a file written with another format version is rejected and should be regenerated.
+
+use std::{
+    io::{self, Read, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use bincode::Options;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    assembly::AssemblyMetadata,
+    condition::ConditionalProperty,
+    graph::DependencyGraph,
+    intern::Interner,
+    migration::MigrationMetadata,
+    packaging::PackagingMetadata,
+    source_link::RepositoryMetadata,
+    strictness::StrictnessMetadata,
+    workloads::WorkloadMetadata,
+    workspace::{PathMapping, Workspace},
+    PackageLicense, PackageReference, Project, ProjectLanguage, ProjectReference,
+};
+
+/// The bytes every encoded file starts with.
+pub const MAGIC: [u8; 4] = *b"DLNS";
+
+/// The version of the encoding written by this library, increased whenever the encoded shape
+/// changes.
+pub const BINARY_FORMAT_VERSION: u32 = 10;
+
+/// The maximum number of bytes decoded after the header, past which the content is rejected.
+pub const MAX_DECODED_SIZE: u64 = 1 << 30;
+
+const WORKSPACE_KIND: u8 = 1;
+const GRAPH_KIND: u8 = 2;
+
+/// Encodes a workspace, with its projects and path mappings.
+///
+/// # Errors
+///
+/// This function will return an error if the workspace could not be written.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use dotnet_lens::{binary, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// binary::write_workspace(File::create("workspace.bin")?, &workspace)?;
+///
+/// // in a later step
+/// let workspace = binary::read_workspace(File::open("workspace.bin")?)?;
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write_workspace<W>(writer: W, workspace: &Workspace) -> Result<(), BinaryError>
+where
+    W: Write,
+{
+    let record = WorkspaceRecord {
+        root: workspace.root().clone(),
+        projects: workspace
+            .projects()
+            .iter()
+            .map(ProjectRecord::from)
+            .collect(),
+        path_mappings: workspace
+            .path_mappings()
+            .iter()
+            .map(|mapping| (mapping.from().clone(), mapping.to().clone()))
+            .collect(),
+    };
+
+    write(writer, WORKSPACE_KIND, &record)
+}
+
+/// Decodes a workspace encoded with [`write_workspace`].
+///
+/// # Errors
+///
+/// This function will return an error if the content could not be read, was not written by
+/// [`write_workspace`] or was written with another [`BINARY_FORMAT_VERSION`].
+pub fn read_workspace<R>(reader: R) -> Result<Workspace, BinaryError>
+where
+    R: Read,
+{
+    let record: WorkspaceRecord = read(reader, WORKSPACE_KIND)?;
+
+    let strings = Interner::new();
+    let projects = record
+        .projects
+        .into_iter()
+        .map(|record| {
+            let mut project = Project::from(record);
+            strings.share(&mut project);
+            project
+        })
+        .collect();
+    let mut workspace = Workspace::from_projects(record.root, projects);
+
+    for (from, to) in record.path_mappings {
+        workspace.add_path_mapping(PathMapping::new(from, to));
+    }
+
+    Ok(workspace)
+}
+
+/// Encodes a dependency graph.
+///
+/// # Errors
+///
+/// This function will return an error if the graph could not be written.
+pub fn write_graph<W>(writer: W, graph: &DependencyGraph) -> Result<(), BinaryError>
+where
+    W: Write,
+{
+    write(writer, GRAPH_KIND, graph)
+}
+
+/// Decodes a dependency graph encoded with [`write_graph`].
+///
+/// # Errors
+///
+/// This function will return an error if the content could not be read, was not written by
+/// [`write_graph`] or was written with another [`BINARY_FORMAT_VERSION`].
+pub fn read_graph<R>(reader: R) -> Result<DependencyGraph, BinaryError>
+where
+    R: Read,
+{
+    read(reader, GRAPH_KIND)
+}
+
+fn write<W, T>(mut writer: W, kind: u8, value: &T) -> Result<(), BinaryError>
+where
+    W: Write,
+    T: Serialize + ?Sized,
+{
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&BINARY_FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(&[kind])?;
+
+    bincode::DefaultOptions::new().serialize_into(writer, value)?;
+
+    Ok(())
+}
+
+fn read<R, T>(mut reader: R, kind: u8) -> Result<T, BinaryError>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    let mut header = [0; 9];
+    reader.read_exact(&mut header)?;
+
+    if header[..4] != MAGIC {
+        return Err(BinaryError::InvalidHeader);
+    }
+
+    let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+
+    if version != BINARY_FORMAT_VERSION {
+        return Err(BinaryError::UnsupportedVersion(version));
+    }
+
+    if header[8] != kind {
+        return Err(BinaryError::InvalidHeader);
+    }
+
+    Ok(bincode::DefaultOptions::new()
+        .with_limit(MAX_DECODED_SIZE)
+        .deserialize_from(reader)?)
+}
+
+/// The encoded shape of a workspace. The projects are encoded as records without the optional
+/// fields skipped by their JSON shape, which bincode cannot read back.
+#[derive(Serialize, Deserialize)]
+struct WorkspaceRecord {
+    root: PathBuf,
+    projects: Vec<ProjectRecord>,
+    path_mappings: Vec<(PathBuf, PathBuf)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProjectRecord {
+    name: String,
+    language: ProjectLanguage,
+    path: PathBuf,
+    target_framework: Option<String>,
+    license_expression: Option<String>,
+    target_framework_candidates: Vec<String>,
+    target_frameworks: Vec<String>,
+    repository: RepositoryRecord,
+    assembly: AssemblyRecord,
+    packaging: PackagingRecord,
+    strictness: StrictnessRecord,
+    workloads: WorkloadsRecord,
+    migration: MigrationRecord,
+    tags: Vec<String>,
+    conditional_properties: Vec<(String, String, String)>,
+    project_references: Vec<ProjectReferenceRecord>,
+    package_references: Vec<PackageReferenceRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RepositoryRecord {
+    url: Option<String>,
+    repository_type: Option<String>,
+    publish_url: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AssemblyRecord {
+    name: Option<String>,
+    version: Option<String>,
+    file_version: Option<String>,
+    informational_version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PackagingRecord {
+    is_packable: Option<bool>,
+    generate_package_on_build: Option<bool>,
+    pack_as_tool: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StrictnessRecord {
+    treat_warnings_as_errors: Option<bool>,
+    no_warn: Vec<String>,
+    analysis_level: Option<String>,
+    nullable: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WorkloadsRecord {
+    use_maui: Option<bool>,
+    run_aot_compilation: Option<bool>,
+    wasm_build_native: Option<bool>,
+    is_aspire_host: Option<bool>,
+    runtime_identifiers: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MigrationRecord {
+    sdks: Vec<String>,
+    packages_config: Option<PathBuf>,
+    target_framework_version: Option<String>,
+    gac_references: Vec<String>,
+    com_references: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProjectReferenceRecord {
+    name: String,
+    path: PathBuf,
+    target_framework: Option<String>,
+    condition: Option<String>,
+    output_item_type: Option<String>,
+    reference_output_assembly: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PackageReferenceRecord {
+    name: String,
+    version: String,
+    license: Option<PackageLicense>,
+    target_framework: Option<String>,
+    assets: AssetsRecord,
+    condition: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AssetsRecord {
+    private_assets: Option<String>,
+    include_assets: Option<String>,
+    exclude_assets: Option<String>,
+}
+
+impl From<&Project> for ProjectRecord {
+    fn from(project: &Project) -> Self {
+        let repository = &project.repository;
+        let assembly = &project.assembly;
+        let packaging = &project.packaging;
+        let strictness = &project.strictness;
+        let workloads = &project.workloads;
+        let migration = &project.migration;
+
+        Self {
+            name: project.name.clone(),
+            language: project.language,
+            path: project.path.clone(),
+            target_framework: project.target_framework.clone(),
+            license_expression: project.license_expression.clone(),
+            target_framework_candidates: project.target_framework_candidates.clone(),
+            target_frameworks: project.target_frameworks.clone(),
+            repository: RepositoryRecord {
+                url: repository.url().cloned(),
+                repository_type: repository.repository_type().cloned(),
+                publish_url: repository.publish_url(),
+            },
+            assembly: AssemblyRecord {
+                name: assembly.name().cloned(),
+                version: assembly.version().cloned(),
+                file_version: assembly.file_version().cloned(),
+                informational_version: assembly.informational_version().cloned(),
+            },
+            packaging: PackagingRecord {
+                is_packable: packaging.is_packable(),
+                generate_package_on_build: packaging.generate_package_on_build(),
+                pack_as_tool: packaging.pack_as_tool(),
+            },
+            strictness: StrictnessRecord {
+                treat_warnings_as_errors: strictness.treat_warnings_as_errors(),
+                no_warn: strictness.no_warn().clone(),
+                analysis_level: strictness.analysis_level().cloned(),
+                nullable: strictness.nullable().cloned(),
+            },
+            workloads: WorkloadsRecord {
+                use_maui: workloads.use_maui(),
+                run_aot_compilation: workloads.run_aot_compilation(),
+                wasm_build_native: workloads.wasm_build_native(),
+                is_aspire_host: workloads.is_aspire_host(),
+                runtime_identifiers: workloads.runtime_identifiers().clone(),
+            },
+            migration: MigrationRecord {
+                sdks: migration.sdks().clone(),
+                packages_config: migration.packages_config().cloned(),
+                target_framework_version: migration.target_framework_version().cloned(),
+                gac_references: migration.gac_references().clone(),
+                com_references: migration.com_references().clone(),
+            },
+            tags: project.tags.clone(),
+            conditional_properties: project
+                .conditional_properties
+                .iter()
+                .map(|property| {
+                    (
+                        property.name().clone(),
+                        property.value().clone(),
+                        property.condition().clone(),
+                    )
+                })
+                .collect(),
+            project_references: project
+                .project_references
+                .iter()
+                .map(|reference| ProjectReferenceRecord {
+                    name: reference.name.to_string(),
+                    path: reference.path.clone(),
+                    target_framework: reference.target_framework.clone(),
+                    condition: reference.condition.clone(),
+                    output_item_type: reference.output_item_type.clone(),
+                    reference_output_assembly: reference.reference_output_assembly.clone(),
+                })
+                .collect(),
+            package_references: project
+                .package_references
+                .iter()
+                .map(|reference| PackageReferenceRecord {
+                    name: reference.name.to_string(),
+                    version: reference.version.to_string(),
+                    license: reference.license.clone(),
+                    target_framework: reference.target_framework.clone(),
+                    assets: AssetsRecord {
+                        private_assets: reference.private_assets.clone(),
+                        include_assets: reference.include_assets.clone(),
+                        exclude_assets: reference.exclude_assets.clone(),
+                    },
+                    condition: reference.condition.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<ProjectRecord> for Project {
+    fn from(record: ProjectRecord) -> Self {
+        let mut repository = RepositoryMetadata::new();
+        repository.set_url(record.repository.url);
+        repository.set_repository_type(record.repository.repository_type);
+        repository.set_publish_url(record.repository.publish_url);
+
+        let mut assembly = AssemblyMetadata::new();
+        assembly.set_name(record.assembly.name);
+        assembly.set_version(record.assembly.version);
+        assembly.set_file_version(record.assembly.file_version);
+        assembly.set_informational_version(record.assembly.informational_version);
+
+        let mut packaging = PackagingMetadata::new();
+        packaging.set_is_packable(record.packaging.is_packable);
+        packaging.set_generate_package_on_build(record.packaging.generate_package_on_build);
+        packaging.set_pack_as_tool(record.packaging.pack_as_tool);
+
+        let mut strictness = StrictnessMetadata::new();
+        strictness.set_treat_warnings_as_errors(record.strictness.treat_warnings_as_errors);
+        strictness.set_no_warn(record.strictness.no_warn);
+        strictness.set_analysis_level(record.strictness.analysis_level);
+        strictness.set_nullable(record.strictness.nullable);
+
+        let mut workloads = WorkloadMetadata::new();
+        workloads.set_use_maui(record.workloads.use_maui);
+        workloads.set_run_aot_compilation(record.workloads.run_aot_compilation);
+        workloads.set_wasm_build_native(record.workloads.wasm_build_native);
+        workloads.set_is_aspire_host(record.workloads.is_aspire_host);
+        workloads.set_runtime_identifiers(record.workloads.runtime_identifiers);
+
+        let mut migration = MigrationMetadata::new();
+        migration.set_sdks(record.migration.sdks);
+        migration.set_packages_config(record.migration.packages_config);
+        migration.set_target_framework_version(record.migration.target_framework_version);
+        for name in record.migration.gac_references {
+            migration.add_gac_reference(name);
+        }
+        for name in record.migration.com_references {
+            migration.add_com_reference(name);
+        }
+
+        Self {
+            name: record.name,
+            language: record.language,
+            path: record.path,
+            target_framework: record.target_framework,
+            license_expression: record.license_expression,
+            target_framework_candidates: record.target_framework_candidates,
+            target_frameworks: record.target_frameworks,
+            repository,
+            assembly,
+            packaging,
+            strictness,
+            workloads,
+            migration,
+            tags: record.tags,
+            conditional_properties: record
+                .conditional_properties
+                .into_iter()
+                .map(|(name, value, condition)| ConditionalProperty::new(name, value, condition))
+                .collect(),
+            project_references: record
+                .project_references
+                .into_iter()
+                .map(|reference| ProjectReference {
+                    name: Arc::new(reference.name),
+                    path: reference.path,
+                    target_framework: reference.target_framework,
+                    condition: reference.condition,
+                    output_item_type: reference.output_item_type,
+                    reference_output_assembly: reference.reference_output_assembly,
+                })
+                .collect(),
+            package_references: record
+                .package_references
+                .into_iter()
+                .map(|reference| PackageReference {
+                    name: Arc::new(reference.name),
+                    version: Arc::new(reference.version),
+                    license: reference.license,
+                    target_framework: reference.target_framework,
+                    private_assets: reference.assets.private_assets,
+                    include_assets: reference.assets.include_assets,
+                    exclude_assets: reference.assets.exclude_assets,
+                    condition: reference.condition,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Represents errors that can occur while encoding or decoding a workspace or a graph.
+#[derive(Debug, Error)]
+pub enum BinaryError {
+    /// The content could not be read or written.
+    #[error("the binary content could not be read or written")]
+    Io(#[from] io::Error),
+    /// The content could not be encoded or decoded.
+    #[error("the binary content could not be encoded or decoded")]
+    Encoding(#[from] bincode::Error),
+    /// The content does not start with the expected header.
+    #[error("the binary content does not have the expected header")]
+    InvalidHeader,
+    /// The content was written with another [`BINARY_FORMAT_VERSION`].
+    #[error("the binary format version {0} is not supported")]
+    UnsupportedVersion(u32),
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::{
+        graph::DependencyGraph,
+        workspace::{PathMapping, WorkspaceBuilder},
+    };
+
+    use super::{
+        read_graph, read_workspace, write_graph, write_workspace, BinaryError,
+        BINARY_FORMAT_VERSION, MAGIC,
+    };
+
+    #[test]
+    pub fn reload_workspace_and_graph() {
+        // given
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project(
+            "src/Api/Api.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <Version>1.2.0</Version>
+    <IsPackable>false</IsPackable>
+  </PropertyGroup>
+  <ItemGroup>
+    <ProjectReference Include="../Domain/Domain.csproj" />
+    <PackageReference Include="Serilog" Version="3.1.1" />
+  </ItemGroup>
+</Project>"#,
+        );
+        builder.add_project(
+            "src/Domain/Domain.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk" />"#,
+        );
+
+        let mut workspace = builder.build().unwrap();
+        workspace.add_path_mapping(PathMapping::new("/old", "/repo"));
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+
+        // when
+        let mut workspace_bytes = vec![];
+        write_workspace(&mut workspace_bytes, &workspace).unwrap();
+        let mut graph_bytes = vec![];
+        write_graph(&mut graph_bytes, &graph).unwrap();
+
+        // then
+        assert_eq!(
+            read_workspace(workspace_bytes.as_slice()).unwrap(),
+            workspace
+        );
+        let read = read_graph(graph_bytes.as_slice()).unwrap();
+        assert_eq!(read, graph);
+        assert_eq!(
+            read.dependencies(0).collect::<Vec<_>>(),
+            graph.dependencies(0).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            read_workspace(workspace_bytes.as_slice()).unwrap().root(),
+            &PathBuf::from("/repo")
+        );
+        assert!(matches!(
+            read_workspace(graph_bytes.as_slice()),
+            Err(BinaryError::InvalidHeader)
+        ));
+
+        workspace_bytes[4] = 99;
+        assert!(matches!(
+            read_workspace(workspace_bytes.as_slice()),
+            Err(BinaryError::UnsupportedVersion(99))
+        ));
+    }
+
+    #[test]
+    pub fn reject_lengths_past_the_limit() {
+        // given
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(BINARY_FORMAT_VERSION.to_le_bytes());
+        bytes.push(1);
+        // the length of the root path, encoded as a u64 larger than the limit
+        bytes.push(253);
+        bytes.extend(u64::MAX.to_le_bytes());
+
+        // when
+        let result = read_workspace(bytes.as_slice());
+
+        // then
+        assert!(matches!(result, Err(BinaryError::Encoding(_))));
+    }
+}
@@ -0,0 +1,905 @@
+//! Reading of MSBuild binary logs (`msbuild -bl`), to compare the static analysis of the
+//! projects with the projects and references a real build evaluated.
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use thiserror::Error;
+
+use crate::{
+    workspace::{normalize_path, Workspace},
+    PackageReference,
+};
+
+/// The oldest format version that can be read, the first one where every record is prefixed
+/// with its length (MSBuild 17.8).
+pub const MIN_SUPPORTED_VERSION: i32 = 18;
+
+const END_OF_FILE: u32 = 0;
+const PROJECT_EVALUATION_FINISHED: u32 = 15;
+const NAME_VALUE_LIST: u32 = 23;
+const STRING: u32 = 24;
+
+/// The index of the first string and name-value list record, lower indexes are reserved.
+const RECORD_START_INDEX: usize = 10;
+/// The index of the empty string.
+const EMPTY_STRING_INDEX: usize = 1;
+
+const FIELD_BUILD_EVENT_CONTEXT: u32 = 1 << 0;
+const FIELD_HELP_KEYWORD: u32 = 1 << 1;
+const FIELD_MESSAGE: u32 = 1 << 2;
+const FIELD_SENDER_NAME: u32 = 1 << 3;
+const FIELD_THREAD_ID: u32 = 1 << 4;
+const FIELD_TIMESTAMP: u32 = 1 << 5;
+/// The fields that can precede the project file of an evaluation.
+const KNOWN_FIELDS: u32 = FIELD_BUILD_EVENT_CONTEXT
+    | FIELD_HELP_KEYWORD
+    | FIELD_MESSAGE
+    | FIELD_SENDER_NAME
+    | FIELD_THREAD_ID
+    | FIELD_TIMESTAMP;
+
+/// Represents the project evaluations recorded in an MSBuild binary log.
+///
+/// Only the evaluations are read: the properties and items they record are the result of the
+/// evaluation of the project file and every file it imports, with the global properties of the
+/// build. A project built for several target frameworks is evaluated once per target framework
+/// and once more for the outer build.
+///
+/// The log must be written with the evaluated properties and items (the default since MSBuild
+/// 17.0) and in format version 18 or later (MSBuild 17.8).
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::binlog::{discrepancies, BinaryLog};
+/// use dotnet_lens::workspace::Workspace;
+///
+/// let log = BinaryLog::load("msbuild.binlog")?;
+/// let workspace = Workspace::load(".")?;
+///
+/// for discrepancy in discrepancies(&workspace, &log) {
+///     println!(
+///         "{:?}: {} ({:?})",
+///         discrepancy.project(),
+///         discrepancy.reference(),
+///         discrepancy.kind()
+///     );
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BinaryLog {
+    version: i32,
+    evaluations: Vec<Evaluation>,
+}
+
+/// Represents the evaluation of a project recorded in a binary log.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Evaluation {
+    project_file: PathBuf,
+    properties: BTreeMap<String, String>,
+    items: Vec<EvaluatedItem>,
+}
+
+/// Represents an item of an evaluated project (ex: a `ProjectReference`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EvaluatedItem {
+    item_type: String,
+    include: String,
+    metadata: BTreeMap<String, String>,
+}
+
+impl BinaryLog {
+    /// Reads a binary log file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or is not a binary log
+    /// in a supported version.
+    pub fn load<P>(path: P) -> Result<Self, BinlogError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a binary log from its compressed content.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be decompressed or is not a
+    /// binary log in a supported version.
+    pub fn read<R>(reader: R) -> Result<Self, BinlogError>
+    where
+        R: Read,
+    {
+        let mut reader = RecordReader::new(GzDecoder::new(reader));
+
+        let version = reader.read_i32()?;
+
+        if version < MIN_SUPPORTED_VERSION {
+            return Err(BinlogError::UnsupportedVersion(version));
+        }
+
+        // the minimum version of the reader
+        reader.read_i32()?;
+
+        let mut log = Self {
+            version,
+            evaluations: vec![],
+        };
+
+        while let Some(kind) = reader.read_kind()? {
+            let mut record = Payload::new(reader.read_record()?);
+
+            match kind {
+                STRING => {
+                    let string = record.read_string()?;
+                    reader.strings.push(string);
+                }
+                NAME_VALUE_LIST => {
+                    let count = record.read_count()?;
+                    let list = (0..count)
+                        .map(|_| Ok((record.read_index()?, record.read_index()?)))
+                        .collect::<Result<Vec<_>, BinlogError>>()?;
+
+                    reader.lists.push(list);
+                }
+                PROJECT_EVALUATION_FINISHED => {
+                    if let Some(evaluation) = reader.read_evaluation(&mut record)? {
+                        log.evaluations.push(evaluation);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(log)
+    }
+
+    /// Returns the format version of the log.
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// Returns the project evaluations, in the order they finished.
+    pub fn evaluations(&self) -> &Vec<Evaluation> {
+        &self.evaluations
+    }
+
+    /// Returns the evaluations of the project file at `path`.
+    pub fn evaluations_of<P>(&self, path: P) -> impl Iterator<Item = &Evaluation>
+    where
+        P: AsRef<Path>,
+    {
+        let path = normalize_path(path.as_ref());
+
+        self.evaluations
+            .iter()
+            .filter(move |evaluation| *evaluation.project_file() == path)
+    }
+
+    /// Returns the evaluated project files, with the projects they reference in any of their
+    /// evaluations.
+    pub fn project_graph(&self) -> BTreeMap<&PathBuf, Vec<PathBuf>> {
+        let mut graph: BTreeMap<&PathBuf, Vec<PathBuf>> = BTreeMap::new();
+
+        for evaluation in &self.evaluations {
+            let references = graph.entry(evaluation.project_file()).or_default();
+
+            for reference in evaluation.project_references() {
+                if !references.contains(&reference) {
+                    references.push(reference);
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+impl Evaluation {
+    /// Returns the path of the evaluated project file, with forward slashes.
+    pub fn project_file(&self) -> &PathBuf {
+        &self.project_file
+    }
+
+    /// Returns the evaluated properties, by name.
+    pub fn properties(&self) -> &BTreeMap<String, String> {
+        &self.properties
+    }
+
+    /// Returns the value of a property, comparing the names ignoring case like MSBuild does.
+    pub fn property(&self, name: &str) -> Option<&String> {
+        self.properties
+            .iter()
+            .find(|(property, _)| property.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the target framework of the evaluation, or `None` for the outer build of a
+    /// project with several target frameworks.
+    pub fn target_framework(&self) -> Option<&String> {
+        self.property("TargetFramework")
+            .filter(|target_framework| !target_framework.is_empty())
+    }
+
+    /// Returns the evaluated items, in the order they were recorded.
+    pub fn items(&self) -> &Vec<EvaluatedItem> {
+        &self.items
+    }
+
+    /// Returns the items of the given type, comparing the types ignoring case.
+    pub fn items_of<'a>(&'a self, item_type: &'a str) -> impl Iterator<Item = &'a EvaluatedItem> {
+        self.items
+            .iter()
+            .filter(move |item| item.item_type.eq_ignore_ascii_case(item_type))
+    }
+
+    /// Returns the paths of the referenced projects, relative to the directory of the log's
+    /// project file, with forward slashes.
+    pub fn project_references(&self) -> Vec<PathBuf> {
+        let directory = self.project_file.parent().unwrap_or(Path::new(""));
+
+        self.items_of("ProjectReference")
+            .map(|item| normalize_path(&directory.join(item.include.replace('\\', "/"))))
+            .collect()
+    }
+
+    /// Returns the referenced packages, with the version the build used (the `Version`
+    /// metadata, which is set from `Directory.Packages.props` with central package management).
+    pub fn package_references(&self) -> Vec<PackageReference> {
+        self.items_of("PackageReference")
+            .map(|item| {
+                PackageReference::new(
+                    item.include.clone(),
+                    item.metadata("Version").cloned().unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl EvaluatedItem {
+    /// Returns the type of the item (ex: `PackageReference`).
+    pub fn item_type(&self) -> &String {
+        &self.item_type
+    }
+
+    /// Returns the evaluated `Include` of the item.
+    pub fn include(&self) -> &String {
+        &self.include
+    }
+
+    /// Returns the metadata of the item, by name.
+    pub fn all_metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Returns the value of a metadata, comparing the names ignoring case.
+    pub fn metadata(&self, name: &str) -> Option<&String> {
+        self.metadata
+            .iter()
+            .find(|(metadata, _)| metadata.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+}
+
+/// Represents how a reference differs between the static analysis and the build.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DiscrepancyKind {
+    /// The project was evaluated by the build but is not part of the workspace.
+    ProjectNotFound,
+    /// The reference was evaluated by the build but is not declared in the project file (ex: it
+    /// is added by an imported file).
+    Missing,
+    /// The reference is declared in the project file but none of the evaluations of the build
+    /// has it (ex: its condition was false).
+    NotBuilt,
+    /// The package was built with another version than the declared one.
+    VersionDiffers,
+}
+
+/// Represents a reference, or a project, that differs between the static analysis and the build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildDiscrepancy {
+    project: PathBuf,
+    reference: String,
+    kind: DiscrepancyKind,
+    declared: Option<String>,
+    built: Option<String>,
+}
+
+impl BuildDiscrepancy {
+    /// Returns the path of the project file, as recorded by the build.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the name of the package, or the path of the referenced project. It is empty when
+    /// the project itself was not found.
+    pub fn reference(&self) -> &String {
+        &self.reference
+    }
+
+    /// Returns how the reference differs.
+    pub fn kind(&self) -> DiscrepancyKind {
+        self.kind
+    }
+
+    /// Returns the version declared in the project file, for packages.
+    pub fn declared(&self) -> Option<&String> {
+        self.declared.as_ref()
+    }
+
+    /// Returns the version the build used, for packages.
+    pub fn built(&self) -> Option<&String> {
+        self.built.as_ref()
+    }
+}
+
+/// Compares the projects of the workspace with the evaluations of the build, returning the
+/// evaluated projects that are not in the workspace and, for the others, the project and package
+/// references that only one of them has and the packages built with another version.
+///
+/// The references of every evaluation of a project are combined. Projects are matched by path,
+/// so the workspace should be loaded from the directory that was built. Packages are compared
+/// ignoring case, and declared versions that were not expanded (ex: `$(SerilogVersion)`) are not
+/// compared.
+pub fn discrepancies(workspace: &Workspace, log: &BinaryLog) -> Vec<BuildDiscrepancy> {
+    let mut discrepancies = vec![];
+
+    for (path, built_references) in log.project_graph() {
+        let discrepancy = |reference: String, kind, declared, built| BuildDiscrepancy {
+            project: path.clone(),
+            reference,
+            kind,
+            declared,
+            built,
+        };
+
+        let Some(project) = workspace.project_by_path(path) else {
+            discrepancies.push(discrepancy(
+                String::new(),
+                DiscrepancyKind::ProjectNotFound,
+                None,
+                None,
+            ));
+            continue;
+        };
+
+        let directory = path.parent().unwrap_or(Path::new(""));
+        let declared_references: Vec<PathBuf> = project
+            .project_references()
+            .iter()
+            .map(|reference| normalize_path(&directory.join(reference.path())))
+            .collect();
+
+        for reference in &built_references {
+            if !declared_references.contains(reference) {
+                let reference = reference.to_string_lossy().to_string();
+                discrepancies.push(discrepancy(reference, DiscrepancyKind::Missing, None, None));
+            }
+        }
+
+        for reference in &declared_references {
+            if !built_references.contains(reference) {
+                let reference = reference.to_string_lossy().to_string();
+                discrepancies.push(discrepancy(
+                    reference,
+                    DiscrepancyKind::NotBuilt,
+                    None,
+                    None,
+                ));
+            }
+        }
+
+        let mut built_packages: Vec<PackageReference> = vec![];
+
+        for package in log
+            .evaluations_of(path)
+            .flat_map(|evaluation| evaluation.package_references())
+        {
+            if !built_packages.contains(&package) {
+                built_packages.push(package);
+            }
+        }
+
+        for built in &built_packages {
+            let declared = project
+                .package_references()
+                .iter()
+                .find(|declared| declared.name().eq_ignore_ascii_case(built.name()));
+
+            let kind = match declared {
+                None => DiscrepancyKind::Missing,
+                Some(declared) if differs(declared.version(), built.version()) => {
+                    DiscrepancyKind::VersionDiffers
+                }
+                Some(_) => continue,
+            };
+
+            discrepancies.push(discrepancy(
+                built.name().to_string(),
+                kind,
+                declared.map(|declared| declared.version().to_string()),
+                Some(built.version().to_string()),
+            ));
+        }
+
+        for declared in project.package_references() {
+            if !built_packages
+                .iter()
+                .any(|built| built.name().eq_ignore_ascii_case(declared.name()))
+            {
+                discrepancies.push(discrepancy(
+                    declared.name().to_string(),
+                    DiscrepancyKind::NotBuilt,
+                    Some(declared.version().to_string()),
+                    None,
+                ));
+            }
+        }
+    }
+
+    discrepancies
+}
+
+fn differs(declared: &str, built: &str) -> bool {
+    !declared.is_empty() && !declared.contains("$(") && !declared.eq_ignore_ascii_case(built)
+}
+
+/// Reads the records of a decompressed binary log, keeping the strings and name-value lists
+/// the events refer to.
+struct RecordReader<R> {
+    reader: R,
+    strings: Vec<String>,
+    lists: Vec<Vec<(usize, usize)>>,
+}
+
+impl<R> RecordReader<R>
+where
+    R: Read,
+{
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            strings: vec![],
+            lists: vec![],
+        }
+    }
+
+    fn read_i32(&mut self) -> Result<i32, BinlogError> {
+        let mut bytes = [0; 4];
+        self.reader.read_exact(&mut bytes)?;
+
+        Ok(i32::from_le_bytes(bytes))
+    }
+
+    /// Reads the kind of the next record, or `None` at the end of the log.
+    fn read_kind(&mut self) -> Result<Option<u32>, BinlogError> {
+        match read_7bit(&mut self.reader) {
+            Ok(END_OF_FILE) => Ok(None),
+            Ok(kind) => Ok(Some(kind)),
+            Err(BinlogError::Io(error)) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn read_record(&mut self) -> Result<Vec<u8>, BinlogError> {
+        let length = read_7bit(&mut self.reader)? as usize;
+
+        // the length is not trusted: the record grows with the bytes actually read, so a
+        // corrupted length fails at the end of the log instead of allocating it upfront
+        let mut record = vec![];
+        (&mut self.reader)
+            .take(length as u64)
+            .read_to_end(&mut record)?;
+
+        if record.len() != length {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        Ok(record)
+    }
+
+    fn string(&self, index: usize) -> Result<Option<String>, BinlogError> {
+        match index {
+            0 => Ok(None),
+            EMPTY_STRING_INDEX => Ok(Some(String::new())),
+            index => self
+                .strings
+                .get(index.wrapping_sub(RECORD_START_INDEX))
+                .cloned()
+                .map(Some)
+                .ok_or(BinlogError::InvalidRecord),
+        }
+    }
+
+    fn read_dictionary(
+        &self,
+        record: &mut Payload,
+    ) -> Result<BTreeMap<String, String>, BinlogError> {
+        let index = record.read_index()?;
+
+        if index == 0 {
+            return Ok(BTreeMap::new());
+        }
+
+        let list = self
+            .lists
+            .get(index.wrapping_sub(RECORD_START_INDEX))
+            .ok_or(BinlogError::InvalidRecord)?;
+
+        list.iter()
+            .map(|(name, value)| {
+                Ok((
+                    self.string(*name)?.unwrap_or_default(),
+                    self.string(*value)?.unwrap_or_default(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Reads a `ProjectEvaluationFinished` event, or returns `None` if it has fields that are
+    /// not read (ex: extended data), since the project file follows them.
+    fn read_evaluation(&self, record: &mut Payload) -> Result<Option<Evaluation>, BinlogError> {
+        let fields = record.read_7bit()?;
+
+        if fields & !KNOWN_FIELDS != 0 {
+            return Ok(None);
+        }
+
+        if fields & FIELD_MESSAGE != 0 {
+            record.read_index()?;
+        }
+
+        if fields & FIELD_BUILD_EVENT_CONTEXT != 0 {
+            // node, project context, target, task, submission, project instance and evaluation
+            for _ in 0..7 {
+                record.read_7bit()?;
+            }
+        }
+
+        if fields & FIELD_THREAD_ID != 0 {
+            record.read_7bit()?;
+        }
+
+        if fields & FIELD_HELP_KEYWORD != 0 {
+            record.read_index()?;
+        }
+
+        if fields & FIELD_SENDER_NAME != 0 {
+            record.read_index()?;
+        }
+
+        if fields & FIELD_TIMESTAMP != 0 {
+            record.skip(8)?;
+            record.read_7bit()?;
+        }
+
+        let project_file = self
+            .string(record.read_index()?)?
+            .unwrap_or_default()
+            .replace('\\', "/");
+
+        // the global properties
+        if record.read_bool()? {
+            self.read_dictionary(record)?;
+        }
+
+        let properties = self.read_dictionary(record)?;
+        let mut items = vec![];
+
+        while let Some(item_type) = self.string(record.read_index()?)? {
+            if item_type.is_empty() {
+                break;
+            }
+
+            for _ in 0..record.read_count()? {
+                let include = self.string(record.read_index()?)?.unwrap_or_default();
+                let metadata = self.read_dictionary(record)?;
+
+                items.push(EvaluatedItem {
+                    item_type: item_type.clone(),
+                    include,
+                    metadata,
+                });
+            }
+        }
+
+        Ok(Some(Evaluation {
+            project_file: normalize_path(Path::new(&project_file)),
+            properties,
+            items,
+        }))
+    }
+}
+
+/// The content of a record.
+struct Payload {
+    content: io::Cursor<Vec<u8>>,
+}
+
+impl Payload {
+    fn new(content: Vec<u8>) -> Self {
+        Self {
+            content: io::Cursor::new(content),
+        }
+    }
+
+    fn read_7bit(&mut self) -> Result<u32, BinlogError> {
+        read_7bit(&mut self.content)
+    }
+
+    fn read_index(&mut self) -> Result<usize, BinlogError> {
+        Ok(self.read_7bit()? as usize)
+    }
+
+    fn read_count(&mut self) -> Result<usize, BinlogError> {
+        self.read_index()
+    }
+
+    fn read_bool(&mut self) -> Result<bool, BinlogError> {
+        let mut byte = [0];
+        self.content.read_exact(&mut byte)?;
+
+        Ok(byte[0] != 0)
+    }
+
+    fn skip(&mut self, length: u64) -> Result<(), BinlogError> {
+        let position = self.content.position() + length;
+
+        if position > self.content.get_ref().len() as u64 {
+            return Err(BinlogError::InvalidRecord);
+        }
+
+        self.content.set_position(position);
+
+        Ok(())
+    }
+
+    /// Reads a string prefixed with its length in bytes, like .NET's `BinaryReader.ReadString`.
+    fn read_string(&mut self) -> Result<String, BinlogError> {
+        let length = self.read_7bit()? as usize;
+        let remaining =
+            (self.content.get_ref().len() as u64).saturating_sub(self.content.position());
+
+        if length as u64 > remaining {
+            return Err(BinlogError::InvalidRecord);
+        }
+
+        let mut bytes = vec![0; length];
+        self.content.read_exact(&mut bytes)?;
+
+        String::from_utf8(bytes).map_err(|_| BinlogError::InvalidRecord)
+    }
+}
+
+/// Reads an integer encoded 7 bits at a time, least significant first, like .NET's
+/// `BinaryReader.Read7BitEncodedInt`.
+fn read_7bit<R>(reader: &mut R) -> Result<u32, BinlogError>
+where
+    R: Read,
+{
+    let mut value = 0;
+
+    for shift in (0..35).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+
+        value |= u32::from(byte[0] & 0x7f) << shift;
+
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(BinlogError::InvalidRecord)
+}
+
+/// Represents errors that can occur while reading a binary log.
+#[derive(Debug, Error)]
+pub enum BinlogError {
+    /// The log could not be read or decompressed.
+    #[error("the binary log could not be read")]
+    Io(#[from] io::Error),
+    /// The format version of the log is older than [`MIN_SUPPORTED_VERSION`].
+    #[error("the binary log format version {0} is not supported")]
+    UnsupportedVersion(i32),
+    /// A record is not valid.
+    #[error("the binary log has an invalid record")]
+    InvalidRecord,
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use crate::{parser::parse, workspace::Workspace};
+
+    use super::{discrepancies, BinaryLog, BinlogError, DiscrepancyKind};
+
+    fn write_7bit(output: &mut Vec<u8>, mut value: u32) {
+        while value >= 0x80 {
+            output.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+
+        output.push(value as u8);
+    }
+
+    fn write_record(output: &mut Vec<u8>, kind: u32, record: &[u8]) {
+        write_7bit(output, kind);
+        write_7bit(output, record.len() as u32);
+        output.extend_from_slice(record);
+    }
+
+    /// Writes a binary log with the strings, name-value lists and evaluations, whose records are
+    /// written as they are.
+    fn binary_log(version: i32, records: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut content = vec![];
+        content.extend_from_slice(&version.to_le_bytes());
+        content.extend_from_slice(&18i32.to_le_bytes());
+
+        for (kind, record) in records {
+            write_record(&mut content, *kind, record);
+        }
+
+        write_7bit(&mut content, 0);
+
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&content).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn string(value: &str) -> (u32, Vec<u8>) {
+        let mut record = vec![];
+        write_7bit(&mut record, value.len() as u32);
+        record.extend_from_slice(value.as_bytes());
+
+        (24, record)
+    }
+
+    fn list(pairs: &[(u32, u32)]) -> (u32, Vec<u8>) {
+        let mut record = vec![];
+        write_7bit(&mut record, pairs.len() as u32);
+
+        for (name, value) in pairs {
+            write_7bit(&mut record, *name);
+            write_7bit(&mut record, *value);
+        }
+
+        (23, record)
+    }
+
+    #[test]
+    pub fn read_evaluations() {
+        // given
+        let strings = [
+            "/repo/src/Api/Api.csproj",  // 10
+            "TargetFramework",           // 11
+            "net8.0",                    // 12
+            "ProjectReference",          // 13
+            "..\\Domain\\Domain.csproj", // 14
+            "PackageReference",          // 15
+            "Serilog",                   // 16
+            "Version",                   // 17
+            "3.1.1",                     // 18
+            "Polly",                     // 19
+            "8.4.1",                     // 20
+            "..\\Legacy\\Legacy.csproj", // 21
+        ];
+
+        let mut records: Vec<(u32, Vec<u8>)> = strings.iter().map(|value| string(value)).collect();
+        records.push(list(&[(11, 12)])); // 10: the properties
+        records.push(list(&[(17, 18)])); // 11: the metadata of Serilog
+        records.push(list(&[(17, 20)])); // 12: the metadata of Polly
+
+        let mut evaluation = vec![];
+        // the build event context and timestamp fields
+        write_7bit(&mut evaluation, 1 | 1 << 5);
+        for _ in 0..7 {
+            write_7bit(&mut evaluation, 1);
+        }
+        evaluation.extend_from_slice(&[0; 8]);
+        write_7bit(&mut evaluation, 1);
+        // the project file, without global properties
+        write_7bit(&mut evaluation, 10);
+        evaluation.push(0);
+        write_7bit(&mut evaluation, 10);
+        // one project reference and two package references
+        write_7bit(&mut evaluation, 13);
+        write_7bit(&mut evaluation, 1);
+        write_7bit(&mut evaluation, 14);
+        write_7bit(&mut evaluation, 0);
+        write_7bit(&mut evaluation, 15);
+        write_7bit(&mut evaluation, 2);
+        write_7bit(&mut evaluation, 16);
+        write_7bit(&mut evaluation, 11);
+        write_7bit(&mut evaluation, 19);
+        write_7bit(&mut evaluation, 12);
+        write_7bit(&mut evaluation, 0);
+        // the profiler result
+        evaluation.push(0);
+        records.push((15, evaluation));
+
+        // an event that is not read
+        records.push((11, vec![1, 2, 3]));
+
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="..\Legacy\Legacy.csproj" />
+    <PackageReference Include="serilog" Version="3.1.0" />
+  </ItemGroup>
+</Project>"#;
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![parse(Cursor::new(content), "/repo/src/Api/Api.csproj").unwrap()],
+        );
+
+        // when
+        let log = BinaryLog::read(Cursor::new(binary_log(18, &records))).unwrap();
+        let discrepancies = discrepancies(&workspace, &log);
+
+        // then
+        assert_eq!(log.evaluations().len(), 1);
+
+        let evaluation = &log.evaluations()[0];
+        assert_eq!(evaluation.target_framework().unwrap(), "net8.0");
+        assert_eq!(evaluation.items().len(), 3);
+        assert_eq!(
+            log.project_graph()[evaluation.project_file()],
+            vec![std::path::PathBuf::from("/repo/src/Domain/Domain.csproj")]
+        );
+
+        let kinds: Vec<(&str, DiscrepancyKind)> = discrepancies
+            .iter()
+            .map(|discrepancy| (discrepancy.reference().as_str(), discrepancy.kind()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("/repo/src/Domain/Domain.csproj", DiscrepancyKind::Missing),
+                ("/repo/src/Legacy/Legacy.csproj", DiscrepancyKind::NotBuilt),
+                ("Serilog", DiscrepancyKind::VersionDiffers),
+                ("Polly", DiscrepancyKind::Missing),
+            ]
+        );
+
+        assert!(matches!(
+            BinaryLog::read(Cursor::new(binary_log(14, &[]))),
+            Err(BinlogError::UnsupportedVersion(14))
+        ));
+    }
+
+    #[test]
+    pub fn reject_truncated_records() {
+        // given
+        let mut content = vec![];
+        content.extend_from_slice(&18i32.to_le_bytes());
+        content.extend_from_slice(&18i32.to_le_bytes());
+        // a string record claiming more bytes than the log contains
+        write_7bit(&mut content, 24);
+        write_7bit(&mut content, u32::MAX >> 4);
+        content.extend_from_slice(b"Serilog");
+
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&content).unwrap();
+
+        // when
+        let result = BinaryLog::read(Cursor::new(encoder.finish().unwrap()));
+
+        // then
+        assert!(matches!(result, Err(BinlogError::Io(_))));
+    }
+}
@@ -0,0 +1,542 @@
+//! Parsing of standalone MSBuild files (`.props`, `.targets`), such as `Directory.Build.props`
+//! or the shared build infrastructure of a repository, into their properties, items and imports.
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use spex::{parsing::XmlReader, xml::Element};
+
+use crate::{
+    condition::{combine, evaluate, Properties},
+    parser::ParseError,
+    PackageReference,
+};
+
+/// The item metadata also read from attributes, since they are usually written that way (ex:
+/// `<PackageReference Include="Serilog" Version="3.1.1" />`).
+const METADATA_ATTRIBUTES: [&str; 5] = [
+    "Version",
+    "VersionOverride",
+    "PrivateAssets",
+    "IncludeAssets",
+    "ExcludeAssets",
+];
+
+/// Represents the kind of an MSBuild file, from its extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BuildFileKind {
+    /// A `.props` file, imported before the project content.
+    Props,
+    /// A `.targets` file, imported after the project content.
+    Targets,
+    /// Any other extension.
+    Other,
+}
+
+/// Represents what an item element does to its item list.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemOperation {
+    /// Adds items (`Include`).
+    Include,
+    /// Changes the metadata of items added elsewhere (`Update`).
+    Update,
+    /// Removes items (`Remove`).
+    Remove,
+}
+
+/// Represents a property definition, as written in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildProperty {
+    name: String,
+    value: String,
+    condition: Option<String>,
+}
+
+/// Represents an item definition (ex: a `PackageVersion`), as written in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildItem {
+    item_type: String,
+    operation: ItemOperation,
+    spec: String,
+    metadata: BTreeMap<String, String>,
+    condition: Option<String>,
+}
+
+/// Represents an `Import` of another MSBuild file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildImport {
+    project: String,
+    condition: Option<String>,
+}
+
+/// Represents the properties, items and imports of an MSBuild file, in the order they are
+/// written.
+///
+/// Nothing is evaluated while parsing: the conditions and values are kept as written, and
+/// [`BuildFile::evaluate`] and [`BuildFile::package_versions`] evaluate them with the known
+/// properties.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{build_file::BuildFile, condition::Properties};
+/// use std::io::Cursor;
+///
+/// let content = r#"<Project>
+///   <PropertyGroup>
+///     <SerilogVersion>3.1.1</SerilogVersion>
+///   </PropertyGroup>
+///   <ItemGroup>
+///     <PackageVersion Include="Serilog" Version="$(SerilogVersion)" />
+///   </ItemGroup>
+/// </Project>"#;
+///
+/// let file = BuildFile::parse(Cursor::new(content), "Directory.Packages.props").unwrap();
+/// let versions = file.package_versions(&Properties::new());
+///
+/// assert_eq!(versions[0].name(), "Serilog");
+/// assert_eq!(versions[0].version(), "3.1.1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildFile {
+    path: PathBuf,
+    #[cfg_attr(feature = "serde", serde(default))]
+    sdks: Vec<String>,
+    properties: Vec<BuildProperty>,
+    items: Vec<BuildItem>,
+    imports: Vec<BuildImport>,
+}
+
+impl BuildProperty {
+    /// Returns the name of the property.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the value of the property, as written.
+    pub fn value(&self) -> &String {
+        &self.value
+    }
+
+    /// Returns the condition of the property, combined with the one of its group, if any.
+    pub fn condition(&self) -> Option<&String> {
+        self.condition.as_ref()
+    }
+}
+
+impl BuildItem {
+    /// Returns the type of the item (ex: `PackageVersion`).
+    pub fn item_type(&self) -> &String {
+        &self.item_type
+    }
+
+    /// Returns what the item element does to its item list.
+    pub fn operation(&self) -> ItemOperation {
+        self.operation
+    }
+
+    /// Returns the items included, updated or removed (ex: a package name), as written.
+    pub fn spec(&self) -> &String {
+        &self.spec
+    }
+
+    /// Returns the metadata of the item, by name, as written.
+    ///
+    /// Metadata are read from the child elements of the item and from the well-known metadata
+    /// attributes (`Version`, `VersionOverride`, `PrivateAssets`, `IncludeAssets` and
+    /// `ExcludeAssets`).
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Returns the condition of the item, combined with the one of its group, if any.
+    pub fn condition(&self) -> Option<&String> {
+        self.condition.as_ref()
+    }
+}
+
+impl BuildImport {
+    /// Returns the imported file, as written (ex: `$(MSBuildThisFileDirectory)Versions.props`).
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    /// Returns the condition of the import, combined with the one of its group, if any.
+    pub fn condition(&self) -> Option<&String> {
+        self.condition.as_ref()
+    }
+}
+
+impl BuildFile {
+    /// Reads an MSBuild file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn load<P>(path: P) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse(File::open(path.as_ref())?, path)
+    }
+
+    /// Parses the content of an MSBuild file.
+    ///
+    /// Targets and their tasks are ignored, since they only run during a build.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be parsed, if an item is
+    /// missing its `Include`, `Update` or `Remove` attribute or if an import is missing its
+    /// `Project` attribute.
+    pub fn parse<R, P>(reader: R, path: P) -> Result<Self, ParseError>
+    where
+        R: Read,
+        P: AsRef<Path>,
+    {
+        let document = XmlReader::parse_auto(reader)?;
+
+        let root = document.root();
+
+        let mut file = Self {
+            path: path.as_ref().to_owned(),
+            sdks: root
+                .att_opt("Sdk")
+                .into_iter()
+                .flat_map(|sdks| sdks.split(';'))
+                .map(str::trim)
+                .filter(|sdk| !sdk.is_empty())
+                .map(str::to_string)
+                .collect(),
+            properties: vec![],
+            items: vec![],
+            imports: vec![],
+        };
+
+        for element in root.elements() {
+            match element.name().local_part() {
+                "Sdk" => {
+                    if let Some(name) = element.att_opt("Name") {
+                        file.add_sdk(name);
+                    }
+                }
+                "PropertyGroup" => file.read_property_group(element, None)?,
+                "ItemGroup" => file.read_item_group(element, None)?,
+                "Import" => file.read_import(element, None)?,
+                "ImportGroup" => {
+                    let condition = element.att_opt("Condition");
+
+                    for import in element
+                        .elements()
+                        .filter(|element| element.name().local_part() == "Import")
+                    {
+                        file.read_import(import, condition)?;
+                    }
+                }
+                "Choose" => file.read_choose(element)?,
+                _ => (),
+            }
+        }
+
+        Ok(file)
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns the kind of the file, from its extension.
+    pub fn kind(&self) -> BuildFileKind {
+        let extension = self
+            .path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("props") => BuildFileKind::Props,
+            Some("targets") => BuildFileKind::Targets,
+            _ => BuildFileKind::Other,
+        }
+    }
+
+    /// Returns the MSBuild project SDKs used by the file, as written (ex: `Microsoft.NET.Sdk` or
+    /// `WixToolset.Sdk/5.0.1`), from the `Sdk` attribute of its `Project` element, its `Sdk`
+    /// elements and its imports with an `Sdk` attribute. Project files without any SDK use the
+    /// legacy, non-SDK-style format.
+    pub fn sdks(&self) -> &Vec<String> {
+        &self.sdks
+    }
+
+    /// Returns the property definitions of the file.
+    pub fn properties(&self) -> &Vec<BuildProperty> {
+        &self.properties
+    }
+
+    /// Returns the item definitions of the file.
+    pub fn items(&self) -> &Vec<BuildItem> {
+        &self.items
+    }
+
+    /// Returns the item definitions of a type (ex: `PackageVersion`), ignoring case.
+    pub fn items_of<'a>(&'a self, item_type: &'a str) -> impl Iterator<Item = &'a BuildItem> {
+        self.items
+            .iter()
+            .filter(move |item| item.item_type.eq_ignore_ascii_case(item_type))
+    }
+
+    /// Returns the imports of the file.
+    pub fn imports(&self) -> &Vec<BuildImport> {
+        &self.imports
+    }
+
+    /// Evaluates the property definitions in order, starting from `properties` (ex: the global
+    /// properties, or the ones of the files imported before), and returns the known properties
+    /// afterwards.
+    ///
+    /// Like when parsing a project, a property set under a condition that could not be evaluated
+    /// or to a value that could not be expanded becomes unknown.
+    pub fn evaluate(&self, properties: &Properties) -> Properties {
+        let mut properties = properties.clone();
+
+        for property in &self.properties {
+            let condition = is_included(property.condition.as_deref(), &properties);
+            if condition == Some(false) {
+                continue;
+            }
+
+            match (condition, properties.expand(&property.value)) {
+                (Some(true), Some(expanded)) => properties.set(&property.name, &expanded),
+                _ => properties.unset(&property.name),
+            }
+        }
+
+        properties
+    }
+
+    /// Returns the package versions declared by the file, from its `PackageVersion` items and
+    /// the `PackageReference` items with a version, in the order they are written.
+    ///
+    /// The properties of the file are evaluated first (see [`BuildFile::evaluate`]). Items whose
+    /// condition is `false` are skipped, and versions that could not be expanded are kept as
+    /// written.
+    pub fn package_versions(&self, properties: &Properties) -> Vec<PackageReference> {
+        let properties = self.evaluate(properties);
+
+        self.items
+            .iter()
+            .filter(|item| {
+                item.operation != ItemOperation::Remove
+                    && ["PackageVersion", "PackageReference"]
+                        .iter()
+                        .any(|item_type| item.item_type.eq_ignore_ascii_case(item_type))
+                    && is_included(item.condition.as_deref(), &properties) != Some(false)
+            })
+            .filter_map(|item| {
+                let version = item.metadata.get("Version")?;
+                let version = properties
+                    .expand(version)
+                    .unwrap_or_else(|| version.clone());
+
+                Some(PackageReference::new(item.spec.clone(), version))
+            })
+            .collect()
+    }
+
+    fn read_property_group(
+        &mut self,
+        element: &Element,
+        parent_condition: Option<&str>,
+    ) -> Result<(), ParseError> {
+        let group_condition = combine(parent_condition, element.att_opt("Condition"));
+
+        for property in element.elements() {
+            self.properties.push(BuildProperty {
+                name: property.name().local_part().to_string(),
+                value: property.text()?.trim().to_string(),
+                condition: combine(group_condition.as_deref(), property.att_opt("Condition")),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn read_item_group(
+        &mut self,
+        element: &Element,
+        parent_condition: Option<&str>,
+    ) -> Result<(), ParseError> {
+        let group_condition = combine(parent_condition, element.att_opt("Condition"));
+
+        for item in element.elements() {
+            let (operation, spec) = [
+                (ItemOperation::Include, "Include"),
+                (ItemOperation::Update, "Update"),
+                (ItemOperation::Remove, "Remove"),
+            ]
+            .into_iter()
+            .find_map(|(operation, attribute)| Some((operation, item.att_opt(attribute)?)))
+            .ok_or(ParseError::DeserializationError)?;
+
+            let mut metadata: BTreeMap<String, String> = METADATA_ATTRIBUTES
+                .iter()
+                .filter_map(|name| Some((name.to_string(), item.att_opt(*name)?.to_string())))
+                .collect();
+
+            for child in item.elements() {
+                metadata.insert(
+                    child.name().local_part().to_string(),
+                    child.text()?.trim().to_string(),
+                );
+            }
+
+            self.items.push(BuildItem {
+                item_type: item.name().local_part().to_string(),
+                operation,
+                spec: spec.to_string(),
+                metadata,
+                condition: combine(group_condition.as_deref(), item.att_opt("Condition")),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn add_sdk(&mut self, sdk: &str) {
+        let sdk = sdk.trim();
+
+        if !sdk.is_empty() && !self.sdks.iter().any(|known| known == sdk) {
+            self.sdks.push(sdk.to_string());
+        }
+    }
+
+    fn read_import(
+        &mut self,
+        element: &Element,
+        parent_condition: Option<&str>,
+    ) -> Result<(), ParseError> {
+        let project = element
+            .att_req("Project")
+            .map_err(|_| ParseError::DeserializationError)?;
+
+        if let Some(sdk) = element.att_opt("Sdk") {
+            self.add_sdk(sdk);
+        }
+
+        self.imports.push(BuildImport {
+            project: project.to_string(),
+            condition: combine(parent_condition, element.att_opt("Condition")),
+        });
+
+        Ok(())
+    }
+
+    /// Reads the groups of every branch of a `Choose`, with the condition of their `When`. The
+    /// `Otherwise` branch is read without a condition, since negating the previous ones would
+    /// often make them impossible to evaluate.
+    fn read_choose(&mut self, element: &Element) -> Result<(), ParseError> {
+        for branch in element.elements() {
+            let condition = match branch.name().local_part() {
+                "When" => branch.att_opt("Condition"),
+                "Otherwise" => None,
+                _ => continue,
+            };
+
+            for group in branch.elements() {
+                match group.name().local_part() {
+                    "PropertyGroup" => self.read_property_group(group, condition)?,
+                    "ItemGroup" => self.read_item_group(group, condition)?,
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Combines the condition of an element with the one of its parent, which both have to be true.
+/// Evaluates the condition of a definition, which is included when it does not have one.
+fn is_included(condition: Option<&str>, properties: &Properties) -> Option<bool> {
+    condition.map_or(Some(true), |condition| evaluate(condition, properties))
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::condition::Properties;
+
+    use super::{BuildFile, BuildFileKind, ItemOperation};
+
+    #[test]
+    pub fn parse_standalone_build_file() {
+        // given
+        let content = r#"<Project>
+  <Import Project="$(MSBuildThisFileDirectory)Versions.props" Condition="Exists('$(MSBuildThisFileDirectory)Versions.props')" />
+  <PropertyGroup>
+    <PollyVersion>8.4.1</PollyVersion>
+    <XunitVersion Condition="'$(Configuration)' == 'Debug'">2.9.0</XunitVersion>
+  </PropertyGroup>
+  <ItemGroup Condition="'$(TargetFramework)' == 'net48'">
+    <PackageReference Update="System.Memory" Version="4.5.5" />
+  </ItemGroup>
+  <ItemGroup>
+    <PackageVersion Include="Polly" Version="$(PollyVersion)" />
+    <PackageVersion Include="xunit" Version="$(XunitVersion)" />
+    <PackageVersion Include="Serilog">
+      <Version>3.1.1</Version>
+      <PrivateAssets>all</PrivateAssets>
+    </PackageVersion>
+    <PackageVersion Remove="Moq" />
+  </ItemGroup>
+  <Target Name="Audit">
+    <Message Text="$(PollyVersion)" />
+  </Target>
+</Project>"#;
+
+        let mut properties = Properties::new();
+        properties.set("TargetFramework", "net8.0");
+
+        // when
+        let file = BuildFile::parse(Cursor::new(content), "build/Packages.targets").unwrap();
+        let versions = file.package_versions(&properties);
+
+        // then
+        assert_eq!(file.kind(), BuildFileKind::Targets);
+        assert!(file.sdks().is_empty());
+        assert_eq!(file.imports().len(), 1);
+        assert_eq!(file.properties().len(), 2);
+        assert_eq!(
+            file.items()[0].condition().unwrap(),
+            "'$(TargetFramework)' == 'net48'"
+        );
+        assert_eq!(file.items()[0].operation(), ItemOperation::Update);
+        assert_eq!(file.items_of("packageversion").count(), 4);
+        assert_eq!(file.items()[3].metadata()["PrivateAssets"].as_str(), "all");
+
+        let summary: Vec<(&str, &str)> = versions
+            .iter()
+            .map(|version| (version.name().as_str(), version.version().as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Polly", "8.4.1"),
+                ("xunit", "$(XunitVersion)"),
+                ("Serilog", "3.1.1")
+            ]
+        );
+    }
+}
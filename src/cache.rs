@@ -0,0 +1,380 @@
+//! An on-disk cache of parsed projects, so repeated runs (ex: in CI or in watch mode) only parse
+//! the project files that changed.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    central_packages::CENTRAL_PACKAGES_FILE_NAME,
+    compose::COMPOSE_FILES,
+    condition::Properties,
+    intern::Interner,
+    parser::{self, ParseError},
+    vfs::OsFileSystem,
+    workspace::{ProjectReader, Workspace, WorkspaceError},
+    Project, PROJECT_SCHEMA_VERSION,
+};
+
+/// The files of the directory of a project, or of one of its parents, that change how it is
+/// parsed.
+const IMPORTED_FILES: [&str; 3] = [
+    "Directory.Build.props",
+    "Directory.Build.targets",
+    CENTRAL_PACKAGES_FILE_NAME,
+];
+
+/// An on-disk cache of parsed projects.
+///
+/// Each entry is keyed by the path, size, modification time and content hash of the project file.
+/// A file whose size and modification time did not change is not read at all. Otherwise, its
+/// content is hashed, so a file that was only touched (ex: by a fresh checkout in CI) is still
+/// not parsed again.
+///
+/// The entries are also keyed by the version of the crate and of the schema of [`Project`], the
+/// properties the projects are evaluated with, and the content of the files read with the
+/// project file: the `Directory.Build.*` and `Directory.Packages.props` files of its directory
+/// and of its parents, and its `packages.config` and compose files. An entry is parsed again
+/// when one of them changes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::cache::ParseCache;
+///
+/// let cache = ParseCache::new(".cache/projects".into());
+/// let workspace = cache.load("path/to/repository")?;
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseCache {
+    directory: PathBuf,
+    properties: Properties,
+}
+
+impl ParseCache {
+    /// Creates a new `ParseCache` storing the parsed projects in the given directory, which is
+    /// created when needed.
+    pub fn new(directory: PathBuf) -> Self {
+        Self::with_properties(directory, Properties::new())
+    }
+
+    /// Creates a new `ParseCache` like [`ParseCache::new`], evaluating the conditions of the
+    /// projects with the given `properties` (see [`Workspace::load_with_properties`]).
+    pub fn with_properties(directory: PathBuf, properties: Properties) -> Self {
+        Self {
+            directory,
+            properties,
+        }
+    }
+
+    /// Returns the directory where the parsed projects are stored.
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Returns the project at `path`, parsing it only if the cached entry is missing or stale.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed, or if the
+    /// entry could not be written to the cache directory.
+    pub fn parse<P>(&self, path: P) -> Result<Project, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        self.parse_with(path.as_ref(), &Interner::new())
+    }
+
+    /// Returns the project at `path` like [`ParseCache::parse`], sharing its names and versions
+    /// with the other projects read with `strings`.
+    pub(crate) fn parse_with(
+        &self,
+        path: &Path,
+        strings: &Interner,
+    ) -> Result<Project, ParseError> {
+        let metadata = fs::metadata(path)?;
+        let size = metadata.len();
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos());
+
+        let key = CacheKey {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version: PROJECT_SCHEMA_VERSION,
+            properties: hash_properties(&self.properties),
+            imports: hash_imports(path),
+        };
+
+        let cached = self.get(path).filter(|entry| entry.key == key);
+
+        if let Some(entry) = &cached {
+            if entry.size == size && entry.modified == modified {
+                let mut project = entry.project.clone();
+                strings.share(&mut project);
+
+                return Ok(project);
+            }
+        }
+
+        let content = fs::read(path)?;
+        let hash = hash(&content);
+
+        let project = match cached {
+            Some(mut entry) if entry.size == size && entry.hash == hash => {
+                strings.share(&mut entry.project);
+                entry.project
+            }
+            _ => parser::parse_in(
+                content.as_slice(),
+                path,
+                &self.properties,
+                &OsFileSystem,
+                strings,
+            )?,
+        };
+
+        self.put(&CacheEntry {
+            path: path.to_owned(),
+            key,
+            size,
+            modified,
+            hash,
+            project: project.clone(),
+        })?;
+
+        Ok(project)
+    }
+
+    /// Searches every project under `root` like [`Workspace::load`], parsing only the projects
+    /// that are not up to date in the cache.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read or if one of the
+    /// projects could not be parsed.
+    pub fn load<P>(&self, root: P) -> Result<Workspace, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        Workspace::load_with_reader(root, ProjectReader::Cache(self.clone()))
+    }
+
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        let hash = hash(path.as_os_str().as_encoded_bytes());
+
+        self.directory.join(format!("{hash:016x}.json"))
+    }
+
+    fn get(&self, path: &Path) -> Option<CacheEntry> {
+        let content = fs::read(self.entry_path(path)).ok()?;
+        let entry: CacheEntry = serde_json::from_slice(&content).ok()?;
+
+        (entry.path == path).then_some(entry)
+    }
+
+    fn put(&self, entry: &CacheEntry) -> Result<(), ParseError> {
+        let content = serde_json::to_vec(entry).map_err(|_| ParseError::DeserializationError)?;
+
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.entry_path(&entry.path), content)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    path: PathBuf,
+    key: CacheKey,
+    size: u64,
+    modified: u128,
+    hash: u64,
+    project: Project,
+}
+
+/// What an entry depends on besides the project file.
+#[derive(Serialize, Deserialize, PartialEq)]
+struct CacheKey {
+    version: String,
+    schema_version: u32,
+    properties: u64,
+    imports: u64,
+}
+
+// FNV-1a, stable across Rust versions, unlike the standard library hasher
+fn hash(bytes: &[u8]) -> u64 {
+    hash_from(0xcbf29ce484222325u64, bytes)
+}
+
+fn hash_from(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn hash_properties(properties: &Properties) -> u64 {
+    let mut values: Vec<(&str, &str)> = properties.iter().collect();
+    values.sort();
+
+    values.into_iter().fold(hash(&[]), |hash, (name, value)| {
+        hash_from(hash, format!("{name}={value}\n").as_bytes())
+    })
+}
+
+/// Hashes the paths and contents of the files read with the project at `path`.
+fn hash_imports(path: &Path) -> u64 {
+    let directory = path.parent().unwrap_or(Path::new(""));
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut files: Vec<PathBuf> = [
+        "packages.config".to_string(),
+        format!("packages.{stem}.config"),
+    ]
+    .into_iter()
+    .chain(COMPOSE_FILES.iter().map(|file_name| file_name.to_string()))
+    .map(|file_name| directory.join(file_name))
+    .collect();
+
+    for ancestor in directory.ancestors() {
+        files.extend(
+            IMPORTED_FILES
+                .iter()
+                .map(|file_name| ancestor.join(file_name)),
+        );
+    }
+
+    files
+        .iter()
+        .fold(hash(&[]), |hash, file| match fs::read(file) {
+            Ok(content) => {
+                let hash = hash_from(hash, file.as_os_str().as_encoded_bytes());
+
+                hash_from(hash, &content)
+            }
+            Err(_) => hash,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::{self, File},
+        time::{Duration, SystemTime},
+    };
+
+    use tempfile::tempdir;
+
+    use crate::condition::Properties;
+
+    use super::ParseCache;
+
+    #[test]
+    pub fn parse_only_changed_projects() {
+        // given
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Api.csproj");
+        let cache = ParseCache::new(dir.path().join("cache"));
+
+        let touch = |seconds: u64| {
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+                .unwrap();
+        };
+
+        fs::write(&path, "<Project></Project>").unwrap();
+        touch(1);
+        let first = cache.parse(&path).unwrap();
+
+        // when
+        // same size and modification time, so the cached project is returned without reading
+        fs::write(&path, "<Project><Project>x").unwrap();
+        touch(1);
+        let unchanged = cache.parse(&path).unwrap();
+
+        // same content with a new modification time, so the content hash matches
+        fs::write(&path, "<Project></Project>").unwrap();
+        touch(2);
+        let touched = cache.parse(&path).unwrap();
+
+        fs::write(
+            &path,
+            "<Project><PropertyGroup><TargetFramework>net8.0</TargetFramework></PropertyGroup></Project>",
+        )
+        .unwrap();
+        let changed = cache.parse(&path).unwrap();
+
+        // then
+        assert_eq!(unchanged, first);
+        assert_eq!(touched, first);
+        assert_eq!(changed.target_framework().unwrap(), "net8.0");
+        assert_eq!(fs::read_dir(cache.directory()).unwrap().count(), 1);
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    pub fn parse_again_when_imports_or_properties_change() {
+        // given
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("src/Api/Api.csproj");
+        let cache = ParseCache::new(dir.path().join("cache"));
+
+        fs::create_dir_all(dir.path().join("src/Api")).unwrap();
+        fs::write(
+            &path,
+            r#"<Project>
+  <ItemGroup Condition="'$(Configuration)' == 'Release'">
+    <PackageReference Include="Serilog" />
+  </ItemGroup>
+</Project>"#,
+        )
+        .unwrap();
+
+        let central_packages = |version: &str| {
+            fs::write(
+                dir.path().join("Directory.Packages.props"),
+                format!(
+                    r#"<Project><ItemGroup><PackageVersion Include="Serilog" Version="{version}" /></ItemGroup></Project>"#
+                ),
+            )
+            .unwrap();
+        };
+
+        central_packages("3.1.1");
+
+        let configuration = |name: &str| {
+            let mut properties = Properties::new();
+            properties.set("Configuration", name);
+
+            ParseCache::with_properties(cache.directory().clone(), properties)
+        };
+        let release = configuration("Release");
+
+        // when
+        let debug = configuration("Debug").parse(&path).unwrap();
+        let first = release.parse(&path).unwrap();
+
+        central_packages("4.0.0");
+        let second = release.parse(&path).unwrap();
+
+        // then
+        assert!(debug.package_references().is_empty());
+        assert_eq!(first.package_references()[0].version(), "3.1.1");
+        assert_eq!(second.package_references()[0].version(), "4.0.0");
+
+        dir.close().unwrap();
+    }
+}
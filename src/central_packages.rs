@@ -0,0 +1,246 @@
+//! Reading of central package versions (`Directory.Packages.props`), used by projects with
+//! Central Package Management (CPM).
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use spex::parsing::XmlReader;
+
+use crate::{
+    intern::Interner,
+    parser::ParseError,
+    vfs::{FileSystem, OsFileSystem},
+    PackageReference, Project,
+};
+
+/// The name of the file that declares the central package versions.
+pub const CENTRAL_PACKAGES_FILE_NAME: &str = "Directory.Packages.props";
+
+/// Represents the package versions declared in a `Directory.Packages.props` file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CentralPackageVersions {
+    path: PathBuf,
+    package_versions: Vec<PackageReference>,
+}
+
+impl CentralPackageVersions {
+    /// Returns the path of the closest `Directory.Packages.props`, looking in the directory and in
+    /// each of its parents, like MSBuild does.
+    pub fn find<P>(directory: P) -> Option<PathBuf>
+    where
+        P: AsRef<Path>,
+    {
+        Self::find_in(directory, &OsFileSystem)
+    }
+
+    /// Returns the path of the closest `Directory.Packages.props` in `files`, like [`Self::find`].
+    pub fn find_in<P>(directory: P, files: &dyn FileSystem) -> Option<PathBuf>
+    where
+        P: AsRef<Path>,
+    {
+        directory
+            .as_ref()
+            .ancestors()
+            .map(|dir| dir.join(CENTRAL_PACKAGES_FILE_NAME))
+            .find(|path| files.is_file(path))
+    }
+
+    /// Reads a `Directory.Packages.props` file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn load<P>(path: P) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_from(path, &OsFileSystem)
+    }
+
+    /// Reads a `Directory.Packages.props` file from `files`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn load_from<P>(path: P, files: &dyn FileSystem) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse(files.read(path.as_ref())?.as_slice(), path)
+    }
+
+    /// Parses the content of a `Directory.Packages.props` file.
+    ///
+    /// The `PackageVersion` items missing their `Include` or `Version` attribute are skipped, so
+    /// one malformed item does not prevent the projects below the file from being parsed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be parsed.
+    pub fn parse<R, P>(reader: R, path: P) -> Result<Self, ParseError>
+    where
+        R: Read,
+        P: AsRef<Path>,
+    {
+        let document = XmlReader::parse_auto(reader)?;
+        let mut package_versions = vec![];
+
+        for item_group in document
+            .root()
+            .elements()
+            .filter(|element| element.name().local_part() == "ItemGroup")
+        {
+            for item in item_group
+                .elements()
+                .filter(|element| element.name().local_part() == "PackageVersion")
+            {
+                let (Some(name), Some(version)) =
+                    (item.att_opt("Include"), item.att_opt("Version"))
+                else {
+                    continue;
+                };
+
+                package_versions.push(PackageReference::new(name.to_string(), version.to_string()));
+            }
+        }
+
+        Ok(Self {
+            path: path.as_ref().to_owned(),
+            package_versions,
+        })
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns the package versions declared in the file.
+    pub fn package_versions(&self) -> &Vec<PackageReference> {
+        &self.package_versions
+    }
+
+    /// Returns the version declared for a package (case insensitive), if any.
+    pub fn version_of(&self, package: &str) -> Option<&String> {
+        self.package_versions
+            .iter()
+            .find(|package_version| package_version.name().eq_ignore_ascii_case(package))
+            .map(|package_version| package_version.version())
+    }
+}
+
+/// Sets the version of the package references of `project` that do not declare one (ex:
+/// `<PackageReference Include="Serilog" />`) to the version of the closest
+/// `Directory.Packages.props`, like MSBuild does with Central Package Management.
+///
+/// References without a central version keep an empty version.
+pub(crate) fn resolve_versions(
+    project: &mut Project,
+    files: &dyn FileSystem,
+    strings: &Interner,
+) -> Result<(), ParseError> {
+    if project
+        .package_references
+        .iter()
+        .all(|reference| !reference.version().is_empty())
+    {
+        return Ok(());
+    }
+
+    let directory = project.path().parent().unwrap_or(Path::new(""));
+    let Some(path) = CentralPackageVersions::find_in(directory, files) else {
+        return Ok(());
+    };
+    let central = CentralPackageVersions::load_from(path, files)?;
+
+    for reference in &mut project.package_references {
+        if !reference.version().is_empty() {
+            continue;
+        }
+
+        if let Some(version) = central.version_of(reference.name()) {
+            reference.version = strings.intern(version);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::{condition::Properties, parser::parse_in, vfs::MemoryFileSystem};
+
+    use super::*;
+
+    #[test]
+    pub fn parse_central_package_versions() {
+        // given
+        let content = r#"
+<Project>
+  <PropertyGroup>
+    <ManagePackageVersionsCentrally>true</ManagePackageVersionsCentrally>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageVersion Include="Serilog" Version="3.1.1" />
+    <PackageVersion Include="Dapper" Version="2.1.35" />
+  </ItemGroup>
+</Project>
+"#;
+
+        // when
+        let central =
+            CentralPackageVersions::parse(Cursor::new(content), "Directory.Packages.props")
+                .unwrap();
+
+        // then
+        assert_eq!(central.package_versions().len(), 2);
+        assert_eq!(central.version_of("serilog"), Some(&"3.1.1".to_string()));
+        assert_eq!(central.version_of("Newtonsoft.Json"), None);
+    }
+    #[test]
+    pub fn skip_malformed_package_versions() {
+        // given
+        let content = r#"
+<Project>
+  <ItemGroup>
+    <PackageVersion Include="Serilog" Version="3.1.1" />
+    <PackageVersion Include="Dapper" />
+    <PackageVersion Version="1.0.0" />
+  </ItemGroup>
+</Project>
+"#;
+
+        let mut files = MemoryFileSystem::new();
+        files.add_file("/repo/Directory.Packages.props", content);
+
+        let project = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Serilog" />
+    <PackageReference Include="Dapper" />
+  </ItemGroup>
+</Project>"#;
+
+        // when
+        let central =
+            CentralPackageVersions::parse(Cursor::new(content), "Directory.Packages.props")
+                .unwrap();
+        let project = parse_in(
+            project.as_bytes(),
+            "/repo/src/Api/Api.csproj",
+            &Properties::new(),
+            &files,
+            &Interner::new(),
+        )
+        .unwrap();
+
+        // then
+        assert_eq!(central.package_versions().len(), 1);
+        assert_eq!(project.package_references()[0].version(), "3.1.1");
+        assert_eq!(project.package_references()[1].version(), "");
+    }
+}
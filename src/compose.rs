@@ -0,0 +1,427 @@
+//! Reading of the services of Docker Compose projects (`.dcproj`), which reference the projects
+//! they build through the `docker-compose.yml` file next to them instead of `ProjectReference`
+//! items.
+//!
+//! The compose files are read on a best-effort basis, with a small reader of the block style
+//! Visual Studio generates rather than a full YAML parser (see [`parse_services`]).
+
+use std::{
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    parser::ParseError,
+    vfs::{FileSystem, OsFileSystem},
+    workspace::normalize_path,
+    Project, ProjectLanguage, ProjectReference,
+};
+
+/// The compose files read next to a `.dcproj`, in the order Visual Studio merges them.
+pub const COMPOSE_FILES: [&str; 4] = [
+    "docker-compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.override.yml",
+    "docker-compose.override.yaml",
+];
+
+/// Represents a service of a compose file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ComposeService {
+    name: String,
+    image: Option<String>,
+    context: Option<PathBuf>,
+    dockerfile: Option<PathBuf>,
+}
+
+impl ComposeService {
+    /// Returns the name of the service.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the image of the service, if any.
+    pub fn image(&self) -> Option<&String> {
+        self.image.as_ref()
+    }
+
+    /// Returns the build context of the service, relative to the compose file, if it is built.
+    pub fn context(&self) -> Option<&PathBuf> {
+        self.context.as_ref()
+    }
+
+    /// Returns the Dockerfile of the service, relative to its build context, if it is set.
+    pub fn dockerfile(&self) -> Option<&PathBuf> {
+        self.dockerfile.as_ref()
+    }
+
+    /// Returns the directory of the Dockerfile, relative to the compose file, where Visual
+    /// Studio puts the project the service is built from.
+    pub fn dockerfile_directory(&self) -> Option<PathBuf> {
+        let context = self.context.as_ref()?;
+        let directory = match &self.dockerfile {
+            Some(dockerfile) => context.join(dockerfile).parent()?.to_path_buf(),
+            None => context.clone(),
+        };
+
+        Some(normalize_path(&directory))
+    }
+}
+
+/// Parses the services of a compose file.
+///
+/// This is a best-effort reader of the block style Visual Studio generates, not a YAML parser:
+/// only the `image` of every service and its `build`, either as a context path or as a block
+/// mapping with `context` and `dockerfile`, are read. Flow mappings and sequences, anchors,
+/// aliases, merge keys and multi-line strings are not supported, and the services using them
+/// are read without their image or build, or not at all, instead of failing.
+///
+/// # Errors
+///
+/// This function will return an error if the content could not be read.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::compose::parse_services;
+///
+/// let content = "
+/// services:
+///   api:
+///     image: shop/api
+///     build:
+///       context: .
+///       dockerfile: src/Api/Dockerfile
+/// ";
+///
+/// let services = parse_services(content.as_bytes()).unwrap();
+///
+/// assert_eq!(services[0].name(), "api");
+/// assert_eq!(services[0].dockerfile_directory().unwrap().to_str(), Some("src/Api"));
+/// ```
+pub fn parse_services<R>(reader: R) -> Result<Vec<ComposeService>, ParseError>
+where
+    R: Read,
+{
+    let mut services: Vec<ComposeService> = vec![];
+    // the indentations of the `services` key, of the service names, of the keys of the current
+    // service and of its `build` key when it is a mapping
+    let mut services_indent = None;
+    let mut service_indent = None;
+    let mut key_indent = None;
+    let mut build_indent = None;
+
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        let content = strip_comment(&line);
+        let indent = content.len() - content.trim_start().len();
+
+        let Some((key, value)) = content.trim().split_once(':') else {
+            continue;
+        };
+        let value = unquote(value.trim());
+
+        if services_indent.is_some_and(|services| indent <= services) {
+            services_indent = None;
+        }
+
+        let Some(_) = services_indent else {
+            if key == "services" && value.is_empty() {
+                services_indent = Some(indent);
+                service_indent = None;
+            }
+
+            continue;
+        };
+
+        if service_indent.is_none_or(|service| indent <= service) {
+            service_indent = Some(indent);
+            key_indent = None;
+            build_indent = None;
+
+            services.push(ComposeService {
+                name: unquote(key).to_string(),
+                ..ComposeService::default()
+            });
+
+            continue;
+        }
+
+        let Some(service) = services.last_mut() else {
+            continue;
+        };
+
+        if build_indent.is_some_and(|build| indent > build) {
+            match key {
+                "context" => service.context = Some(PathBuf::from(value)),
+                "dockerfile" => service.dockerfile = Some(PathBuf::from(value)),
+                _ => (),
+            }
+
+            continue;
+        }
+
+        build_indent = None;
+
+        // keys nested under other keys of the service (ex: `ports`, `environment`)
+        if *key_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+
+        match key {
+            "image" => service.image = Some(value.to_string()),
+            "build" if value.is_empty() => build_indent = Some(indent),
+            "build" => service.context = Some(PathBuf::from(value)),
+            _ => (),
+        }
+    }
+
+    Ok(services)
+}
+
+/// Reads the services of the compose files of a Docker Compose project (see [`COMPOSE_FILES`]).
+///
+/// # Errors
+///
+/// This function will return an error if one of the compose files could not be read.
+pub fn project_services(project: &Project) -> Result<Vec<ComposeService>, ParseError> {
+    services_in(project, &OsFileSystem)
+}
+
+/// Reads the services of the compose files of a Docker Compose project from `files`.
+fn services_in(
+    project: &Project,
+    files: &dyn FileSystem,
+) -> Result<Vec<ComposeService>, ParseError> {
+    let directory = project.path().parent().unwrap_or(Path::new(""));
+    let mut services: Vec<ComposeService> = vec![];
+
+    for file_name in COMPOSE_FILES {
+        let path = directory.join(file_name);
+
+        if !files.is_file(&path) {
+            continue;
+        }
+
+        for service in parse_services(files.read(&path)?.as_slice())? {
+            // the override files only change some keys of the services
+            match services.iter_mut().find(|other| other.name == service.name) {
+                Some(other) => {
+                    other.image = service.image.or(other.image.take());
+                    other.context = service.context.or(other.context.take());
+                    other.dockerfile = service.dockerfile.or(other.dockerfile.take());
+                }
+                None => services.push(service),
+            }
+        }
+    }
+
+    Ok(services)
+}
+
+/// Adds a project reference to the project each service of a Docker Compose project is built
+/// from: the .NET project in the directory of the service's Dockerfile.
+///
+/// Other projects, and services without a .NET project, are left as they are. Since the compose
+/// files are read on a best-effort basis (see [`parse_services`]), some references can be
+/// missing.
+pub(crate) fn add_service_references(
+    project: &mut Project,
+    files: &dyn FileSystem,
+) -> Result<(), ParseError> {
+    if project.language() != ProjectLanguage::DockerCompose {
+        return Ok(());
+    }
+
+    let root = project
+        .path()
+        .parent()
+        .unwrap_or(Path::new(""))
+        .to_path_buf();
+
+    for service in services_in(project, files)? {
+        let Some(directory) = service.dockerfile_directory() else {
+            continue;
+        };
+        let Some(path) = find_project(files, &root, &directory) else {
+            continue;
+        };
+
+        let exists = project
+            .project_references()
+            .iter()
+            .any(|reference| normalize_path(reference.path()) == path);
+
+        if !exists {
+            let name = Project::get_project_name(&path).unwrap_or_default();
+            project
+                .project_references
+                .push(ProjectReference::new(name, path));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the path, relative to `root`, of the first .NET project file in `directory`.
+fn find_project(files: &dyn FileSystem, root: &Path, directory: &Path) -> Option<PathBuf> {
+    let mut projects: Vec<PathBuf> = files
+        .read_dir(&root.join(directory))
+        .ok()?
+        .into_iter()
+        .filter(|file_name| {
+            file_name
+                .extension()
+                .and_then(ProjectLanguage::from_extension)
+                .is_some_and(|language| !language.is_deployment())
+        })
+        .collect();
+
+    projects.sort();
+
+    projects
+        .into_iter()
+        .next()
+        .map(|file_name| directory.join(file_name))
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find(" #") {
+        Some(index) => &line[..index],
+        None if line.trim_start().starts_with('#') => "",
+        None => line,
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| {
+            value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+        })
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use tempfile::tempdir;
+
+    use crate::{
+        graph::DependencyGraph,
+        parser,
+        validation::{default_rules, validate},
+        workspace::Workspace,
+        ProjectLanguage,
+    };
+
+    use super::parse_services;
+
+    #[test]
+    pub fn parse_compose_services() {
+        // given
+        let content = r#"
+version: "3.4"
+
+services:
+  # the public API
+  api:
+    image: ${DOCKER_REGISTRY-}shopapi
+    build:
+      context: .
+      dockerfile: src/Api/Dockerfile
+    depends_on:
+      - db
+
+  worker:
+    build: src/Worker
+
+  db:
+    image: "postgres:16"
+volumes:
+  data:
+"#;
+
+        // when
+        let services = parse_services(content.as_bytes()).unwrap();
+
+        // then
+        let names: Vec<&str> = services
+            .iter()
+            .map(|service| service.name().as_str())
+            .collect();
+        assert_eq!(names, vec!["api", "worker", "db"]);
+
+        assert_eq!(
+            services[0].dockerfile_directory(),
+            Some(PathBuf::from("src/Api"))
+        );
+        assert_eq!(
+            services[1].dockerfile_directory(),
+            Some(PathBuf::from("src/Worker"))
+        );
+        assert_eq!(services[2].image().unwrap(), "postgres:16");
+        assert_eq!(services[2].dockerfile_directory(), None);
+    }
+
+    #[test]
+    pub fn load_deployment_projects() {
+        // given
+        let dir = tempdir().unwrap();
+        let sdk = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#;
+
+        for directory in ["src/Api", "src/Worker", "fabric"] {
+            fs::create_dir_all(dir.path().join(directory)).unwrap();
+        }
+
+        fs::write(dir.path().join("src/Api/Api.csproj"), sdk).unwrap();
+        fs::write(dir.path().join("src/Worker/Worker.csproj"), sdk).unwrap();
+        fs::write(
+            dir.path().join("docker-compose.dcproj"),
+            r#"<Project ToolsVersion="15.0" Sdk="Microsoft.Docker.Sdk" />"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("docker-compose.yml"),
+            "services:\n  api:\n    build:\n      context: .\n      dockerfile: src/Api/Dockerfile\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("fabric/Shop.sfproj"),
+            r#"<Project Sdk="Microsoft.ServiceFabric.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="..\src\Worker\Worker.csproj" />
+  </ItemGroup>
+</Project>"#,
+        )
+        .unwrap();
+
+        // when
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let graph = DependencyGraph::from_workspace(&workspace, false);
+
+        // then
+        let compose = workspace.project("docker-compose").unwrap();
+        assert_eq!(compose.language(), ProjectLanguage::DockerCompose);
+        assert_eq!(compose.project_references()[0].name(), "Api");
+
+        let fabric = workspace.project("Shop").unwrap();
+        assert_eq!(fabric.language(), ProjectLanguage::ServiceFabric);
+
+        assert_eq!(graph.nodes().len(), 4);
+        assert_eq!(graph.edges().len(), 2);
+        assert!(validate(workspace.projects(), &default_rules()).is_empty());
+
+        let path = dir.path().join("docker-compose.dcproj");
+        let parsed = parser::parse(fs::read(&path).unwrap().as_slice(), &path).unwrap();
+        assert_eq!(parsed.project_references(), compose.project_references());
+    }
+}
@@ -0,0 +1,449 @@
+//! Evaluation of MSBuild conditions (ex: `'$(Configuration)' == 'Release'`) and property
+//! references.
+//!
+//! Project files are not built, so the value of a property is often unknown: it may come from
+//! the command line, the SDK or an imported file. The evaluation is three-valued: a condition
+//! that depends on an unknown property evaluates to `None` instead of guessing.
+
+use std::collections::HashMap;
+
+/// The MSBuild properties known while evaluating a project, with case-insensitive names.
+///
+/// A property that is not set is unknown, not empty, since it may be set outside of the
+/// project file.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::condition::{evaluate, Properties};
+///
+/// let mut properties = Properties::new();
+/// properties.set("Configuration", "Release");
+///
+/// assert_eq!(evaluate("'$(Configuration)' == 'release'", &properties), Some(true));
+/// assert_eq!(evaluate("'$(Platform)' == 'x64'", &properties), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Properties {
+    values: HashMap<String, String>,
+}
+
+impl Properties {
+    /// Creates an empty `Properties`, where every property is unknown.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of a property.
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values
+            .insert(name.to_ascii_lowercase(), value.to_string());
+    }
+
+    /// Marks a property as unknown, for example when it is set under a condition that could not
+    /// be evaluated.
+    pub fn unset(&mut self, name: &str) {
+        self.values.remove(&name.to_ascii_lowercase());
+    }
+
+    /// Returns the value of a property, if it is known.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .get(&name.to_ascii_lowercase())
+            .map(|value| value.as_str())
+    }
+
+    /// Returns the known properties, with their names in lowercase, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Replaces the property references (`$(Name)`) of `value` with their values.
+    ///
+    /// Returns `None` if `value` references an unknown property, or uses a property function,
+    /// an item list or item metadata, which are not evaluated.
+    pub fn expand(&self, value: &str) -> Option<String> {
+        if value.contains("@(") || value.contains("%(") {
+            return None;
+        }
+
+        let mut expanded = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(start) = rest.find("$(") {
+            expanded.push_str(&rest[..start]);
+
+            let end = rest[start..].find(')')? + start;
+            let name = rest[start + 2..end].trim();
+
+            if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return None;
+            }
+
+            expanded.push_str(self.get(name)?);
+            rest = &rest[end + 1..];
+        }
+
+        expanded.push_str(rest);
+
+        Some(expanded)
+    }
+}
+
+/// Represents a property set under a condition in a project file, kept as written since the
+/// condition is not evaluated while parsing (ex: `DefineConstants` set to `TRACE` when
+/// `'$(Configuration)' == 'Debug'`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConditionalProperty {
+    name: String,
+    value: String,
+    condition: String,
+}
+
+impl ConditionalProperty {
+    /// Creates a new `ConditionalProperty` with the name and the value of the property and its
+    /// condition, as written.
+    pub fn new(name: String, value: String, condition: String) -> Self {
+        Self {
+            name,
+            value,
+            condition,
+        }
+    }
+
+    /// Returns the name of the property.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the value of the property, as written.
+    pub fn value(&self) -> &String {
+        &self.value
+    }
+
+    /// Returns the condition of the property, as written, combined with the one of its
+    /// `PropertyGroup` as `(group) and (property)`.
+    pub fn condition(&self) -> &String {
+        &self.condition
+    }
+}
+
+/// Evaluates an MSBuild condition with the known `properties`.
+///
+/// Supports the `==`, `!=`, `<`, `>`, `<=` and `>=` comparisons, the `and`, `or` and `!`
+/// operators, parentheses and the `true` and `false` literals. String comparisons ignore case,
+/// like MSBuild.
+///
+/// Returns `None` if the result depends on an unknown property or on something that is not
+/// evaluated, such as a function call (ex: `Exists('file')`) or a malformed condition.
+pub fn evaluate(condition: &str, properties: &Properties) -> Option<bool> {
+    let tokens = tokenize(condition)?;
+    let mut parser = ConditionParser {
+        tokens: &tokens,
+        position: 0,
+        properties,
+    };
+
+    let result = parser.or()?;
+
+    if parser.position != tokens.len() {
+        return None;
+    }
+
+    result.known()
+}
+
+/// Combines the raw conditions of an element and of its parent group, which both have to be
+/// `true`, as `(parent) and (condition)`.
+pub(crate) fn combine(parent: Option<&str>, condition: Option<&str>) -> Option<String> {
+    match (parent, condition) {
+        (Some(parent), Some(condition)) => Some(format!("({parent}) and ({condition})")),
+        (Some(condition), None) | (None, Some(condition)) => Some(condition.to_string()),
+        (None, None) => None,
+    }
+}
+
+/// Combines the conditions of an element and of its parent group, which both have to be `true`.
+pub(crate) fn both(first: Option<bool>, second: Option<bool>) -> Option<bool> {
+    match (first, second) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A quoted string, with its property references not expanded yet.
+    String(String),
+    /// An unquoted word, such as `true`, a number or `$(Name)`.
+    Word(String),
+    Operator(&'static str),
+    And,
+    Or,
+    Not,
+    OpenParenthesis,
+    CloseParenthesis,
+}
+
+fn tokenize(condition: &str) -> Option<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = condition.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '\'' => {
+                let start = index + 1;
+                let end = condition[start..].find('\'')? + start;
+
+                tokens.push(Token::String(condition[start..end].to_string()));
+
+                while chars.next_if(|(i, _)| *i <= end).is_some() {}
+            }
+            '(' => tokens.push(Token::OpenParenthesis),
+            ')' => tokens.push(Token::CloseParenthesis),
+            '=' | '!' | '<' | '>' => {
+                let equals = chars.next_if(|(_, next)| *next == '=').is_some();
+
+                tokens.push(match (c, equals) {
+                    ('=', true) => Token::Operator("=="),
+                    ('!', true) => Token::Operator("!="),
+                    ('<', true) => Token::Operator("<="),
+                    ('>', true) => Token::Operator(">="),
+                    ('<', false) => Token::Operator("<"),
+                    ('>', false) => Token::Operator(">"),
+                    ('!', false) => Token::Not,
+                    _ => return None,
+                });
+            }
+            _ => {
+                let mut word = c.to_string();
+                let mut depth = 0;
+
+                // keeps `$(Name)` and function arguments in the word
+                while let Some((_, next)) = chars.peek().copied() {
+                    match next {
+                        '(' => depth += 1,
+                        ')' if depth == 0 => break,
+                        ')' => depth -= 1,
+                        c if depth == 0 && (c.is_whitespace() || "=!<>'".contains(c)) => break,
+                        _ => (),
+                    }
+
+                    word.push(next);
+                    chars.next();
+                }
+
+                tokens.push(match word.to_ascii_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+
+    Some(tokens)
+}
+
+/// The value of a sub-expression: a boolean, an operand not compared yet, or unknown.
+enum Value {
+    Boolean(Option<bool>),
+    Operand(Option<String>),
+}
+
+impl Value {
+    fn known(self) -> Option<bool> {
+        match self {
+            Value::Boolean(value) => value,
+            Value::Operand(value) => match value?.to_ascii_lowercase().as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+        }
+    }
+}
+
+struct ConditionParser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    properties: &'a Properties,
+}
+
+impl ConditionParser<'_> {
+    fn next_if(&mut self, token: &Token) -> bool {
+        let matches = self.tokens.get(self.position) == Some(token);
+
+        if matches {
+            self.position += 1;
+        }
+
+        matches
+    }
+
+    fn or(&mut self) -> Option<Value> {
+        let mut result = self.and()?;
+
+        while self.next_if(&Token::Or) {
+            let right = self.and()?.known();
+
+            result = Value::Boolean(match (result.known(), right) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            });
+        }
+
+        Some(result)
+    }
+
+    fn and(&mut self) -> Option<Value> {
+        let mut result = self.not()?;
+
+        while self.next_if(&Token::And) {
+            let right = self.not()?.known();
+
+            result = Value::Boolean(both(result.known(), right));
+        }
+
+        Some(result)
+    }
+
+    fn not(&mut self) -> Option<Value> {
+        if self.next_if(&Token::Not) {
+            return Some(Value::Boolean(self.not()?.known().map(|value| !value)));
+        }
+
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Option<Value> {
+        let left = self.operand()?;
+
+        let Some(Token::Operator(operator)) = self.tokens.get(self.position) else {
+            return Some(left);
+        };
+        self.position += 1;
+
+        let right = self.operand()?;
+
+        let (Value::Operand(left), Value::Operand(right)) = (left, right) else {
+            return None;
+        };
+
+        let (Some(left), Some(right)) = (left, right) else {
+            return Some(Value::Boolean(None));
+        };
+
+        Some(Value::Boolean(compare(&left, operator, &right)))
+    }
+
+    fn operand(&mut self) -> Option<Value> {
+        let token = self.tokens.get(self.position)?.clone();
+        self.position += 1;
+
+        match token {
+            Token::OpenParenthesis => {
+                let value = self.or()?;
+
+                self.next_if(&Token::CloseParenthesis).then_some(value)
+            }
+            Token::String(value) => Some(Value::Operand(self.properties.expand(&value))),
+            // a function call, such as `Exists('file')`
+            Token::Word(word) if !word.starts_with('$') && word.contains('(') => {
+                Some(Value::Boolean(None))
+            }
+            Token::Word(word) => Some(Value::Operand(self.properties.expand(&word))),
+            _ => None,
+        }
+    }
+}
+
+fn compare(left: &str, operator: &str, right: &str) -> Option<bool> {
+    match operator {
+        "==" => Some(left.eq_ignore_ascii_case(right)),
+        "!=" => Some(!left.eq_ignore_ascii_case(right)),
+        _ => {
+            let left: f64 = left.trim().parse().ok()?;
+            let right: f64 = right.trim().parse().ok()?;
+
+            match operator {
+                "<" => Some(left < right),
+                ">" => Some(left > right),
+                "<=" => Some(left <= right),
+                ">=" => Some(left >= right),
+                _ => None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{evaluate, Properties};
+
+    #[test]
+    pub fn evaluate_conditions() {
+        // given
+        let mut properties = Properties::new();
+        properties.set("Configuration", "Release");
+        properties.set("LangVersion", "12");
+        properties.set("TargetFrameworkPrefix", "net");
+
+        // when
+        let results: Vec<Option<bool>> = [
+            "'$(Configuration)' == 'RELEASE'",
+            " '$(Configuration)|$(Platform)' == 'Release|AnyCPU' ",
+            "'$(Configuration)' != 'Release' or '$(Platform)' == 'x64'",
+            "'$(Configuration)' == 'Release' or '$(Platform)' == 'x64'",
+            "!('$(Configuration)' == 'Debug') and $(LangVersion) >= 11",
+            "'$(TargetFrameworkPrefix)8.0' == 'net8.0'",
+            "Exists('Directory.Build.props')",
+            "true and !false",
+            "'$(Configuration)' ==",
+        ]
+        .iter()
+        .map(|condition| evaluate(condition, &properties))
+        .collect();
+
+        // then
+        assert_eq!(
+            results,
+            vec![
+                Some(true),
+                None,
+                None,
+                Some(true),
+                Some(true),
+                Some(true),
+                None,
+                Some(true),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    pub fn expand_property_references() {
+        // given
+        let mut properties = Properties::new();
+        properties.set("Version", "8.0");
+
+        // then
+        assert_eq!(
+            properties.expand("net$(version)"),
+            Some("net8.0".to_string())
+        );
+        assert_eq!(properties.expand("$(Missing)"), None);
+        assert_eq!(
+            properties.expand("$([MSBuild]::GetTargetFrameworkIdentifier('net8.0'))"),
+            None
+        );
+        assert_eq!(properties.expand("@(Compile)"), None);
+    }
+}
@@ -0,0 +1,471 @@
+//! Prediction of the package version conflicts NuGet reports on restore, from the `.nuspec` files
+//! of the global packages folder: the downgrades of a direct reference (`NU1605`) and the
+//! transitive packages required with incompatible versions (`NU1107`).
+
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Display,
+    path::PathBuf,
+};
+
+use crate::{
+    packages_folder::GlobalPackagesFolder,
+    parser::ParseError,
+    version::{NuGetVersion, VersionRange},
+    workspace::Workspace,
+    Project,
+};
+
+/// Represents the kind of a version conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConflictKind {
+    /// A package is referenced directly with a version lower than the one required by a
+    /// dependency (`NU1605`).
+    Downgrade,
+    /// Several dependencies require versions of a package that no version satisfies (`NU1107`).
+    Incompatible,
+}
+
+impl ConflictKind {
+    /// Returns the code of the NuGet warning or error predicted by the conflict.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ConflictKind::Downgrade => "NU1605",
+            ConflictKind::Incompatible => "NU1107",
+        }
+    }
+}
+
+impl Display for ConflictKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictKind::Downgrade => write!(f, "downgrade"),
+            ConflictKind::Incompatible => write!(f, "incompatible"),
+        }
+    }
+}
+
+/// Represents a version range of a package required by a project or by another package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionRequirement {
+    range: String,
+    path: Vec<String>,
+}
+
+impl VersionRequirement {
+    /// Returns the required version range (ex: `[2.0.0, )`).
+    pub fn range(&self) -> &String {
+        &self.range
+    }
+
+    /// Returns the chain through which the requirement reaches the project: the project itself,
+    /// then the referenced projects and packages, up to the one requiring the package.
+    pub fn path(&self) -> &Vec<String> {
+        &self.path
+    }
+
+    /// Returns the name of the project or package requiring the package.
+    pub fn required_by(&self) -> &String {
+        self.path.last().expect("a requirement path is never empty")
+    }
+
+    /// Returns `true` if the package is referenced by the project itself.
+    pub fn is_direct(&self) -> bool {
+        self.path.len() == 1
+    }
+}
+
+/// Represents a package whose requirements in the restore graph of a project conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionConflict {
+    project: PathBuf,
+    package: String,
+    kind: ConflictKind,
+    requirements: Vec<VersionRequirement>,
+}
+
+impl VersionConflict {
+    /// Returns the path of the project whose restore would report the conflict.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the name of the conflicting package.
+    pub fn package(&self) -> &String {
+        &self.package
+    }
+
+    /// Returns the kind of the conflict.
+    pub fn kind(&self) -> ConflictKind {
+        self.kind
+    }
+
+    /// Returns the requirements in conflict. For a downgrade, the direct reference comes first,
+    /// followed by the requirements of higher versions.
+    pub fn requirements(&self) -> &Vec<VersionRequirement> {
+        &self.requirements
+    }
+}
+
+/// Predicts the version conflicts of every project of a workspace.
+///
+/// See [`project_conflicts`].
+///
+/// # Errors
+///
+/// This function will return an error if a `.nuspec` could not be read or parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{conflicts, packages_folder::GlobalPackagesFolder, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// let folder = GlobalPackagesFolder::from_env().unwrap();
+///
+/// for conflict in conflicts::detect_conflicts(&workspace, &folder)? {
+///     println!(
+///         "{}: {} {} ({})",
+///         conflict.project().display(),
+///         conflict.kind().code(),
+///         conflict.package(),
+///         conflict.kind()
+///     );
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn detect_conflicts(
+    workspace: &Workspace,
+    folder: &GlobalPackagesFolder,
+) -> Result<Vec<VersionConflict>, ParseError> {
+    let mut conflicts = vec![];
+
+    for project in workspace.projects() {
+        conflicts.extend(project_conflicts(workspace, project, folder)?);
+    }
+
+    Ok(conflicts)
+}
+
+/// Predicts the version conflicts NuGet would report when restoring a project.
+///
+/// The requirements are collected from the package references of the project and of the
+/// projects it references transitively, then from the dependencies of those packages, read from
+/// the `.nuspec` of the lowest installed version satisfying each range, like NuGet resolves them.
+/// Packages missing from the folder end the chain.
+///
+/// A package referenced directly by the project is a downgrade when another requirement has a
+/// higher minimum version, since the direct reference wins. Any other package is incompatible
+/// when two of its requirements have no version in common.
+///
+/// # Errors
+///
+/// This function will return an error if a `.nuspec` could not be read or parsed.
+pub fn project_conflicts(
+    workspace: &Workspace,
+    project: &Project,
+    folder: &GlobalPackagesFolder,
+) -> Result<Vec<VersionConflict>, ParseError> {
+    let target_framework = project.target_framework().map(|tfm| tfm.as_str());
+    let mut requirements: Vec<(String, VersionRequirement)> = vec![];
+
+    // the packages to expand, with the path of the project or package referencing them
+    let mut queue = VecDeque::new();
+
+    for (path, referenced) in referenced_projects(workspace, project) {
+        for package_reference in referenced.package_references() {
+            queue.push_back((
+                package_reference.name().to_string(),
+                package_reference.version().to_string(),
+                path.clone(),
+            ));
+        }
+    }
+
+    let mut expanded = HashSet::new();
+
+    while let Some((name, range, path)) = queue.pop_front() {
+        let resolved = resolve(folder, &name, &range)?;
+
+        requirements.push((
+            name.clone(),
+            VersionRequirement {
+                range,
+                path: path.clone(),
+            },
+        ));
+
+        let Some(version) = resolved else {
+            continue;
+        };
+
+        if !expanded.insert((name.to_lowercase(), version.to_string())) {
+            continue;
+        }
+
+        let Some(nuspec) = folder.nuspec(&name, &version.to_string())? else {
+            continue;
+        };
+
+        let mut dependency_path = path;
+        dependency_path.push(name);
+
+        for dependency in nuspec.dependencies_for(target_framework) {
+            queue.push_back((
+                dependency.id().clone(),
+                dependency.version_range().clone(),
+                dependency_path.clone(),
+            ));
+        }
+    }
+
+    let mut conflicts = vec![];
+    let mut checked = HashSet::new();
+
+    for (package, _) in &requirements {
+        if !checked.insert(package.to_lowercase()) {
+            continue;
+        }
+
+        let package_requirements: Vec<&VersionRequirement> = requirements
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case(package))
+            .map(|(_, requirement)| requirement)
+            .collect();
+
+        if let Some((kind, requirements)) = conflict(&package_requirements) {
+            conflicts.push(VersionConflict {
+                project: project.path().clone(),
+                package: package.clone(),
+                kind,
+                requirements,
+            });
+        }
+    }
+
+    Ok(conflicts)
+}
+
+/// Returns the project and the projects it references transitively, each with the chain of
+/// project names leading to it.
+fn referenced_projects<'a>(
+    workspace: &'a Workspace,
+    project: &'a Project,
+) -> Vec<(Vec<String>, &'a Project)> {
+    let mut visited = HashSet::from([project.path().clone()]);
+    let mut projects = vec![(vec![project.name().clone()], project)];
+    let mut index = 0;
+
+    while let Some((path, current)) = projects.get(index).cloned() {
+        index += 1;
+
+        for reference in current.project_references() {
+            let Some(referenced) = workspace.resolve_reference(current, reference) else {
+                continue;
+            };
+
+            if visited.insert(referenced.path().clone()) {
+                let mut path = path.clone();
+                path.push(referenced.name().clone());
+                projects.push((path, referenced));
+            }
+        }
+    }
+
+    projects
+}
+
+/// Returns the lowest installed version of a package satisfying a range, if any.
+fn resolve(
+    folder: &GlobalPackagesFolder,
+    name: &str,
+    range: &str,
+) -> Result<Option<NuGetVersion>, ParseError> {
+    let Ok(range) = range.parse::<VersionRange>() else {
+        return Ok(None);
+    };
+
+    Ok(folder
+        .installed_versions(name)?
+        .into_iter()
+        .find(|version| range.satisfies(version)))
+}
+
+/// Returns the conflict between the requirements of a package, if any, with the requirements
+/// involved.
+fn conflict(
+    requirements: &[&VersionRequirement],
+) -> Option<(ConflictKind, Vec<VersionRequirement>)> {
+    let ranges: Vec<(&VersionRequirement, VersionRange)> = requirements
+        .iter()
+        .filter_map(|requirement| {
+            requirement
+                .range
+                .parse::<VersionRange>()
+                .ok()
+                .map(|range| (*requirement, range))
+        })
+        .collect();
+
+    if let Some((direct, direct_range)) = ranges
+        .iter()
+        .find(|(requirement, _)| requirement.is_direct())
+    {
+        let direct_version = direct_range.min()?;
+
+        let higher: Vec<VersionRequirement> = ranges
+            .iter()
+            .filter(|(requirement, range)| {
+                !requirement.is_direct() && range.min().is_some_and(|min| min > direct_version)
+            })
+            .map(|(requirement, _)| (*requirement).clone())
+            .collect();
+
+        if higher.is_empty() {
+            return None;
+        }
+
+        let mut involved = vec![(*direct).clone()];
+        involved.extend(higher);
+
+        return Some((ConflictKind::Downgrade, involved));
+    }
+
+    let incompatible = ranges.iter().enumerate().any(|(index, (_, range))| {
+        ranges[index + 1..]
+            .iter()
+            .any(|(_, other)| !range.intersects(other))
+    });
+
+    incompatible.then(|| {
+        (
+            ConflictKind::Incompatible,
+            ranges
+                .iter()
+                .map(|(requirement, _)| (*requirement).clone())
+                .collect(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use tempfile::tempdir;
+
+    use crate::{nuspec, packages_folder::GlobalPackagesFolder, workspace::WorkspaceBuilder};
+
+    use super::{detect_conflicts, ConflictKind};
+
+    fn write_nuspec(root: &Path, name: &str, version: &str, dependencies: &[(&str, &str)]) {
+        let path = nuspec::global_packages_path(root, name, version);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let dependencies: String = dependencies
+            .iter()
+            .map(|(id, range)| format!(r#"<dependency id="{id}" version="{range}" />"#))
+            .collect();
+
+        fs::write(
+            path,
+            format!(
+                r#"<package><metadata><id>{name}</id><version>{version}</version><dependencies><group targetFramework="net8.0">{dependencies}</group></dependencies></metadata></package>"#
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    pub fn detect_diamond_conflicts() {
+        // given
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        write_nuspec(root, "A", "1.0.0", &[("D", "[1.0.0]"), ("C", "2.0.0")]);
+        write_nuspec(root, "B", "1.0.0", &[("D", "[2.0.0, )")]);
+        write_nuspec(root, "C", "1.0.0", &[]);
+        write_nuspec(root, "D", "1.0.0", &[]);
+        write_nuspec(root, "D", "2.0.0", &[]);
+
+        let folder = GlobalPackagesFolder::new(root.to_path_buf());
+
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project(
+            "src/Api/Api.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <ProjectReference Include="../Domain/Domain.csproj" />
+    <PackageReference Include="A" Version="1.0.0" />
+    <PackageReference Include="C" Version="1.0.0" />
+  </ItemGroup>
+</Project>"#,
+        );
+        builder.add_project(
+            "src/Domain/Domain.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="B" Version="1.0.0" />
+  </ItemGroup>
+</Project>"#,
+        );
+        let workspace = builder.build().unwrap();
+
+        // when
+        let conflicts = detect_conflicts(&workspace, &folder).unwrap();
+
+        // then
+        let summary: Vec<(&str, &str, ConflictKind, Vec<String>)> = conflicts
+            .iter()
+            .map(|conflict| {
+                (
+                    conflict.project().to_str().unwrap(),
+                    conflict.package().as_str(),
+                    conflict.kind(),
+                    conflict
+                        .requirements()
+                        .iter()
+                        .map(|requirement| {
+                            format!("{} {}", requirement.path().join(" > "), requirement.range())
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "/repo/src/Api/Api.csproj",
+                    "C",
+                    ConflictKind::Downgrade,
+                    vec!["Api 1.0.0".to_string(), "Api > A 2.0.0".to_string()]
+                ),
+                (
+                    "/repo/src/Api/Api.csproj",
+                    "D",
+                    ConflictKind::Incompatible,
+                    vec![
+                        "Api > A [1.0.0]".to_string(),
+                        "Api > Domain > B [2.0.0, )".to_string()
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(conflicts[1].requirements()[1].required_by(), "B");
+        assert_eq!(conflicts[0].kind().code(), "NU1605");
+
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,466 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+use crate::{workspace::Workspace, Project};
+
+/// Represents the differences between two workspaces, for example the main branch and a pull
+/// request branch.
+///
+/// Projects are identified by their path relative to the workspace root, so the two workspaces
+/// can be checked out at different locations. Every list is sorted.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{diff::WorkspaceDiff, workspace::Workspace};
+///
+/// let base = Workspace::load("path/to/main")?;
+/// let head = Workspace::load("path/to/pull-request")?;
+///
+/// let diff = WorkspaceDiff::between(&base, &head);
+///
+/// if !diff.is_empty() {
+///     println!("{}", diff.to_markdown());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkspaceDiff {
+    added_projects: Vec<String>,
+    removed_projects: Vec<String>,
+    added_references: Vec<ReferenceChange>,
+    removed_references: Vec<ReferenceChange>,
+    added_packages: Vec<PackageChange>,
+    removed_packages: Vec<PackageChange>,
+    version_changes: Vec<VersionChange>,
+}
+
+/// Represents a project reference that was added or removed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReferenceChange {
+    project: String,
+    reference: String,
+}
+
+/// Represents a package reference that was added or removed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackageChange {
+    project: String,
+    name: String,
+    version: String,
+}
+
+/// Represents a package reference whose version changed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionChange {
+    project: String,
+    name: String,
+    from: String,
+    to: String,
+}
+
+/// The relative paths of the projects, with their referenced projects and their packages keyed
+/// by lowercase name.
+type Index = BTreeMap<String, (BTreeSet<String>, BTreeMap<String, (String, String)>)>;
+
+impl WorkspaceDiff {
+    /// Compares the `base` workspace with the `head` workspace.
+    ///
+    /// Project references are compared by the relative path of the referenced project. Package
+    /// references are compared by name, ignoring case; a package whose version changed is
+    /// reported as a version change instead of a removal and an addition.
+    pub fn between(base: &Workspace, head: &Workspace) -> Self {
+        let base = index(base);
+        let head = index(head);
+
+        let mut diff = Self::default();
+
+        for (project, (references, packages)) in &head {
+            let Some((base_references, base_packages)) = base.get(project) else {
+                diff.added_projects.push(project.clone());
+                continue;
+            };
+
+            for reference in references.difference(base_references) {
+                diff.added_references
+                    .push(ReferenceChange::new(project.clone(), reference.clone()));
+            }
+
+            for reference in base_references.difference(references) {
+                diff.removed_references
+                    .push(ReferenceChange::new(project.clone(), reference.clone()));
+            }
+
+            for (key, (name, version)) in packages {
+                match base_packages.get(key) {
+                    None => diff.added_packages.push(PackageChange::new(
+                        project.clone(),
+                        name.clone(),
+                        version.clone(),
+                    )),
+                    Some((_, base_version)) if base_version != version => {
+                        diff.version_changes.push(VersionChange::new(
+                            project.clone(),
+                            name.clone(),
+                            base_version.clone(),
+                            version.clone(),
+                        ))
+                    }
+                    Some(_) => (),
+                }
+            }
+
+            for (key, (name, version)) in base_packages {
+                if !packages.contains_key(key) {
+                    diff.removed_packages.push(PackageChange::new(
+                        project.clone(),
+                        name.clone(),
+                        version.clone(),
+                    ));
+                }
+            }
+        }
+
+        diff.removed_projects = base
+            .keys()
+            .filter(|project| !head.contains_key(*project))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    /// Returns the relative paths of the projects only found in the head workspace.
+    pub fn added_projects(&self) -> &Vec<String> {
+        &self.added_projects
+    }
+
+    /// Returns the relative paths of the projects only found in the base workspace.
+    pub fn removed_projects(&self) -> &Vec<String> {
+        &self.removed_projects
+    }
+
+    /// Returns the project references added to projects found in both workspaces.
+    pub fn added_references(&self) -> &Vec<ReferenceChange> {
+        &self.added_references
+    }
+
+    /// Returns the project references removed from projects found in both workspaces.
+    pub fn removed_references(&self) -> &Vec<ReferenceChange> {
+        &self.removed_references
+    }
+
+    /// Returns the package references added to projects found in both workspaces.
+    pub fn added_packages(&self) -> &Vec<PackageChange> {
+        &self.added_packages
+    }
+
+    /// Returns the package references removed from projects found in both workspaces.
+    pub fn removed_packages(&self) -> &Vec<PackageChange> {
+        &self.removed_packages
+    }
+
+    /// Returns the package references whose version changed.
+    pub fn version_changes(&self) -> &Vec<VersionChange> {
+        &self.version_changes
+    }
+
+    /// Returns `true` if the workspaces have the same projects and references.
+    pub fn is_empty(&self) -> bool {
+        self.added_projects.is_empty()
+            && self.removed_projects.is_empty()
+            && self.added_references.is_empty()
+            && self.removed_references.is_empty()
+            && self.added_packages.is_empty()
+            && self.removed_packages.is_empty()
+            && self.version_changes.is_empty()
+    }
+
+    /// Returns the differences as a Markdown summary, suitable for a pull request comment.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("## Dependency changes\n");
+
+        if self.is_empty() {
+            markdown.push_str("\nNo dependency changes.\n");
+            return markdown;
+        }
+
+        let mut section = |title: &str, lines: Vec<String>| {
+            if lines.is_empty() {
+                return;
+            }
+
+            let _ = write!(markdown, "\n### {}\n\n", title);
+
+            for line in lines {
+                let _ = writeln!(markdown, "- {}", line);
+            }
+        };
+
+        section(
+            "Added projects",
+            self.added_projects
+                .iter()
+                .map(|project| format!("`{}`", project))
+                .collect(),
+        );
+        section(
+            "Removed projects",
+            self.removed_projects
+                .iter()
+                .map(|project| format!("`{}`", project))
+                .collect(),
+        );
+        section(
+            "Added project references",
+            self.added_references
+                .iter()
+                .map(|change| format!("`{}` → `{}`", change.project, change.reference))
+                .collect(),
+        );
+        section(
+            "Removed project references",
+            self.removed_references
+                .iter()
+                .map(|change| format!("`{}` → `{}`", change.project, change.reference))
+                .collect(),
+        );
+        section(
+            "Added packages",
+            self.added_packages
+                .iter()
+                .map(|change| format!("`{}`: {} {}", change.project, change.name, change.version))
+                .collect(),
+        );
+        section(
+            "Removed packages",
+            self.removed_packages
+                .iter()
+                .map(|change| format!("`{}`: {} {}", change.project, change.name, change.version))
+                .collect(),
+        );
+        section(
+            "Version changes",
+            self.version_changes
+                .iter()
+                .map(|change| {
+                    format!(
+                        "`{}`: {} {} → {}",
+                        change.project, change.name, change.from, change.to
+                    )
+                })
+                .collect(),
+        );
+
+        markdown
+    }
+}
+
+impl ReferenceChange {
+    /// Creates a new `ReferenceChange`.
+    pub fn new(project: String, reference: String) -> Self {
+        Self { project, reference }
+    }
+
+    /// Returns the relative path of the referencing project.
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    /// Returns the relative path of the referenced project.
+    pub fn reference(&self) -> &String {
+        &self.reference
+    }
+}
+
+impl PackageChange {
+    /// Creates a new `PackageChange`.
+    pub fn new(project: String, name: String, version: String) -> Self {
+        Self {
+            project,
+            name,
+            version,
+        }
+    }
+
+    /// Returns the relative path of the project.
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    /// Returns the name of the package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the version of the package.
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+}
+
+impl VersionChange {
+    /// Creates a new `VersionChange`.
+    pub fn new(project: String, name: String, from: String, to: String) -> Self {
+        Self {
+            project,
+            name,
+            from,
+            to,
+        }
+    }
+
+    /// Returns the relative path of the project.
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    /// Returns the name of the package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the version in the base workspace.
+    pub fn from(&self) -> &String {
+        &self.from
+    }
+
+    /// Returns the version in the head workspace.
+    pub fn to(&self) -> &String {
+        &self.to
+    }
+}
+
+fn index(workspace: &Workspace) -> Index {
+    workspace
+        .projects()
+        .iter()
+        .map(|project| {
+            let references = resolved_references(workspace, project);
+
+            let packages = project
+                .package_references()
+                .iter()
+                .map(|package| {
+                    (
+                        package.name().to_lowercase(),
+                        (package.name().to_string(), package.version().to_string()),
+                    )
+                })
+                .collect();
+
+            (
+                workspace.relative_path(project.path()),
+                (references, packages),
+            )
+        })
+        .collect()
+}
+
+/// Returns the relative paths of the projects referenced by `project`, or the paths of the
+/// references as written when they do not point to a project of the workspace.
+pub(crate) fn resolved_references(workspace: &Workspace, project: &Project) -> BTreeSet<String> {
+    project
+        .project_references()
+        .iter()
+        .map(
+            |reference| match workspace.resolve_reference(project, reference) {
+                Some(referenced) => workspace.relative_path(referenced.path()),
+                None => reference.path().to_string_lossy().replace('\\', "/"),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{graph::test::project, workspace::Workspace};
+
+    use super::{PackageChange, ReferenceChange, VersionChange, WorkspaceDiff};
+
+    #[test]
+    pub fn diff_between_workspaces() {
+        // given
+        let base = Workspace::from_projects(
+            "/main",
+            vec![
+                project(
+                    "/main/src/Api/Api.csproj",
+                    &[],
+                    &[("Serilog", "3.1.1"), ("Polly", "8.0.0")],
+                ),
+                project("/main/src/Legacy/Legacy.csproj", &[], &[]),
+            ],
+        );
+        let head = Workspace::from_projects(
+            "/pr",
+            vec![
+                project(
+                    "/pr/src/Api/Api.csproj",
+                    &["../Domain/Domain.csproj"],
+                    &[("serilog", "4.0.0"), ("Dapper", "2.1.35")],
+                ),
+                project("/pr/src/Domain/Domain.csproj", &[], &[]),
+            ],
+        );
+
+        // when
+        let diff = WorkspaceDiff::between(&base, &head);
+
+        // then
+        assert_eq!(diff.added_projects(), &vec!["src/Domain/Domain.csproj"]);
+        assert_eq!(diff.removed_projects(), &vec!["src/Legacy/Legacy.csproj"]);
+        assert_eq!(
+            diff.added_references(),
+            &vec![ReferenceChange::new(
+                "src/Api/Api.csproj".to_string(),
+                "src/Domain/Domain.csproj".to_string()
+            )]
+        );
+        assert!(diff.removed_references().is_empty());
+        assert_eq!(
+            diff.added_packages(),
+            &vec![PackageChange::new(
+                "src/Api/Api.csproj".to_string(),
+                "Dapper".to_string(),
+                "2.1.35".to_string()
+            )]
+        );
+        assert_eq!(
+            diff.removed_packages(),
+            &vec![PackageChange::new(
+                "src/Api/Api.csproj".to_string(),
+                "Polly".to_string(),
+                "8.0.0".to_string()
+            )]
+        );
+        assert_eq!(
+            diff.version_changes(),
+            &vec![VersionChange::new(
+                "src/Api/Api.csproj".to_string(),
+                "serilog".to_string(),
+                "3.1.1".to_string(),
+                "4.0.0".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    pub fn markdown_of_empty_diff() {
+        let workspace = Workspace::from_projects("/repo", vec![]);
+        let diff = WorkspaceDiff::between(&workspace, &workspace);
+
+        assert!(diff.is_empty());
+        assert_eq!(
+            diff.to_markdown(),
+            "## Dependency changes\n\nNo dependency changes.\n"
+        );
+    }
+}
@@ -0,0 +1,530 @@
+//! Output formats for dependency graphs.
+
+pub mod dot;
+pub mod graphml;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod mermaid;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod tree;
+
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use thiserror::Error;
+
+use crate::{
+    graph::{DependencyGraph, Edge, Node, NodeKind},
+    solution::Solution,
+    workspace::Workspace,
+};
+
+type NodeStyler = Arc<dyn Fn(&Node) -> NodeStyle + Send + Sync>;
+type EdgeStyler = Arc<dyn Fn(&DependencyGraph, &Edge) -> EdgeStyle + Send + Sync>;
+
+/// Represents the options of the DOT and Mermaid exporters.
+#[derive(Clone, Default)]
+pub struct ExportOptions {
+    clusters: Option<Clusters>,
+    node_style: Option<NodeStyler>,
+    edge_style: Option<EdgeStyler>,
+}
+
+/// Represents the style attributes of a node. Unset attributes keep the default style.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NodeStyle {
+    color: Option<String>,
+    shape: Option<String>,
+    label: Option<String>,
+}
+
+/// Represents the style attributes of an edge. Unset attributes keep the default style.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EdgeStyle {
+    color: Option<String>,
+    label: Option<String>,
+}
+
+impl ExportOptions {
+    /// Creates new `ExportOptions` with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the clusters the nodes are grouped into, if any.
+    pub fn clusters(&self) -> Option<&Clusters> {
+        self.clusters.as_ref()
+    }
+
+    /// Sets the clusters the nodes are grouped into. Nodes without a cluster are drawn outside
+    /// of every cluster.
+    pub fn set_clusters(&mut self, clusters: Option<Clusters>) {
+        self.clusters = clusters;
+    }
+
+    /// Sets a function returning the style of each node, for example to color the projects by
+    /// target framework, team ownership or validation status.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::{
+    ///     export::{dot, ExportOptions, NodeStyle},
+    ///     graph::{DependencyGraph, NodeKind},
+    /// };
+    ///
+    /// let mut options = ExportOptions::new();
+    /// options.set_node_style(|node| {
+    ///     let mut style = NodeStyle::new();
+    ///
+    ///     if node.kind() == NodeKind::Project && node.name().ends_with(".Tests") {
+    ///         style.set_color(Some("gray".to_string()));
+    ///     }
+    ///
+    ///     style
+    /// });
+    ///
+    /// let output = dot::to_string_with(&DependencyGraph::default(), &options);
+    /// ```
+    pub fn set_node_style<F>(&mut self, style: F)
+    where
+        F: Fn(&Node) -> NodeStyle + Send + Sync + 'static,
+    {
+        self.node_style = Some(Arc::new(style));
+    }
+
+    /// Sets a function returning the style of each edge.
+    pub fn set_edge_style<F>(&mut self, style: F)
+    where
+        F: Fn(&DependencyGraph, &Edge) -> EdgeStyle + Send + Sync + 'static,
+    {
+        self.edge_style = Some(Arc::new(style));
+    }
+
+    /// Returns the style of a node, or the default style if no function was set.
+    pub fn node_style(&self, node: &Node) -> NodeStyle {
+        self.node_style
+            .as_ref()
+            .map(|style| style(node))
+            .unwrap_or_default()
+    }
+
+    /// Returns the style of an edge, or the default style if no function was set.
+    pub fn edge_style(&self, graph: &DependencyGraph, edge: &Edge) -> EdgeStyle {
+        self.edge_style
+            .as_ref()
+            .map(|style| style(graph, edge))
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for ExportOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExportOptions")
+            .field("clusters", &self.clusters)
+            .field("node_style", &self.node_style.is_some())
+            .field("edge_style", &self.edge_style.is_some())
+            .finish()
+    }
+}
+
+impl NodeStyle {
+    /// Creates a new `NodeStyle` with every attribute unset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the color of the node (ex: `red`, `#ff0000`).
+    pub fn color(&self) -> Option<&String> {
+        self.color.as_ref()
+    }
+
+    /// Sets the color of the node.
+    pub fn set_color(&mut self, color: Option<String>) {
+        self.color = color;
+    }
+
+    /// Returns the shape of the node.
+    pub fn shape(&self) -> Option<&String> {
+        self.shape.as_ref()
+    }
+
+    /// Sets the shape of the node, as a Graphviz shape name (ex: `box`, `ellipse`, `circle`,
+    /// `diamond`). The Mermaid exporter maps the shapes it supports and ignores the others.
+    pub fn set_shape(&mut self, shape: Option<String>) {
+        self.shape = shape;
+    }
+
+    /// Returns the label of the node.
+    pub fn label(&self) -> Option<&String> {
+        self.label.as_ref()
+    }
+
+    /// Sets the label of the node, replacing the project name or package name and version.
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+}
+
+impl EdgeStyle {
+    /// Creates a new `EdgeStyle` with every attribute unset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the color of the edge.
+    pub fn color(&self) -> Option<&String> {
+        self.color.as_ref()
+    }
+
+    /// Sets the color of the edge.
+    pub fn set_color(&mut self, color: Option<String>) {
+        self.color = color;
+    }
+
+    /// Returns the label of the edge.
+    pub fn label(&self) -> Option<&String> {
+        self.label.as_ref()
+    }
+
+    /// Sets the label of the edge.
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+}
+
+/// Represents the assignment of the nodes of a graph to named clusters, drawn as DOT or Mermaid
+/// subgraphs to make large diagrams easier to read.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{
+///     export::{dot, Clusters, ExportOptions},
+///     graph::DependencyGraph,
+/// };
+///
+/// let graph = DependencyGraph::default();
+///
+/// let mut options = ExportOptions::new();
+/// options.set_clusters(Some(Clusters::by_directory(&graph, 1)));
+///
+/// let output = dot::to_string_with(&graph, &options);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Clusters {
+    names: Vec<Option<String>>,
+}
+
+impl Clusters {
+    /// Groups the projects by the first `depth` directories of their path relative to the
+    /// workspace root (ex: `src` for `src/Api/Api.csproj` with a depth of 1).
+    ///
+    /// Projects at the root of the workspace and packages are not grouped.
+    pub fn by_directory(graph: &DependencyGraph, depth: usize) -> Self {
+        Self::from_fn(graph, |node| {
+            if node.kind() != NodeKind::Project {
+                return None;
+            }
+
+            let directory = Path::new(node.id()).parent()?;
+            let components: Vec<String> = directory
+                .components()
+                .take(depth)
+                .map(|component| component.as_os_str().to_string_lossy().to_string())
+                .collect();
+
+            (!components.is_empty()).then(|| components.join("/"))
+        })
+    }
+
+    /// Groups the projects by the solution folder containing them (ex: `src/Core` for nested
+    /// folders).
+    ///
+    /// Projects missing from the solution or at its root, and packages, are not grouped.
+    pub fn by_solution_folder(graph: &DependencyGraph, solution: &Solution) -> Self {
+        Self::from_fn(graph, |node| {
+            let path = node.path()?;
+            let project = solution.find(path)?;
+
+            solution.folder(project)
+        })
+    }
+
+    /// Groups the projects by their first tag (see [`crate::Project::tags`]), looking them up in
+    /// the workspace the graph was built from.
+    ///
+    /// Projects without tags and packages are not grouped.
+    pub fn by_tag(graph: &DependencyGraph, workspace: &Workspace) -> Self {
+        Self::from_fn(graph, |node| {
+            let project = workspace.project_by_path(node.path()?)?;
+
+            project.tags().first().cloned()
+        })
+    }
+
+    /// Groups the nodes with a function returning the name of the cluster of each node, or
+    /// `None` to leave the node outside of every cluster.
+    pub fn from_fn<F>(graph: &DependencyGraph, cluster: F) -> Self
+    where
+        F: Fn(&Node) -> Option<String>,
+    {
+        Self {
+            names: graph.nodes().iter().map(cluster).collect(),
+        }
+    }
+
+    /// Returns the name of the cluster of the node at `index`.
+    pub fn cluster(&self, index: usize) -> Option<&String> {
+        self.names.get(index).and_then(|name| name.as_ref())
+    }
+
+    /// Returns the cluster names, in the order of their first node, with the indexes of their
+    /// nodes.
+    pub(crate) fn groups(&self) -> Vec<(&String, Vec<usize>)> {
+        let mut groups: Vec<(&String, Vec<usize>)> = vec![];
+
+        for (index, name) in self.names.iter().enumerate() {
+            let Some(name) = name else {
+                continue;
+            };
+
+            match groups.iter_mut().find(|(group, _)| *group == name) {
+                Some((_, indexes)) => indexes.push(index),
+                None => groups.push((name, vec![index])),
+            }
+        }
+
+        groups
+    }
+}
+
+/// Represents an output format writing a dependency graph, and the workspace it was built from,
+/// into a writer.
+///
+/// The built-in graph formats are [`dot::DotExporter`], [`mermaid::MermaidExporter`],
+/// [`json::JsonExporter`], [`graphml::GraphmlExporter`] and [`tree::TreeExporter`]. The SBOM
+/// ([`crate::sbom::cyclonedx::CycloneDxExporter`], [`crate::sbom::spdx::SpdxExporter`]),
+/// validation ([`crate::output::sarif::SarifExporter`], [`crate::output::github::GithubExporter`])
+/// and package listing ([`crate::output::dotnet_list::DotnetListExporter`]) formats are exporters
+/// too, describing the workspace without using the graph. The SQLite and Parquet exporters write
+/// databases and directories instead of a single stream, so they are not exporters.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Write;
+///
+/// use dotnet_lens::{
+///     export::{ExportError, Exporter, Exporters},
+///     graph::DependencyGraph,
+///     workspace::Workspace,
+/// };
+///
+/// /// Writes one `from -> to` line per edge.
+/// struct EdgeList;
+///
+/// impl Exporter for EdgeList {
+///     fn name(&self) -> &str {
+///         "edges"
+///     }
+///
+///     fn export(
+///         &self,
+///         writer: &mut dyn Write,
+///         graph: &DependencyGraph,
+///         _workspace: &Workspace,
+///     ) -> Result<(), ExportError> {
+///         for edge in graph.edges() {
+///             let from = graph.node(edge.from()).name();
+///             let to = graph.node(edge.to()).name();
+///
+///             writeln!(writer, "{} -> {}", from, to)?;
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let mut exporters = Exporters::new();
+/// exporters.register(EdgeList);
+///
+/// assert!(exporters.get("edges").is_some());
+/// ```
+pub trait Exporter {
+    /// Returns the name of the format (ex: `dot`), used to select it.
+    fn name(&self) -> &str;
+
+    /// Writes the graph into `writer`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `ExportError` if the output could not be written.
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        graph: &DependencyGraph,
+        workspace: &Workspace,
+    ) -> Result<(), ExportError>;
+}
+
+/// Represents the exporters available to write a graph, looked up by name.
+#[derive(Default)]
+pub struct Exporters {
+    exporters: Vec<Box<dyn Exporter>>,
+}
+
+impl Exporters {
+    /// Creates a new `Exporters` without any exporter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `Exporters` with the built-in formats, with the DOT and Mermaid formats
+    /// using the given options, the tree format without colors, the SBOM formats with a stable
+    /// serial number and the validation formats with the built-in rules.
+    pub fn builtin(options: &ExportOptions) -> Self {
+        let mut exporters = Self::new();
+        exporters.register(dot::DotExporter::new(options.clone()));
+        exporters.register(mermaid::MermaidExporter::new(options.clone()));
+        #[cfg(feature = "serde")]
+        exporters.register(json::JsonExporter);
+        exporters.register(graphml::GraphmlExporter);
+        exporters.register(tree::TreeExporter::default());
+        #[cfg(feature = "serde")]
+        {
+            exporters.register(crate::sbom::cyclonedx::CycloneDxExporter::default());
+            exporters.register(crate::sbom::spdx::SpdxExporter::default());
+            exporters.register(crate::output::sarif::SarifExporter::default());
+            exporters.register(crate::output::dotnet_list::DotnetListExporter);
+        }
+        exporters.register(crate::output::github::GithubExporter::default());
+
+        exporters
+    }
+
+    /// Registers an exporter, replacing the exporter previously registered with the same name.
+    pub fn register<E>(&mut self, exporter: E)
+    where
+        E: Exporter + 'static,
+    {
+        self.exporters
+            .retain(|registered| registered.name() != exporter.name());
+        self.exporters.push(Box::new(exporter));
+    }
+
+    /// Returns the exporter with the given name.
+    pub fn get(&self, name: &str) -> Option<&dyn Exporter> {
+        self.exporters
+            .iter()
+            .find(|exporter| exporter.name() == name)
+            .map(|exporter| exporter.as_ref())
+    }
+
+    /// Returns the names of the exporters, in the order they were registered.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.exporters.iter().map(|exporter| exporter.name())
+    }
+}
+
+impl fmt::Debug for Exporters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Exporters")
+            .field("names", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Represents errors that can occur while exporting a graph.
+#[derive(Debug, Error)]
+pub enum ExportError {
+    /// The output could not be written.
+    #[error("the output could not be written")]
+    Io(#[from] io::Error),
+    /// The output could not be serialized as JSON.
+    #[cfg(feature = "serde")]
+    #[error("the output could not be serialized as JSON")]
+    Json(#[from] serde_json::Error),
+    /// A custom exporter failed.
+    #[error("the exporter failed: {0}")]
+    Other(Box<dyn Error + Send + Sync>),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph::{test::workspace, DependencyGraph};
+
+    #[cfg(feature = "serde")]
+    use crate::sbom::SbomOptions;
+
+    use super::{dot, Clusters, ExportOptions, Exporters};
+
+    #[test]
+    pub fn cluster_projects_by_directory() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+
+        // when
+        let top_level = Clusters::by_directory(&graph, 1);
+        let nested = Clusters::by_directory(&graph, 2);
+
+        // then
+        assert_eq!(top_level.cluster(0), Some(&"src".to_string()));
+        assert_eq!(nested.cluster(1), Some(&"src/Domain".to_string()));
+        assert_eq!(top_level.cluster(2), None);
+        assert_eq!(top_level.groups().len(), 1);
+    }
+
+    #[test]
+    pub fn export_with_builtin_exporters() {
+        // given
+        let workspace = workspace();
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+        let exporters = Exporters::builtin(&ExportOptions::new());
+
+        // when
+        let mut output = vec![];
+        exporters
+            .get("dot")
+            .unwrap()
+            .export(&mut output, &graph, &workspace)
+            .unwrap();
+
+        // then
+        assert_eq!(String::from_utf8(output).unwrap(), dot::to_string(&graph));
+        assert!(exporters.names().any(|name| name == "graphml"));
+        assert!(exporters.names().any(|name| name == "github"));
+        assert!(exporters.get("svg").is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn export_workspace_formats() {
+        // given
+        let workspace = workspace();
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+        let exporters = Exporters::builtin(&ExportOptions::new());
+
+        // when
+        let mut output = vec![];
+        exporters
+            .get("cyclonedx")
+            .unwrap()
+            .export(&mut output, &graph, &workspace)
+            .unwrap();
+
+        // then
+        let expected =
+            crate::sbom::cyclonedx::to_string(&workspace, &SbomOptions::stable(&workspace))
+                .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), expected + "\n");
+    }
+}
@@ -0,0 +1,262 @@
+use std::io::{self, Write};
+
+use crate::{
+    export::{ExportError, ExportOptions, Exporter},
+    graph::{DependencyGraph, EdgeKind, Node, NodeKind},
+    workspace::Workspace,
+};
+
+/// Writes the dependency graph in the Graphviz DOT format.
+///
+/// Projects are drawn as boxes and packages as ellipses, with package references dashed.
+///
+/// # Arguments
+///
+/// * `writer` - The writer where the graph will be written.
+/// * `graph` - The dependency graph.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{export::dot, graph::DependencyGraph};
+///
+/// let mut output = vec![];
+/// dot::write(&mut output, &DependencyGraph::default()).unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), "digraph dependencies {\n}\n");
+/// ```
+pub fn write<W>(mut writer: W, graph: &DependencyGraph) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(to_string(graph).as_bytes())
+}
+
+/// Writes the dependency graph in the Graphviz DOT format with the given options.
+///
+/// Clusters are drawn as `cluster_` subgraphs labeled with the cluster name.
+pub fn write_with<W>(
+    mut writer: W,
+    graph: &DependencyGraph,
+    options: &ExportOptions,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(to_string_with(graph, options).as_bytes())
+}
+
+/// Returns the dependency graph in the Graphviz DOT format.
+///
+/// See [`write`] for more details.
+pub fn to_string(graph: &DependencyGraph) -> String {
+    to_string_with(graph, &ExportOptions::default())
+}
+
+/// Returns the dependency graph in the Graphviz DOT format with the given options.
+///
+/// See [`write_with`] for more details.
+pub fn to_string_with(graph: &DependencyGraph, options: &ExportOptions) -> String {
+    let mut output = String::from("digraph dependencies {\n");
+
+    for (index, node) in graph.nodes().iter().enumerate() {
+        if options
+            .clusters()
+            .is_none_or(|clusters| clusters.cluster(index).is_none())
+        {
+            output += &format!("  {}\n", node_statement(node, options));
+        }
+    }
+
+    if let Some(clusters) = options.clusters() {
+        for (position, (name, indexes)) in clusters.groups().into_iter().enumerate() {
+            output += &format!("  subgraph \"cluster_{}\" {{\n", position);
+            output += &format!("    label=\"{}\";\n", escape(name));
+
+            for index in indexes {
+                output += &format!("    {}\n", node_statement(graph.node(index), options));
+            }
+
+            output += "  }\n";
+        }
+    }
+
+    for edge in graph.edges() {
+        let style = options.edge_style(graph, edge);
+        let mut attributes = vec![];
+
+        if edge.kind() != EdgeKind::ProjectReference {
+            attributes.push("style=dashed".to_string());
+        }
+
+        if let Some(color) = style.color() {
+            attributes.push(format!("color=\"{}\"", escape(color)));
+        }
+
+        if let Some(label) = style.label() {
+            attributes.push(format!("label=\"{}\"", escape(label)));
+        }
+
+        let attributes = if attributes.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attributes.join(", "))
+        };
+
+        output += &format!(
+            "  \"{}\" -> \"{}\"{};\n",
+            escape(graph.node(edge.from()).id()),
+            escape(graph.node(edge.to()).id()),
+            attributes
+        );
+    }
+
+    output += "}\n";
+
+    output
+}
+
+fn node_statement(node: &Node, options: &ExportOptions) -> String {
+    let (shape, label) = match node.kind() {
+        NodeKind::Project => ("box", node.name().clone()),
+        NodeKind::Package => (
+            "ellipse",
+            format!("{} {}", node.name(), node.version().map_or("", |v| v)),
+        ),
+    };
+
+    let style = options.node_style(node);
+    let color = style
+        .color()
+        .map(|color| format!(", color=\"{}\"", escape(color)))
+        .unwrap_or_default();
+
+    format!(
+        "\"{}\" [label=\"{}\", shape={}{}];",
+        escape(node.id()),
+        escape(style.label().unwrap_or(&label)),
+        style.shape().map_or(shape, |shape| shape.as_str()),
+        color
+    )
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// An [`Exporter`] writing the graph in the Graphviz DOT format (see [`write_with`]).
+#[derive(Debug, Clone, Default)]
+pub struct DotExporter {
+    options: ExportOptions,
+}
+
+impl DotExporter {
+    /// Creates a new `DotExporter` with the given options.
+    pub fn new(options: ExportOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Exporter for DotExporter {
+    fn name(&self) -> &str {
+        "dot"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        graph: &DependencyGraph,
+        _workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        Ok(write_with(writer, graph, &self.options)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        export::{Clusters, EdgeStyle, ExportOptions, NodeStyle},
+        graph::{test::workspace, DependencyGraph},
+    };
+
+    use super::{to_string, to_string_with};
+
+    #[test]
+    pub fn dot_contains_nodes_and_edges() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+
+        // when
+        let dot = to_string(&graph);
+
+        // then
+        assert_eq!(
+            dot,
+            r#"digraph dependencies {
+  "src/Api/Api.csproj" [label="Api", shape=box];
+  "src/Domain/Domain.csproj" [label="Domain", shape=box];
+  "Serilog@3.1.1" [label="Serilog 3.1.1", shape=ellipse];
+  "src/Api/Api.csproj" -> "src/Domain/Domain.csproj";
+  "src/Api/Api.csproj" -> "Serilog@3.1.1" [style=dashed];
+  "src/Domain/Domain.csproj" -> "Serilog@3.1.1" [style=dashed];
+}
+"#
+        );
+    }
+
+    #[test]
+    pub fn dot_groups_clustered_nodes() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+        let mut options = ExportOptions::new();
+        options.set_clusters(Some(Clusters::by_directory(&graph, 1)));
+
+        // when
+        let dot = to_string_with(&graph, &options);
+
+        // then
+        assert!(dot.starts_with(
+            r#"digraph dependencies {
+  "Serilog@3.1.1" [label="Serilog 3.1.1", shape=ellipse];
+  subgraph "cluster_0" {
+    label="src";
+    "src/Api/Api.csproj" [label="Api", shape=box];
+    "src/Domain/Domain.csproj" [label="Domain", shape=box];
+  }
+"#
+        ));
+    }
+
+    #[test]
+    pub fn dot_applies_styles() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), false);
+        let mut options = ExportOptions::new();
+
+        options.set_node_style(|node| {
+            let mut style = NodeStyle::new();
+            style.set_color(Some("red".to_string()));
+            style.set_label(Some(format!("{} (net8.0)", node.name())));
+            style
+        });
+        options.set_edge_style(|_, _| {
+            let mut style = EdgeStyle::new();
+            style.set_label(Some("uses".to_string()));
+            style
+        });
+
+        // when
+        let dot = to_string_with(&graph, &options);
+
+        // then
+        assert_eq!(
+            dot,
+            r#"digraph dependencies {
+  "src/Api/Api.csproj" [label="Api (net8.0)", shape=box, color="red"];
+  "src/Domain/Domain.csproj" [label="Domain (net8.0)", shape=box, color="red"];
+  "src/Api/Api.csproj" -> "src/Domain/Domain.csproj" [label="uses"];
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::io::{self, Write};
+
+use crate::{
+    export::{ExportError, Exporter},
+    graph::{DependencyGraph, EdgeKind, NodeKind},
+    workspace::Workspace,
+};
+
+/// Writes the dependency graph in the GraphML format, which can be opened by tools such as yEd,
+/// Gephi and Cytoscape.
+///
+/// Nodes carry `name`, `kind` (`project` or `package`) and `version` attributes, and edges `kind`
+/// (`project-reference`, `package-reference` or `package-dependency`) and `class` (`runtime`,
+/// `compile-only`, `analyzer` or `build`) attributes.
+///
+/// # Arguments
+///
+/// * `writer` - The writer where the document will be written.
+/// * `graph` - The dependency graph.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{export::graphml, graph::DependencyGraph};
+///
+/// let mut output = vec![];
+/// graphml::write(&mut output, &DependencyGraph::default()).unwrap();
+/// ```
+pub fn write<W>(mut writer: W, graph: &DependencyGraph) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(to_string(graph).as_bytes())
+}
+
+/// Returns the dependency graph as a GraphML document.
+///
+/// See [`write`] for more details.
+pub fn to_string(graph: &DependencyGraph) -> String {
+    let mut output = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n",
+        "  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+        "  <key id=\"version\" for=\"node\" attr.name=\"version\" attr.type=\"string\"/>\n",
+        "  <key id=\"edge_kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+        "  <key id=\"edge_class\" for=\"edge\" attr.name=\"class\" attr.type=\"string\"/>\n",
+        "  <graph id=\"dependencies\" edgedefault=\"directed\">\n",
+    ));
+
+    for node in graph.nodes() {
+        let kind = match node.kind() {
+            NodeKind::Project => "project",
+            NodeKind::Package => "package",
+        };
+
+        output += &format!(
+            "    <node id=\"{}\">\n      <data key=\"name\">{}</data>\n      <data key=\"kind\">{}</data>\n",
+            escape(node.id()),
+            escape(node.name()),
+            kind
+        );
+
+        if let Some(version) = node.version() {
+            output += &format!("      <data key=\"version\">{}</data>\n", escape(version));
+        }
+
+        output += "    </node>\n";
+    }
+
+    for edge in graph.edges() {
+        let kind = match edge.kind() {
+            EdgeKind::ProjectReference => "project-reference",
+            EdgeKind::PackageReference => "package-reference",
+            EdgeKind::PackageDependency => "package-dependency",
+        };
+
+        output += &format!(
+            "    <edge source=\"{}\" target=\"{}\">\n      <data key=\"edge_kind\">{}</data>\n      <data key=\"edge_class\">{}</data>\n    </edge>\n",
+            escape(graph.node(edge.from()).id()),
+            escape(graph.node(edge.to()).id()),
+            kind,
+            edge.class().name()
+        );
+    }
+
+    output += "  </graph>\n</graphml>\n";
+
+    output
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// An [`Exporter`] writing the graph as a GraphML document (see [`write`]).
+#[derive(Debug, Copy, Clone, Default)]
+pub struct GraphmlExporter;
+
+impl Exporter for GraphmlExporter {
+    fn name(&self) -> &str {
+        "graphml"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        graph: &DependencyGraph,
+        _workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        Ok(write(writer, graph)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph::{test::workspace, DependencyGraph};
+
+    use super::to_string;
+
+    #[test]
+    pub fn graphml_contains_nodes_and_edges() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+
+        // when
+        let graphml = to_string(&graph);
+
+        // then
+        assert!(graphml.contains(concat!(
+            "    <node id=\"Serilog@3.1.1\">\n",
+            "      <data key=\"name\">Serilog</data>\n",
+            "      <data key=\"kind\">package</data>\n",
+            "      <data key=\"version\">3.1.1</data>\n",
+            "    </node>\n"
+        )));
+        assert!(graphml.contains(concat!(
+            "    <edge source=\"src/Api/Api.csproj\" target=\"src/Domain/Domain.csproj\">\n",
+            "      <data key=\"edge_kind\">project-reference</data>\n",
+            "      <data key=\"edge_class\">runtime</data>\n",
+            "    </edge>\n"
+        )));
+        assert!(graphml.ends_with("</graphml>\n"));
+    }
+}
@@ -0,0 +1,134 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::{
+    export::{ExportError, Exporter},
+    graph::{DependencyGraph, EdgeClass, EdgeKind, NodeKind},
+    workspace::Workspace,
+};
+
+/// Writes the dependency graph as a JSON object with `nodes` and `edges` arrays.
+///
+/// Edges reference the nodes by id instead of by index, so the output stays readable and does
+/// not depend on the order of the nodes.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{export::json, graph::DependencyGraph};
+///
+/// let mut output = vec![];
+/// json::write(&mut output, &DependencyGraph::default()).unwrap();
+/// ```
+pub fn write<W>(writer: W, graph: &DependencyGraph) -> Result<(), serde_json::Error>
+where
+    W: Write,
+{
+    serde_json::to_writer_pretty(writer, &build_document(graph))
+}
+
+/// Serializes the dependency graph as a JSON string.
+///
+/// See [`write`] for more details.
+pub fn to_string(graph: &DependencyGraph) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&build_document(graph))
+}
+
+fn build_document(graph: &DependencyGraph) -> Document<'_> {
+    Document {
+        nodes: graph
+            .nodes()
+            .iter()
+            .map(|node| JsonNode {
+                id: node.id(),
+                name: node.name(),
+                kind: node.kind(),
+                version: node.version(),
+            })
+            .collect(),
+        edges: graph
+            .edges()
+            .iter()
+            .map(|edge| JsonEdge {
+                from: graph.node(edge.from()).id(),
+                to: graph.node(edge.to()).id(),
+                kind: edge.kind(),
+                class: edge.class(),
+            })
+            .collect(),
+    }
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    nodes: Vec<JsonNode<'a>>,
+    edges: Vec<JsonEdge<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    id: &'a String,
+    name: &'a String,
+    kind: NodeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a String>,
+}
+
+#[derive(Serialize)]
+struct JsonEdge<'a> {
+    from: &'a String,
+    to: &'a String,
+    kind: EdgeKind,
+    class: EdgeClass,
+}
+
+/// An [`Exporter`] writing the graph as a JSON object (see [`write`]), followed by a newline.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct JsonExporter;
+
+impl Exporter for JsonExporter {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        graph: &DependencyGraph,
+        _workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        write(&mut *writer, graph)?;
+        writeln!(writer)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph::{test::workspace, DependencyGraph};
+
+    use super::to_string;
+
+    #[test]
+    pub fn json_references_nodes_by_id() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+
+        // when
+        let json: serde_json::Value = serde_json::from_str(&to_string(&graph).unwrap()).unwrap();
+
+        // then
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            json["edges"][1],
+            serde_json::json!({
+                "from": "src/Api/Api.csproj",
+                "to": "Serilog@3.1.1",
+                "kind": "PackageReference",
+                "class": "Runtime"
+            })
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use std::io::{self, Write};
+
+use crate::{
+    export::{ExportError, ExportOptions, Exporter},
+    graph::{DependencyGraph, EdgeKind, Node, NodeKind},
+    workspace::Workspace,
+};
+
+/// Writes the dependency graph as a Mermaid flowchart.
+///
+/// Mermaid ids cannot contain most punctuation, so nodes are identified by their index in the
+/// graph (`n0`, `n1`, ...) and labeled with the project or package name. Projects are drawn as
+/// rectangles and packages as stadiums, with package references dotted.
+///
+/// # Arguments
+///
+/// * `writer` - The writer where the flowchart will be written.
+/// * `graph` - The dependency graph.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{export::mermaid, graph::DependencyGraph};
+///
+/// let mut output = vec![];
+/// mermaid::write(&mut output, &DependencyGraph::default()).unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), "graph LR\n");
+/// ```
+pub fn write<W>(mut writer: W, graph: &DependencyGraph) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(to_string(graph).as_bytes())
+}
+
+/// Writes the dependency graph as a Mermaid flowchart with the given options.
+///
+/// Clusters are drawn as subgraphs (`c0`, `c1`, ...) titled with the cluster name.
+pub fn write_with<W>(
+    mut writer: W,
+    graph: &DependencyGraph,
+    options: &ExportOptions,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(to_string_with(graph, options).as_bytes())
+}
+
+/// Returns the dependency graph as a Mermaid flowchart.
+///
+/// See [`write`] for more details.
+pub fn to_string(graph: &DependencyGraph) -> String {
+    to_string_with(graph, &ExportOptions::default())
+}
+
+/// Returns the dependency graph as a Mermaid flowchart with the given options.
+///
+/// See [`write_with`] for more details.
+pub fn to_string_with(graph: &DependencyGraph, options: &ExportOptions) -> String {
+    let mut output = String::from("graph LR\n");
+
+    for (index, node) in graph.nodes().iter().enumerate() {
+        if options
+            .clusters()
+            .is_none_or(|clusters| clusters.cluster(index).is_none())
+        {
+            output += &format!("  {}\n", node_shape(index, node, options));
+        }
+    }
+
+    if let Some(clusters) = options.clusters() {
+        for (position, (name, indexes)) in clusters.groups().into_iter().enumerate() {
+            output += &format!("  subgraph c{}[\"{}\"]\n", position, escape(name));
+
+            for index in indexes {
+                output += &format!("    {}\n", node_shape(index, graph.node(index), options));
+            }
+
+            output += "  end\n";
+        }
+    }
+
+    let mut styles = vec![];
+
+    for (index, node) in graph.nodes().iter().enumerate() {
+        if let Some(color) = options.node_style(node).color() {
+            styles.push(format!("  style n{} stroke:{}\n", index, color));
+        }
+    }
+
+    for (position, edge) in graph.edges().iter().enumerate() {
+        let style = options.edge_style(graph, edge);
+
+        let arrow = match edge.kind() {
+            EdgeKind::ProjectReference => "-->",
+            EdgeKind::PackageReference | EdgeKind::PackageDependency => "-.->",
+        };
+
+        let label = style
+            .label()
+            .map(|label| format!("|\"{}\"|", escape(label)))
+            .unwrap_or_default();
+
+        output += &format!("  n{} {}{} n{}\n", edge.from(), arrow, label, edge.to());
+
+        if let Some(color) = style.color() {
+            styles.push(format!("  linkStyle {} stroke:{}\n", position, color));
+        }
+    }
+
+    for style in styles {
+        output += &style;
+    }
+
+    output
+}
+
+fn node_shape(index: usize, node: &Node, options: &ExportOptions) -> String {
+    let style = options.node_style(node);
+
+    let label = match (style.label(), node.kind()) {
+        (Some(label), _) => label.clone(),
+        (None, NodeKind::Project) => node.name().clone(),
+        (None, NodeKind::Package) => {
+            format!("{} {}", node.name(), node.version().map_or("", |v| v))
+        }
+    };
+
+    let (open, close) = match (style.shape().map(|shape| shape.as_str()), node.kind()) {
+        (Some("box" | "rect" | "rectangle"), _) => ("[", "]"),
+        (Some("ellipse" | "oval" | "stadium"), _) => ("([", "])"),
+        (Some("circle"), _) => ("((", "))"),
+        (Some("diamond"), _) => ("{", "}"),
+        (Some("hexagon"), _) => ("{{", "}}"),
+        (_, NodeKind::Project) => ("[", "]"),
+        (_, NodeKind::Package) => ("([", "])"),
+    };
+
+    format!("n{}{}\"{}\"{}", index, open, escape(&label), close)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+/// An [`Exporter`] writing the graph as a Mermaid flowchart (see [`write_with`]).
+#[derive(Debug, Clone, Default)]
+pub struct MermaidExporter {
+    options: ExportOptions,
+}
+
+impl MermaidExporter {
+    /// Creates a new `MermaidExporter` with the given options.
+    pub fn new(options: ExportOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Exporter for MermaidExporter {
+    fn name(&self) -> &str {
+        "mermaid"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        graph: &DependencyGraph,
+        _workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        Ok(write_with(writer, graph, &self.options)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        export::{Clusters, EdgeStyle, ExportOptions, NodeStyle},
+        graph::{test::workspace, DependencyGraph},
+    };
+
+    use super::{to_string, to_string_with};
+
+    #[test]
+    pub fn mermaid_contains_nodes_and_edges() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+
+        // when
+        let mermaid = to_string(&graph);
+
+        // then
+        assert_eq!(
+            mermaid,
+            r#"graph LR
+  n0["Api"]
+  n1["Domain"]
+  n2(["Serilog 3.1.1"])
+  n0 --> n1
+  n0 -.-> n2
+  n1 -.-> n2
+"#
+        );
+    }
+
+    #[test]
+    pub fn mermaid_groups_clustered_nodes() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+        let mut options = ExportOptions::new();
+        options.set_clusters(Some(Clusters::by_directory(&graph, 2)));
+
+        // when
+        let mermaid = to_string_with(&graph, &options);
+
+        // then
+        assert_eq!(
+            mermaid,
+            r#"graph LR
+  n2(["Serilog 3.1.1"])
+  subgraph c0["src/Api"]
+    n0["Api"]
+  end
+  subgraph c1["src/Domain"]
+    n1["Domain"]
+  end
+  n0 --> n1
+  n0 -.-> n2
+  n1 -.-> n2
+"#
+        );
+    }
+
+    #[test]
+    pub fn mermaid_applies_styles() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), false);
+        let mut options = ExportOptions::new();
+
+        options.set_node_style(|node| {
+            let mut style = NodeStyle::new();
+
+            if node.name() == "Domain" {
+                style.set_color(Some("#f00".to_string()));
+                style.set_shape(Some("circle".to_string()));
+            }
+
+            style
+        });
+        options.set_edge_style(|_, _| {
+            let mut style = EdgeStyle::new();
+            style.set_color(Some("blue".to_string()));
+            style
+        });
+
+        // when
+        let mermaid = to_string_with(&graph, &options);
+
+        // then
+        assert_eq!(
+            mermaid,
+            r#"graph LR
+  n0["Api"]
+  n1(("Domain"))
+  n0 --> n1
+  style n1 stroke:#f00
+  linkStyle 0 stroke:blue
+"#
+        );
+    }
+}
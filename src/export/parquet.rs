@@ -0,0 +1,269 @@
+use std::{fs::File, io::Write, path::Path, sync::Arc};
+
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
+
+use crate::{
+    graph::{DependencyGraph, EdgeKind, NodeKind},
+    workspace::Workspace,
+};
+
+/// Returns the projects of the workspace as an Arrow record batch.
+///
+/// Columns: `workspace`, `id` (the path relative to the workspace root), `name`, `language`,
+/// `path`, `target_framework` and `license_expression`. Every table has a `workspace` column
+/// with the workspace root, so the inventories of many repositories can be loaded into the same
+/// table.
+pub fn projects(workspace: &Workspace) -> Result<RecordBatch, ArrowError> {
+    let projects = workspace.projects();
+
+    batch(vec![
+        (
+            "workspace",
+            column(projects.iter().map(|_| Some(root(workspace)))),
+        ),
+        (
+            "id",
+            column(
+                projects
+                    .iter()
+                    .map(|project| Some(workspace.relative_path(project.path()))),
+            ),
+        ),
+        (
+            "name",
+            column(projects.iter().map(|project| Some(project.name().clone()))),
+        ),
+        (
+            "language",
+            column(
+                projects
+                    .iter()
+                    .map(|project| Some(project.language().label().to_string())),
+            ),
+        ),
+        (
+            "path",
+            column(
+                projects
+                    .iter()
+                    .map(|project| Some(project.path().to_string_lossy().to_string())),
+            ),
+        ),
+        (
+            "target_framework",
+            column(
+                projects
+                    .iter()
+                    .map(|project| project.target_framework().cloned()),
+            ),
+        ),
+        (
+            "license_expression",
+            column(
+                projects
+                    .iter()
+                    .map(|project| project.license_expression().cloned()),
+            ),
+        ),
+    ])
+}
+
+/// Returns the packages referenced by the workspace as an Arrow record batch, one row per
+/// package version.
+///
+/// Columns: `workspace`, `id` (`Name@Version`), `name` and `version`.
+pub fn packages(workspace: &Workspace) -> Result<RecordBatch, ArrowError> {
+    let graph = DependencyGraph::from_workspace(workspace, true);
+    let packages: Vec<_> = graph
+        .nodes()
+        .iter()
+        .filter(|node| node.kind() == NodeKind::Package)
+        .collect();
+
+    batch(vec![
+        (
+            "workspace",
+            column(packages.iter().map(|_| Some(root(workspace)))),
+        ),
+        (
+            "id",
+            column(packages.iter().map(|node| Some(node.id().clone()))),
+        ),
+        (
+            "name",
+            column(packages.iter().map(|node| Some(node.name().clone()))),
+        ),
+        (
+            "version",
+            column(packages.iter().map(|node| node.version().cloned())),
+        ),
+    ])
+}
+
+/// Returns the project and package references of the workspace as an Arrow record batch.
+///
+/// Columns: `workspace`, `source` (a project id), `target` (a project or package id) and `kind`
+/// (`project` or `package`).
+pub fn edges(workspace: &Workspace) -> Result<RecordBatch, ArrowError> {
+    let graph = DependencyGraph::from_workspace(workspace, true);
+    let edges = graph.edges();
+
+    batch(vec![
+        (
+            "workspace",
+            column(edges.iter().map(|_| Some(root(workspace)))),
+        ),
+        (
+            "source",
+            column(
+                edges
+                    .iter()
+                    .map(|edge| Some(graph.node(edge.from()).id().clone())),
+            ),
+        ),
+        (
+            "target",
+            column(
+                edges
+                    .iter()
+                    .map(|edge| Some(graph.node(edge.to()).id().clone())),
+            ),
+        ),
+        (
+            "kind",
+            column(edges.iter().map(|edge| {
+                Some(
+                    match edge.kind() {
+                        EdgeKind::ProjectReference => "project",
+                        EdgeKind::PackageReference => "package",
+                        EdgeKind::PackageDependency => "dependency",
+                    }
+                    .to_string(),
+                )
+            })),
+        ),
+    ])
+}
+
+/// Writes a record batch as a Parquet file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use dotnet_lens::{export::parquet, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// parquet::write(File::create("edges.parquet")?, &parquet::edges(&workspace)?)?;
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write<W>(writer: W, batch: &RecordBatch) -> Result<(), ParquetError>
+where
+    W: Write + Send,
+{
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+
+    writer.write(batch)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+/// Writes the `projects.parquet`, `packages.parquet` and `edges.parquet` tables of the
+/// workspace into `directory`, which must exist.
+///
+/// See [`projects`], [`packages`] and [`edges`] for the columns of each table.
+pub fn write_tables<P>(directory: P, workspace: &Workspace) -> Result<(), ParquetError>
+where
+    P: AsRef<Path>,
+{
+    let directory = directory.as_ref();
+
+    let tables = [
+        ("projects.parquet", projects(workspace)?),
+        ("packages.parquet", packages(workspace)?),
+        ("edges.parquet", edges(workspace)?),
+    ];
+
+    for (file_name, batch) in tables {
+        let file = File::create(directory.join(file_name))
+            .map_err(|error| ParquetError::External(Box::new(error)))?;
+
+        write(file, &batch)?;
+    }
+
+    Ok(())
+}
+
+fn root(workspace: &Workspace) -> String {
+    workspace.root().to_string_lossy().to_string()
+}
+
+fn column<I>(values: I) -> ArrayRef
+where
+    I: Iterator<Item = Option<String>>,
+{
+    Arc::new(values.collect::<StringArray>())
+}
+
+fn batch(columns: Vec<(&str, ArrayRef)>) -> Result<RecordBatch, ArrowError> {
+    // every column is nullable so the schema is the same for every workspace, which lets the
+    // tables of many repositories be read as a single dataset
+    let schema = Schema::new(
+        columns
+            .iter()
+            .map(|(name, _)| Field::new(*name, DataType::Utf8, true))
+            .collect::<Vec<_>>(),
+    );
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        columns.into_iter().map(|(_, array)| array).collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::{Array, StringArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use crate::graph::test::workspace;
+
+    use super::{edges, write};
+
+    #[test]
+    pub fn write_edges_as_parquet() {
+        // given
+        let batch = edges(&workspace()).unwrap();
+        let file = tempfile::tempfile().unwrap();
+
+        // when
+        write(file.try_clone().unwrap(), &batch).unwrap();
+
+        // then
+        let read = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        let targets = read
+            .column_by_name("target")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+
+        assert_eq!(read.num_rows(), 3);
+        assert_eq!(targets.value(0), "src/Domain/Domain.csproj");
+        assert_eq!(targets.value(1), "Serilog@3.1.1");
+        assert_eq!(targets.len(), 3);
+    }
+}
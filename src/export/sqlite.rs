@@ -0,0 +1,224 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+
+use crate::{
+    graph::{DependencyGraph, EdgeKind, NodeKind},
+    workspace::Workspace,
+};
+
+/// The schema of the database written by [`write`].
+///
+/// - `projects`: one row per project, identified by its path relative to the workspace root.
+/// - `packages`: one row per package version, identified by `Name@Version`.
+/// - `edges`: one row per reference, from a project id to a project or package id, with the
+///   `kind` being `project` or `package`.
+/// - `package_edges`: one row per dependency of a package, from a package id to a package id.
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS projects (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    language TEXT NOT NULL,
+    path TEXT NOT NULL,
+    target_framework TEXT,
+    license_expression TEXT
+);
+
+CREATE TABLE IF NOT EXISTS packages (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    version TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS edges (
+    source TEXT NOT NULL REFERENCES projects (id),
+    target TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    PRIMARY KEY (source, target, kind)
+);
+
+CREATE TABLE IF NOT EXISTS package_edges (
+    source TEXT NOT NULL REFERENCES packages (id),
+    target TEXT NOT NULL REFERENCES packages (id),
+    PRIMARY KEY (source, target)
+);
+";
+
+/// Writes the projects, packages and references of the workspace into a SQLite database,
+/// creating the tables of [`SCHEMA`] if they do not exist.
+///
+/// The rows of a previous export are replaced, so the same database can be refreshed on every
+/// run. Everything is written in a single transaction.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{export::sqlite, workspace::Workspace};
+/// use rusqlite::Connection;
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// let mut connection = Connection::open("dependencies.db")?;
+/// sqlite::write(&mut connection, &workspace)?;
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write(connection: &mut Connection, workspace: &Workspace) -> rusqlite::Result<()> {
+    write_graph(
+        connection,
+        workspace,
+        &DependencyGraph::from_workspace(workspace, true),
+    )
+}
+
+/// Writes the projects of the workspace and the packages and references of its graph, which
+/// can have the dependencies between the packages (ex:
+/// [`DependencyGraph::add_restored_dependencies`]).
+///
+/// See [`write`] for more details.
+pub fn write_graph(
+    connection: &mut Connection,
+    workspace: &Workspace,
+    graph: &DependencyGraph,
+) -> rusqlite::Result<()> {
+    let transaction = connection.transaction()?;
+
+    transaction.execute_batch(SCHEMA)?;
+    transaction.execute_batch(
+        "DELETE FROM package_edges; DELETE FROM edges; DELETE FROM packages; DELETE FROM projects;",
+    )?;
+
+    {
+        let mut insert_project = transaction.prepare(
+            "INSERT INTO projects (id, name, language, path, target_framework, license_expression)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+
+        for project in workspace.projects() {
+            insert_project.execute(params![
+                workspace.relative_path(project.path()),
+                project.name(),
+                project.language().label(),
+                project.path().to_string_lossy(),
+                project.target_framework(),
+                project.license_expression(),
+            ])?;
+        }
+
+        let mut insert_package =
+            transaction.prepare("INSERT INTO packages (id, name, version) VALUES (?1, ?2, ?3)")?;
+
+        for node in graph.nodes() {
+            if node.kind() == NodeKind::Package {
+                insert_package.execute(params![node.id(), node.name(), node.version()])?;
+            }
+        }
+
+        let mut insert_edge = transaction
+            .prepare("INSERT OR IGNORE INTO edges (source, target, kind) VALUES (?1, ?2, ?3)")?;
+        let mut insert_package_edge = transaction
+            .prepare("INSERT OR IGNORE INTO package_edges (source, target) VALUES (?1, ?2)")?;
+
+        for edge in graph.edges() {
+            let source = graph.node(edge.from()).id();
+            let target = graph.node(edge.to()).id();
+
+            match edge.kind() {
+                EdgeKind::ProjectReference => {
+                    insert_edge.execute(params![source, target, "project"])?;
+                }
+                EdgeKind::PackageReference => {
+                    insert_edge.execute(params![source, target, "package"])?;
+                }
+                EdgeKind::PackageDependency => {
+                    insert_package_edge.execute(params![source, target])?;
+                }
+            }
+        }
+    }
+
+    transaction.commit()
+}
+
+/// Writes the workspace into the SQLite database at `path`, creating it if it does not exist.
+///
+/// See [`write`] for more details.
+pub fn write_file<P>(path: P, workspace: &Workspace) -> rusqlite::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut connection = Connection::open(path)?;
+
+    write(&mut connection, workspace)
+}
+
+#[cfg(test)]
+mod test {
+    use rusqlite::Connection;
+
+    use crate::graph::{test::workspace, DependencyGraph};
+
+    use super::{write, write_graph};
+
+    #[test]
+    pub fn export_workspace_to_sqlite() {
+        // given
+        let mut connection = Connection::open_in_memory().unwrap();
+
+        // when
+        write(&mut connection, &workspace()).unwrap();
+        write(&mut connection, &workspace()).unwrap();
+
+        // then
+        let dependents: Vec<String> = connection
+            .prepare(
+                "SELECT p.name FROM edges e JOIN projects p ON p.id = e.source
+                 WHERE e.target = 'Serilog@3.1.1' ORDER BY p.name",
+            )
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(dependents, vec!["Api", "Domain"]);
+
+        let packages: i64 = connection
+            .query_row("SELECT COUNT(*) FROM packages", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(packages, 1);
+    }
+
+    #[test]
+    pub fn export_package_dependencies_with_foreign_keys() {
+        // given
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection
+            .pragma_update(None, "foreign_keys", "ON")
+            .unwrap();
+
+        let workspace = workspace();
+        let mut graph = DependencyGraph::from_workspace(&workspace, true);
+        graph.add_package_dependency("Serilog", "3.1.1", "System.Memory", "4.5.5");
+
+        // when
+        write_graph(&mut connection, &workspace, &graph).unwrap();
+
+        // then
+        let dependencies: Vec<(String, String)> = connection
+            .prepare("SELECT source, target FROM package_edges")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            dependencies,
+            vec![(
+                "Serilog@3.1.1".to_string(),
+                "System.Memory@4.5.5".to_string()
+            )]
+        );
+    }
+}
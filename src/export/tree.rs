@@ -0,0 +1,362 @@
+use std::io::{self, Write};
+
+use crate::{
+    export::{ExportError, Exporter},
+    graph::{DependencyGraph, NodeKind},
+    suggestions::{FindingKind, PackageFinding},
+    workspace::Workspace,
+};
+
+const RESET: &str = "\x1b[0m";
+const BOLD_CYAN: &str = "\x1b[1;36m";
+const DIM: &str = "\x1b[2m";
+const YELLOW: &str = "\x1b[33m";
+const RED: &str = "\x1b[31m";
+
+/// Represents the options of the terminal tree renderer.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TreeOptions {
+    color: bool,
+    findings: Vec<PackageFinding>,
+}
+
+impl TreeOptions {
+    /// Creates new `TreeOptions`.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - Whether ANSI colors are used. Callers should disable them when the output is
+    ///   not a terminal or the `NO_COLOR` environment variable is set.
+    /// * `findings` - Outdated, deprecated and vulnerable packages, shown next to the packages.
+    pub fn new(color: bool, findings: Vec<PackageFinding>) -> Self {
+        Self { color, findings }
+    }
+
+    /// Returns whether ANSI colors are used.
+    pub fn color(&self) -> bool {
+        self.color
+    }
+
+    /// Returns the findings shown next to the packages.
+    pub fn findings(&self) -> &Vec<PackageFinding> {
+        &self.findings
+    }
+}
+
+/// Writes the dependency graph as a tree, starting from the projects no other project depends
+/// on.
+///
+/// Projects are bold cyan, outdated and deprecated packages are yellow and vulnerable packages
+/// are red. Like `cargo tree`, the dependencies of a node are only expanded the first time it
+/// appears: its next occurrences are marked with `(*)`, and a reference back to a node already
+/// in the current branch is marked as a cycle.
+///
+/// # Arguments
+///
+/// * `writer` - The writer where the tree will be written.
+/// * `graph` - The dependency graph.
+/// * `options` - The colors and findings of the tree.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{export::tree::{self, TreeOptions}, graph::DependencyGraph};
+///
+/// let mut output = vec![];
+/// tree::write(&mut output, &DependencyGraph::default(), &TreeOptions::default()).unwrap();
+/// ```
+pub fn write<W>(mut writer: W, graph: &DependencyGraph, options: &TreeOptions) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(to_string(graph, options).as_bytes())
+}
+
+/// Returns the dependency graph as a tree.
+///
+/// See [`write`] for more details.
+pub fn to_string(graph: &DependencyGraph, options: &TreeOptions) -> String {
+    let projects: Vec<usize> = (0..graph.nodes().len())
+        .filter(|index| graph.node(*index).kind() == NodeKind::Project)
+        .collect();
+
+    let mut roots: Vec<usize> = projects
+        .iter()
+        .copied()
+        .filter(|index| graph.dependents(*index).next().is_none())
+        .collect();
+
+    // every project is part of a cycle, so there is no natural root
+    if roots.is_empty() {
+        roots = projects;
+    }
+
+    let mut output = String::new();
+    let mut expanded = vec![false; graph.nodes().len()];
+
+    for root in roots {
+        output += &label(graph, root, options);
+
+        if expanded[root] && graph.dependencies(root).next().is_some() {
+            output.push_str(&paint(" (*)", DIM, options));
+            output.push('\n');
+
+            continue;
+        }
+
+        output.push('\n');
+
+        expanded[root] = true;
+        render_children(
+            graph,
+            root,
+            "",
+            &mut vec![root],
+            &mut expanded,
+            options,
+            &mut output,
+        );
+    }
+
+    output
+}
+
+fn render_children(
+    graph: &DependencyGraph,
+    index: usize,
+    prefix: &str,
+    branch: &mut Vec<usize>,
+    expanded: &mut [bool],
+    options: &TreeOptions,
+    output: &mut String,
+) {
+    let mut children: Vec<usize> = graph.dependencies(index).collect();
+
+    // projects before packages
+    children.sort_by_key(|child| graph.node(*child).kind() == NodeKind::Package);
+
+    for (position, child) in children.iter().enumerate() {
+        let last = position == children.len() - 1;
+        let (connector, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        output.push_str(prefix);
+        output.push_str(connector);
+        output.push_str(&label(graph, *child, options));
+
+        if branch.contains(child) {
+            output.push_str(&paint(" (cycle)", RED, options));
+            output.push('\n');
+
+            continue;
+        }
+
+        // the dependencies were already written the first time the node appeared
+        if expanded[*child] && graph.dependencies(*child).next().is_some() {
+            output.push_str(&paint(" (*)", DIM, options));
+            output.push('\n');
+
+            continue;
+        }
+
+        output.push('\n');
+
+        expanded[*child] = true;
+        branch.push(*child);
+        render_children(
+            graph,
+            *child,
+            &format!("{}{}", prefix, indent),
+            branch,
+            expanded,
+            options,
+            output,
+        );
+        branch.pop();
+    }
+}
+
+fn label(graph: &DependencyGraph, index: usize, options: &TreeOptions) -> String {
+    let node = graph.node(index);
+
+    if node.kind() == NodeKind::Project {
+        return paint(node.name(), BOLD_CYAN, options);
+    }
+
+    let version = node.version().map_or("", |v| v);
+
+    let findings: Vec<&FindingKind> = options
+        .findings
+        .iter()
+        .filter(|finding| {
+            finding.name().eq_ignore_ascii_case(node.name())
+                && finding.version().to_string() == version
+        })
+        .map(|finding| finding.kind())
+        .collect();
+
+    let mut notes = vec![];
+    let mut color = None;
+
+    for finding in &findings {
+        match finding {
+            FindingKind::Vulnerable { ids, .. } => {
+                notes.push(format!("vulnerable: {}", ids.join(", ")));
+                color = Some(RED);
+            }
+            FindingKind::Deprecated { .. } => {
+                notes.push("deprecated".to_string());
+                color = color.or(Some(YELLOW));
+            }
+            FindingKind::Outdated { latest } => {
+                notes.push(format!("latest: {}", latest));
+                color = color.or(Some(YELLOW));
+            }
+        }
+    }
+
+    let mut label = match color {
+        Some(color) => paint(node.name(), color, options),
+        None => node.name().clone(),
+    };
+
+    label.push(' ');
+    label += &paint(version, DIM, options);
+
+    if !notes.is_empty() {
+        let notes = format!(" ({})", notes.join(", "));
+        label += &paint(&notes, color.unwrap_or(DIM), options);
+    }
+
+    label
+}
+
+fn paint(text: &str, color: &str, options: &TreeOptions) -> String {
+    if options.color {
+        format!("{}{}{}", color, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// An [`Exporter`] writing the graph as a tree (see [`write`]).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TreeExporter {
+    options: TreeOptions,
+}
+
+impl TreeExporter {
+    /// Creates a new `TreeExporter` with the given options.
+    pub fn new(options: TreeOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Exporter for TreeExporter {
+    fn name(&self) -> &str {
+        "tree"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        graph: &DependencyGraph,
+        _workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        Ok(write(writer, graph, &self.options)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::{
+        graph::{
+            test::{project, workspace},
+            DependencyGraph,
+        },
+        suggestions::{FindingKind, PackageFinding},
+        workspace::Workspace,
+    };
+
+    use super::{to_string, TreeOptions};
+
+    #[test]
+    pub fn tree_without_color() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+        let finding = PackageFinding::new(
+            PathBuf::from("/repo/src/Api/Api.csproj"),
+            "Serilog".to_string(),
+            "3.1.1".parse().unwrap(),
+            FindingKind::Outdated {
+                latest: "4.0.0".parse().unwrap(),
+            },
+        );
+
+        // when
+        let tree = to_string(&graph, &TreeOptions::new(false, vec![finding]));
+
+        // then
+        assert_eq!(
+            tree,
+            "Api\n├── Domain\n│   └── Serilog 3.1.1 (latest: 4.0.0)\n└── Serilog 3.1.1 (latest: 4.0.0)\n"
+        );
+    }
+
+    #[test]
+    pub fn expand_shared_dependencies_once() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/Web/Web.csproj",
+                    &["../Api/Api.csproj", "../Jobs/Jobs.csproj"],
+                    &[],
+                ),
+                project("/repo/Api/Api.csproj", &["../Domain/Domain.csproj"], &[]),
+                project("/repo/Jobs/Jobs.csproj", &["../Domain/Domain.csproj"], &[]),
+                project("/repo/Domain/Domain.csproj", &[], &[("Serilog", "3.1.1")]),
+            ],
+        );
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+
+        // when
+        let tree = to_string(&graph, &TreeOptions::default());
+
+        // then
+        assert_eq!(
+            tree,
+            "Web\n├── Api\n│   └── Domain\n│       └── Serilog 3.1.1\n└── Jobs\n    └── Domain (*)\n"
+        );
+    }
+
+    #[test]
+    pub fn vulnerable_packages_are_red() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+        let finding = PackageFinding::new(
+            PathBuf::from("/repo/src/Domain/Domain.csproj"),
+            "Serilog".to_string(),
+            "3.1.1".parse().unwrap(),
+            FindingKind::Vulnerable {
+                ids: vec!["GHSA-1234".to_string()],
+                fixed_in: None,
+            },
+        );
+
+        // when
+        let tree = to_string(&graph, &TreeOptions::new(true, vec![finding]));
+
+        // then
+        assert!(tree.starts_with("\x1b[1;36mApi\x1b[0m\n"));
+        assert!(tree.contains(
+            "\x1b[31mSerilog\x1b[0m \x1b[2m3.1.1\x1b[0m\x1b[31m (vulnerable: GHSA-1234)\x1b[0m"
+        ));
+    }
+}
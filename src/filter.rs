@@ -0,0 +1,362 @@
+//! Filters keeping the projects and packages whose names or paths match glob or regex patterns,
+//! applied to a workspace before listing, graphing or reporting it, and chainable filters on the
+//! projects of a workspace.
+
+use thiserror::Error;
+
+use crate::{architecture::Pattern, workspace::Workspace, Project, ProjectLanguage};
+
+/// The prefix of the patterns parsed as regular expressions by [`Matcher::parse`].
+pub const REGEX_PREFIX: &str = "regex:";
+
+/// Represents a pattern matched against a name or a path.
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    /// A glob pattern matching the whole text, ignoring case (see [`Pattern`]).
+    Glob(Pattern),
+    /// A regular expression matching any part of the text, unless it is anchored with `^` and
+    /// `$`.
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    /// Parses a pattern: a regular expression when it starts with `regex:` (ex:
+    /// `regex:^Shop\.(Api|Web)$`), or else a glob pattern (ex: `Shop.*`).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the regular expression is not valid, or if the
+    /// `regex` feature is not enabled.
+    pub fn parse(value: &str) -> Result<Self, FilterError> {
+        match value.strip_prefix(REGEX_PREFIX) {
+            #[cfg(feature = "regex")]
+            Some(regex) => Ok(Self::Regex(regex::Regex::new(regex)?)),
+            #[cfg(not(feature = "regex"))]
+            Some(_) => Err(FilterError::RegexNotSupported),
+            None => Ok(Self::Glob(Pattern::new(value))),
+        }
+    }
+
+    /// Returns `true` if the pattern matches `text`.
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Glob(pattern) => pattern.matches(text),
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Represents the patterns the projects and packages have to match.
+///
+/// A project is kept if its name matches one of the name patterns and its path matches one of
+/// the path patterns, and a package reference is kept if its name matches one of the package
+/// patterns. Empty lists of patterns match everything. Paths are matched relative to the root of
+/// the workspace, with forward slashes (ex: `src/*`).
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::filter::{Matcher, ProjectFilter};
+///
+/// let mut filter = ProjectFilter::new();
+/// filter.add_name(Matcher::parse("Shop.*").unwrap());
+/// filter.add_package(Matcher::parse("Microsoft.Extensions.*").unwrap());
+///
+/// assert!(filter.matches_name("shop.api"));
+/// assert!(!filter.matches_package("Serilog"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProjectFilter {
+    names: Vec<Matcher>,
+    paths: Vec<Matcher>,
+    packages: Vec<Matcher>,
+}
+
+impl ProjectFilter {
+    /// Creates a new `ProjectFilter` matching every project and package.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pattern for the names of the projects.
+    pub fn add_name(&mut self, matcher: Matcher) {
+        self.names.push(matcher);
+    }
+
+    /// Adds a pattern for the paths of the projects.
+    pub fn add_path(&mut self, matcher: Matcher) {
+        self.paths.push(matcher);
+    }
+
+    /// Adds a pattern for the names of the packages.
+    pub fn add_package(&mut self, matcher: Matcher) {
+        self.packages.push(matcher);
+    }
+
+    /// Returns `true` if the filter does not have any pattern, so it matches everything.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.paths.is_empty() && self.packages.is_empty()
+    }
+
+    /// Returns `true` if the name of a project matches the filter.
+    pub fn matches_name(&self, name: &str) -> bool {
+        any(&self.names, name)
+    }
+
+    /// Returns `true` if the name of a package matches the filter.
+    pub fn matches_package(&self, name: &str) -> bool {
+        any(&self.packages, name)
+    }
+
+    /// Returns `true` if the project matches the name and path patterns of the filter, its path
+    /// being matched relative to the root of `workspace`.
+    pub fn matches_project(&self, workspace: &Workspace, project: &Project) -> bool {
+        self.matches_name(project.name())
+            && any(&self.paths, &workspace.relative_path(project.path()))
+    }
+
+    /// Returns the projects of `workspace` matching the filter, with only the package references
+    /// matching it.
+    pub fn apply(&self, workspace: &Workspace) -> Vec<Project> {
+        workspace
+            .projects()
+            .iter()
+            .filter(|project| self.matches_project(workspace, project))
+            .map(|project| {
+                let mut project = project.clone();
+                project
+                    .package_references
+                    .retain(|reference| self.matches_package(reference.name()));
+
+                project
+            })
+            .collect()
+    }
+}
+
+fn any(matchers: &[Matcher], text: &str) -> bool {
+    matchers.is_empty() || matchers.iter().any(|matcher| matcher.is_match(text))
+}
+
+/// Chainable filters on the projects of a workspace, returning iterators, implemented for
+/// everything iterating over `&Project` (ex: [`Workspace::projects`]).
+///
+/// [`Workspace::projects`]: crate::workspace::Workspace::projects
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{filter::ProjectIteratorExt, workspace::Workspace, ProjectLanguage};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// for project in workspace
+///     .projects()
+///     .language(ProjectLanguage::FSharp)
+///     .target_framework("net8.0")
+/// {
+///     println!("{}", project.name());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait ProjectIteratorExt<'a>: IntoIterator<Item = &'a Project> + Sized {
+    /// Keeps the projects written in the language.
+    fn language(self, language: ProjectLanguage) -> LanguageFilter<Self::IntoIter> {
+        LanguageFilter {
+            projects: self.into_iter(),
+            language,
+        }
+    }
+
+    /// Keeps the projects targeting the framework, ignoring case (see
+    /// [`Project::target_frameworks`]).
+    fn target_framework(self, target_framework: &str) -> TargetFrameworkFilter<'_, Self::IntoIter> {
+        TargetFrameworkFilter {
+            projects: self.into_iter(),
+            target_framework,
+        }
+    }
+}
+
+impl<'a, I> ProjectIteratorExt<'a> for I where I: IntoIterator<Item = &'a Project> {}
+
+/// An iterator keeping the projects written in a language, created by
+/// [`ProjectIteratorExt::language`].
+#[derive(Debug, Clone)]
+pub struct LanguageFilter<I> {
+    projects: I,
+    language: ProjectLanguage,
+}
+
+impl<'a, I> Iterator for LanguageFilter<I>
+where
+    I: Iterator<Item = &'a Project>,
+{
+    type Item = &'a Project;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let language = self.language;
+
+        self.projects.find(|project| project.language() == language)
+    }
+}
+
+/// An iterator keeping the projects targeting a framework, created by
+/// [`ProjectIteratorExt::target_framework`].
+#[derive(Debug, Clone)]
+pub struct TargetFrameworkFilter<'t, I> {
+    projects: I,
+    target_framework: &'t str,
+}
+
+impl<'a, I> Iterator for TargetFrameworkFilter<'_, I>
+where
+    I: Iterator<Item = &'a Project>,
+{
+    type Item = &'a Project;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let target_framework = self.target_framework;
+
+        self.projects.find(|project| {
+            project
+                .target_frameworks()
+                .iter()
+                .any(|framework| framework.eq_ignore_ascii_case(target_framework))
+        })
+    }
+}
+
+/// Represents errors that can occur while parsing a pattern.
+#[derive(Debug, Error)]
+pub enum FilterError {
+    /// The regular expression is not valid.
+    #[cfg(feature = "regex")]
+    #[error("the regular expression is not valid")]
+    InvalidRegex(#[from] regex::Error),
+    /// Regular expressions require the `regex` feature.
+    #[cfg(not(feature = "regex"))]
+    #[error("regular expressions require the `regex` feature")]
+    RegexNotSupported,
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::{parser::parse, workspace::Workspace, ProjectLanguage};
+
+    use super::{Matcher, ProjectFilter, ProjectIteratorExt};
+
+    #[test]
+    pub fn filter_projects_and_packages() {
+        // given
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.1.1" />
+    <PackageReference Include="Serilog.Sinks.Console" Version="5.0.1" />
+    <PackageReference Include="Polly" Version="8.4.1" />
+  </ItemGroup>
+</Project>"#;
+
+        let projects = vec![
+            parse(Cursor::new(content), "/repo/src/Shop.Api/Shop.Api.csproj").unwrap(),
+            parse(
+                Cursor::new(content),
+                "/repo/legacy/Shop.Web/Shop.Web.csproj",
+            )
+            .unwrap(),
+            parse(Cursor::new(content), "/repo/src/Admin/Admin.csproj").unwrap(),
+        ];
+        let workspace = Workspace::from_projects("/repo", projects);
+
+        let mut filter = ProjectFilter::new();
+        filter.add_name(Matcher::parse("shop.*").unwrap());
+        filter.add_path(Matcher::parse("src/*").unwrap());
+        filter.add_package(Matcher::parse("Serilog*").unwrap());
+
+        // when
+        let filtered = filter.apply(&workspace);
+
+        // then
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name(), "Shop.Api");
+
+        let packages: Vec<&str> = filtered[0]
+            .package_references()
+            .iter()
+            .map(|reference| reference.name().as_str())
+            .collect();
+        assert_eq!(packages, vec!["Serilog", "Serilog.Sinks.Console"]);
+
+        assert_eq!(
+            &ProjectFilter::new().apply(&workspace),
+            workspace.projects()
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    pub fn match_regular_expressions() {
+        let matcher = Matcher::parse(r"regex:^Shop\.(Api|Web)$").unwrap();
+
+        assert!(matcher.is_match("Shop.Api"));
+        assert!(!matcher.is_match("Shop.Admin"));
+        assert!(Matcher::parse("regex:(").is_err());
+    }
+
+    #[test]
+    pub fn chain_language_and_target_framework_filters() {
+        // given
+        let projects = vec![
+            parse(
+                Cursor::new(
+                    r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFrameworks>net6.0;net8.0</TargetFrameworks>
+  </PropertyGroup>
+</Project>"#,
+                ),
+                "src/Domain/Domain.fsproj",
+            )
+            .unwrap(),
+            parse(
+                Cursor::new(
+                    r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net6.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+                ),
+                "src/Legacy/Legacy.fsproj",
+            )
+            .unwrap(),
+            parse(
+                Cursor::new(
+                    r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+                ),
+                "src/Api/Api.csproj",
+            )
+            .unwrap(),
+        ];
+
+        // when
+        let names: Vec<&str> = projects
+            .language(ProjectLanguage::FSharp)
+            .target_framework("NET8.0")
+            .map(|project| project.name().as_str())
+            .collect();
+
+        // then
+        assert_eq!(names, vec!["Domain"]);
+        assert_eq!(projects.target_framework("net6.0").count(), 2);
+        assert_eq!(projects.language(ProjectLanguage::CSharp).count(), 1);
+    }
+}
@@ -0,0 +1,384 @@
+//! Target framework monikers (ex: `net8.0`, `netstandard2.0`, `net48`) and the compatibility
+//! between them, with a knowledge base that can be extended with custom aliases and rules.
+
+use std::collections::BTreeMap;
+
+/// Represents the family of a target framework.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameworkIdentifier {
+    /// The .NET Framework (ex: `net48`).
+    NetFramework,
+    /// .NET Standard (ex: `netstandard2.0`).
+    NetStandard,
+    /// .NET Core and .NET 5 or later (ex: `netcoreapp3.1`, `net8.0`).
+    NetCoreApp,
+    /// A framework that is not known, with its lowercase moniker.
+    Other(String),
+}
+
+/// Represents a parsed target framework moniker.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::framework::{FrameworkIdentifier, TargetFramework};
+///
+/// let framework = TargetFramework::parse("net8.0-windows");
+///
+/// assert_eq!(framework.identifier(), &FrameworkIdentifier::NetCoreApp);
+/// assert_eq!(framework.version(), (8, 0, 0));
+/// assert_eq!(framework.platform(), Some(&"windows".to_string()));
+/// assert!(framework.is_compatible_with(&TargetFramework::parse("netstandard2.0")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TargetFramework {
+    identifier: FrameworkIdentifier,
+    version: (u32, u32, u32),
+    platform: Option<String>,
+}
+
+impl TargetFramework {
+    /// Parses a target framework moniker, ignoring case.
+    ///
+    /// Monikers that are not recognized are parsed as [`FrameworkIdentifier::Other`].
+    pub fn parse(moniker: &str) -> Self {
+        let moniker = moniker.trim().to_lowercase();
+        let (framework, platform) = match moniker.split_once('-') {
+            Some((framework, platform)) => (framework, Some(platform.to_string())),
+            None => (moniker.as_str(), None),
+        };
+
+        let parsed = if let Some(version) = framework.strip_prefix("netstandard") {
+            dotted_version(version).map(|version| (FrameworkIdentifier::NetStandard, version))
+        } else if let Some(version) = framework.strip_prefix("netcoreapp") {
+            dotted_version(version).map(|version| (FrameworkIdentifier::NetCoreApp, version))
+        } else if let Some(version) = framework.strip_prefix("net") {
+            if version.contains('.') {
+                dotted_version(version)
+                    .filter(|(major, _, _)| *major >= 5)
+                    .map(|version| (FrameworkIdentifier::NetCoreApp, version))
+            } else {
+                compact_version(version).map(|version| (FrameworkIdentifier::NetFramework, version))
+            }
+        } else {
+            None
+        };
+
+        match parsed {
+            Some((identifier, version)) => Self {
+                identifier,
+                version,
+                platform,
+            },
+            None => Self {
+                identifier: FrameworkIdentifier::Other(moniker),
+                version: (0, 0, 0),
+                platform: None,
+            },
+        }
+    }
+
+    /// Returns the family of the framework.
+    pub fn identifier(&self) -> &FrameworkIdentifier {
+        &self.identifier
+    }
+
+    /// Returns the version of the framework as major, minor and patch (ex: `(4, 7, 2)` for
+    /// `net472`).
+    pub fn version(&self) -> (u32, u32, u32) {
+        self.version
+    }
+
+    /// Returns the platform of the framework, if any (ex: `windows` for `net8.0-windows`).
+    pub fn platform(&self) -> Option<&String> {
+        self.platform.as_ref()
+    }
+
+    /// Returns `true` if a project targeting this framework can reference a project or package
+    /// targeting `other`, following the built-in .NET compatibility rules.
+    pub fn is_compatible_with(&self, other: &TargetFramework) -> bool {
+        let platform_compatible = other.platform.as_ref().is_none_or(|platform| {
+            self.platform
+                .as_ref()
+                .is_some_and(|own| platform_name(own) == platform_name(platform))
+        });
+
+        if !platform_compatible {
+            return false;
+        }
+
+        match (&self.identifier, &other.identifier) {
+            (FrameworkIdentifier::Other(own), FrameworkIdentifier::Other(other)) => own == other,
+            (own, FrameworkIdentifier::NetStandard) if *own != FrameworkIdentifier::NetStandard => {
+                self.supported_net_standard()
+                    .is_some_and(|supported| other.version <= supported)
+            }
+            (own, other_identifier) => {
+                own == other_identifier
+                    && !matches!(own, FrameworkIdentifier::Other(_))
+                    && other.version <= self.version
+            }
+        }
+    }
+
+    /// Returns the highest .NET Standard version implemented by the framework.
+    fn supported_net_standard(&self) -> Option<(u32, u32, u32)> {
+        let version = self.version;
+
+        match self.identifier {
+            FrameworkIdentifier::NetCoreApp if version >= (3, 0, 0) => Some((2, 1, 0)),
+            FrameworkIdentifier::NetCoreApp if version >= (2, 0, 0) => Some((2, 0, 0)),
+            FrameworkIdentifier::NetCoreApp => Some((1, 6, 0)),
+            FrameworkIdentifier::NetFramework if version >= (4, 6, 1) => Some((2, 0, 0)),
+            FrameworkIdentifier::NetFramework if version >= (4, 6, 0) => Some((1, 3, 0)),
+            FrameworkIdentifier::NetFramework if version >= (4, 5, 1) => Some((1, 2, 0)),
+            FrameworkIdentifier::NetFramework if version >= (4, 5, 0) => Some((1, 1, 0)),
+            _ => None,
+        }
+    }
+}
+
+/// Represents a custom compatibility rule: projects targeting `framework` can reference projects
+/// and packages targeting `compatible_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Compatibility {
+    framework: String,
+    compatible_with: String,
+}
+
+impl Compatibility {
+    /// Creates a new `Compatibility`.
+    pub fn new(framework: String, compatible_with: String) -> Self {
+        Self {
+            framework,
+            compatible_with,
+        }
+    }
+
+    /// Returns the framework of the referencing projects.
+    pub fn framework(&self) -> &String {
+        &self.framework
+    }
+
+    /// Returns the framework that can be referenced.
+    pub fn compatible_with(&self) -> &String {
+        &self.compatible_with
+    }
+}
+
+/// The knowledge base used to check target frameworks, extending the built-in .NET rules with
+/// custom aliases (ex: internal monikers), compatibility rules and out-of-support frameworks.
+///
+/// Monikers are compared ignoring case, after resolving their aliases.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::framework::FrameworkRules;
+///
+/// let mut frameworks = FrameworkRules::new();
+/// frameworks.add_alias("contoso-lts", "net8.0");
+/// frameworks.add_compatibility("net48", "contoso-shim");
+/// frameworks.add_unsupported("net5.0");
+///
+/// assert!(frameworks.is_compatible("contoso-lts", "netstandard2.0"));
+/// assert!(frameworks.is_compatible("net48", "contoso-shim"));
+/// assert!(!frameworks.is_compatible("net48", "net8.0"));
+/// assert!(frameworks.is_unsupported("NET5.0"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameworkRules {
+    aliases: BTreeMap<String, String>,
+    compatibilities: Vec<Compatibility>,
+    unsupported: Vec<String>,
+}
+
+impl FrameworkRules {
+    /// Creates a new `FrameworkRules` with only the built-in .NET rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the aliases, keyed by the lowercase alias.
+    pub fn aliases(&self) -> &BTreeMap<String, String> {
+        &self.aliases
+    }
+
+    /// Registers `alias` as another name of the framework `moniker`.
+    pub fn add_alias(&mut self, alias: &str, moniker: &str) {
+        self.aliases
+            .insert(alias.trim().to_lowercase(), moniker.trim().to_lowercase());
+    }
+
+    /// Returns the custom compatibility rules.
+    pub fn compatibilities(&self) -> &Vec<Compatibility> {
+        &self.compatibilities
+    }
+
+    /// Allows projects targeting `framework` to reference projects and packages targeting
+    /// `compatible_with`, on top of the built-in rules.
+    pub fn add_compatibility(&mut self, framework: &str, compatible_with: &str) {
+        self.compatibilities.push(Compatibility::new(
+            self.resolve(framework),
+            self.resolve(compatible_with),
+        ));
+    }
+
+    /// Returns the frameworks that are out of support.
+    pub fn unsupported(&self) -> &Vec<String> {
+        &self.unsupported
+    }
+
+    /// Marks the framework `moniker` as out of support.
+    pub fn add_unsupported(&mut self, moniker: &str) {
+        let moniker = self.resolve(moniker);
+
+        if !self.unsupported.contains(&moniker) {
+            self.unsupported.push(moniker);
+        }
+    }
+
+    /// Returns the lowercase moniker `moniker` is an alias of, or `moniker` itself.
+    pub fn resolve(&self, moniker: &str) -> String {
+        let moniker = moniker.trim().to_lowercase();
+
+        match self.aliases.get(&moniker) {
+            Some(resolved) => resolved.clone(),
+            None => moniker,
+        }
+    }
+
+    /// Parses `moniker` after resolving its alias.
+    pub fn parse(&self, moniker: &str) -> TargetFramework {
+        TargetFramework::parse(&self.resolve(moniker))
+    }
+
+    /// Returns `true` if a project targeting `framework` can reference a project or package
+    /// targeting `reference`, following the custom rules first, then the built-in ones.
+    pub fn is_compatible(&self, framework: &str, reference: &str) -> bool {
+        let (framework, reference) = (self.resolve(framework), self.resolve(reference));
+
+        if framework == reference {
+            return true;
+        }
+
+        let custom = self.compatibilities.iter().any(|compatibility| {
+            compatibility.framework == framework && compatibility.compatible_with == reference
+        });
+
+        custom
+            || TargetFramework::parse(&framework)
+                .is_compatible_with(&TargetFramework::parse(&reference))
+    }
+
+    /// Returns `true` if the framework `moniker` was marked as out of support.
+    pub fn is_unsupported(&self, moniker: &str) -> bool {
+        self.unsupported.contains(&self.resolve(moniker))
+    }
+}
+
+/// Parses a version like `8.0` or `2.0.3`.
+fn dotted_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+
+    parts.next().is_none().then_some((major, minor, patch))
+}
+
+/// Parses a .NET Framework version without dots, with one digit per part (ex: `472`).
+fn compact_version(version: &str) -> Option<(u32, u32, u32)> {
+    let digits: Vec<u32> = version
+        .chars()
+        .map(|c| c.to_digit(10))
+        .collect::<Option<_>>()?;
+
+    match digits[..] {
+        [major, minor] => Some((major, minor, 0)),
+        [major, minor, patch] => Some((major, minor, patch)),
+        _ => None,
+    }
+}
+
+/// Returns the platform without its version (ex: `windows` for `windows10.0.19041`).
+pub(crate) fn platform_name(platform: &str) -> &str {
+    platform.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FrameworkIdentifier, FrameworkRules, TargetFramework};
+
+    #[test]
+    pub fn parse_target_frameworks() {
+        // given
+        let monikers = [
+            "net8.0",
+            "NET472",
+            "netstandard2.1",
+            "netcoreapp3.1",
+            "net4.8",
+            "uap10.0",
+        ];
+
+        // when
+        let frameworks: Vec<(FrameworkIdentifier, (u32, u32, u32))> = monikers
+            .iter()
+            .map(|moniker| TargetFramework::parse(moniker))
+            .map(|framework| (framework.identifier().clone(), framework.version()))
+            .collect();
+
+        // then
+        assert_eq!(
+            frameworks,
+            vec![
+                (FrameworkIdentifier::NetCoreApp, (8, 0, 0)),
+                (FrameworkIdentifier::NetFramework, (4, 7, 2)),
+                (FrameworkIdentifier::NetStandard, (2, 1, 0)),
+                (FrameworkIdentifier::NetCoreApp, (3, 1, 0)),
+                (FrameworkIdentifier::Other("net4.8".to_string()), (0, 0, 0)),
+                (FrameworkIdentifier::Other("uap10.0".to_string()), (0, 0, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn check_built_in_compatibility() {
+        // given
+        let frameworks = FrameworkRules::new();
+
+        // then
+        assert!(frameworks.is_compatible("net8.0", "net6.0"));
+        assert!(frameworks.is_compatible("net8.0", "netstandard2.1"));
+        assert!(frameworks.is_compatible("net472", "netstandard2.0"));
+        assert!(frameworks.is_compatible("net8.0-windows", "net8.0"));
+        assert!(!frameworks.is_compatible("net8.0", "net8.0-windows"));
+        assert!(!frameworks.is_compatible("net6.0", "net8.0"));
+        assert!(!frameworks.is_compatible("net472", "netstandard2.1"));
+        assert!(!frameworks.is_compatible("net48", "net8.0"));
+        assert!(!frameworks.is_compatible("netstandard2.0", "net48"));
+    }
+
+    #[test]
+    pub fn extend_with_custom_rules() {
+        // given
+        let mut frameworks = FrameworkRules::new();
+        frameworks.add_alias("Contoso-LTS", "net8.0");
+        frameworks.add_compatibility("contoso-legacy", "net48");
+        frameworks.add_unsupported("contoso-lts");
+
+        // then
+        assert!(frameworks.is_compatible("contoso-lts", "net6.0"));
+        assert!(frameworks.is_compatible("net9.0", "CONTOSO-LTS"));
+        assert!(frameworks.is_compatible("contoso-legacy", "net48"));
+        assert!(!frameworks.is_compatible("contoso-legacy", "net472"));
+        assert!(frameworks.is_unsupported("net8.0"));
+        assert!(!frameworks.is_unsupported("net9.0"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Loading of the projects of a git revision directly from the object database, without checking
+//! it out.
+
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+use gix::{bstr::ByteSlice, traverse::tree::Recorder};
+use thiserror::Error;
+
+use crate::{
+    condition::Properties, intern::Interner, parser, parser::ParseError, search, vfs::OsFileSystem,
+    workspace::Workspace,
+};
+
+/// Loads the projects of a revision of a git repository (ex: `HEAD~3`, `v1.2.0`, a commit id).
+///
+/// The projects are read from the tree of the revision, so the working directory is left
+/// untouched. The workspace root is the directory of the repository and the project paths are
+/// the paths they would have in a checkout, so the result can be compared with the current
+/// state of the repository (see [`crate::diff::WorkspaceDiff`]).
+///
+/// # Errors
+///
+/// This function will return an error if the repository could not be opened, if the revision
+/// could not be resolved to a tree or if one of the projects could not be parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{diff::WorkspaceDiff, git, workspace::Workspace};
+///
+/// let base = git::load_revision("path/to/repository", "main")?;
+/// let head = Workspace::load("path/to/repository")?;
+///
+/// println!("{}", WorkspaceDiff::between(&base, &head).to_markdown());
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn load_revision<P>(repository: P, revision: &str) -> Result<Workspace, GitError>
+where
+    P: AsRef<Path>,
+{
+    let root = repository.as_ref();
+    let repository = gix::open(root).map_err(|error| GitError::OpenError(Box::new(error)))?;
+
+    let revision_error = |error: Box<dyn Error + Send + Sync>| GitError::RevisionError {
+        revision: revision.to_string(),
+        source: error,
+    };
+
+    let tree = repository
+        .rev_parse_single(revision)
+        .map_err(|error| revision_error(Box::new(error)))?
+        .object()
+        .map_err(|error| revision_error(Box::new(error)))?
+        .peel_to_tree()
+        .map_err(|error| revision_error(Box::new(error)))?;
+
+    let mut recorder = Recorder::default();
+    tree.traverse()
+        .breadthfirst(&mut recorder)
+        .map_err(|error| GitError::TreeError(Box::new(error)))?;
+
+    let strings = Interner::new();
+    let mut projects = vec![];
+
+    for entry in recorder.records {
+        let path = gix::path::from_bstr(entry.filepath.as_bstr()).to_path_buf();
+
+        if !entry.mode.is_blob() || !search::is_project_file(&path) {
+            continue;
+        }
+
+        let object = repository
+            .find_object(entry.oid)
+            .map_err(|error| GitError::TreeError(Box::new(error)))?;
+
+        let path = root.join(path);
+        let project = parser::parse_in(
+            object.data.as_slice(),
+            &path,
+            &Properties::new(),
+            &OsFileSystem,
+            &strings,
+        )
+        .map_err(|source| GitError::ParseError { path, source })?;
+
+        projects.push(project);
+    }
+
+    projects.sort_by(|a, b| a.path().cmp(b.path()));
+
+    Ok(Workspace::from_projects(root, projects))
+}
+
+/// Represents errors that can occur while loading the projects of a git revision.
+#[derive(Debug, Error)]
+pub enum GitError {
+    /// The repository could not be opened.
+    #[error("the git repository could not be opened")]
+    OpenError(#[source] Box<gix::open::Error>),
+    /// The revision does not exist or does not point to a tree.
+    #[error("the revision {revision:?} could not be resolved")]
+    RevisionError {
+        revision: String,
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// An object of the tree could not be read.
+    #[error("the tree of the revision could not be read")]
+    TreeError(#[source] Box<dyn Error + Send + Sync>),
+    /// A project of the revision could not be parsed.
+    #[error("the project {path:?} could not be parsed")]
+    ParseError {
+        path: PathBuf,
+        #[source]
+        source: ParseError,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path, process::Command};
+
+    use tempfile::tempdir;
+
+    use super::{load_revision, GitError};
+
+    fn git(directory: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(directory)
+            .output()
+            .unwrap()
+            .status;
+
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    pub fn load_projects_of_a_previous_revision() {
+        // given
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        fs::create_dir_all(root.join("src/Api")).unwrap();
+        fs::write(
+            root.join("src/Api/Api.csproj"),
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="2.12.0" />
+  </ItemGroup>
+</Project>"#,
+        )
+        .unwrap();
+
+        git(root, &["init", "-q"]);
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "first"]);
+
+        fs::write(
+            root.join("src/Api/Api.csproj"),
+            r#"<Project Sdk="Microsoft.NET.Sdk" />"#,
+        )
+        .unwrap();
+        git(root, &["commit", "-q", "-am", "second"]);
+
+        // when
+        let previous = load_revision(root, "HEAD~1").unwrap();
+        let missing = load_revision(root, "does-not-exist");
+
+        // then
+        let api = &previous.projects()[0];
+
+        assert_eq!(previous.relative_path(api.path()), "src/Api/Api.csproj");
+        assert_eq!(api.package_references()[0].version(), "2.12.0");
+        assert!(matches!(missing, Err(GitError::RevisionError { .. })));
+
+        dir.close().unwrap();
+    }
+}
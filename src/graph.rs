@@ -0,0 +1,1989 @@
+//! A dependency graph between the projects and packages of a workspace.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "serde")]
+use crate::assets::ProjectAssets;
+use crate::{
+    framework::FrameworkRules, packages_folder::GlobalPackagesFolder, packaging,
+    parser::ParseError, workspace::Workspace, PackageReference, Project, ProjectReference,
+};
+
+/// Represents the kind of a node of a `DependencyGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NodeKind {
+    Project,
+    Package,
+}
+
+/// Represents a project or a package version in a `DependencyGraph`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    id: String,
+    name: String,
+    kind: NodeKind,
+    version: Option<String>,
+    path: Option<PathBuf>,
+}
+
+/// Represents the kind of an edge of a `DependencyGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeKind {
+    ProjectReference,
+    PackageReference,
+    /// A dependency of a package on another package, from its `.nuspec` or the restore graph.
+    PackageDependency,
+}
+
+/// Represents how the assets of the dependency of an edge are used.
+///
+/// The package references are classified from their `PrivateAssets`, `IncludeAssets` and
+/// `ExcludeAssets` (see [`EdgeClass::of`]), and the project references from their
+/// `OutputItemType` and `ReferenceOutputAssembly` (see [`EdgeClass::of_project_reference`]).
+/// The dependencies between packages are [`EdgeClass::Runtime`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeClass {
+    /// The dependency is loaded at runtime.
+    #[default]
+    Runtime,
+    /// The dependency is only compiled against, without its runtime assets (ex:
+    /// `ExcludeAssets="runtime"`).
+    CompileOnly,
+    /// The dependency only provides analyzers (ex: `StyleCop.Analyzers`, or a source generator
+    /// project referenced with `OutputItemType="Analyzer"`).
+    Analyzer,
+    /// The dependency only takes part in the build (ex: `Microsoft.SourceLink.GitHub` with
+    /// `IncludeAssets="build"`, or a project referenced with `ReferenceOutputAssembly="false"`).
+    Build,
+}
+
+/// The assets of a package, as named by `IncludeAssets` and `ExcludeAssets`.
+const ASSETS: [&str; 8] = [
+    "compile",
+    "runtime",
+    "contentfiles",
+    "build",
+    "buildmultitargeting",
+    "buildtransitive",
+    "analyzers",
+    "native",
+];
+
+impl EdgeClass {
+    /// Classifies a package reference.
+    ///
+    /// The assets consumed by the project are its `IncludeAssets` (every asset by default)
+    /// without its `ExcludeAssets`. Analyzer packages, named like `*Analyzers`, and packages only
+    /// consuming analyzers are [`EdgeClass::Analyzer`]. Otherwise, a package consuming its
+    /// runtime assets is [`EdgeClass::Runtime`], even with `PrivateAssets="all"`, which only
+    /// stops the assets from flowing to the consumers of the project. A package only consuming
+    /// its compile assets is [`EdgeClass::CompileOnly`], unless it is private, which makes it a
+    /// development dependency like the packages consuming neither: [`EdgeClass::Build`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::{graph::EdgeClass, PackageReference};
+    ///
+    /// let mut package_reference = PackageReference::new("Serilog".to_string(), "3.1.1".to_string());
+    /// assert_eq!(EdgeClass::of(&package_reference), EdgeClass::Runtime);
+    ///
+    /// package_reference.set_exclude_assets(Some("runtime".to_string()));
+    /// assert_eq!(EdgeClass::of(&package_reference), EdgeClass::CompileOnly);
+    /// ```
+    pub fn of(package_reference: &PackageReference) -> Self {
+        let mut assets = parse_assets(package_reference.include_assets(), &ASSETS);
+        let excluded = parse_assets(package_reference.exclude_assets(), &[]);
+        assets.retain(|asset| !excluded.contains(asset));
+
+        let private_assets = parse_assets(package_reference.private_assets(), &[]);
+        let has = |asset: &str| assets.contains(&asset);
+        let runtime = has("runtime");
+
+        let analyzer_package = package_reference
+            .name()
+            .to_lowercase()
+            .ends_with("analyzers");
+
+        if has("analyzers") && (analyzer_package || !(runtime || has("compile"))) {
+            EdgeClass::Analyzer
+        } else if runtime {
+            EdgeClass::Runtime
+        } else if has("compile") && private_assets.len() < ASSETS.len() {
+            EdgeClass::CompileOnly
+        } else {
+            EdgeClass::Build
+        }
+    }
+
+    /// Classifies a project reference.
+    ///
+    /// A project referenced with `OutputItemType="Analyzer"`, like a source generator, is
+    /// [`EdgeClass::Analyzer`]. Otherwise, a project referenced with
+    /// `ReferenceOutputAssembly="false"`, which is only built before the referencing one, is
+    /// [`EdgeClass::Build`], and the other projects are [`EdgeClass::Runtime`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::{graph::EdgeClass, ProjectReference};
+    /// use std::path::PathBuf;
+    ///
+    /// let mut reference = ProjectReference::new(
+    ///     "Generators".to_string(),
+    ///     PathBuf::from("../Generators/Generators.csproj"),
+    /// );
+    /// assert_eq!(EdgeClass::of_project_reference(&reference), EdgeClass::Runtime);
+    ///
+    /// reference.set_output_item_type(Some("Analyzer".to_string()));
+    /// reference.set_reference_output_assembly(Some("false".to_string()));
+    /// assert_eq!(EdgeClass::of_project_reference(&reference), EdgeClass::Analyzer);
+    /// ```
+    pub fn of_project_reference(project_reference: &ProjectReference) -> Self {
+        let analyzer = project_reference
+            .output_item_type()
+            .is_some_and(|item_type| item_type.eq_ignore_ascii_case("Analyzer"));
+        let references_assembly = !project_reference
+            .reference_output_assembly()
+            .is_some_and(|value| value.eq_ignore_ascii_case("false"));
+
+        if analyzer {
+            EdgeClass::Analyzer
+        } else if references_assembly {
+            EdgeClass::Runtime
+        } else {
+            EdgeClass::Build
+        }
+    }
+
+    /// Returns the name of the class (ex: `compile-only`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            EdgeClass::Runtime => "runtime",
+            EdgeClass::CompileOnly => "compile-only",
+            EdgeClass::Analyzer => "analyzer",
+            EdgeClass::Build => "build",
+        }
+    }
+}
+
+/// Returns the assets of a list separated by `;`, where `all` and `none` stand for every asset and
+/// no asset, or `default` when the list is not declared.
+fn parse_assets(value: Option<&String>, default: &[&'static str]) -> Vec<&'static str> {
+    let Some(value) = value else {
+        return default.to_vec();
+    };
+
+    let mut assets = vec![];
+
+    for asset in value.split(';').map(str::trim) {
+        if asset.eq_ignore_ascii_case("all") {
+            return ASSETS.to_vec();
+        }
+
+        if let Some(asset) = ASSETS
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(asset))
+        {
+            assets.push(*asset);
+        }
+    }
+
+    assets
+}
+
+/// Represents a dependency from the node at index `from` to the node at index `to`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge {
+    from: usize,
+    to: usize,
+    kind: EdgeKind,
+    #[cfg_attr(feature = "serde", serde(default))]
+    class: EdgeClass,
+}
+
+/// Represents the direction followed when extracting a subgraph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Follows the nodes a node depends on.
+    Upstream,
+    /// Follows the nodes that depend on a node.
+    Downstream,
+    /// Follows both directions.
+    Both,
+}
+
+/// Represents the dependencies between the projects of a workspace and, optionally, the packages
+/// they reference.
+///
+/// Nodes are identified by their index in `nodes()`. Project ids are the project paths relative
+/// to the workspace root and package ids are `Name@Version`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{graph::DependencyGraph, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// let graph = DependencyGraph::from_workspace(&workspace, true);
+///
+/// for edge in graph.edges() {
+///     println!("{} -> {}", graph.node(edge.from()).name(), graph.node(edge.to()).name());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "GraphDocument")
+)]
+pub struct DependencyGraph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    index: GraphIndex,
+}
+
+/// The lookups of a [`DependencyGraph`], kept in sync with its nodes and edges so the package
+/// nodes, and the dependencies and dependents of a node, are found without scanning the graph.
+#[derive(Debug, Clone, Default)]
+struct GraphIndex {
+    /// The package nodes by lowercase id, since package names are case-insensitive.
+    packages: HashMap<String, usize>,
+    edges: HashSet<Edge>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+impl GraphIndex {
+    fn new(nodes: &[Node], edges: &[Edge]) -> Self {
+        let mut index = Self::default();
+
+        for (position, node) in nodes.iter().enumerate() {
+            index.insert_node(position, node);
+        }
+
+        for edge in edges {
+            index.insert(*edge);
+        }
+
+        index
+    }
+
+    fn insert_node(&mut self, position: usize, node: &Node) {
+        if node.kind == NodeKind::Package {
+            self.packages.insert(node.id.to_lowercase(), position);
+        }
+    }
+
+    /// Returns the index of the node of the package `name` at `version`.
+    fn package(&self, name: &str, version: &str) -> Option<usize> {
+        self.packages
+            .get(&format!("{}@{}", name, version).to_lowercase())
+            .copied()
+    }
+
+    /// Adds an edge, returning `false` if the graph already has it.
+    fn insert(&mut self, edge: Edge) -> bool {
+        if !self.edges.insert(edge) {
+            return false;
+        }
+
+        let len = edge.from.max(edge.to) + 1;
+        if self.outgoing.len() < len {
+            self.outgoing.resize(len, vec![]);
+            self.incoming.resize(len, vec![]);
+        }
+
+        self.outgoing[edge.from].push(edge.to);
+        self.incoming[edge.to].push(edge.from);
+
+        true
+    }
+}
+
+/// The serialized shape of a [`DependencyGraph`], without its index.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GraphDocument {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+#[cfg(feature = "serde")]
+impl From<GraphDocument> for DependencyGraph {
+    fn from(document: GraphDocument) -> Self {
+        Self {
+            index: GraphIndex::new(&document.nodes, &document.edges),
+            nodes: document.nodes,
+            edges: document.edges,
+        }
+    }
+}
+
+impl PartialEq for DependencyGraph {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes && self.edges == other.edges
+    }
+}
+
+impl DependencyGraph {
+    /// Builds the graph of a workspace.
+    ///
+    /// Project references that do not point to a project of the workspace are ignored. Package
+    /// nodes are only added when `include_packages` is `true`.
+    pub fn from_workspace(workspace: &Workspace, include_packages: bool) -> Self {
+        Self::build(workspace, include_packages, None)
+    }
+
+    /// Builds the graph of a workspace for one target framework, as it is built for that
+    /// framework, instead of the union of every target framework.
+    ///
+    /// The graph has the projects targeting the framework (see
+    /// [`crate::Project::target_frameworks`]) or without a known target framework, and the
+    /// projects they reference transitively, which are built for their nearest framework
+    /// compatible with the referencing one (ex: `netstandard2.0` for a `net48` project). Each
+    /// project only has the references that apply to the framework it is built for (see
+    /// [`crate::Project::project_references_for`]). A referenced project without any compatible
+    /// framework cannot be built (`NU1201`): it is left out, and reported by
+    /// [`crate::validation::IncompatibleProjectReference`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{export::dot, graph::DependencyGraph, workspace::Workspace};
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    ///
+    /// for framework in ["net48", "net8.0"] {
+    ///     let graph = DependencyGraph::from_workspace_for(&workspace, true, framework);
+    ///     println!("{}", dot::to_string(&graph));
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_workspace_for(
+        workspace: &Workspace,
+        include_packages: bool,
+        target_framework: &str,
+    ) -> Self {
+        Self::build(workspace, include_packages, Some(target_framework))
+    }
+
+    fn build(
+        workspace: &Workspace,
+        include_packages: bool,
+        target_framework: Option<&str>,
+    ) -> Self {
+        let mut graph = Self::default();
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+
+        // the projects of the view, with the framework they are built for
+        let frameworks =
+            target_framework.map(|target_framework| built_frameworks(workspace, target_framework));
+        let projects: Vec<(&Project, Option<&str>)> = workspace
+            .projects()
+            .iter()
+            .filter_map(|project| match &frameworks {
+                Some(frameworks) => frameworks
+                    .get(project.path())
+                    .map(|framework| (project, Some(framework.as_str()))),
+                None => Some((project, None)),
+            })
+            .collect();
+
+        for (project, _) in &projects {
+            let index = graph.add_project(workspace, project);
+            indexes.insert(graph.nodes[index].id.clone(), index);
+        }
+
+        for (project, framework) in projects {
+            let from = indexes[&workspace.relative_path(project.path())];
+
+            graph.add_project_references(workspace, project, from, &indexes, framework);
+
+            if include_packages {
+                graph.add_package_references(project, from, framework);
+            }
+        }
+
+        graph
+    }
+
+    /// Adds an edge from the package `name` at `version` to the package it depends on, adding
+    /// the nodes of the packages that are not in the graph yet.
+    pub fn add_package_dependency(
+        &mut self,
+        name: &str,
+        version: &str,
+        dependency: &str,
+        dependency_version: &str,
+    ) {
+        let from = self.package_node(name, version);
+        let to = self.package_node(dependency, dependency_version);
+
+        self.add_edge(from, to, EdgeKind::PackageDependency, EdgeClass::Runtime);
+    }
+
+    /// Adds the dependencies between the packages restored for the projects of `workspace`,
+    /// read from their restore graphs (see [`crate::assets::ProjectAssets`]), so the graph
+    /// shows the whole supply chain. Projects that were not restored are skipped.
+    ///
+    /// The graph should be built with packages. Every package gets the version it resolved to, so
+    /// the references of a restored project point to the resolved version instead of the
+    /// declared one (ex: `[8.0,9.0)`), and the packages that are no longer referenced are
+    /// dropped, which shifts the indexes of the nodes after them. The dependencies of every
+    /// target framework are added.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a restore graph could not be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{graph::DependencyGraph, workspace::Workspace};
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    /// let mut graph = DependencyGraph::from_workspace(&workspace, true);
+    /// graph.add_restored_dependencies(&workspace)?;
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn add_restored_dependencies(&mut self, workspace: &Workspace) -> Result<(), ParseError> {
+        let projects: HashMap<String, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.kind == NodeKind::Project)
+            .map(|(index, node)| (node.id.clone(), index))
+            .collect();
+
+        // the references to a declared version replaced by a reference to the resolved one
+        let mut redirected: HashSet<(usize, usize)> = HashSet::new();
+
+        for project in workspace.projects() {
+            let Some(assets) = ProjectAssets::for_project(project)? else {
+                continue;
+            };
+
+            let from = projects
+                .get(&workspace.relative_path(project.path()))
+                .copied();
+
+            for target_framework in assets.target_frameworks() {
+                let Some(resolved) = assets.resolved(target_framework) else {
+                    continue;
+                };
+
+                let versions: HashMap<String, &String> = resolved
+                    .iter()
+                    .map(|(package, version)| (package.to_lowercase(), version))
+                    .collect();
+
+                for reference in project.package_references() {
+                    let (Some(from), Some(version), Some(declared)) = (
+                        from,
+                        versions.get(&reference.name().to_lowercase()),
+                        self.index.package(reference.name(), reference.version()),
+                    ) else {
+                        continue;
+                    };
+
+                    let to = self.package_node(reference.name(), version);
+
+                    if to != declared {
+                        redirected.insert((from, declared));
+                        self.add_edge(
+                            from,
+                            to,
+                            EdgeKind::PackageReference,
+                            EdgeClass::of(reference),
+                        );
+                    }
+                }
+
+                for (package, version) in resolved {
+                    let dependencies = assets.dependencies(target_framework, package);
+
+                    for dependency in dependencies.into_iter().flat_map(|d| d.keys()) {
+                        if let Some(dependency_version) = versions.get(&dependency.to_lowercase()) {
+                            self.add_package_dependency(
+                                package,
+                                version,
+                                dependency,
+                                dependency_version,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        if !redirected.is_empty() {
+            self.edges.retain(|edge| {
+                edge.kind != EdgeKind::PackageReference
+                    || !redirected.contains(&(edge.from, edge.to))
+            });
+            self.index = GraphIndex::new(&self.nodes, &self.edges);
+
+            self.drop_unreferenced_packages();
+        }
+
+        Ok(())
+    }
+
+    /// Adds the dependencies of the packages of the graph, and of their own dependencies, read
+    /// from the `.nuspec` files of the global packages folder (see
+    /// [`GlobalPackagesFolder::transitive_dependencies`]), for projects that were not restored.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a `.nuspec` could not be read or parsed.
+    pub fn add_installed_dependencies(
+        &mut self,
+        folder: &GlobalPackagesFolder,
+        target_framework: Option<&str>,
+    ) -> Result<(), ParseError> {
+        let packages: Vec<(String, String)> = self
+            .nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Package)
+            .filter_map(|node| Some((node.name.clone(), node.version.clone()?)))
+            .collect();
+
+        for (name, version) in packages {
+            let resolved = folder.transitive_dependencies(&name, &version, target_framework)?;
+
+            for package in &resolved {
+                let parent_version = if package.required_by().eq_ignore_ascii_case(&name) {
+                    Some(&version)
+                } else {
+                    resolved
+                        .iter()
+                        .find(|parent| parent.name() == package.required_by())
+                        .map(|parent| parent.version())
+                };
+
+                if let Some(parent_version) = parent_version {
+                    self.add_package_dependency(
+                        package.required_by(),
+                        parent_version,
+                        package.name(),
+                        package.version(),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Updates the graph of `workspace` after the projects at `paths` changed (see
+    /// [`Workspace::refresh`]), without building it again. The graph must have been built with
+    /// [`DependencyGraph::from_workspace`].
+    ///
+    /// Only the references of the changed projects are resolved again, unless a project was
+    /// added or removed, since the project references of the other projects may then point to
+    /// another project. `include_packages` must be the value the graph was built with.
+    ///
+    /// New nodes are appended, and the nodes of removed projects and of packages that are no
+    /// longer referenced are dropped, which shifts the indexes of the nodes after them. A graph
+    /// that was not built from `workspace` is built again.
+    pub fn refresh<P>(&mut self, workspace: &Workspace, paths: &[P], include_packages: bool)
+    where
+        P: AsRef<Path>,
+    {
+        let mut indexes: HashMap<String, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id.clone(), index))
+            .collect();
+
+        let changed: HashSet<String> = paths
+            .iter()
+            .map(|path| workspace.relative_path(path))
+            .collect();
+        let current: HashSet<String> = workspace
+            .projects()
+            .iter()
+            .map(|project| workspace.relative_path(project.path()))
+            .collect();
+
+        let removed = self.nodes.iter().any(|node| {
+            node.kind == NodeKind::Project
+                && changed.contains(&node.id)
+                && !current.contains(&node.id)
+        });
+        let added: Vec<&Project> = workspace
+            .projects()
+            .iter()
+            .filter(|project| {
+                let id = workspace.relative_path(project.path());
+
+                changed.contains(&id) && !indexes.contains_key(&id)
+            })
+            .collect();
+        let moved = removed || !added.is_empty();
+
+        let nodes = &self.nodes;
+        self.edges.retain(|edge| {
+            let stale = changed.contains(&nodes[edge.from].id)
+                || (moved && edge.kind == EdgeKind::ProjectReference);
+
+            !stale
+        });
+        self.index = GraphIndex::new(&self.nodes, &self.edges);
+
+        if removed {
+            let keep: Vec<bool> = self
+                .nodes
+                .iter()
+                .map(|node| node.kind == NodeKind::Package || current.contains(&node.id))
+                .collect();
+
+            *self = self.induced(&keep);
+        }
+
+        if moved {
+            for project in added {
+                self.add_project(workspace, project);
+            }
+
+            indexes = self
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(index, node)| (node.id.clone(), index))
+                .collect();
+        }
+
+        for project in workspace.projects() {
+            let id = workspace.relative_path(project.path());
+            let is_changed = changed.contains(&id);
+
+            if !is_changed && !moved {
+                continue;
+            }
+
+            // a project missing from the graph was not built with this workspace
+            let Some(&from) = indexes.get(&id) else {
+                *self = Self::from_workspace(workspace, include_packages);
+                return;
+            };
+
+            self.add_project_references(workspace, project, from, &indexes, None);
+
+            if is_changed && include_packages {
+                self.add_package_references(project, from, None);
+            }
+        }
+
+        self.drop_unreferenced_packages();
+    }
+
+    /// Returns a reference to the list of nodes.
+    pub fn nodes(&self) -> &Vec<Node> {
+        &self.nodes
+    }
+
+    /// Returns a reference to the list of edges.
+    pub fn edges(&self) -> &Vec<Edge> {
+        &self.edges
+    }
+
+    /// Returns the node at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn node(&self, index: usize) -> &Node {
+        &self.nodes[index]
+    }
+
+    /// Returns the index of the node with the given id.
+    pub fn find(&self, id: &str) -> Option<usize> {
+        self.nodes.iter().position(|node| node.id == id)
+    }
+
+    /// Returns the indexes of the nodes with the given name, ignoring case.
+    ///
+    /// Packages have one node per referenced version, so a package name can match several
+    /// nodes.
+    pub fn find_by_name(&self, name: &str) -> Vec<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.name.eq_ignore_ascii_case(name))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the indexes of the nodes the node at `index` depends on.
+    pub fn dependencies(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.index
+            .outgoing
+            .get(index)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// Returns the indexes of the nodes that depend on the node at `index`.
+    pub fn dependents(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.index
+            .incoming
+            .get(index)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// Returns the indexes of the nodes that depend on the node at `index`, directly or
+    /// transitively, in ascending order. The node itself is only included if it is in a cycle.
+    pub fn transitive_dependents(&self, index: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = self.dependents(index).collect();
+
+        while let Some(node) = stack.pop() {
+            if !visited[node] {
+                visited[node] = true;
+                stack.extend(self.dependents(node));
+            }
+        }
+
+        (0..self.nodes.len())
+            .filter(|node| visited[*node])
+            .collect()
+    }
+
+    /// Returns the subgraph with the node at `index`, every node it depends on and every node
+    /// that depends on it, directly or transitively.
+    ///
+    /// Useful to export the neighborhood of a single project in large workspaces.
+    pub fn focus(&self, index: usize) -> Self {
+        let mut keep = self.reachable(index, |graph, node| graph.dependencies(node).collect());
+        let dependents = self.reachable(index, |graph, node| graph.dependents(node).collect());
+
+        for (keep, dependent) in keep.iter_mut().zip(dependents) {
+            *keep |= dependent;
+        }
+
+        self.induced(&keep)
+    }
+
+    /// Returns the shortest chain of references explaining why the node at `from` depends on the
+    /// node at `to`, like `dotnet nuget why` but offline.
+    ///
+    /// The path starts with `from` and ends with `to`. Returns `None` if `from` does not depend
+    /// on `to`. See [`DependencyGraph::all_paths`] for the other chains.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{graph::DependencyGraph, workspace::Workspace};
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    /// let graph = DependencyGraph::from_workspace(&workspace, true);
+    ///
+    /// let api = graph.find_by_name("Api")[0];
+    ///
+    /// for package in graph.find_by_name("Newtonsoft.Json") {
+    ///     if let Some(path) = graph.why(api, package) {
+    ///         let names: Vec<&String> = path.iter().map(|i| graph.node(*i).name()).collect();
+    ///         println!("{:?}", names);
+    ///     }
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn why(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::from([from]);
+        visited[from] = true;
+
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![to];
+
+                while let Some(node) = previous[path[path.len() - 1]] {
+                    path.push(node);
+                }
+
+                path.reverse();
+
+                return Some(path);
+            }
+
+            for next in self.dependencies(node) {
+                if !visited[next] {
+                    visited[next] = true;
+                    previous[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns up to `max_paths` chains of references from the node at `from` to the node at
+    /// `to`, without cycles, in order of length.
+    ///
+    /// The number of chains grows exponentially with the depth of the graph, so they are only
+    /// enumerated until `max_paths` are found.
+    pub fn all_paths(&self, from: usize, to: usize, max_paths: usize) -> Vec<Vec<usize>> {
+        // only the nodes that lead to `to` are explored
+        let leads_to_target = self.reachable(to, |graph, node| graph.dependents(node).collect());
+
+        let mut paths = vec![];
+        let mut queue = VecDeque::new();
+
+        if leads_to_target[from] {
+            queue.push_back(vec![from]);
+        }
+
+        while let Some(path) = queue.pop_front() {
+            if paths.len() == max_paths {
+                break;
+            }
+
+            let current = path[path.len() - 1];
+
+            if current == to {
+                paths.push(path);
+                continue;
+            }
+
+            for node in self.dependencies(current) {
+                if leads_to_target[node] && !path.contains(&node) {
+                    let mut next = path.clone();
+                    next.push(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        paths
+    }
+
+    /// Returns the graph without the test projects of `workspace` (the projects referencing
+    /// `Microsoft.NET.Test.Sdk`), so diagrams and metrics only show the production code.
+    ///
+    /// When `exclusive_dependencies` is `true`, only the nodes the other projects depend on,
+    /// directly or transitively, are kept, which removes the packages only the tests use (ex:
+    /// `xunit`, `Moq`, and their own dependencies) and the test helper projects: the projects
+    /// only referenced by the tests that reference one of those packages. The other projects
+    /// only referenced by the tests are kept, since they are usually the applications under
+    /// test.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{graph::DependencyGraph, metrics, workspace::Workspace};
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    /// let graph = DependencyGraph::from_workspace(&workspace, true).without_tests(&workspace, true);
+    ///
+    /// for coupling in metrics::coupling(&graph) {
+    ///     println!("{}: I={:.2}", coupling.name(), coupling.instability());
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn without_tests(&self, workspace: &Workspace, exclusive_dependencies: bool) -> Self {
+        let is_test: Vec<bool> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                node.path
+                    .as_ref()
+                    .and_then(|path| workspace.project_by_path(path))
+                    .is_some_and(packaging::is_test_project)
+            })
+            .collect();
+
+        if !exclusive_dependencies {
+            let keep: Vec<bool> = is_test.iter().map(|is_test| !is_test).collect();
+
+            return self.induced(&keep);
+        }
+
+        // the test projects, and the test helpers found until none is left
+        let mut is_test = is_test;
+        loop {
+            let only_used_by = |node: usize, helper: usize| {
+                let mut dependents = self.dependents(node).peekable();
+
+                dependents.peek().is_some()
+                    && dependents.all(|dependent| is_test[dependent] || dependent == helper)
+            };
+
+            let helpers: Vec<usize> = (0..self.nodes.len())
+                .filter(|node| self.nodes[*node].kind == NodeKind::Project && !is_test[*node])
+                .filter(|node| only_used_by(*node, *node))
+                .filter(|node| {
+                    self.dependencies(*node).any(|dependency| {
+                        self.nodes[dependency].kind == NodeKind::Package
+                            && only_used_by(dependency, *node)
+                    })
+                })
+                .collect();
+
+            if helpers.is_empty() {
+                break;
+            }
+
+            for helper in helpers {
+                is_test[helper] = true;
+            }
+        }
+
+        let mut keep = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = (0..self.nodes.len())
+            .filter(|node| self.nodes[*node].kind == NodeKind::Project && !is_test[*node])
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            if keep[node] {
+                continue;
+            }
+
+            keep[node] = true;
+            stack.extend(
+                self.dependencies(node)
+                    .filter(|dependency| !is_test[*dependency]),
+            );
+        }
+
+        self.induced(&keep)
+    }
+
+    /// Returns the graph with only the edges of the given classes, for example to show what is
+    /// deployed with [`EdgeClass::Runtime`] alone.
+    ///
+    /// The projects are always kept, and the packages are kept while the projects still reach
+    /// them through the remaining edges.
+    pub fn with_edge_classes(&self, classes: &[EdgeClass]) -> Self {
+        let mut graph = self.clone();
+        graph.edges.retain(|edge| classes.contains(&edge.class));
+        graph.index = GraphIndex::new(&graph.nodes, &graph.edges);
+
+        let mut keep: Vec<bool> = graph
+            .nodes
+            .iter()
+            .map(|node| node.kind == NodeKind::Project)
+            .collect();
+        let mut stack: Vec<usize> = (0..keep.len()).filter(|index| keep[*index]).collect();
+
+        while let Some(index) = stack.pop() {
+            for dependency in graph.dependencies(index) {
+                if !keep[dependency] {
+                    keep[dependency] = true;
+                    stack.push(dependency);
+                }
+            }
+        }
+
+        graph.induced(&keep)
+    }
+
+    /// Returns the subgraph with the nodes within `depth` references of the node at `root`.
+    ///
+    /// [`Direction::Upstream`] follows the references of the node (what it depends on),
+    /// [`Direction::Downstream`] follows the nodes referencing it (what depends on it) and
+    /// [`Direction::Both`] follows both. A depth of `0` keeps only the root node.
+    ///
+    /// Useful to export readable diagrams of a single project in large workspaces, where even
+    /// [`DependencyGraph::focus`] returns too many nodes.
+    pub fn subgraph(&self, root: usize, depth: usize, direction: Direction) -> Self {
+        let mut keep = vec![false; self.nodes.len()];
+        keep[root] = true;
+
+        let mut frontier = vec![root];
+
+        for _ in 0..depth {
+            if frontier.is_empty() {
+                break;
+            }
+
+            let mut next = vec![];
+
+            for node in frontier {
+                if direction != Direction::Downstream {
+                    next.extend(self.dependencies(node));
+                }
+
+                if direction != Direction::Upstream {
+                    next.extend(self.dependents(node));
+                }
+            }
+
+            next.retain(|node| !keep[*node]);
+            next.dedup();
+
+            for node in &next {
+                keep[*node] = true;
+            }
+
+            frontier = next;
+        }
+
+        self.induced(&keep)
+    }
+
+    /// Returns the strongly connected components of the graph: the groups of nodes that can all
+    /// reach each other through references.
+    ///
+    /// Every node belongs to exactly one component. The indexes of each component are sorted
+    /// and the components are sorted by their first index. See
+    /// [`DependencyGraph::cyclic_components`] to only get the components with cycles.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // iterative Tarjan's algorithm, so deep graphs do not overflow the stack
+        let count = self.nodes.len();
+        let successors: Vec<Vec<usize>> = (0..count)
+            .map(|node| self.dependencies(node).collect())
+            .collect();
+
+        let mut next_index = 0;
+        let mut indexes: Vec<Option<usize>> = vec![None; count];
+        let mut low_links = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = vec![];
+        let mut components = vec![];
+
+        for start in 0..count {
+            if indexes[start].is_some() {
+                continue;
+            }
+
+            // (node, position of the next successor to visit)
+            let mut call_stack = vec![(start, 0)];
+
+            while let Some((node, position)) = call_stack.pop() {
+                if position == 0 {
+                    indexes[node] = Some(next_index);
+                    low_links[node] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+
+                if let Some(&successor) = successors[node].get(position) {
+                    call_stack.push((node, position + 1));
+
+                    match indexes[successor] {
+                        None => call_stack.push((successor, 0)),
+                        Some(index) if on_stack[successor] => {
+                            low_links[node] = low_links[node].min(index);
+                        }
+                        Some(_) => (),
+                    }
+
+                    continue;
+                }
+
+                if Some(low_links[node]) == indexes[node] {
+                    let mut component = vec![];
+
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+
+                        if member == node {
+                            break;
+                        }
+                    }
+
+                    component.sort();
+                    components.push(component);
+                }
+
+                if let Some((parent, _)) = call_stack.last() {
+                    low_links[*parent] = low_links[*parent].min(low_links[node]);
+                }
+            }
+        }
+
+        components.sort();
+
+        components
+    }
+
+    /// Returns the strongly connected components that contain a cycle: the clusters of
+    /// mutually entangled projects, which are good refactoring candidates.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{graph::DependencyGraph, workspace::Workspace};
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    /// let graph = DependencyGraph::from_workspace(&workspace, false);
+    ///
+    /// for component in graph.cyclic_components() {
+    ///     let names: Vec<&String> = component.iter().map(|i| graph.node(*i).name()).collect();
+    ///     println!("entangled: {:?}", names);
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn cyclic_components(&self) -> Vec<Vec<usize>> {
+        self.strongly_connected_components()
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.dependencies(component[0]).any(|n| n == component[0])
+            })
+            .collect()
+    }
+
+    fn reachable<F>(&self, start: usize, next: F) -> Vec<bool>
+    where
+        F: Fn(&Self, usize) -> Vec<usize>,
+    {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+
+            visited[node] = true;
+            stack.extend(next(self, node));
+        }
+
+        visited
+    }
+
+    /// Returns the subgraph with the nodes marked in `keep` and the edges between them.
+    fn induced(&self, keep: &[bool]) -> Self {
+        let mut indexes = vec![None; self.nodes.len()];
+        let mut graph = Self::default();
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            if keep[index] {
+                indexes[index] = Some(graph.push_node(node.clone()));
+            }
+        }
+
+        for edge in &self.edges {
+            if let (Some(from), Some(to)) = (indexes[edge.from], indexes[edge.to]) {
+                graph.add_edge(from, to, edge.kind, edge.class);
+            }
+        }
+
+        graph
+    }
+
+    fn add_project(&mut self, workspace: &Workspace, project: &Project) -> usize {
+        self.push_node(Node {
+            id: workspace.relative_path(project.path()),
+            name: project.name().clone(),
+            kind: NodeKind::Project,
+            version: None,
+            path: Some(project.path().clone()),
+        })
+    }
+
+    fn push_node(&mut self, node: Node) -> usize {
+        self.index.insert_node(self.nodes.len(), &node);
+        self.nodes.push(node);
+
+        self.nodes.len() - 1
+    }
+
+    fn add_project_references(
+        &mut self,
+        workspace: &Workspace,
+        project: &Project,
+        from: usize,
+        indexes: &HashMap<String, usize>,
+        target_framework: Option<&str>,
+    ) {
+        for reference in project.project_references() {
+            if !target_framework.is_none_or(|t| reference.applies_to(t)) {
+                continue;
+            }
+
+            let Some(target) = workspace.resolve_reference(project, reference) else {
+                continue;
+            };
+
+            // the target is missing from the views of frameworks it is not compatible with
+            let Some(&to) = indexes.get(&workspace.relative_path(target.path())) else {
+                continue;
+            };
+
+            self.add_edge(
+                from,
+                to,
+                EdgeKind::ProjectReference,
+                EdgeClass::of_project_reference(reference),
+            );
+        }
+    }
+
+    fn add_package_references(
+        &mut self,
+        project: &Project,
+        from: usize,
+        target_framework: Option<&str>,
+    ) {
+        for package_reference in project.package_references() {
+            if !target_framework.is_none_or(|t| package_reference.applies_to(t)) {
+                continue;
+            }
+
+            let to = self.package_node(package_reference.name(), package_reference.version());
+
+            self.add_edge(
+                from,
+                to,
+                EdgeKind::PackageReference,
+                EdgeClass::of(package_reference),
+            );
+        }
+    }
+
+    fn package_node(&mut self, name: &str, version: &str) -> usize {
+        if let Some(index) = self.index.package(name, version) {
+            return index;
+        }
+
+        self.push_node(Node {
+            id: format!("{}@{}", name, version),
+            name: name.to_string(),
+            kind: NodeKind::Package,
+            version: Some(version.to_string()),
+            path: None,
+        })
+    }
+
+    /// Drops the packages that are no longer the target of an edge, which shifts the indexes of
+    /// the nodes after them.
+    fn drop_unreferenced_packages(&mut self) {
+        let mut keep: Vec<bool> = self
+            .nodes
+            .iter()
+            .map(|node| node.kind == NodeKind::Project)
+            .collect();
+
+        for edge in &self.edges {
+            keep[edge.to] = true;
+        }
+
+        if keep.contains(&false) {
+            *self = self.induced(&keep);
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, kind: EdgeKind, class: EdgeClass) {
+        let edge = Edge {
+            from,
+            to,
+            kind,
+            class,
+        };
+
+        if self.index.insert(edge) {
+            self.edges.push(edge);
+        }
+    }
+}
+
+/// Returns the projects built when building the workspace for `target_framework`, by path, with
+/// the framework each of them is built for.
+fn built_frameworks(workspace: &Workspace, target_framework: &str) -> HashMap<PathBuf, String> {
+    let rules = FrameworkRules::new();
+    let mut frameworks = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for project in workspace.projects() {
+        let targets = project.target_frameworks();
+
+        let framework = if targets.is_empty() {
+            Some(target_framework.to_string())
+        } else {
+            targets
+                .into_iter()
+                .find(|framework| framework.eq_ignore_ascii_case(target_framework))
+                .cloned()
+        };
+
+        if let Some(framework) = framework {
+            frameworks.insert(project.path().clone(), framework);
+            queue.push_back(project);
+        }
+    }
+
+    while let Some(project) = queue.pop_front() {
+        let framework = frameworks[project.path()].clone();
+
+        for reference in project.project_references_for(&framework) {
+            let Some(target) = workspace.resolve_reference(project, reference) else {
+                continue;
+            };
+
+            if frameworks.contains_key(target.path()) {
+                continue;
+            }
+
+            let targets = target.target_frameworks();
+
+            // a target without a compatible framework cannot be built for the project (NU1201)
+            let nearest = if targets.is_empty() {
+                framework.clone()
+            } else {
+                match nearest_framework(&rules, &framework, &targets) {
+                    Some(nearest) => nearest,
+                    None => continue,
+                }
+            };
+
+            frameworks.insert(target.path().clone(), nearest);
+            queue.push_back(target);
+        }
+    }
+
+    frameworks
+}
+
+/// Returns the framework of `candidates` a project targeting `framework` uses: the same
+/// framework, or else the highest compatible version. Returns `None` when none is compatible.
+fn nearest_framework(
+    rules: &FrameworkRules,
+    framework: &str,
+    candidates: &[&String],
+) -> Option<String> {
+    if let Some(same) = candidates
+        .iter()
+        .find(|candidate| candidate.eq_ignore_ascii_case(framework))
+    {
+        return Some(same.to_string());
+    }
+
+    candidates
+        .iter()
+        .filter(|candidate| rules.is_compatible(framework, candidate))
+        .max_by_key(|candidate| rules.parse(candidate).version())
+        .map(|candidate| candidate.to_string())
+}
+
+impl Node {
+    /// Returns the id of the node.
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Returns the name of the project or package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the kind of the node.
+    pub fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    /// Returns the version of the package, or `None` for projects.
+    pub fn version(&self) -> Option<&String> {
+        self.version.as_ref()
+    }
+
+    /// Returns the path of the project, or `None` for packages.
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+}
+
+impl Edge {
+    /// Returns the index of the dependent node.
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// Returns the index of the dependency node.
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    /// Returns the kind of the edge.
+    pub fn kind(&self) -> EdgeKind {
+        self.kind
+    }
+
+    /// Returns how the assets of the dependency are used.
+    pub fn class(&self) -> EdgeClass {
+        self.class
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::path::PathBuf;
+
+    use crate::{PackageReference, Project, ProjectLanguage, ProjectReference};
+
+    use super::*;
+
+    pub(crate) fn project(path: &str, references: &[&str], packages: &[(&str, &str)]) -> Project {
+        let path = PathBuf::from(path);
+        let name = Project::get_project_name(&path).unwrap();
+
+        let mut project = Project::empty(name, ProjectLanguage::CSharp, path);
+        project.target_framework = Some("net8.0".to_string());
+
+        for reference in references {
+            let path = PathBuf::from(reference);
+            project.add_project_reference(ProjectReference::new(
+                Project::get_project_name(&path).unwrap(),
+                path,
+            ));
+        }
+
+        for (name, version) in packages {
+            project.add_package_reference(PackageReference::new(
+                name.to_string(),
+                version.to_string(),
+            ));
+        }
+
+        project
+    }
+
+    pub(crate) fn workspace() -> Workspace {
+        Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/src/Api/Api.csproj",
+                    &["../Domain/Domain.csproj"],
+                    &[("Serilog", "3.1.1")],
+                ),
+                project(
+                    "/repo/src/Domain/Domain.csproj",
+                    &[],
+                    &[("Serilog", "3.1.1")],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    pub fn build_graph_with_packages() {
+        // given
+        let workspace = workspace();
+
+        // when
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+
+        // then
+        let ids: Vec<&String> = graph.nodes().iter().map(|node| node.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "src/Api/Api.csproj",
+                "src/Domain/Domain.csproj",
+                "Serilog@3.1.1"
+            ]
+        );
+
+        assert_eq!(graph.dependencies(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(graph.dependents(2).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    pub fn focus_keeps_dependencies_and_dependents() {
+        // given
+        let mut workspace = workspace();
+        workspace.projects_mut().extend([
+            project("/repo/src/Web/Web.csproj", &["../Api/Api.csproj"], &[]),
+            project("/repo/tools/Cli/Cli.csproj", &[], &[]),
+        ]);
+
+        let graph = DependencyGraph::from_workspace(&workspace, false);
+
+        // when
+        let focused = graph.focus(graph.find("src/Api/Api.csproj").unwrap());
+
+        // then
+        let names: Vec<&String> = focused.nodes().iter().map(|node| node.name()).collect();
+        assert_eq!(names, vec!["Api", "Domain", "Web"]);
+        assert_eq!(focused.edges().len(), 2);
+    }
+
+    #[test]
+    pub fn explain_why_a_project_depends_on_a_package() {
+        // given
+        let graph = DependencyGraph::from_workspace(&workspace(), true);
+        let api = graph.find_by_name("api")[0];
+        let domain = graph.find_by_name("Domain")[0];
+        let serilog = graph.find_by_name("Serilog")[0];
+
+        // when
+        let path = graph.why(api, serilog);
+        let paths = graph.all_paths(api, serilog, 10);
+
+        // then
+        assert_eq!(path, Some(vec![api, serilog]));
+        assert_eq!(paths, vec![vec![api, serilog], vec![api, domain, serilog]]);
+        assert_eq!(graph.all_paths(api, serilog, 1), vec![vec![api, serilog]]);
+        assert!(graph.why(domain, api).is_none());
+        assert!(graph.all_paths(domain, api, 10).is_empty());
+    }
+
+    #[test]
+    pub fn remove_test_projects() {
+        // given
+        let mut workspace = workspace();
+        workspace.projects_mut().push(project(
+            "/repo/tests/Api.Tests/Api.Tests.csproj",
+            &[
+                "../../src/Api/Api.csproj",
+                "../TestHelpers/TestHelpers.csproj",
+            ],
+            &[
+                ("Microsoft.NET.Test.Sdk", "17.10.0"),
+                ("xunit", "2.9.0"),
+                ("Serilog", "3.1.1"),
+            ],
+        ));
+        workspace.projects_mut().push(project(
+            "/repo/tests/TestHelpers/TestHelpers.csproj",
+            &["../../src/Domain/Domain.csproj"],
+            &[("Moq", "4.20.70")],
+        ));
+
+        let mut graph = DependencyGraph::from_workspace(&workspace, true);
+        graph.add_package_dependency("xunit", "2.9.0", "xunit.core", "2.9.0");
+
+        // when
+        let without_tests = graph.without_tests(&workspace, false);
+        let without_exclusive = graph.without_tests(&workspace, true);
+
+        // then
+        let names = |graph: &DependencyGraph| -> Vec<String> {
+            graph
+                .nodes()
+                .iter()
+                .map(|node| node.name().clone())
+                .collect()
+        };
+
+        assert_eq!(
+            names(&without_tests),
+            vec![
+                "Api",
+                "Domain",
+                "TestHelpers",
+                "Serilog",
+                "Microsoft.NET.Test.Sdk",
+                "xunit",
+                "Moq",
+                "xunit.core"
+            ]
+        );
+        assert_eq!(names(&without_exclusive), vec!["Api", "Domain", "Serilog"]);
+        assert_eq!(without_exclusive.edges().len(), 3);
+    }
+
+    #[test]
+    pub fn classify_package_edges() {
+        // given
+        let mut api = project(
+            "/repo/src/Api/Api.csproj",
+            &[
+                "../Domain/Domain.csproj",
+                "../Generators/Generators.csproj",
+                "../Tools/Tools.csproj",
+            ],
+            &[
+                ("Serilog", "3.1.1"),
+                ("StyleCop.Analyzers", "1.1.118"),
+                ("Microsoft.SourceLink.GitHub", "8.0.0"),
+                ("Contracts", "1.0.0"),
+                ("Polly", "8.4.1"),
+            ],
+        );
+        api.project_references[1].set_output_item_type(Some("Analyzer".to_string()));
+        api.project_references[1].set_reference_output_assembly(Some("false".to_string()));
+        api.project_references[2].set_reference_output_assembly(Some("False".to_string()));
+
+        let assets = [
+            (None, None, None),
+            (
+                Some("all"),
+                Some("runtime; build; native; contentfiles; analyzers"),
+                None,
+            ),
+            (Some("all"), Some("build; buildtransitive"), None),
+            (None, None, Some("runtime")),
+            (Some("all"), None, None),
+        ];
+
+        for (package_reference, (private, include, exclude)) in
+            api.package_references.iter_mut().zip(assets)
+        {
+            package_reference.set_private_assets(private.map(str::to_string));
+            package_reference.set_include_assets(include.map(str::to_string));
+            package_reference.set_exclude_assets(exclude.map(str::to_string));
+        }
+
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                api,
+                project("/repo/src/Domain/Domain.csproj", &[], &[]),
+                project("/repo/src/Generators/Generators.csproj", &[], &[]),
+                project("/repo/src/Tools/Tools.csproj", &[], &[]),
+            ],
+        );
+
+        // when
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+        let runtime = graph.with_edge_classes(&[EdgeClass::Runtime]);
+
+        // then
+        let classes: Vec<(&str, EdgeClass)> = graph
+            .edges()
+            .iter()
+            .map(|edge| (graph.node(edge.to()).name().as_str(), edge.class()))
+            .collect();
+        assert_eq!(
+            classes,
+            vec![
+                ("Domain", EdgeClass::Runtime),
+                ("Generators", EdgeClass::Analyzer),
+                ("Tools", EdgeClass::Build),
+                ("Serilog", EdgeClass::Runtime),
+                ("StyleCop.Analyzers", EdgeClass::Analyzer),
+                ("Microsoft.SourceLink.GitHub", EdgeClass::Build),
+                ("Contracts", EdgeClass::CompileOnly),
+                ("Polly", EdgeClass::Runtime),
+            ]
+        );
+
+        let names: Vec<&String> = runtime.nodes().iter().map(|node| node.name()).collect();
+        assert_eq!(
+            names,
+            vec!["Api", "Domain", "Generators", "Tools", "Serilog", "Polly"]
+        );
+        assert_eq!(runtime.edges().len(), 3);
+    }
+
+    #[test]
+    pub fn subgraph_within_depth() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/Web/Web.csproj", &["../Api/Api.csproj"], &[]),
+                project("/repo/Api/Api.csproj", &["../Domain/Domain.csproj"], &[]),
+                project("/repo/Domain/Domain.csproj", &[], &[("Serilog", "3.1.1")]),
+            ],
+        );
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+        let api = graph.find("Api/Api.csproj").unwrap();
+
+        // when
+        let upstream = graph.subgraph(api, 1, Direction::Upstream);
+        let both = graph.subgraph(api, 1, Direction::Both);
+        let deep = graph.subgraph(api, 2, Direction::Upstream);
+
+        // then
+        let ids = |graph: &DependencyGraph| -> Vec<String> {
+            graph.nodes().iter().map(|node| node.id().clone()).collect()
+        };
+
+        assert_eq!(
+            ids(&upstream),
+            vec!["Api/Api.csproj", "Domain/Domain.csproj"]
+        );
+        assert_eq!(
+            ids(&both),
+            vec!["Web/Web.csproj", "Api/Api.csproj", "Domain/Domain.csproj"]
+        );
+        assert_eq!(
+            ids(&deep),
+            vec!["Api/Api.csproj", "Domain/Domain.csproj", "Serilog@3.1.1"]
+        );
+        assert_eq!(upstream.edges().len(), 1);
+    }
+
+    #[test]
+    pub fn find_entangled_projects() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/A/A.csproj", &["../B/B.csproj"], &[]),
+                project("/repo/B/B.csproj", &["../C/C.csproj"], &[]),
+                project("/repo/C/C.csproj", &["../A/A.csproj", "../D/D.csproj"], &[]),
+                project("/repo/D/D.csproj", &[], &[("Serilog", "3.1.1")]),
+            ],
+        );
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+
+        // when
+        let components = graph.strongly_connected_components();
+        let cyclic = graph.cyclic_components();
+
+        // then
+        assert_eq!(components, vec![vec![0, 1, 2], vec![3], vec![4]]);
+        assert_eq!(cyclic, vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    pub fn build_graph_without_packages() {
+        let graph = DependencyGraph::from_workspace(&workspace(), false);
+
+        assert_eq!(graph.nodes().len(), 2);
+        assert_eq!(
+            graph.edges(),
+            &vec![Edge {
+                from: 0,
+                to: 1,
+                kind: EdgeKind::ProjectReference,
+                class: EdgeClass::Runtime,
+            }]
+        );
+    }
+
+    #[test]
+    pub fn refresh_changed_projects() {
+        // given
+        let mut workspace = workspace();
+        let mut graph = DependencyGraph::from_workspace(&workspace, true);
+
+        workspace
+            .projects_mut()
+            .retain(|project| project.name() != "Domain");
+        workspace.projects_mut().extend([
+            project(
+                "/repo/src/Web/Web.csproj",
+                &["../Api/Api.csproj"],
+                &[("Dapper", "2.1.35")],
+            ),
+            project("/repo/src/Domain/Domain.Core.csproj", &[], &[]),
+        ]);
+
+        // when
+        graph.refresh(
+            &workspace,
+            &[
+                PathBuf::from("/repo/src/Domain/Domain.csproj"),
+                PathBuf::from("/repo/src/Web/Web.csproj"),
+                PathBuf::from("/repo/src/Domain/Domain.Core.csproj"),
+            ],
+            true,
+        );
+
+        // then
+        let edges = |graph: &DependencyGraph| -> Vec<(String, String)> {
+            let mut edges: Vec<_> = graph
+                .edges()
+                .iter()
+                .map(|edge| {
+                    (
+                        graph.node(edge.from()).id().clone(),
+                        graph.node(edge.to()).id().clone(),
+                    )
+                })
+                .collect();
+            edges.sort();
+            edges
+        };
+
+        let rebuilt = DependencyGraph::from_workspace(&workspace, true);
+
+        assert_eq!(graph.nodes().len(), rebuilt.nodes().len());
+        assert_eq!(edges(&graph), edges(&rebuilt));
+        assert_eq!(
+            edges(&graph),
+            vec![
+                (
+                    "src/Api/Api.csproj".to_string(),
+                    "Serilog@3.1.1".to_string()
+                ),
+                (
+                    "src/Web/Web.csproj".to_string(),
+                    "Dapper@2.1.35".to_string()
+                ),
+                (
+                    "src/Web/Web.csproj".to_string(),
+                    "src/Api/Api.csproj".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn rebuild_graph_of_another_workspace_on_refresh() {
+        // given
+        let workspace = workspace();
+        let mut graph = DependencyGraph::default();
+
+        // when
+        graph.refresh(
+            &workspace,
+            &[PathBuf::from("/repo/src/Api/Api.csproj")],
+            true,
+        );
+
+        // then
+        assert_eq!(graph, DependencyGraph::from_workspace(&workspace, true));
+    }
+
+    #[test]
+    pub fn build_graph_for_target_framework() {
+        // given
+        let mut api = project(
+            "/repo/src/Api/Api.csproj",
+            &["../Domain/Domain.csproj", "../Legacy/Legacy.csproj"],
+            &[("Serilog", "3.1.1"), ("System.ValueTuple", "4.5.0")],
+        );
+        api.target_frameworks = vec!["net8.0".to_string(), "net48".to_string()];
+        api.project_references[1].set_target_framework(Some("net48".to_string()));
+        api.package_references[1].set_target_framework(Some("net48".to_string()));
+
+        let mut domain = project(
+            "/repo/src/Domain/Domain.csproj",
+            &["../Modern/Modern.csproj"],
+            &[],
+        );
+        domain.target_frameworks = vec!["netstandard2.0".to_string(), "net8.0".to_string()];
+        domain.project_references[0].set_target_framework(Some("net8.0".to_string()));
+
+        let mut legacy = project(
+            "/repo/src/Legacy/Legacy.csproj",
+            &["../Modern/Modern.csproj"],
+            &[],
+        );
+        legacy.target_framework = Some("net48".to_string());
+
+        let mut modern = project("/repo/src/Modern/Modern.csproj", &[], &[]);
+        modern.target_framework = Some("net8.0".to_string());
+
+        let workspace = Workspace::from_projects("/repo", vec![api, domain, legacy, modern]);
+
+        // when
+        let net8 = DependencyGraph::from_workspace_for(&workspace, true, "net8.0");
+        let net48 = DependencyGraph::from_workspace_for(&workspace, true, "NET48");
+
+        // then
+        let names = |graph: &DependencyGraph| -> Vec<String> {
+            graph
+                .nodes()
+                .iter()
+                .map(|node| node.name().clone())
+                .collect()
+        };
+        let dependencies = |graph: &DependencyGraph| -> Vec<String> {
+            graph
+                .dependencies(0)
+                .map(|index| graph.node(index).name().clone())
+                .collect()
+        };
+
+        assert_eq!(names(&net8), vec!["Api", "Domain", "Modern", "Serilog"]);
+        assert_eq!(dependencies(&net8), vec!["Domain", "Serilog"]);
+        assert_eq!(net8.dependencies(1).count(), 1);
+
+        // the domain is built for netstandard2.0, without the reference of net8.0
+        assert_eq!(
+            names(&net48),
+            vec!["Api", "Domain", "Legacy", "Serilog", "System.ValueTuple"]
+        );
+        assert_eq!(
+            dependencies(&net48),
+            vec!["Domain", "Legacy", "Serilog", "System.ValueTuple"]
+        );
+        assert_eq!(net48.dependencies(1).count(), 0);
+
+        // the modern project cannot be built for the legacy one
+        assert_eq!(net48.dependencies(2).count(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn add_restored_package_dependencies() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Api/Api.csproj");
+
+        std::fs::create_dir_all(dir.path().join("Api/obj")).unwrap();
+        std::fs::write(
+            dir.path().join("Api/obj/project.assets.json"),
+            r#"{
+  "targets": {
+    "net8.0": {
+      "Polly/8.4.1": {
+        "type": "package",
+        "dependencies": { "Polly.Core": "8.4.1" }
+      },
+      "Polly.Core/8.4.1": {
+        "type": "package",
+        "dependencies": { "Microsoft.Bcl.TimeProvider": "8.0.0" }
+      },
+      "Microsoft.Bcl.TimeProvider/8.0.1": { "type": "package" }
+    }
+  }
+}"#,
+        )
+        .unwrap();
+
+        let workspace = Workspace::from_projects(
+            dir.path(),
+            vec![project(
+                path.to_str().unwrap(),
+                &[],
+                &[("Polly", "[8.0,9.0)")],
+            )],
+        );
+        let mut graph = DependencyGraph::from_workspace(&workspace, true);
+
+        // when
+        graph.add_restored_dependencies(&workspace).unwrap();
+
+        // then
+        let ids: Vec<&String> = graph.nodes().iter().map(|node| node.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "Api/Api.csproj",
+                "Polly@8.4.1",
+                "Polly.Core@8.4.1",
+                "Microsoft.Bcl.TimeProvider@8.0.1"
+            ]
+        );
+
+        let kinds: Vec<(usize, usize, EdgeKind)> = graph
+            .edges()
+            .iter()
+            .map(|edge| (edge.from(), edge.to(), edge.kind()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (0, 1, EdgeKind::PackageReference),
+                (1, 2, EdgeKind::PackageDependency),
+                (2, 3, EdgeKind::PackageDependency)
+            ]
+        );
+    }
+}
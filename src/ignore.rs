@@ -0,0 +1,257 @@
+//! Reading of the `.dotnetlensignore` file, which excludes directories and project files from the
+//! search and the workspace loading.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::{Component, Path},
+};
+
+use crate::architecture::Pattern;
+
+/// The name of the ignore file read at the root of the searched directory.
+pub const IGNORE_FILE_NAME: &str = ".dotnetlensignore";
+
+/// Represents the rules of an ignore file, written with the gitignore syntax.
+///
+/// Each line is a glob pattern matched against the paths relative to the directory of the file,
+/// with forward slashes:
+/// - blank lines and lines starting with `#` are skipped;
+/// - a pattern starting with `!` re-includes the paths excluded by a previous pattern;
+/// - a pattern ending with `/` only matches directories;
+/// - a pattern containing a `/` elsewhere than at its end is anchored to the directory of the
+///   file, otherwise it matches a file or directory name at any depth;
+/// - `*` and `?` match within a path segment and `**` matches any number of segments.
+///
+/// Like git, the paths inside an excluded directory cannot be re-included. Unlike git, patterns
+/// are matched ignoring case, like the other patterns of dotnet-lens (see [`Pattern`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::ignore::IgnoreFile;
+///
+/// let content = "
+/// # vendored code
+/// samples/
+/// /archive/**/*.csproj
+/// !archive/Current/Current.csproj
+/// ";
+///
+/// let ignore = IgnoreFile::parse(content.as_bytes()).unwrap();
+///
+/// assert!(ignore.is_ignored("src/samples/Demo/Demo.csproj"));
+/// assert!(ignore.is_ignored("archive/Old/Old.csproj"));
+/// assert!(!ignore.is_ignored("archive/Current/Current.csproj"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IgnoreFile {
+    rules: Vec<IgnoreRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IgnoreRule {
+    segments: Vec<Pattern>,
+    negated: bool,
+    directory_only: bool,
+}
+
+impl IgnoreFile {
+    /// Creates a new `IgnoreFile` without any rule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the content of an ignore file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be read.
+    pub fn parse<R>(reader: R) -> Result<Self, io::Error>
+    where
+        R: Read,
+    {
+        let mut ignore = Self::new();
+
+        for line in BufReader::new(reader).lines() {
+            ignore.add(&line?);
+        }
+
+        Ok(ignore)
+    }
+
+    /// Reads the ignore file of a directory ([`IGNORE_FILE_NAME`]). Returns an `IgnoreFile`
+    /// without any rule if the directory does not have one.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file exists but could not be read.
+    pub fn load<P>(directory: P) -> Result<Self, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = directory.as_ref().join(IGNORE_FILE_NAME);
+
+        if !path.is_file() {
+            return Ok(Self::new());
+        }
+
+        Self::parse(File::open(path)?)
+    }
+
+    /// Adds a line of the gitignore syntax. Blank lines and comments are skipped.
+    pub fn add(&mut self, line: &str) {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            return;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(line) => (true, line),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (directory_only, line) = match line.strip_suffix('/') {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+
+        let anchored = line.contains('/');
+        let mut segments: Vec<Pattern> = line
+            .trim_start_matches('/')
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(Pattern::new)
+            .collect();
+
+        if segments.is_empty() {
+            return;
+        }
+
+        if !anchored {
+            segments.insert(0, Pattern::new("**"));
+        }
+
+        self.rules.push(IgnoreRule {
+            segments,
+            negated,
+            directory_only,
+        });
+    }
+
+    /// Returns `true` if the file has no rule.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns `true` if a file, whose path is relative to the directory of the ignore file, is
+    /// excluded by the rules, either directly or because one of its directories is excluded.
+    pub fn is_ignored<P>(&self, path: P) -> bool
+    where
+        P: AsRef<Path>,
+    {
+        self.is_excluded(path.as_ref(), false)
+    }
+
+    /// Returns `true` if a directory, whose path is relative to the directory of the ignore
+    /// file, is excluded by the rules, so the search does not need to enter it.
+    pub fn is_ignored_directory<P>(&self, path: P) -> bool
+    where
+        P: AsRef<Path>,
+    {
+        self.is_excluded(path.as_ref(), true)
+    }
+
+    fn is_excluded(&self, path: &Path, is_directory: bool) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+
+        let segments: Vec<String> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(segment) => Some(segment.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+
+        // the parent directories are checked first, since their content cannot be re-included
+        (1..=segments.len()).any(|length| {
+            let is_directory = is_directory || length < segments.len();
+
+            self.matches(&segments[..length], is_directory)
+        })
+    }
+
+    /// Returns `true` if the last rule matching the path excludes it.
+    fn matches(&self, segments: &[String], is_directory: bool) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                (is_directory || !rule.directory_only) && matches_segments(&rule.segments, segments)
+            })
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+/// Matches the segments of a path against the segments of a pattern, where `**` matches any
+/// number of segments.
+fn matches_segments(patterns: &[Pattern], segments: &[String]) -> bool {
+    match patterns.split_first() {
+        None => segments.is_empty(),
+        Some((pattern, rest)) if pattern.value() == "**" => {
+            (0..=segments.len()).any(|skipped| matches_segments(rest, &segments[skipped..]))
+        }
+        Some((pattern, rest)) => segments.split_first().is_some_and(|(segment, segments)| {
+            pattern.matches(segment) && matches_segments(rest, segments)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::workspace::Workspace;
+
+    use super::IGNORE_FILE_NAME;
+
+    #[test]
+    pub fn skip_ignored_projects() {
+        // given
+        let dir = tempdir().unwrap();
+        let project = r#"<Project Sdk="Microsoft.NET.Sdk" />"#;
+
+        for path in [
+            "src/Api/Api.csproj",
+            "samples/Demo/Demo.csproj",
+            "src/Legacy/Legacy.csproj",
+            "archive/Old/Old.csproj",
+            "archive/Kept/Kept.csproj",
+        ] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, project).unwrap();
+        }
+
+        fs::write(
+            dir.path().join(IGNORE_FILE_NAME),
+            "# vendored and archived code\nsamples/\nLegacy.csproj\n/archive/*/*.csproj\n!archive/Kept/*\n",
+        )
+        .unwrap();
+
+        // when
+        let workspace = Workspace::load(dir.path()).unwrap();
+
+        // then
+        let names: Vec<&str> = workspace
+            .projects()
+            .iter()
+            .map(|project| project.name().as_str())
+            .collect();
+        assert_eq!(names, vec!["Kept", "Api"]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Interning of the strings repeated across the projects of a workspace, such as package names
+//! and versions, so every occurrence shares the same allocation.
+
+use std::{
+    borrow::Borrow,
+    collections::HashSet,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use crate::Project;
+
+/// Shares the equal names and versions read while parsing several projects, so repositories
+/// referencing the same packages from thousands of projects only keep one copy of each.
+///
+/// An interner is created for each load (ex: [`crate::workspace::Workspace::load`]) and passed
+/// to [`crate::parser::parse_in`], and the strings are freed with the last project using them.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::intern::Interner;
+/// use std::sync::Arc;
+///
+/// let strings = Interner::new();
+///
+/// assert!(Arc::ptr_eq(&strings.intern("Serilog"), &strings.intern("Serilog")));
+/// ```
+#[derive(Default)]
+pub struct Interner {
+    strings: Mutex<HashSet<Shared>>,
+}
+
+impl Interner {
+    /// Creates a new `Interner` without any string.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared copy of `value`, allocating it the first time it is interned.
+    pub fn intern(&self, value: &str) -> Arc<String> {
+        let mut strings = self.lock();
+
+        if let Some(Shared(interned)) = strings.get(value) {
+            return Arc::clone(interned);
+        }
+
+        let interned = Arc::new(value.to_string());
+        strings.insert(Shared(Arc::clone(&interned)));
+
+        interned
+    }
+
+    /// Makes the names and versions of the references of `project` share the interned copies,
+    /// for the projects that were not parsed with this interner (ex: read from a
+    /// [`crate::cache::ParseCache`] or by a [`crate::parser::ProjectParser`]).
+    pub(crate) fn share(&self, project: &mut Project) {
+        for reference in &mut project.project_references {
+            reference.name = self.intern(&reference.name);
+        }
+
+        for reference in &mut project.package_references {
+            reference.name = self.intern(&reference.name);
+            reference.version = self.intern(&reference.version);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashSet<Shared>> {
+        self.strings
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl fmt::Debug for Interner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("strings", &self.lock().len())
+            .finish()
+    }
+}
+
+/// An interned string, looked up by its content so interning a `&str` that is already shared
+/// does not allocate.
+#[derive(PartialEq, Eq, Hash)]
+struct Shared(Arc<String>);
+
+impl Borrow<str> for Shared {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Cursor, path::PathBuf, sync::Arc};
+
+    use crate::{
+        condition::Properties, parser::parse_in, vfs::MemoryFileSystem, PackageReference, Project,
+        ProjectLanguage,
+    };
+
+    use super::Interner;
+
+    #[test]
+    pub fn share_equal_strings_while_parsing() {
+        // given
+        let strings = Interner::new();
+        let files = MemoryFileSystem::new();
+        let parse = |path: &str, content: &str| {
+            parse_in(
+                Cursor::new(content),
+                path,
+                &Properties::new(),
+                &files,
+                &strings,
+            )
+            .unwrap()
+        };
+
+        // when
+        let api = parse(
+            "/repo/Api/Api.csproj",
+            r#"<Project><ItemGroup>
+                <PackageReference Include="Microsoft.Extensions.Logging" Version="8.0.0" />
+            </ItemGroup></Project>"#,
+        );
+        let jobs = parse(
+            "/repo/Jobs/Jobs.csproj",
+            r#"<Project><ItemGroup>
+                <PackageReference Include="Microsoft.Extensions.Logging" Version="8.0.0" />
+                <PackageReference Include="Serilog" Version="8.0.0" />
+            </ItemGroup></Project>"#,
+        );
+
+        // then
+        let api = &api.package_references[0];
+        let jobs = &jobs.package_references;
+
+        assert!(Arc::ptr_eq(&api.name, &jobs[0].name));
+        assert!(Arc::ptr_eq(&api.version, &jobs[1].version));
+        assert!(!Arc::ptr_eq(&api.name, &jobs[1].name));
+    }
+
+    #[test]
+    pub fn share_the_strings_of_a_project_read_elsewhere() {
+        // given
+        let strings = Interner::new();
+        let name = strings.intern("Serilog");
+
+        let mut project = Project::empty(
+            "Api".to_string(),
+            ProjectLanguage::CSharp,
+            PathBuf::from("Api.csproj"),
+        );
+        project.add_package_reference(PackageReference::new(
+            "Serilog".to_string(),
+            "3.1.1".to_string(),
+        ));
+
+        // when
+        strings.share(&mut project);
+
+        // then
+        assert!(Arc::ptr_eq(&name, &project.package_references[0].name));
+    }
+}
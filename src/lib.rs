@@ -1,25 +1,151 @@
 //! dotnet-lens is a library for listing dependencies between .NET projects and packages.
 //!
-//! This library provides functionality to parse .NET project files (`.csproj`, `.fsproj`, `.vbproj`)
+//! This library provides functionality to parse .NET project files (`.csproj`, `.fsproj`, `.vbproj`,
+//! as well as Docker Compose `.dcproj`, Service Fabric `.sfproj` and WiX `.wixproj` projects)
 //! and extract information about project dependencies, including project references and package references.
 //!
 //! ## Overview
 //!
 //! The main components of this library include:
 //! - `Project`: A struct representing a .NET project, including its language, name, path, target framework,
-//!   project references, and package references.
+//!   license, project references, and package references.
 //! - `ProjectLanguage`: An enum representing the language of the project based on the file extension.
 //! - `ProjectReference`: A struct representing a reference to another project.
 //! - `PackageReference`: A struct representing a reference to a NuGet package.
+//! - `PackageLicense`: An enum representing the license of a NuGet package.
 //!
 //! ## Modules
 //!
 //! - `parser`: A module for parsing .NET project files and extracting dependency information.
+//! - `parser_registry`: A registry of parsers for custom project types, by file extension.
 //! - `search`: A module for searching .NET project files in a directory.
+//! - `ignore`: A module reading the `.dotnetlensignore` file, which excludes directories and
+//!   project files from the search with the gitignore syntax.
+//! - `version`: A module for parsing and comparing NuGet package versions.
+//! - `nuget`: A client for NuGet V3 feeds (requires the `nuget-client` feature).
+//! - `license`: A module checking the licenses of the dependencies against the project's license.
+//! - `nuget_config`: A module for reading package sources and credentials from `NuGet.config` files.
+//! - `nuspec`: A module for reading NuGet package metadata (`.nuspec`), such as licenses.
+//! - `packages_folder`: A module for reading package metadata and transitive dependencies offline
+//!   from the NuGet global packages folder.
+//! - `conflicts`: A module predicting the package version conflicts of the restore (`NU1605`
+//!   downgrades and `NU1107` incompatible versions) from the NuGet global packages folder.
+//! - `namespaces`: A module mapping packages to the root namespaces they likely provide, to find
+//!   which package provides a namespace and the packages not used by any namespace.
+//! - `osv`: A client for the OSV vulnerability database (requires the `osv` feature).
+//! - `report`: A module aggregating validation results into a summary for CI integrations.
+//! - `central_packages`: A module for reading central package versions (`Directory.Packages.props`).
+//! - `build_file`: A module parsing standalone `.props` and `.targets` files into their properties,
+//!   items and imports, for example to audit the package versions of shared build files.
+//! - `condition`: A module evaluating MSBuild conditions and property references.
+//! - `assembly`: A module reporting the assembly versions stamped by the projects and their
+//!   consistency.
+//! - `framework`: A module parsing target framework monikers and checking their compatibility,
+//!   extensible with custom aliases, compatibility rules and out-of-support frameworks.
+//! - `suggestions`: A module combining outdated, deprecated and vulnerable packages into update
+//!   suggestions.
+//! - `architecture`: A module with rules reporting the project references that break layering
+//!   constraints defined by name or path patterns.
+//! - `validation`: A module with rules that check projects for common problems.
+//! - `workspace`: A module loading every project under a directory.
+//! - `vfs`: A file system abstraction, so projects and the files next to them (ex:
+//!   `Directory.Packages.props`) can be parsed from memory instead of the local disk.
+//! - `filter`: A module filtering the projects and packages of a workspace by name or path, with glob
+//!   patterns, or with regular expressions when the `regex` feature is enabled, and chainable
+//!   filters on its projects by language and target framework.
+//! - `query`: A small expression language filtering projects (ex: `kind == test && tfm startswith
+//!   'net4'`).
+//! - `registry`: A registry sharing parsed projects between loads, so a project reachable from
+//!   several roots is parsed once.
+//! - `cache`: An on-disk cache of parsed projects, so repeated runs only parse the changed files
+//!   (requires the `serde` feature).
+//! - `watch`: A filesystem watcher refreshing a workspace and reporting the added, changed and
+//!   removed projects and references (requires the `watch` feature).
+//! - `archive`: A module loading the projects of `.zip` and `.tar.gz` archives without extracting
+//!   them (requires the `archive` feature).
+//! - `git`: A module loading the projects of a git revision without checking it out (requires the
+//!   `git` feature).
+//! - `wasm`: JavaScript bindings parsing project contents in a browser (requires the `wasm`
+//!   feature).
+//! - `node`: Node.js bindings searching and parsing projects and building dependency graphs
+//!   (requires the `node` feature).
+//! - `solution`: A module reading the projects, solution folders and configuration/platform
+//!   matrix of Visual Studio solutions, and the drift between the solutions and the projects on
+//!   disk.
+//! - `packaging`: A module enumerating the projects that produce NuGet packages or dotnet tools.
+//! - `strictness`: A module reporting the warning suppressions (`NoWarn`), `TreatWarningsAsErrors` and
+//!   `AnalysisLevel` of the projects.
+//! - `nullable`: A module summarizing the adoption of nullable reference types (`Nullable`) by the
+//!   C# projects, grouped by directory.
+//! - `workloads`: A module detecting the .NET SDK workloads (ex: `maui-android`, `wasm-tools`)
+//!   required by the projects, to provision CI images.
+//! - `migration`: A module listing what ties the projects to .NET Framework (legacy project format,
+//!   `packages.config`, `TargetFrameworkVersion`, GAC and COM references), to plan their migration.
+//! - `upgrade`: A module reporting whether the projects and the projects they reference are ready
+//!   for an upgrade to a target framework (ex: `net9.0`), with the projects blocking it.
+//! - `assets`: A module reading the restore graph of the projects (`project.assets.json`) and
+//!   reporting the packages restored differently from their declaration (requires the `serde` feature).
+//! - `compose`: A module reading the services of Docker Compose projects (`.dcproj`), which reference
+//!   the projects they are built from.
+//! - `binlog`: A module reading the project evaluations of MSBuild binary logs and reporting the
+//!   references the build evaluated differently from the static analysis (requires the `binlog`
+//!   feature).
+//! - `msbuild`: A module evaluating the projects the parser cannot resolve with `dotnet msbuild`
+//!   and merging the evaluated values (requires the `msbuild` feature).
+//! - `binary`: A module encoding workspaces and dependency graphs in a compact binary format for
+//!   fast reloads (requires the `binary` feature).
+//! - `runtime_config`: A module reading the runtime frameworks and knobs of the projects from their
+//!   `runtimeconfig.json` files (requires the `serde` feature).
+//! - `source_link`: A module auditing the repository metadata and SourceLink packages of the
+//!   projects.
+//! - `graph`: A module building the dependency graph between projects and packages.
+//! - `metrics`: A module computing architecture metrics, such as the coupling and instability of
+//!   each project or the distance matrix between projects, exportable as CSV.
+//! - `diff`: A module comparing two workspaces, for example to summarize the dependency changes of a
+//!   pull request.
+//! - `export`: A module with output formats for dependency graphs (DOT, Mermaid, JSON, GraphML,
+//!   a colorized terminal tree, SQLite and Parquet).
+//! - `sbom`: A module generating software bills of materials in the CycloneDX and SPDX formats
+//!   (requires the `serde` feature).
+//! - `output`: A module with output formats for validation results (ex: SARIF, GitHub Actions
+//!   annotations) and package listings compatible with `dotnet list package`.
 //!
 //! ## Features
 //! - `serde`: Adds support for serde serialization and deserialization for the Project struct and
-//!    adjacent types
+//!   adjacent types, with a stable shape versioned by `PROJECT_SCHEMA_VERSION`, and enables the
+//!   SARIF output format
+//! - `nuget-client`: Adds an HTTP client that queries NuGet V3 feeds for the latest versions of the
+//!   referenced packages, used to detect outdated and deprecated packages
+//! - `osv`: Adds an HTTP client that queries the OSV database for known vulnerabilities in the
+//!   referenced packages
+//! - `cli`: Builds the `dotnet-lens` command line tool, with the `list`, `graph`, `tree`, `check`,
+//!   `outdated`, `sbom`, `affected`, `workloads`, `upgrade`, `migration`, `strictness`,
+//!   `nullable`, `distances` and `serve` (JSON-RPC daemon) subcommands
+//! - `tui`: Adds an interactive dependency explorer to the command line tool (`dotnet-lens tui`)
+//! - `sqlite`: Adds an exporter writing the projects, packages and references into a SQLite
+//!   database
+//! - `parquet`: Adds an exporter writing the projects, packages and references as Parquet tables or
+//!   Arrow record batches, for analytics engines such as Spark or DuckDB
+//! - `archive`: Adds loading the projects of `.zip`, `.tar` and `.tar.gz` archives
+//! - `git`: Adds loading the projects of any commit or tree of a git repository, for historical
+//!   analysis
+//! - `wasm`: Adds `wasm-bindgen` bindings parsing project contents, for building with the
+//!   `wasm32-unknown-unknown` target
+//! - `node`: Adds napi-rs bindings, for loading the library as a Node.js addon
+//! - `tracing`: Instruments the search, parsing and workspace loading with `tracing` spans and
+//!   events, to diagnose where the time goes on large repositories
+//! - `mmap`: Adds reading project files through memory maps instead of buffered reads, which is
+//!   faster for batch parsing on network filesystems and for large legacy project files (`unsafe`:
+//!   the files must not change while they are read)
+//! - `watch`: Adds a filesystem watcher emitting typed events as the project files of a workspace
+//!   change
+//! - `regex`: Adds regular expressions to the filters on project names, paths and package names
+//! - `binlog`: Adds reading MSBuild binary logs, to cross-check the static analysis against a real
+//!   build
+//! - `msbuild`: Adds an opt-in evaluator running `dotnet msbuild -getProperty -getItem` for the
+//!   projects whose properties cannot be resolved statically
+//! - `binary`: Adds a compact binary encoding of workspaces and dependency graphs, to persist a
+//!   loaded model and reload it without parsing the projects again
 //!
 //! ## Examples
 //!
@@ -44,26 +170,137 @@
 use std::{
     ffi::OsStr,
     fs::File,
-    os::unix::ffi::OsStrExt,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
+use assembly::AssemblyMetadata;
+use condition::{ConditionalProperty, Properties};
+use migration::MigrationMetadata;
+use packaging::PackagingMetadata;
 use parser::ParseError;
+use source_link::RepositoryMetadata;
+use strictness::StrictnessMetadata;
+use workloads::WorkloadMetadata;
 
+pub mod architecture;
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod assembly;
+#[cfg(feature = "serde")]
+pub mod assets;
+#[cfg(feature = "binary")]
+pub mod binary;
+#[cfg(feature = "binlog")]
+pub mod binlog;
+pub mod build_file;
+#[cfg(feature = "serde")]
+pub mod cache;
+pub mod central_packages;
+pub mod compose;
+pub mod condition;
+pub mod conflicts;
+pub mod diff;
+pub mod export;
+pub mod filter;
+pub mod framework;
+#[cfg(feature = "git")]
+pub mod git;
+pub mod graph;
+pub mod ignore;
+pub mod intern;
+pub mod license;
+pub mod metrics;
+pub mod migration;
+#[cfg(feature = "msbuild")]
+pub mod msbuild;
+pub mod namespaces;
+#[cfg(feature = "node")]
+pub mod node;
+#[cfg(feature = "nuget-client")]
+pub mod nuget;
+pub mod nuget_config;
+pub mod nullable;
+pub mod nuspec;
+#[cfg(feature = "osv")]
+pub mod osv;
+pub mod output;
+pub mod packages_folder;
+pub mod packaging;
 pub mod parser;
+pub mod parser_registry;
+pub mod query;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "serde")]
+pub mod runtime_config;
+#[cfg(feature = "serde")]
+pub mod sbom;
+#[cfg(feature = "serde")]
+mod schema;
 pub mod search;
+pub mod solution;
+pub mod source_link;
+pub mod strictness;
+pub mod suggestions;
+pub mod upgrade;
+pub mod validation;
+pub mod version;
+pub mod vfs;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "watch")]
+pub mod watch;
+pub mod workloads;
+pub mod workspace;
 
-/// List of valid extensions: "csproj", "fsproj", "vbproj".
-pub const VALID_EXTENSIONS: [&str; 3] = ["csproj", "fsproj", "vbproj"];
+/// List of valid extensions: "csproj", "fsproj", "vbproj", "dcproj", "sfproj", "wixproj".
+pub const VALID_EXTENSIONS: [&str; 6] =
+    ["csproj", "fsproj", "vbproj", "dcproj", "sfproj", "wixproj"];
+
+/// The version of the serialized shape of [`Project`], written in its `schema_version` field.
+///
+/// The version only changes when a field is removed or changes meaning. New optional fields are
+/// added without changing it, and documents without a `schema_version` are read as version 1.
+pub const PROJECT_SCHEMA_VERSION: u32 = 1;
 
 /// Represents a .NET project.
+///
+/// With the `serde` feature, a project is serialized with a stable shape versioned by
+/// [`PROJECT_SCHEMA_VERSION`], with the fields:
+/// - `schema_version`, `name`, `language`, `path`, `target_framework`, `license_expression`,
+///   `project_references` and `package_references`, always written;
+/// - `target_framework_candidates`, when the target framework is ambiguous;
+/// - `target_frameworks`, when the project is multi-targeted;
+/// - `repository`, `assembly`, `packaging`, `strictness` and `workloads`, when the project sets
+///   repository, assembly, packaging, build strictness or workload properties;
+/// - `migration`, when the project uses SDKs or .NET Framework specific parts;
+/// - `tags`, when the project is tagged;
+/// - `conditional_properties`, when the project sets properties under conditions.
+///
+/// Deserialization accepts documents where the optional fields are missing.
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "schema::ProjectDocument", try_from = "schema::ProjectDocument")
+)]
 pub struct Project {
     name: String,
     language: ProjectLanguage,
     path: PathBuf,
     target_framework: Option<String>,
+    license_expression: Option<String>,
+    target_framework_candidates: Vec<String>,
+    target_frameworks: Vec<String>,
+    repository: RepositoryMetadata,
+    assembly: AssemblyMetadata,
+    packaging: PackagingMetadata,
+    strictness: StrictnessMetadata,
+    workloads: WorkloadMetadata,
+    migration: MigrationMetadata,
+    tags: Vec<String>,
+    conditional_properties: Vec<ConditionalProperty>,
     project_references: Vec<ProjectReference>,
     package_references: Vec<PackageReference>,
 }
@@ -113,6 +350,100 @@ impl Project {
         parser::parse(file_reader, path)
     }
 
+    /// Creates a new `Project` instance by parsing a .NET project file like [`Project::new`],
+    /// evaluating its conditions with the given `properties` (see
+    /// [`parser::parse_with_properties`]).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file could not be opened.
+    /// - The file could not be parsed as a .NET project file.
+    pub fn with_properties<P>(path: P, properties: &Properties) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        let file_reader = File::open(path.as_ref())?;
+
+        parser::parse_with_properties(file_reader, path, properties)
+    }
+
+    /// Creates a new `Project` instance by parsing a .NET project file mapped into memory,
+    /// instead of reading it through a buffer like [`Project::new`].
+    ///
+    /// Mapping is faster on network filesystems and for large project files.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this process or another, until the
+    /// function returns. Reading the map of a truncated file raises `SIGBUS` on most platforms,
+    /// and a file modified while it is parsed is undefined behavior. Use [`Project::new`] when
+    /// other processes can write to the project files.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file could not be opened or mapped.
+    /// - The file could not be parsed as a .NET project file.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map<P>(path: P) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        // SAFETY: the caller upholds the contract of `map`
+        unsafe { Self::map_with(path.as_ref(), &intern::Interner::new()) }
+    }
+
+    /// Maps and parses a project file like [`Project::map`], sharing the names and versions
+    /// with the other projects parsed with `strings`.
+    ///
+    /// # Safety
+    ///
+    /// See [`Project::map`].
+    #[cfg(feature = "mmap")]
+    pub(crate) unsafe fn map_with(
+        path: &Path,
+        strings: &intern::Interner,
+    ) -> Result<Self, ParseError> {
+        let file = File::open(path)?;
+
+        // SAFETY: the map is read only and dropped as soon as the project is parsed, and the
+        // caller guarantees the file is not modified in the meantime
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+
+        parser::parse_in(
+            &map[..],
+            path,
+            &Properties::new(),
+            &vfs::OsFileSystem,
+            strings,
+        )
+    }
+
+    /// Creates a project without any property or reference, for the parsers of other project
+    /// formats (see [`parser::ProjectParser`]).
+    pub fn empty(name: String, language: ProjectLanguage, path: PathBuf) -> Self {
+        Self {
+            name,
+            language,
+            path,
+            target_framework: None,
+            license_expression: None,
+            target_framework_candidates: vec![],
+            target_frameworks: vec![],
+            repository: RepositoryMetadata::new(),
+            assembly: AssemblyMetadata::new(),
+            packaging: PackagingMetadata::new(),
+            strictness: StrictnessMetadata::new(),
+            workloads: WorkloadMetadata::new(),
+            migration: MigrationMetadata::new(),
+            tags: vec![],
+            conditional_properties: vec![],
+            project_references: vec![],
+            package_references: vec![],
+        }
+    }
+
     /// Returns the name of the project based on the file name of the provided path.
     ///
     /// # Arguments
@@ -148,10 +479,111 @@ impl Project {
     }
 
     /// Returns the target framework of the project, if any.
+    ///
+    /// The property groups are evaluated in order, so a later `TargetFramework` overrides an
+    /// earlier one. When it is set under conditions that could not be evaluated (ex: they depend
+    /// on the `Configuration`), this is the value set without such conditions, and every
+    /// possible value is returned by [`Project::target_framework_candidates`]. For multi-targeted
+    /// projects, this is the first framework of `TargetFrameworks` (see
+    /// [`Project::target_frameworks`]).
     pub fn target_framework(&self) -> Option<&String> {
         self.target_framework.as_ref()
     }
 
+    /// Returns every value `TargetFramework` may have when it could not be evaluated
+    /// unambiguously, in the order they are set, or an empty list otherwise.
+    pub fn target_framework_candidates(&self) -> &Vec<String> {
+        &self.target_framework_candidates
+    }
+
+    /// Returns every framework the project may be built for: the
+    /// [`Project::target_framework_candidates`] if `TargetFramework` is ambiguous, otherwise the
+    /// frameworks of `TargetFrameworks` for multi-targeted projects, otherwise its target
+    /// framework, if any.
+    ///
+    /// Like with MSBuild, `TargetFrameworks` is ignored when `TargetFramework` is set.
+    pub fn target_frameworks(&self) -> Vec<&String> {
+        if !self.target_framework_candidates.is_empty() {
+            self.target_framework_candidates.iter().collect()
+        } else if !self.target_frameworks.is_empty() {
+            self.target_frameworks.iter().collect()
+        } else {
+            self.target_framework.iter().collect()
+        }
+    }
+
+    /// Returns the SPDX license expression of the project (`PackageLicenseExpression`), if any.
+    pub fn license_expression(&self) -> Option<&String> {
+        self.license_expression.as_ref()
+    }
+
+    /// Returns the repository properties of the project (`RepositoryUrl`, `RepositoryType` and
+    /// `PublishRepositoryUrl`).
+    pub fn repository(&self) -> &RepositoryMetadata {
+        &self.repository
+    }
+
+    /// Returns the assembly properties of the project (`AssemblyName`, `AssemblyVersion`,
+    /// `FileVersion` and `InformationalVersion`).
+    pub fn assembly(&self) -> &AssemblyMetadata {
+        &self.assembly
+    }
+
+    /// Returns the packaging properties of the project (`IsPackable`, `GeneratePackageOnBuild` and
+    /// `PackAsTool`).
+    pub fn packaging(&self) -> &PackagingMetadata {
+        &self.packaging
+    }
+
+    /// Returns the build strictness properties of the project (`TreatWarningsAsErrors`, `NoWarn`,
+    /// `AnalysisLevel` and `Nullable`).
+    pub fn strictness(&self) -> &StrictnessMetadata {
+        &self.strictness
+    }
+
+    /// Returns the properties of the project requiring SDK workloads (`UseMaui`,
+    /// `RunAOTCompilation`, `WasmBuildNative`, `IsAspireHost` and the runtime identifiers), see
+    /// [`workloads::project_workloads`].
+    pub fn workloads(&self) -> &WorkloadMetadata {
+        &self.workloads
+    }
+
+    /// Returns the SDKs of the project file and its parts tied to .NET Framework
+    /// (`packages.config`, `TargetFrameworkVersion`, GAC and COM references), see
+    /// [`migration::project_inventory`].
+    pub fn migration(&self) -> &MigrationMetadata {
+        &self.migration
+    }
+
+    /// Returns the name of the assembly built by the project: its `AssemblyName`, or else the
+    /// name of the project.
+    pub fn assembly_name(&self) -> &str {
+        self.assembly.name().unwrap_or(&self.name)
+    }
+
+    /// Returns the tags of the project, read from the `ProjectTags` property (ex:
+    /// `<ProjectTags>payments;backend</ProjectTags>`), in the order they are written and
+    /// without duplicates.
+    ///
+    /// Another property can be read instead by setting its name in the `DotnetLensTagsProperty`
+    /// property (ex: `-p DotnetLensTagsProperty=Owners`, see
+    /// [`parser::parse_with_properties`]).
+    pub fn tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    /// Returns `true` if the project has the tag, ignoring case.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Returns the properties the project file sets under a condition, with their values and
+    /// conditions as written, in the order they are written. Conditions are not evaluated, so the
+    /// values may not apply.
+    pub fn conditional_properties(&self) -> &Vec<ConditionalProperty> {
+        &self.conditional_properties
+    }
+
     /// Returns a reference to the list of project references.
     pub fn project_references(&self) -> &Vec<ProjectReference> {
         &self.project_references
@@ -162,11 +594,38 @@ impl Project {
         self.project_references.push(value);
     }
 
+    /// Returns the project references that apply when building for `target_framework`: the
+    /// unconditional references and the references conditioned on that target framework.
+    pub fn project_references_for<'a>(
+        &'a self,
+        target_framework: &'a str,
+    ) -> impl Iterator<Item = &'a ProjectReference> + 'a {
+        self.project_references
+            .iter()
+            .filter(move |reference| reference.applies_to(target_framework))
+    }
+
     /// Returns a reference to the list of package references.
     pub fn package_references(&self) -> &Vec<PackageReference> {
         &self.package_references
     }
 
+    /// Returns the package references that apply when building for `target_framework`: the
+    /// unconditional references and the references conditioned on that target framework.
+    pub fn package_references_for<'a>(
+        &'a self,
+        target_framework: &'a str,
+    ) -> impl Iterator<Item = &'a PackageReference> + 'a {
+        self.package_references
+            .iter()
+            .filter(move |reference| reference.applies_to(target_framework))
+    }
+
+    /// Returns a mutable reference to the list of package references.
+    pub fn package_references_mut(&mut self) -> &mut Vec<PackageReference> {
+        &mut self.package_references
+    }
+
     /// Adds a new package reference to the list of package references.
     pub fn add_package_reference(&mut self, value: PackageReference) {
         self.package_references.push(value);
@@ -174,18 +633,31 @@ impl Project {
 }
 
 /// Represents the language of a .NET project based on the file extension.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProjectLanguage {
+    #[cfg_attr(feature = "serde", serde(rename = "CSharp"))]
     CSharp,
+    #[cfg_attr(feature = "serde", serde(rename = "FSharp"))]
     FSharp,
+    #[cfg_attr(feature = "serde", serde(rename = "VB"))]
     VB,
+    /// A Docker Compose project (`.dcproj`), whose services are built from other projects.
+    #[cfg_attr(feature = "serde", serde(rename = "DockerCompose"))]
+    DockerCompose,
+    /// A Service Fabric application (`.sfproj`), referencing the projects of its services.
+    #[cfg_attr(feature = "serde", serde(rename = "ServiceFabric"))]
+    ServiceFabric,
+    /// A WiX installer project (`.wixproj`), packaging the output of the projects it references.
+    #[cfg_attr(feature = "serde", serde(rename = "Wix"))]
+    Wix,
 }
 
 impl ProjectLanguage {
     /// Determines the project language from the file extension.
     ///
-    /// The extension must be provided without a "." (ex: "csproj", "fsproj", "vbproj").
+    /// The extension must be provided without a "." (ex: "csproj", "fsproj", "vbproj", "dcproj",
+    /// "sfproj", "wixproj"), and is compared ignoring case.
     ///
     /// # Arguments
     ///
@@ -195,21 +667,93 @@ impl ProjectLanguage {
     ///
     /// An `Option<ProjectLanguage>` containing the project language if it could be determined, otherwise `None`.
     pub fn from_extension(extension: &OsStr) -> Option<Self> {
-        match extension.as_bytes() {
-            b"csproj" => Some(Self::CSharp),
-            b"fsproj" => Some(Self::FSharp),
-            b"vbproj" => Some(Self::VB),
-            _ => None,
+        let extension = extension.to_str()?;
+
+        [
+            Self::CSharp,
+            Self::FSharp,
+            Self::VB,
+            Self::DockerCompose,
+            Self::ServiceFabric,
+            Self::Wix,
+        ]
+        .into_iter()
+        .find(|language| language.extension().eq_ignore_ascii_case(extension))
+    }
+
+    /// Returns the extension of the project files of the language, without a "." (ex: "csproj").
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::CSharp => "csproj",
+            Self::FSharp => "fsproj",
+            Self::VB => "vbproj",
+            Self::DockerCompose => "dcproj",
+            Self::ServiceFabric => "sfproj",
+            Self::Wix => "wixproj",
         }
     }
+
+    /// Returns the label of the language used by the exports and queries (ex: "csharp",
+    /// "docker-compose").
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::CSharp => "csharp",
+            Self::FSharp => "fsharp",
+            Self::VB => "vb",
+            Self::DockerCompose => "docker-compose",
+            Self::ServiceFabric => "service-fabric",
+            Self::Wix => "wix",
+        }
+    }
+
+    /// Returns `true` for the container, orchestration and installer projects (`.dcproj`,
+    /// `.sfproj`, `.wixproj`), which do not compile code but express deployment-level
+    /// dependencies on other projects.
+    pub fn is_deployment(&self) -> bool {
+        matches!(self, Self::DockerCompose | Self::ServiceFabric | Self::Wix)
+    }
 }
 
 /// Represents a reference to another .NET project.
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProjectReference {
-    name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "name"))]
+    name: Arc<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "path"))]
     path: PathBuf,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "target_framework",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    target_framework: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "condition", default, skip_serializing_if = "Option::is_none")
+    )]
+    condition: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "output_item_type",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    output_item_type: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "reference_output_assembly",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    reference_output_assembly: Option<String>,
 }
 
 impl ProjectReference {
@@ -231,7 +775,29 @@ impl ProjectReference {
     /// println!("Project Path: {:?}", project_ref.path());
     /// ```
     pub fn new(name: String, path: PathBuf) -> Self {
-        Self { name, path }
+        Self::from_shared(Arc::new(name), path)
+    }
+
+    /// Creates a new `ProjectReference` with a name shared with other references (see
+    /// [`intern::Interner`]).
+    pub(crate) fn from_shared(name: Arc<String>, path: PathBuf) -> Self {
+        Self {
+            name,
+            path,
+            target_framework: None,
+            condition: None,
+            output_item_type: None,
+            reference_output_assembly: None,
+        }
+    }
+
+    /// Creates a new `ProjectReference` named after the file name of `path`.
+    ///
+    /// Returns `None` if the path does not have a file name.
+    pub(crate) fn from_path(path: PathBuf, strings: &intern::Interner) -> Option<Self> {
+        let name = strings.intern(path.file_stem()?.to_str()?);
+
+        Some(Self::from_shared(name, path))
     }
 
     /// Returns the name of the referenced project.
@@ -263,14 +829,113 @@ impl ProjectReference {
     pub fn path(&self) -> &Path {
         &self.path
     }
+
+    /// Returns the target framework the reference is conditioned on, if it is declared in an
+    /// `ItemGroup` with a condition such as `'$(TargetFramework)' == 'net48'`.
+    pub fn target_framework(&self) -> Option<&String> {
+        self.target_framework.as_ref()
+    }
+
+    /// Sets the target framework the reference is conditioned on.
+    pub fn set_target_framework(&mut self, target_framework: Option<String>) {
+        self.target_framework = target_framework;
+    }
+
+    /// Returns the condition of the reference, as written, combined with the one of its
+    /// `ItemGroup` as `(group) and (reference)`, if any.
+    ///
+    /// Unlike [`ProjectReference::target_framework`], the condition is kept even when it does
+    /// not compare the target framework (ex: `'$(Configuration)' == 'Debug'`).
+    pub fn condition(&self) -> Option<&String> {
+        self.condition.as_ref()
+    }
+
+    /// Sets the condition of the reference.
+    pub fn set_condition(&mut self, condition: Option<String>) {
+        self.condition = condition;
+    }
+
+    /// Returns the item type the output of the referenced project is added as
+    /// (`OutputItemType`, ex: `Analyzer` for a source generator), if it is declared.
+    pub fn output_item_type(&self) -> Option<&String> {
+        self.output_item_type.as_ref()
+    }
+
+    /// Sets the item type the output of the referenced project is added as.
+    pub fn set_output_item_type(&mut self, output_item_type: Option<String>) {
+        self.output_item_type = output_item_type;
+    }
+
+    /// Returns whether the project compiles against the assembly of the referenced project
+    /// (`ReferenceOutputAssembly`, ex: `false`), if it is declared. It does by default.
+    pub fn reference_output_assembly(&self) -> Option<&String> {
+        self.reference_output_assembly.as_ref()
+    }
+
+    /// Sets whether the project compiles against the assembly of the referenced project.
+    pub fn set_reference_output_assembly(&mut self, reference_output_assembly: Option<String>) {
+        self.reference_output_assembly = reference_output_assembly;
+    }
+
+    /// Returns `true` if the reference is unconditional or conditioned on `target_framework`.
+    pub fn applies_to(&self, target_framework: &str) -> bool {
+        self.target_framework
+            .as_ref()
+            .is_none_or(|condition| condition.eq_ignore_ascii_case(target_framework))
+    }
 }
 
 /// Represents a reference to a NuGet package.
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PackageReference {
-    name: String,
-    version: String,
+    #[cfg_attr(feature = "serde", serde(rename = "name"))]
+    name: Arc<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "version"))]
+    version: Arc<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "license", default))]
+    license: Option<PackageLicense>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "target_framework",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    target_framework: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "private_assets",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    private_assets: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "include_assets",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    include_assets: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "exclude_assets",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    exclude_assets: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "condition", default, skip_serializing_if = "Option::is_none")
+    )]
+    condition: Option<String>,
 }
 
 impl PackageReference {
@@ -291,7 +956,22 @@ impl PackageReference {
     /// println!("Package Version: {}", package_ref.version());
     /// ```
     pub fn new(name: String, version: String) -> Self {
-        Self { name, version }
+        Self::from_shared(Arc::new(name), Arc::new(version))
+    }
+
+    /// Creates a new `PackageReference` with a name and version shared with other references
+    /// (see [`intern::Interner`]).
+    pub(crate) fn from_shared(name: Arc<String>, version: Arc<String>) -> Self {
+        Self {
+            name,
+            version,
+            license: None,
+            target_framework: None,
+            private_assets: None,
+            include_assets: None,
+            exclude_assets: None,
+            condition: None,
+        }
     }
 
     /// Returns the name of the package.
@@ -308,7 +988,9 @@ impl PackageReference {
         &self.name
     }
 
-    /// Returns the version of the package.
+    /// Returns the version of the package: the `VersionOverride` or `Version` of the reference, or
+    /// the version declared in `Directory.Packages.props` with Central Package Management. Empty
+    /// when no version is declared.
     ///
     /// # Examples
     ///
@@ -321,4 +1003,97 @@ impl PackageReference {
     pub fn version(&self) -> &String {
         &self.version
     }
+
+    /// Returns the license of the package, if it was retrieved from the package metadata.
+    ///
+    /// The license is not part of the project file, so it is only available after it is attached
+    /// with `nuspec::attach_licenses` (or `NugetClient::attach_licenses`).
+    pub fn license(&self) -> Option<&PackageLicense> {
+        self.license.as_ref()
+    }
+
+    /// Sets the license of the package.
+    pub fn set_license(&mut self, license: Option<PackageLicense>) {
+        self.license = license;
+    }
+
+    /// Returns the target framework the reference is conditioned on, if it is declared in an
+    /// `ItemGroup` with a condition such as `'$(TargetFramework)' == 'net48'`.
+    pub fn target_framework(&self) -> Option<&String> {
+        self.target_framework.as_ref()
+    }
+
+    /// Sets the target framework the reference is conditioned on.
+    pub fn set_target_framework(&mut self, target_framework: Option<String>) {
+        self.target_framework = target_framework;
+    }
+
+    /// Returns `true` if the reference is unconditional or conditioned on `target_framework`.
+    pub fn applies_to(&self, target_framework: &str) -> bool {
+        self.target_framework
+            .as_ref()
+            .is_none_or(|condition| condition.eq_ignore_ascii_case(target_framework))
+    }
+
+    /// Returns the assets of the package that do not flow to the projects referencing this
+    /// project (`PrivateAssets`, ex: `all`), if they are declared.
+    pub fn private_assets(&self) -> Option<&String> {
+        self.private_assets.as_ref()
+    }
+
+    /// Sets the assets of the package that do not flow to the referencing projects.
+    pub fn set_private_assets(&mut self, private_assets: Option<String>) {
+        self.private_assets = private_assets;
+    }
+
+    /// Returns the assets of the package consumed by the project (`IncludeAssets`, ex:
+    /// `runtime; build; analyzers`), if they are declared. NuGet consumes every asset by default.
+    pub fn include_assets(&self) -> Option<&String> {
+        self.include_assets.as_ref()
+    }
+
+    /// Sets the assets of the package consumed by the project.
+    pub fn set_include_assets(&mut self, include_assets: Option<String>) {
+        self.include_assets = include_assets;
+    }
+
+    /// Returns the assets of the package not consumed by the project (`ExcludeAssets`, ex:
+    /// `runtime`), if they are declared.
+    pub fn exclude_assets(&self) -> Option<&String> {
+        self.exclude_assets.as_ref()
+    }
+
+    /// Sets the assets of the package not consumed by the project.
+    pub fn set_exclude_assets(&mut self, exclude_assets: Option<String>) {
+        self.exclude_assets = exclude_assets;
+    }
+
+    /// Returns the condition of the reference, as written, combined with the one of its
+    /// `ItemGroup` as `(group) and (reference)`, if any.
+    ///
+    /// Unlike [`PackageReference::target_framework`], the condition is kept even when it does
+    /// not compare the target framework (ex: `'$(Configuration)' == 'Debug'`).
+    pub fn condition(&self) -> Option<&String> {
+        self.condition.as_ref()
+    }
+
+    /// Sets the condition of the reference.
+    pub fn set_condition(&mut self, condition: Option<String>) {
+        self.condition = condition;
+    }
+}
+
+/// Represents the license of a NuGet package, as declared in its nuspec.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PackageLicense {
+    /// An SPDX license expression (ex: "MIT", "Apache-2.0 OR MIT").
+    #[cfg_attr(feature = "serde", serde(rename = "Expression"))]
+    Expression(String),
+    /// The path of a license file inside the package.
+    #[cfg_attr(feature = "serde", serde(rename = "File"))]
+    File(String),
+    /// The URL of the license (deprecated by NuGet in favor of license expressions).
+    #[cfg_attr(feature = "serde", serde(rename = "Url"))]
+    Url(String),
 }
@@ -0,0 +1,379 @@
+//! License compatibility analysis between a project and the packages it depends on.
+
+use crate::{
+    validation::{Severity, Violation},
+    PackageLicense, PackageReference, Project,
+};
+
+/// The rule id used for violations about license compatibility.
+pub const LICENSE_COMPATIBILITY_RULE_ID: &str = "DL0103";
+
+/// Represents the family of a license, ordered from the least to the most restrictive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LicenseCategory {
+    /// Licenses such as MIT, Apache-2.0 and BSD.
+    Permissive,
+    /// File or library level copyleft licenses such as LGPL, MPL and EPL.
+    WeakCopyleft,
+    /// Copyleft licenses such as GPL.
+    StrongCopyleft,
+    /// Copyleft licenses that also cover network use, such as AGPL.
+    NetworkCopyleft,
+    /// The license is not a known SPDX license, or it is only available as a file or URL.
+    Unknown,
+}
+
+/// Represents whether a dependency's license can be used by a project.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Compatibility {
+    Compatible,
+    /// The license is usually compatible, but comes with obligations that should be reviewed.
+    ReviewRequired,
+    Incompatible,
+    /// The license of the dependency could not be determined.
+    Unknown,
+}
+
+/// Represents the assessment of a dependency's license against the license of a project.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LicenseAssessment {
+    project: std::path::PathBuf,
+    package: String,
+    version: String,
+    license: Option<PackageLicense>,
+    category: LicenseCategory,
+    compatibility: Compatibility,
+}
+
+impl LicenseCategory {
+    /// Returns the category of an SPDX license id (ex: "MIT", "GPL-3.0-only").
+    pub fn from_spdx_id(id: &str) -> Self {
+        let id = id.trim().to_uppercase();
+        let id = id.trim_end_matches('+');
+
+        const PERMISSIVE: [&str; 16] = [
+            "MIT",
+            "MIT-0",
+            "APACHE-2.0",
+            "BSD-2-CLAUSE",
+            "BSD-3-CLAUSE",
+            "0BSD",
+            "ISC",
+            "ZLIB",
+            "UNLICENSE",
+            "MS-PL",
+            "BSL-1.0",
+            "POSTGRESQL",
+            "CC0-1.0",
+            "WTFPL",
+            "X11",
+            "NCSA",
+        ];
+
+        if PERMISSIVE
+            .iter()
+            .any(|license| license.eq_ignore_ascii_case(id))
+        {
+            Self::Permissive
+        } else if id.starts_with("AGPL-") {
+            Self::NetworkCopyleft
+        } else if id.starts_with("LGPL-")
+            || id.starts_with("MPL-")
+            || id.starts_with("EPL-")
+            || id.starts_with("CDDL-")
+            || id == "MS-RL"
+        {
+            Self::WeakCopyleft
+        } else if id.starts_with("GPL-") {
+            Self::StrongCopyleft
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Returns the category of an SPDX license expression (ex: "MIT OR GPL-3.0-only").
+    ///
+    /// For `OR` expressions the least restrictive option is used, since the licensee can choose
+    /// it. For `AND` expressions the most restrictive license is used. License exceptions
+    /// (`WITH`) are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::license::LicenseCategory;
+    ///
+    /// assert_eq!(LicenseCategory::from_expression("MIT OR GPL-3.0-only"), LicenseCategory::Permissive);
+    /// assert_eq!(LicenseCategory::from_expression("MIT AND LGPL-2.1-only"), LicenseCategory::WeakCopyleft);
+    /// ```
+    pub fn from_expression(expression: &str) -> Self {
+        let cleaned = expression.replace(['(', ')'], " ");
+
+        let options: Vec<Self> = cleaned
+            .split(" OR ")
+            .map(|option| {
+                let categories: Vec<Self> = option
+                    .split(" AND ")
+                    .map(|license| {
+                        let license = license.split(" WITH ").next().unwrap_or_default();
+
+                        Self::from_spdx_id(license)
+                    })
+                    .collect();
+
+                if categories.contains(&Self::Unknown) {
+                    Self::Unknown
+                } else {
+                    categories.into_iter().max().unwrap_or(Self::Unknown)
+                }
+            })
+            .collect();
+
+        options
+            .iter()
+            .copied()
+            .filter(|category| *category != Self::Unknown)
+            .min()
+            .unwrap_or(Self::Unknown)
+    }
+
+    /// Returns the category of a package license. Licenses only available as a file or URL are
+    /// `LicenseCategory::Unknown`.
+    pub fn from_package_license(license: &PackageLicense) -> Self {
+        match license {
+            PackageLicense::Expression(expression) => Self::from_expression(expression),
+            PackageLicense::File(_) | PackageLicense::Url(_) => Self::Unknown,
+        }
+    }
+}
+
+impl LicenseAssessment {
+    /// Returns the path of the project.
+    pub fn project(&self) -> &std::path::PathBuf {
+        &self.project
+    }
+
+    /// Returns the name of the package.
+    pub fn package(&self) -> &String {
+        &self.package
+    }
+
+    /// Returns the version of the package.
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+
+    /// Returns the license of the package, if known.
+    pub fn license(&self) -> Option<&PackageLicense> {
+        self.license.as_ref()
+    }
+
+    /// Returns the category of the package license.
+    pub fn category(&self) -> LicenseCategory {
+        self.category
+    }
+
+    /// Returns whether the package license can be used by the project.
+    pub fn compatibility(&self) -> Compatibility {
+        self.compatibility
+    }
+
+    /// Converts the assessment into a `Violation`, or `None` if the license is compatible.
+    ///
+    /// Incompatible licenses are errors, licenses that require review are warnings and unknown
+    /// licenses are notes.
+    pub fn to_violation(&self) -> Option<Violation> {
+        let (severity, problem) = match self.compatibility {
+            Compatibility::Compatible => return None,
+            Compatibility::ReviewRequired => (Severity::Warning, "requires a license review"),
+            Compatibility::Incompatible => (Severity::Error, "has an incompatible license"),
+            Compatibility::Unknown => (Severity::Note, "has a license that could not be verified"),
+        };
+
+        let license = match &self.license {
+            Some(PackageLicense::Expression(expression)) => expression.as_str(),
+            Some(PackageLicense::File(file)) => file.as_str(),
+            Some(PackageLicense::Url(url)) => url.as_str(),
+            None => "unknown",
+        };
+
+        Some(Violation::new(
+            LICENSE_COMPATIBILITY_RULE_ID.to_string(),
+            severity,
+            format!(
+                "package '{}' {} {} ({})",
+                self.package, self.version, problem, license
+            ),
+            self.project.clone(),
+        ))
+    }
+}
+
+/// Assesses the license of every package against the license of the project.
+///
+/// The packages should be the transitive closure of the project's dependencies, with their
+/// licenses attached (see `GlobalPackagesFolder::license_closure`). A project without a
+/// `PackageLicenseExpression` is treated as proprietary, so any copyleft dependency requires
+/// attention.
+///
+/// The compatibility rules are a simplification meant to flag risks, not legal advice:
+/// - strong copyleft dependencies are only compatible with strong copyleft projects;
+/// - network copyleft dependencies are only compatible with network copyleft projects;
+/// - weak copyleft dependencies require review unless the project is copyleft itself.
+pub fn assess(project: &Project, packages: &[PackageReference]) -> Vec<LicenseAssessment> {
+    let project_category = project
+        .license_expression()
+        .map(|expression| LicenseCategory::from_expression(expression));
+
+    packages
+        .iter()
+        .map(|package| {
+            let category = package.license().map_or(
+                LicenseCategory::Unknown,
+                LicenseCategory::from_package_license,
+            );
+
+            LicenseAssessment {
+                project: project.path().clone(),
+                package: package.name().to_string(),
+                version: package.version().to_string(),
+                license: package.license().cloned(),
+                category,
+                compatibility: compatibility(project_category, category),
+            }
+        })
+        .collect()
+}
+
+/// Returns the violations for every package whose license is not compatible with the project.
+///
+/// See [`assess`] for more details.
+pub fn check_project(project: &Project, packages: &[PackageReference]) -> Vec<Violation> {
+    assess(project, packages)
+        .iter()
+        .filter_map(LicenseAssessment::to_violation)
+        .collect()
+}
+
+fn compatibility(project: Option<LicenseCategory>, dependency: LicenseCategory) -> Compatibility {
+    use LicenseCategory::*;
+
+    match (project, dependency) {
+        (_, Unknown) => Compatibility::Unknown,
+        (_, Permissive) => Compatibility::Compatible,
+        (Some(WeakCopyleft | StrongCopyleft | NetworkCopyleft), WeakCopyleft) => {
+            Compatibility::Compatible
+        }
+        (_, WeakCopyleft) => Compatibility::ReviewRequired,
+        (Some(StrongCopyleft | NetworkCopyleft), StrongCopyleft) => Compatibility::Compatible,
+        (Some(NetworkCopyleft), NetworkCopyleft) => Compatibility::Compatible,
+        (Some(Unknown), _) => Compatibility::ReviewRequired,
+        _ => Compatibility::Incompatible,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::ProjectLanguage;
+
+    use super::*;
+
+    fn package(name: &str, license: Option<PackageLicense>) -> PackageReference {
+        let mut package = PackageReference::new(name.to_string(), "1.0.0".to_string());
+        package.set_license(license);
+
+        package
+    }
+
+    fn project(license_expression: Option<&str>) -> Project {
+        let mut project = Project::empty(
+            "Api".to_string(),
+            ProjectLanguage::CSharp,
+            PathBuf::from("Api.csproj"),
+        );
+        project.license_expression = license_expression.map(|l| l.to_string());
+
+        project
+    }
+
+    #[test]
+    pub fn categorize_expressions() {
+        assert_eq!(
+            LicenseCategory::from_expression("Apache-2.0"),
+            LicenseCategory::Permissive
+        );
+        assert_eq!(
+            LicenseCategory::from_expression("GPL-2.0-or-later WITH Classpath-exception-2.0"),
+            LicenseCategory::StrongCopyleft
+        );
+        assert_eq!(
+            LicenseCategory::from_expression("(AGPL-3.0-only OR LGPL-3.0-only)"),
+            LicenseCategory::WeakCopyleft
+        );
+        assert_eq!(
+            LicenseCategory::from_expression("MIT AND Custom-License"),
+            LicenseCategory::Unknown
+        );
+    }
+
+    #[test]
+    pub fn flag_gpl_dependency_in_mit_project() {
+        // given
+        let packages = vec![
+            package(
+                "Permissive",
+                Some(PackageLicense::Expression("MIT".to_string())),
+            ),
+            package(
+                "Copyleft",
+                Some(PackageLicense::Expression("GPL-3.0-only".to_string())),
+            ),
+            package(
+                "Weak",
+                Some(PackageLicense::Expression("LGPL-2.1-only".to_string())),
+            ),
+            package("NoLicense", None),
+        ];
+
+        // when
+        let violations = check_project(&project(Some("MIT")), &packages);
+
+        // then
+        let summary: Vec<(Severity, &str)> = violations
+            .iter()
+            .map(|v| (v.severity(), v.message().as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    Severity::Error,
+                    "package 'Copyleft' 1.0.0 has an incompatible license (GPL-3.0-only)"
+                ),
+                (
+                    Severity::Warning,
+                    "package 'Weak' 1.0.0 requires a license review (LGPL-2.1-only)"
+                ),
+                (
+                    Severity::Note,
+                    "package 'NoLicense' 1.0.0 has a license that could not be verified (unknown)"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn gpl_dependency_in_gpl_project() {
+        let packages = vec![package(
+            "Copyleft",
+            Some(PackageLicense::Expression("GPL-3.0-only".to_string())),
+        )];
+
+        assert!(check_project(&project(Some("GPL-3.0-or-later")), &packages).is_empty());
+    }
+}
@@ -0,0 +1,709 @@
+//! Architecture metrics computed from the dependency graph, such as the coupling between
+//! projects, the length of the dependency chains, the most depended upon nodes or the distances
+//! between projects.
+
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+};
+
+use crate::graph::{DependencyGraph, NodeKind};
+
+/// Represents the coupling of a project with the other projects of the workspace.
+///
+/// The instability is Robert C. Martin's metric `efferent / (afferent + efferent)`: `0` for a
+/// project that only has dependents (stable, hard to change), `1` for a project that only has
+/// dependencies (unstable, easy to change).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectCoupling {
+    id: String,
+    name: String,
+    afferent: usize,
+    efferent: usize,
+    instability: f64,
+}
+
+/// Represents the depth of the dependency chains of a workspace.
+///
+/// Deep chains force projects to be built one after the other, so they correlate with
+/// incremental build times.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepthMetrics {
+    projects: Vec<ProjectDepth>,
+    depth: usize,
+    longest_chain: Vec<String>,
+}
+
+/// Represents the length of the longest dependency chain starting from a project.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectDepth {
+    id: String,
+    name: String,
+    depth: usize,
+}
+
+/// Represents a project or package ranked by the number of projects depending on it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotSpot {
+    index: usize,
+    id: String,
+    name: String,
+    kind: NodeKind,
+    direct_dependents: usize,
+    transitive_dependents: usize,
+}
+
+/// Represents the length of the shortest chain of project references between every pair of a
+/// selection of projects.
+///
+/// Comparing the matrices computed before and after a refactoring shows how far apart the
+/// projects moved.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistanceMatrix {
+    ids: Vec<String>,
+    names: Vec<String>,
+    distances: Vec<Vec<Option<usize>>>,
+}
+
+impl ProjectCoupling {
+    /// Returns the id of the project in the graph (its path relative to the workspace root).
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Returns the name of the project.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the afferent coupling (fan-in): the number of projects referencing this project.
+    pub fn afferent(&self) -> usize {
+        self.afferent
+    }
+
+    /// Returns the efferent coupling (fan-out): the number of projects referenced by this
+    /// project.
+    pub fn efferent(&self) -> usize {
+        self.efferent
+    }
+
+    /// Returns the instability of the project, between `0` and `1`. Projects without any
+    /// reference have an instability of `0`.
+    pub fn instability(&self) -> f64 {
+        self.instability
+    }
+}
+
+impl DepthMetrics {
+    /// Returns the depth of every project, in the order of the graph nodes.
+    pub fn projects(&self) -> &Vec<ProjectDepth> {
+        &self.projects
+    }
+
+    /// Returns the depth of the workspace: the number of references in its longest chain.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the ids of the projects of the longest chain, from the top-level project to the
+    /// deepest dependency. Empty if the graph has no project.
+    pub fn longest_chain(&self) -> &Vec<String> {
+        &self.longest_chain
+    }
+}
+
+impl ProjectDepth {
+    /// Returns the id of the project in the graph (its path relative to the workspace root).
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Returns the name of the project.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the number of references in the longest chain starting from the project. Projects
+    /// without project references have a depth of `0`.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl HotSpot {
+    /// Returns the index of the node in the graph.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the id of the node in the graph.
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Returns the name of the project or package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns whether the node is a project or a package.
+    pub fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    /// Returns the number of projects referencing the node.
+    pub fn direct_dependents(&self) -> usize {
+        self.direct_dependents
+    }
+
+    /// Returns the number of projects depending on the node, directly or transitively: the
+    /// projects rebuilt when the node changes.
+    pub fn transitive_dependents(&self) -> usize {
+        self.transitive_dependents
+    }
+}
+
+/// Computes the coupling of every project of the graph, in the order of the graph nodes.
+///
+/// Only the references between projects are counted; package nodes are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{graph::DependencyGraph, metrics, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// let graph = DependencyGraph::from_workspace(&workspace, false);
+///
+/// for coupling in metrics::coupling(&graph) {
+///     println!(
+///         "{}: Ca={} Ce={} I={:.2}",
+///         coupling.name(),
+///         coupling.afferent(),
+///         coupling.efferent(),
+///         coupling.instability()
+///     );
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn coupling(graph: &DependencyGraph) -> Vec<ProjectCoupling> {
+    let is_project = |index: &usize| graph.node(*index).kind() == NodeKind::Project;
+
+    graph
+        .nodes()
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.kind() == NodeKind::Project)
+        .map(|(index, node)| {
+            let afferent = graph.dependents(index).filter(is_project).count();
+            let efferent = graph.dependencies(index).filter(is_project).count();
+
+            let instability = if afferent + efferent == 0 {
+                0.0
+            } else {
+                efferent as f64 / (afferent + efferent) as f64
+            };
+
+            ProjectCoupling {
+                id: node.id().clone(),
+                name: node.name().clone(),
+                afferent,
+                efferent,
+                instability,
+            }
+        })
+        .collect()
+}
+
+/// Computes the longest chain of project references starting from every project, and the
+/// longest chain of the whole graph.
+///
+/// Package nodes are ignored. Projects referencing each other in a cycle are counted as a single
+/// level, so they all have the same depth and can appear next to each other in the longest chain
+/// without a direct reference between them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{graph::DependencyGraph, metrics, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// let metrics = metrics::depth(&DependencyGraph::from_workspace(&workspace, false));
+///
+/// println!("depth: {}", metrics.depth());
+/// println!("longest chain: {}", metrics.longest_chain().join(" -> "));
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn depth(graph: &DependencyGraph) -> DepthMetrics {
+    let components = graph.strongly_connected_components();
+
+    let mut component_of = vec![0; graph.nodes().len()];
+    for (component, nodes) in components.iter().enumerate() {
+        for node in nodes {
+            component_of[*node] = component;
+        }
+    }
+
+    // the references leaving each component, which form an acyclic graph between components
+    let mut exits: Vec<Vec<(usize, usize)>> = vec![vec![]; components.len()];
+    for edge in graph.edges() {
+        let is_project = |index: usize| graph.node(index).kind() == NodeKind::Project;
+
+        if is_project(edge.from())
+            && is_project(edge.to())
+            && component_of[edge.from()] != component_of[edge.to()]
+        {
+            exits[component_of[edge.from()]].push((edge.from(), edge.to()));
+        }
+    }
+
+    let mut depths: Vec<Option<usize>> = vec![None; components.len()];
+    let mut deepest_exits: Vec<Option<(usize, usize)>> = vec![None; components.len()];
+
+    for start in 0..components.len() {
+        // post-order traversal, so the depths of the dependencies are known first
+        let mut stack = vec![(start, false)];
+
+        while let Some((component, visited)) = stack.pop() {
+            if depths[component].is_some() {
+                continue;
+            }
+
+            if !visited {
+                stack.push((component, true));
+                stack.extend(
+                    exits[component]
+                        .iter()
+                        .map(|(_, to)| (component_of[*to], false)),
+                );
+                continue;
+            }
+
+            let deepest = exits[component]
+                .iter()
+                .copied()
+                .max_by_key(|(_, to)| depths[component_of[*to]]);
+
+            depths[component] =
+                Some(deepest.map_or(0, |(_, to)| depths[component_of[to]].unwrap_or(0) + 1));
+            deepest_exits[component] = deepest;
+        }
+    }
+
+    let projects: Vec<ProjectDepth> = graph
+        .nodes()
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.kind() == NodeKind::Project)
+        .map(|(index, node)| ProjectDepth {
+            id: node.id().clone(),
+            name: node.name().clone(),
+            depth: depths[component_of[index]].unwrap_or(0),
+        })
+        .collect();
+
+    let start = graph
+        .nodes()
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.kind() == NodeKind::Project)
+        .map(|(index, _)| index)
+        .rev()
+        .max_by_key(|index| depths[component_of[*index]]);
+
+    let mut longest_chain = vec![];
+
+    if let Some(start) = start {
+        let mut current = start;
+        longest_chain.push(current);
+
+        while let Some((from, to)) = deepest_exits[component_of[current]] {
+            if from != current {
+                longest_chain.push(from);
+            }
+
+            longest_chain.push(to);
+            current = to;
+        }
+    }
+
+    DepthMetrics {
+        depth: projects
+            .iter()
+            .map(|project| project.depth)
+            .max()
+            .unwrap_or(0),
+        projects,
+        longest_chain: longest_chain
+            .into_iter()
+            .map(|index| graph.node(index).id().clone())
+            .collect(),
+    }
+}
+
+/// Ranks the projects and packages of the graph by the number of projects depending on them,
+/// directly or transitively, to find the nodes whose changes trigger the widest rebuilds.
+///
+/// The nodes are sorted by transitive dependents, then by direct dependents, most depended upon
+/// first. Nodes without dependents are not returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{graph::DependencyGraph, metrics, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// let graph = DependencyGraph::from_workspace(&workspace, true);
+///
+/// for hot_spot in metrics::hot_spots(&graph).iter().take(10) {
+///     println!("{}: {}", hot_spot.name(), hot_spot.transitive_dependents());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn hot_spots(graph: &DependencyGraph) -> Vec<HotSpot> {
+    let mut hot_spots: Vec<HotSpot> = graph
+        .nodes()
+        .iter()
+        .enumerate()
+        .map(|(index, node)| HotSpot {
+            index,
+            id: node.id().clone(),
+            name: node.name().clone(),
+            kind: node.kind(),
+            direct_dependents: graph.dependents(index).count(),
+            transitive_dependents: graph
+                .transitive_dependents(index)
+                .into_iter()
+                .filter(|dependent| *dependent != index)
+                .count(),
+        })
+        .filter(|hot_spot| hot_spot.direct_dependents > 0)
+        .collect();
+
+    hot_spots.sort_by(|left, right| {
+        right
+            .transitive_dependents
+            .cmp(&left.transitive_dependents)
+            .then_with(|| right.direct_dependents.cmp(&left.direct_dependents))
+            .then_with(|| left.index.cmp(&right.index))
+    });
+
+    hot_spots
+}
+
+impl DistanceMatrix {
+    /// Returns the ids of the projects of the matrix (their paths relative to the workspace root),
+    /// in the order of the rows and columns.
+    pub fn ids(&self) -> &Vec<String> {
+        &self.ids
+    }
+
+    /// Returns the names of the projects of the matrix, in the order of the rows and columns.
+    pub fn names(&self) -> &Vec<String> {
+        &self.names
+    }
+
+    /// Returns the distances of the matrix, by row: the distance from the project of the row to
+    /// the project of the column.
+    pub fn distances(&self) -> &Vec<Vec<Option<usize>>> {
+        &self.distances
+    }
+
+    /// Returns the number of references in the shortest chain from the project at row `from` to
+    /// the project at column `to`, or `None` if `to` cannot be reached from `from`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` is out of bounds.
+    pub fn distance(&self, from: usize, to: usize) -> Option<usize> {
+        self.distances[from][to]
+    }
+
+    /// Writes the matrix as CSV: a header with the ids of the projects, then one row per project
+    /// starting with its id. Unreachable projects have an empty cell.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the matrix could not be written.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let header: Vec<String> = self.ids.iter().map(|id| csv_field(id)).collect();
+        writeln!(writer, "project,{}", header.join(","))?;
+
+        for (id, distances) in self.ids.iter().zip(&self.distances) {
+            let cells: Vec<String> = distances
+                .iter()
+                .map(|distance| distance.map(|d| d.to_string()).unwrap_or_default())
+                .collect();
+
+            writeln!(writer, "{},{}", csv_field(id), cells.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Computes the distance between every pair of the selected projects: the number of references
+/// in the shortest chain of project references from one to the other.
+///
+/// `projects` are indexes of project nodes of the graph, in the order of the rows and columns of
+/// the matrix; every project of the graph is selected when it is empty. The chains can go
+/// through projects that are not selected, but not through packages. Distances are directed: a
+/// project referencing another is at distance `1` from it, while the other cannot reach it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{graph::DependencyGraph, metrics, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// let graph = DependencyGraph::from_workspace(&workspace, false);
+///
+/// metrics::distance_matrix(&graph, &[]).write_csv(std::io::stdout())?;
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn distance_matrix(graph: &DependencyGraph, projects: &[usize]) -> DistanceMatrix {
+    let is_project = |index: &usize| graph.node(*index).kind() == NodeKind::Project;
+
+    let projects: Vec<usize> = if projects.is_empty() {
+        (0..graph.nodes().len()).filter(is_project).collect()
+    } else {
+        projects.to_vec()
+    };
+
+    let distances = projects
+        .iter()
+        .map(|from| {
+            // breadth-first search, so each project is first reached through a shortest chain
+            let mut reached = vec![None; graph.nodes().len()];
+            let mut queue = VecDeque::from([*from]);
+            reached[*from] = Some(0);
+
+            while let Some(index) = queue.pop_front() {
+                let distance = reached[index].unwrap_or_default() + 1;
+
+                for dependency in graph.dependencies(index).filter(is_project) {
+                    if reached[dependency].is_none() {
+                        reached[dependency] = Some(distance);
+                        queue.push_back(dependency);
+                    }
+                }
+            }
+
+            projects.iter().map(|to| reached[*to]).collect()
+        })
+        .collect();
+
+    DistanceMatrix {
+        ids: projects
+            .iter()
+            .map(|index| graph.node(*index).id().clone())
+            .collect(),
+        names: projects
+            .iter()
+            .map(|index| graph.node(*index).name().clone())
+            .collect(),
+        distances,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graph::{
+            test::{project, workspace},
+            DependencyGraph,
+        },
+        workspace::Workspace,
+    };
+
+    use super::{coupling, depth, distance_matrix, hot_spots};
+
+    #[test]
+    pub fn compute_coupling_between_projects() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/Api/Api.csproj", &["../Domain/Domain.csproj"], &[]),
+                project("/repo/Jobs/Jobs.csproj", &["../Domain/Domain.csproj"], &[]),
+                project(
+                    "/repo/Domain/Domain.csproj",
+                    &["../Shared/Shared.csproj"],
+                    &[("Serilog", "3.1.1")],
+                ),
+                project("/repo/Shared/Shared.csproj", &[], &[]),
+            ],
+        );
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+
+        // when
+        let metrics = coupling(&graph);
+
+        // then
+        assert_eq!(metrics.len(), 4);
+        assert_eq!(metrics[0].instability(), 1.0);
+        assert_eq!(metrics[2].name(), "Domain");
+        assert_eq!(metrics[2].afferent(), 2);
+        assert_eq!(metrics[2].efferent(), 1);
+        assert!((metrics[2].instability() - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(metrics[3].instability(), 0.0);
+    }
+
+    #[test]
+    pub fn isolated_project_is_stable() {
+        let graph = DependencyGraph::from_workspace(
+            &Workspace::from_projects("/repo", vec![workspace().projects()[1].clone()]),
+            true,
+        );
+
+        let metrics = coupling(&graph);
+
+        assert_eq!(metrics[0].afferent(), 0);
+        assert_eq!(metrics[0].instability(), 0.0);
+    }
+
+    #[test]
+    pub fn compute_longest_chain() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/Tests/Tests.csproj", &["../Api/Api.csproj"], &[]),
+                project("/repo/Api/Api.csproj", &["../Domain/Domain.csproj"], &[]),
+                project("/repo/Domain/Domain.csproj", &["../Data/Data.csproj"], &[]),
+                project(
+                    "/repo/Data/Data.csproj",
+                    &["../Domain/Domain.csproj", "../Shared/Shared.csproj"],
+                    &[("Dapper", "2.1.35")],
+                ),
+                project("/repo/Shared/Shared.csproj", &[], &[]),
+                project(
+                    "/repo/Tools/Tools.csproj",
+                    &["../Shared/Shared.csproj"],
+                    &[],
+                ),
+            ],
+        );
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+
+        // when
+        let metrics = depth(&graph);
+
+        // then
+        let depths: Vec<usize> = metrics.projects().iter().map(|p| p.depth()).collect();
+
+        assert_eq!(depths, vec![3, 2, 1, 1, 0, 1]);
+        assert_eq!(metrics.depth(), 3);
+        assert_eq!(
+            metrics.longest_chain(),
+            &vec![
+                "Tests/Tests.csproj",
+                "Api/Api.csproj",
+                "Domain/Domain.csproj",
+                "Data/Data.csproj",
+                "Shared/Shared.csproj"
+            ]
+        );
+    }
+
+    #[test]
+    pub fn rank_hot_spots() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/Api/Api.csproj", &["../Domain/Domain.csproj"], &[]),
+                project("/repo/Jobs/Jobs.csproj", &["../Domain/Domain.csproj"], &[]),
+                project(
+                    "/repo/Domain/Domain.csproj",
+                    &["../Shared/Shared.csproj"],
+                    &[("Serilog", "3.1.1")],
+                ),
+                project("/repo/Shared/Shared.csproj", &[], &[]),
+            ],
+        );
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+
+        // when
+        let hot_spots = hot_spots(&graph);
+
+        // then
+        let ranking: Vec<(&str, usize, usize)> = hot_spots
+            .iter()
+            .map(|h| {
+                (
+                    h.name().as_str(),
+                    h.direct_dependents(),
+                    h.transitive_dependents(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            ranking,
+            vec![("Shared", 1, 3), ("Serilog", 1, 3), ("Domain", 2, 2)]
+        );
+    }
+
+    #[test]
+    pub fn compute_distances_between_projects() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/Api/Api.csproj", &["../Domain/Domain.csproj"], &[]),
+                project(
+                    "/repo/Domain/Domain.csproj",
+                    &["../Shared/Shared.csproj"],
+                    &[("Serilog", "3.1.1")],
+                ),
+                project("/repo/Shared/Shared.csproj", &[], &[]),
+                project("/repo/Jobs/Jobs.csproj", &["../Shared/Shared.csproj"], &[]),
+            ],
+        );
+        let graph = DependencyGraph::from_workspace(&workspace, true);
+        let api = graph.find("Api/Api.csproj").unwrap();
+        let shared = graph.find("Shared/Shared.csproj").unwrap();
+        let jobs = graph.find("Jobs/Jobs.csproj").unwrap();
+
+        // when
+        let all = distance_matrix(&graph, &[]);
+        let selected = distance_matrix(&graph, &[api, shared, jobs]);
+
+        // then
+        assert_eq!(all.ids().len(), 4);
+        assert_eq!(selected.names(), &vec!["Api", "Shared", "Jobs"]);
+        assert_eq!(selected.distance(0, 1), Some(2));
+        assert_eq!(selected.distance(1, 0), None);
+        assert_eq!(selected.distance(2, 2), Some(0));
+
+        let mut csv = vec![];
+        selected.write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "project,Api/Api.csproj,Shared/Shared.csproj,Jobs/Jobs.csproj\n\
+             Api/Api.csproj,0,2,\n\
+             Shared/Shared.csproj,,0,\n\
+             Jobs/Jobs.csproj,,1,0\n"
+        );
+    }
+}
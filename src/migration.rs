@@ -0,0 +1,372 @@
+//! Inventory of what ties the projects of a workspace to .NET Framework (legacy project format,
+//! `packages.config`, `TargetFrameworkVersion`, GAC and COM references), to plan their migration
+//! to modern .NET.
+
+use std::path::{Path, PathBuf};
+
+use crate::{vfs::FileSystem, workspace::Workspace, Project};
+
+/// Represents what a project has to migrate off .NET Framework.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MigrationInventory {
+    project: PathBuf,
+    legacy_format: bool,
+    packages_config: Option<PathBuf>,
+    target_framework_version: Option<String>,
+    gac_references: Vec<String>,
+    com_references: Vec<String>,
+}
+
+impl MigrationInventory {
+    /// Returns the path of the project.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns `true` if the project file uses the legacy, non-SDK-style format (see
+    /// [`MigrationMetadata::sdks`]).
+    pub fn is_legacy_format(&self) -> bool {
+        self.legacy_format
+    }
+
+    /// Returns the path of the `packages.config` file declaring the packages of the project, if
+    /// any.
+    pub fn packages_config(&self) -> Option<&PathBuf> {
+        self.packages_config.as_ref()
+    }
+
+    /// Returns the `TargetFrameworkVersion` of the project, as evaluated (ex: `v4.6.1`), if any.
+    pub fn target_framework_version(&self) -> Option<&String> {
+        self.target_framework_version.as_ref()
+    }
+
+    /// Returns the target framework moniker equivalent to the `TargetFrameworkVersion` of the
+    /// project (ex: `net461` for `v4.6.1`), if any.
+    pub fn target_framework(&self) -> Option<String> {
+        let version = self.target_framework_version.as_ref()?;
+        let digits: String = version
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .split('.')
+            .collect();
+
+        (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+            .then(|| format!("net{digits}"))
+    }
+
+    /// Returns the names of the assemblies referenced from the global assembly cache, that is
+    /// the `Reference` items without a `HintPath` (ex: `System.Web`), without their version and
+    /// public key token.
+    pub fn gac_references(&self) -> &Vec<String> {
+        &self.gac_references
+    }
+
+    /// Returns the COM components referenced by the `COMReference` and `COMFileReference` items,
+    /// as written (ex: `Microsoft.Office.Interop.Excel`).
+    pub fn com_references(&self) -> &Vec<String> {
+        &self.com_references
+    }
+
+    /// Returns `true` if nothing was found to migrate.
+    pub fn is_empty(&self) -> bool {
+        !self.legacy_format
+            && self.packages_config.is_none()
+            && self.target_framework_version.is_none()
+            && self.gac_references.is_empty()
+            && self.com_references.is_empty()
+    }
+}
+
+/// Represents the parts of a project file that tie it to .NET Framework, as read when the project
+/// is parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MigrationMetadata {
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    sdks: Vec<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    packages_config: Option<PathBuf>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    target_framework_version: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    gac_references: Vec<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    com_references: Vec<String>,
+}
+
+impl MigrationMetadata {
+    /// Creates a new `MigrationMetadata` without anything to migrate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the MSBuild project SDKs used by the project file, as written (ex:
+    /// `Microsoft.NET.Sdk`), like [`crate::build_file::BuildFile::sdks`]. Project files without
+    /// any SDK use the legacy, non-SDK-style format.
+    pub fn sdks(&self) -> &Vec<String> {
+        &self.sdks
+    }
+
+    /// Sets the MSBuild project SDKs used by the project file.
+    pub fn set_sdks(&mut self, sdks: Vec<String>) {
+        self.sdks = sdks;
+    }
+
+    /// Returns the path of the `packages.config` file next to the project file, if any.
+    pub fn packages_config(&self) -> Option<&PathBuf> {
+        self.packages_config.as_ref()
+    }
+
+    /// Sets the path of the `packages.config` file next to the project file.
+    pub fn set_packages_config(&mut self, packages_config: Option<PathBuf>) {
+        self.packages_config = packages_config;
+    }
+
+    /// Returns the `TargetFrameworkVersion` of the project (ex: `v4.6.1`), if any.
+    pub fn target_framework_version(&self) -> Option<&String> {
+        self.target_framework_version.as_ref()
+    }
+
+    /// Sets the `TargetFrameworkVersion` of the project.
+    pub fn set_target_framework_version(&mut self, target_framework_version: Option<String>) {
+        self.target_framework_version = target_framework_version;
+    }
+
+    /// Returns the names of the assemblies referenced from the global assembly cache (see
+    /// [`MigrationInventory::gac_references`]).
+    pub fn gac_references(&self) -> &Vec<String> {
+        &self.gac_references
+    }
+
+    /// Adds an assembly referenced from the global assembly cache.
+    pub fn add_gac_reference(&mut self, name: String) {
+        self.gac_references.push(name);
+    }
+
+    /// Returns the COM components referenced by the project, as written.
+    pub fn com_references(&self) -> &Vec<String> {
+        &self.com_references
+    }
+
+    /// Adds a referenced COM component.
+    pub fn add_com_reference(&mut self, name: String) {
+        self.com_references.push(name);
+    }
+
+    /// Returns `true` if the project file does not use any of these parts. Note that a project
+    /// file without any SDK is empty too, while it uses the legacy format.
+    pub fn is_empty(&self) -> bool {
+        self.sdks.is_empty()
+            && self.packages_config.is_none()
+            && self.target_framework_version.is_none()
+            && self.gac_references.is_empty()
+            && self.com_references.is_empty()
+    }
+}
+
+/// Returns the `packages.config` file of the project at `path` in `files`: next to the project
+/// file, also as `packages.<project>.config`.
+pub(crate) fn find_packages_config(path: &Path, files: &dyn FileSystem) -> Option<PathBuf> {
+    let directory = path.parent().unwrap_or(Path::new(""));
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    [
+        "packages.config".to_string(),
+        format!("packages.{stem}.config"),
+    ]
+    .into_iter()
+    .map(|name| directory.join(name))
+    .find(|path| files.is_file(path))
+}
+
+/// Returns what a project has to migrate off .NET Framework.
+///
+/// The `packages.config` file is looked up next to the project file, also as
+/// `packages.<project>.config`. Everything is read when the project is parsed (see
+/// [`Project::migration`]), from the project file only: properties and items set by imported
+/// files, such as `Directory.Build.props`, are not seen. Deployment projects (ex: `.sfproj`,
+/// WiX v3 `.wixproj`) have their own formats and are never reported as legacy.
+pub fn project_inventory(project: &Project) -> MigrationInventory {
+    let metadata = project.migration();
+
+    MigrationInventory {
+        project: project.path().clone(),
+        legacy_format: metadata.sdks().is_empty() && !project.language().is_deployment(),
+        packages_config: metadata.packages_config().cloned(),
+        target_framework_version: metadata.target_framework_version().cloned(),
+        gac_references: metadata.gac_references().clone(),
+        com_references: metadata.com_references().clone(),
+    }
+}
+
+/// Returns what the projects of a workspace have to migrate off .NET Framework, skipping the
+/// projects with nothing to migrate (see [`project_inventory`]).
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{migration, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// for inventory in migration::migration_inventory(&workspace) {
+///     if inventory.is_legacy_format() {
+///         println!("{} uses the legacy format", inventory.project().display());
+///     }
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn migration_inventory(workspace: &Workspace) -> Vec<MigrationInventory> {
+    let mut inventories = vec![];
+
+    for project in workspace.projects() {
+        let inventory = project_inventory(project);
+
+        if !inventory.is_empty() {
+            inventories.push(inventory);
+        }
+    }
+
+    inventories
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::workspace::WorkspaceBuilder;
+
+    use super::migration_inventory;
+
+    #[test]
+    pub fn inventory_legacy_projects() {
+        // given
+        let projects = [
+            (
+                "Legacy/Legacy.csproj",
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<Project ToolsVersion="15.0" xmlns="http://schemas.microsoft.com/developer/msbuild/2003">
+  <Import Project="$(MSBuildExtensionsPath)\$(MSBuildToolsVersion)\Microsoft.Common.props" />
+  <PropertyGroup>
+    <OutputType>Library</OutputType>
+    <TargetFrameworkVersion>v4.6.1</TargetFrameworkVersion>
+  </PropertyGroup>
+  <ItemGroup>
+    <Reference Include="System.Web" />
+    <Reference Include="log4net, Version=2.0.8.0, Culture=neutral, PublicKeyToken=669e0ddf0bb1aa2a">
+      <HintPath>..\packages\log4net.2.0.8\lib\net45-full\log4net.dll</HintPath>
+    </Reference>
+    <Reference Include="System.EnterpriseServices, Version=4.0.0.0, Culture=neutral" />
+  </ItemGroup>
+  <ItemGroup>
+    <COMReference Include="Microsoft.Office.Interop.Excel">
+      <Guid>{00020813-0000-0000-C000-000000000046}</Guid>
+    </COMReference>
+  </ItemGroup>
+  <Import Project="$(MSBuildToolsPath)\Microsoft.CSharp.targets" />
+</Project>"#,
+            ),
+            (
+                "Api/Api.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+            ),
+            (
+                "Tools/Tools.csproj",
+                r#"<Project>
+  <Import Project="Sdk.props" Sdk="Microsoft.NET.Sdk" />
+  <PropertyGroup>
+    <TargetFramework>net48</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <Reference Include="System.Configuration" />
+  </ItemGroup>
+  <Import Project="Sdk.targets" Sdk="Microsoft.NET.Sdk" />
+</Project>"#,
+            ),
+            (
+                "Setup/Setup.wixproj",
+                r#"<Project ToolsVersion="4.0" xmlns="http://schemas.microsoft.com/developer/msbuild/2003">
+  <PropertyGroup>
+    <OutputType>Package</OutputType>
+  </PropertyGroup>
+  <Import Project="$(WixTargetsPath)" />
+</Project>"#,
+            ),
+        ];
+
+        let mut builder = WorkspaceBuilder::new("/repo");
+        for (path, content) in projects {
+            builder.add_project(path, content);
+        }
+        builder.add_file(
+            "Legacy/packages.config",
+            r#"<packages><package id="log4net" version="2.0.8" targetFramework="net461" /></packages>"#,
+        );
+
+        let workspace = builder.build().unwrap();
+
+        // when
+        let mut inventories = migration_inventory(&workspace);
+
+        // then
+        inventories.sort_by(|a, b| a.project().cmp(b.project()));
+
+        let projects: Vec<String> = inventories
+            .iter()
+            .map(|inventory| workspace.relative_path(inventory.project()))
+            .collect();
+        assert_eq!(projects, vec!["Legacy/Legacy.csproj", "Tools/Tools.csproj"]);
+
+        let legacy = &inventories[0];
+        assert!(legacy.is_legacy_format());
+        assert_eq!(
+            legacy.packages_config().unwrap(),
+            &PathBuf::from("/repo/Legacy/packages.config")
+        );
+        assert_eq!(legacy.target_framework_version().unwrap(), "v4.6.1");
+        assert_eq!(legacy.target_framework().unwrap(), "net461");
+        assert_eq!(
+            legacy.gac_references(),
+            &vec![
+                "System.Web".to_string(),
+                "System.EnterpriseServices".to_string()
+            ]
+        );
+        assert_eq!(
+            legacy.com_references(),
+            &vec!["Microsoft.Office.Interop.Excel".to_string()]
+        );
+
+        let tools = &inventories[1];
+        assert!(!tools.is_legacy_format());
+        assert!(tools.packages_config().is_none());
+        assert_eq!(
+            tools.gac_references(),
+            &vec!["System.Configuration".to_string()]
+        );
+    }
+}
@@ -0,0 +1,717 @@
+//! Evaluation of the projects the parser cannot resolve statically (heavy imports, custom SDKs)
+//! by running `dotnet msbuild -getProperty -getItem`, merging the evaluated values into the
+//! parsed projects.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::{condition::Properties, PackageReference, Project, ProjectReference};
+
+/// The properties evaluated by default, the ones the parser reads from project files.
+pub const DEFAULT_PROPERTIES: [&str; 6] = [
+    "TargetFramework",
+    "TargetFrameworks",
+    "AssemblyName",
+    "PackageLicenseExpression",
+    "RepositoryUrl",
+    "IsPackable",
+];
+
+/// The items evaluated by default.
+pub const DEFAULT_ITEMS: [&str; 2] = ["ProjectReference", "PackageReference"];
+
+/// The time `dotnet msbuild` is given by default to evaluate a project before it is killed.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The interval at which a running `dotnet msbuild` is checked for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Represents an evaluator running `dotnet msbuild` on a project to read the values of its
+/// properties and items after a complete evaluation, as the build sees them.
+///
+/// Running MSBuild is much slower than parsing, requires the .NET SDK and may run the code of
+/// the SDKs and imported files, so it is opt-in: [`needs_evaluation`] tells which projects are
+/// worth evaluating.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{
+///     msbuild::{needs_evaluation, MsBuildEvaluator},
+///     Project,
+/// };
+///
+/// let mut project = Project::new("src/Api/Api.csproj")?;
+///
+/// if needs_evaluation(&project) {
+///     let evaluation = MsBuildEvaluator::new().evaluate(project.path())?;
+///
+///     for value in evaluation.merge_into(&mut project) {
+///         println!("evaluated by MSBuild: {:?}", value);
+///     }
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MsBuildEvaluator {
+    dotnet: PathBuf,
+    properties: Vec<String>,
+    items: Vec<String>,
+    global_properties: Properties,
+    timeout: Duration,
+}
+
+/// Represents the properties and items of a project evaluated by MSBuild.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LiveEvaluation {
+    properties: BTreeMap<String, String>,
+    items: BTreeMap<String, Vec<EvaluatedItem>>,
+}
+
+/// Represents an item evaluated by MSBuild, with its metadata.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EvaluatedItem {
+    identity: String,
+    metadata: BTreeMap<String, String>,
+}
+
+/// Represents a value of a project that came from the evaluation by MSBuild instead of the
+/// project file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiveValue {
+    /// A property that was missing or could not be expanded (ex: `TargetFramework`).
+    Property {
+        /// The name of the property.
+        name: String,
+        /// The evaluated value.
+        value: String,
+    },
+    /// A package reference declared in an imported file, or whose version could not be
+    /// resolved from the project file.
+    PackageReference {
+        /// The name of the package.
+        name: String,
+        /// The evaluated version.
+        version: String,
+    },
+    /// A project reference declared in an imported file.
+    ProjectReference {
+        /// The path of the referenced project, relative to the referencing project.
+        path: PathBuf,
+    },
+}
+
+impl MsBuildEvaluator {
+    /// Creates a new `MsBuildEvaluator` running `dotnet` from the `PATH`, evaluating the
+    /// [`DEFAULT_PROPERTIES`] and [`DEFAULT_ITEMS`] within the [`DEFAULT_TIMEOUT`].
+    pub fn new() -> Self {
+        Self {
+            dotnet: PathBuf::from("dotnet"),
+            properties: DEFAULT_PROPERTIES
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            items: DEFAULT_ITEMS.iter().map(|name| name.to_string()).collect(),
+            global_properties: Properties::new(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Returns the path of the `dotnet` executable.
+    pub fn dotnet(&self) -> &PathBuf {
+        &self.dotnet
+    }
+
+    /// Sets the path of the `dotnet` executable.
+    pub fn set_dotnet(&mut self, dotnet: PathBuf) {
+        self.dotnet = dotnet;
+    }
+
+    /// Returns the properties that are evaluated.
+    pub fn properties(&self) -> &Vec<String> {
+        &self.properties
+    }
+
+    /// Adds a property to evaluate.
+    pub fn add_property(&mut self, name: &str) {
+        self.properties.push(name.to_string());
+    }
+
+    /// Returns the items that are evaluated.
+    pub fn items(&self) -> &Vec<String> {
+        &self.items
+    }
+
+    /// Adds an item type to evaluate.
+    pub fn add_item(&mut self, item_type: &str) {
+        self.items.push(item_type.to_string());
+    }
+
+    /// Returns the global properties passed to MSBuild (ex: `Configuration=Release`).
+    pub fn global_properties(&self) -> &Properties {
+        &self.global_properties
+    }
+
+    /// Sets the global properties passed to MSBuild.
+    pub fn set_global_properties(&mut self, global_properties: Properties) {
+        self.global_properties = global_properties;
+    }
+
+    /// Returns the time `dotnet msbuild` is given to evaluate a project.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Sets the time `dotnet msbuild` is given to evaluate a project before it is killed.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Evaluates the project file at `path` with `dotnet msbuild`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `dotnet` could not be run, if the evaluation
+    /// failed (ex: the project was not restored and uses an SDK from a package), if it did not
+    /// finish within the [`timeout`](MsBuildEvaluator::timeout) or if its output could not be
+    /// read.
+    pub fn evaluate<P>(&self, path: P) -> Result<LiveEvaluation, MsBuildError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut command = Command::new(&self.dotnet);
+        command.arg("msbuild").arg(path.as_ref()).arg("-nologo");
+
+        for property in &self.properties {
+            command.arg(format!("-getProperty:{}", property));
+        }
+
+        for item in &self.items {
+            command.arg(format!("-getItem:{}", item));
+        }
+
+        for (name, value) in self.global_properties.iter() {
+            command.arg(format!("-property:{}={}", name, value));
+        }
+
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        // the output is read on another thread so MSBuild does not block on a full pipe
+        let mut stdout = child.stdout.take().expect("the output of MSBuild is piped");
+        let reader = thread::spawn(move || {
+            let mut output = vec![];
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+
+            if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+
+                return Err(MsBuildError::TimedOut(self.timeout));
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        let output = reader
+            .join()
+            .map_err(|_| io::Error::other("the output of MSBuild could not be read"))??;
+
+        if !status.success() {
+            return Err(MsBuildError::EvaluationFailed(
+                String::from_utf8_lossy(&output).trim().to_string(),
+            ));
+        }
+
+        let output = String::from_utf8_lossy(&output);
+
+        // MSBuild prints the value alone when a single property is requested
+        match (self.properties.as_slice(), self.items.is_empty()) {
+            ([property], true) => {
+                let mut evaluation = LiveEvaluation::default();
+                evaluation
+                    .properties
+                    .insert(property.clone(), output.trim().to_string());
+
+                Ok(evaluation)
+            }
+            _ => LiveEvaluation::parse(&output),
+        }
+    }
+}
+
+impl Default for MsBuildEvaluator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LiveEvaluation {
+    /// Parses the JSON output of `dotnet msbuild -getProperty -getItem`, with the `Properties`
+    /// and `Items` objects.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the output is not a JSON object.
+    pub fn parse(output: &str) -> Result<Self, MsBuildError> {
+        let document: Value =
+            serde_json::from_str(output).map_err(|_| MsBuildError::InvalidOutput)?;
+
+        if !document.is_object() {
+            return Err(MsBuildError::InvalidOutput);
+        }
+
+        let mut evaluation = Self::default();
+
+        for (name, value) in objects(&document, "Properties") {
+            if let Some(value) = value.as_str() {
+                evaluation
+                    .properties
+                    .insert(name.clone(), value.to_string());
+            }
+        }
+
+        for (item_type, items) in objects(&document, "Items") {
+            let items = items
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_object)
+                .map(|item| {
+                    let metadata: BTreeMap<String, String> = item
+                        .iter()
+                        .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.into())))
+                        .collect();
+
+                    EvaluatedItem {
+                        identity: metadata.get("Identity").cloned().unwrap_or_default(),
+                        metadata,
+                    }
+                })
+                .collect();
+
+            evaluation.items.insert(item_type.clone(), items);
+        }
+
+        Ok(evaluation)
+    }
+
+    /// Returns the evaluated properties, by name.
+    pub fn properties(&self) -> &BTreeMap<String, String> {
+        &self.properties
+    }
+
+    /// Returns the value of a property, or `None` if it is empty or was not evaluated.
+    pub fn property(&self, name: &str) -> Option<&String> {
+        self.properties
+            .iter()
+            .find(|(property, _)| property.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Returns the evaluated items of the given type.
+    pub fn items(&self, item_type: &str) -> &[EvaluatedItem] {
+        self.items
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(item_type))
+            .map(|(_, items)| items.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Merges the evaluated values into the project, filling the values that are missing or
+    /// could not be expanded (ex: `$(DefaultTargetFramework)`) and adding the references
+    /// declared in imported files. Values read from the project file are kept.
+    ///
+    /// Project references whose path could not be expanded (ex: `$(SharedDir)/Shared.csproj`)
+    /// are replaced by the evaluated reference they match, keeping their metadata, and dropped
+    /// if none matches, since MSBuild evaluated every project reference.
+    ///
+    /// Returns the values that came from the evaluation.
+    pub fn merge_into(&self, project: &mut Project) -> Vec<LiveValue> {
+        let mut values = vec![];
+
+        let target_framework = self.property("TargetFramework").cloned().or_else(|| {
+            self.property("TargetFrameworks")
+                .and_then(|frameworks| frameworks.split(';').next())
+                .map(|framework| framework.trim().to_string())
+        });
+
+        if let Some(value) = merge(&mut project.target_framework, target_framework) {
+            project.target_framework_candidates.clear();
+            values.push(property("TargetFramework", value));
+        }
+
+        if let Some(value) = merge(
+            &mut project.license_expression,
+            self.property("PackageLicenseExpression").cloned(),
+        ) {
+            values.push(property("PackageLicenseExpression", value));
+        }
+
+        let mut assembly_name = project.assembly.name().cloned();
+
+        if let Some(value) = merge(&mut assembly_name, self.property("AssemblyName").cloned()) {
+            project.assembly.set_name(assembly_name);
+            values.push(property("AssemblyName", value));
+        }
+
+        let mut repository_url = project.repository.url().cloned();
+
+        if let Some(value) = merge(&mut repository_url, self.property("RepositoryUrl").cloned()) {
+            project.repository.set_url(repository_url);
+            values.push(property("RepositoryUrl", value));
+        }
+
+        if let (None, Some(value)) = (project.packaging.is_packable(), self.property("IsPackable"))
+        {
+            project
+                .packaging
+                .set_is_packable(Some(value.eq_ignore_ascii_case("true")));
+            values.push(property("IsPackable", value.clone()));
+        }
+
+        for item in self.items("PackageReference") {
+            let version = item.metadata("Version").cloned().unwrap_or_default();
+            let declared = project
+                .package_references
+                .iter_mut()
+                .find(|reference| reference.name().eq_ignore_ascii_case(&item.identity));
+
+            match declared {
+                Some(declared) if !is_unresolved(declared.version()) || version.is_empty() => {
+                    continue
+                }
+                Some(declared) => {
+                    let mut reference =
+                        PackageReference::new(declared.name().into(), version.clone());
+                    reference.set_target_framework(declared.target_framework().cloned());
+                    reference.set_license(declared.license().cloned());
+
+                    *declared = reference;
+                }
+                None => project.package_references.push(PackageReference::new(
+                    item.identity.clone(),
+                    version.clone(),
+                )),
+            }
+
+            values.push(LiveValue::PackageReference {
+                name: item.identity.clone(),
+                version,
+            });
+        }
+
+        for item in self.items("ProjectReference") {
+            let path = PathBuf::from(item.identity.replace('\\', "/"));
+
+            if project
+                .project_references
+                .iter()
+                .any(|reference| *reference.path() == path)
+            {
+                continue;
+            }
+
+            let name = Project::get_project_name(&path).unwrap_or_default();
+            let unresolved = project.project_references.iter_mut().find(|reference| {
+                let pattern = reference.path().to_string_lossy();
+
+                pattern.contains("$(") && matches_unresolved(&pattern, &path.to_string_lossy())
+            });
+
+            match unresolved {
+                Some(reference) => {
+                    reference.name = name.into();
+                    reference.path = path.clone();
+                }
+                None => project
+                    .project_references
+                    .push(ProjectReference::new(name, path.clone())),
+            }
+
+            values.push(LiveValue::ProjectReference { path });
+        }
+
+        if self
+            .items
+            .keys()
+            .any(|item_type| item_type.eq_ignore_ascii_case("ProjectReference"))
+        {
+            project
+                .project_references
+                .retain(|reference| !reference.path().to_string_lossy().contains("$("));
+        }
+
+        values
+    }
+}
+
+impl EvaluatedItem {
+    /// Returns the identity of the item, its evaluated `Include`.
+    pub fn identity(&self) -> &String {
+        &self.identity
+    }
+
+    /// Returns the value of a metadata, comparing the names ignoring case.
+    pub fn metadata(&self, name: &str) -> Option<&String> {
+        self.metadata
+            .iter()
+            .find(|(metadata, _)| metadata.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+}
+
+/// Returns `true` if some values of the project could not be resolved by the parser: the target
+/// framework is missing or not expanded, or a reference has a version or path that is not
+/// expanded.
+///
+/// Package references without a version are not counted, since their version usually comes from
+/// central package management.
+pub fn needs_evaluation(project: &Project) -> bool {
+    project
+        .target_framework()
+        .is_none_or(|target_framework| is_unresolved(target_framework))
+        || project
+            .package_references()
+            .iter()
+            .any(|reference| reference.version().contains("$("))
+        || project
+            .project_references()
+            .iter()
+            .any(|reference| reference.path().to_string_lossy().contains("$("))
+}
+
+fn is_unresolved(value: &str) -> bool {
+    value.is_empty() || value.contains("$(")
+}
+
+/// Returns `true` if `value` matches `pattern` with its `$(...)` references standing for any
+/// text (ex: `$(SharedDir)/Shared.csproj` matches `../Shared/Shared.csproj`), ignoring case.
+fn matches_unresolved(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let value = value.to_ascii_lowercase();
+
+    let mut literals = vec![];
+    let mut rest = pattern.as_str();
+
+    while let Some(start) = rest.find("$(") {
+        literals.push(&rest[..start]);
+        rest = match rest[start..].find(')') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        };
+    }
+
+    let Some((first, middle)) = literals.split_first() else {
+        return pattern == value;
+    };
+
+    if !value.starts_with(first) {
+        return false;
+    }
+
+    let mut position = first.len();
+
+    for literal in middle {
+        match value[position..].find(literal) {
+            Some(index) => position += index + literal.len(),
+            None => return false,
+        }
+    }
+
+    value.len() >= position + rest.len() && value.ends_with(rest)
+}
+
+/// Replaces a missing or unexpanded value with the evaluated one, returning it if it was merged.
+fn merge(value: &mut Option<String>, evaluated: Option<String>) -> Option<String> {
+    let evaluated = evaluated?;
+
+    if value.as_deref().is_some_and(|value| !is_unresolved(value)) {
+        return None;
+    }
+
+    *value = Some(evaluated.clone());
+
+    Some(evaluated)
+}
+
+fn property(name: &str, value: String) -> LiveValue {
+    LiveValue::Property {
+        name: name.to_string(),
+        value,
+    }
+}
+
+/// Returns the entries of the object `key` of `value`, or nothing if it is not an object.
+fn objects<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    value
+        .get(key)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+/// Represents errors that can occur while evaluating a project with MSBuild.
+#[derive(Debug, Error)]
+pub enum MsBuildError {
+    /// `dotnet` could not be run.
+    #[error("dotnet could not be run")]
+    Io(#[from] io::Error),
+    /// MSBuild could not evaluate the project, with the errors it printed.
+    #[error("MSBuild could not evaluate the project: {0}")]
+    EvaluationFailed(String),
+    /// MSBuild did not finish evaluating the project within the timeout.
+    #[error("MSBuild did not finish evaluating the project within {0:?}")]
+    TimedOut(Duration),
+    /// The output of MSBuild is not valid.
+    #[error("the output of MSBuild is not valid")]
+    InvalidOutput,
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Cursor, path::PathBuf};
+
+    use crate::parser::parse;
+
+    use super::{matches_unresolved, needs_evaluation, LiveEvaluation, LiveValue};
+
+    #[test]
+    pub fn merge_live_evaluation() {
+        // given
+        let content = r#"<Project Sdk="Custom.Sdk/1.0.0">
+  <PropertyGroup>
+    <AssemblyName>Shop.Api</AssemblyName>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="$(SerilogVersion)" />
+    <PackageReference Include="Polly" Version="8.4.1" />
+    <ProjectReference Include="$(SharedDir)\Shared\Shared.csproj" PrivateAssets="all" />
+    <ProjectReference Include="$(GeneratedDir)\Generated.csproj" />
+  </ItemGroup>
+</Project>"#;
+
+        let output = r#"{
+  "Properties": {
+    "TargetFramework": "",
+    "TargetFrameworks": "net8.0;net48",
+    "AssemblyName": "Api",
+    "PackageLicenseExpression": "",
+    "RepositoryUrl": "",
+    "IsPackable": "false"
+  },
+  "Items": {
+    "ProjectReference": [
+      { "Identity": "..\\Shared\\Shared.csproj", "FullPath": "/repo/src/Shared/Shared.csproj" }
+    ],
+    "PackageReference": [
+      { "Identity": "Serilog", "Version": "3.1.1" },
+      { "Identity": "Polly", "Version": "8.4.1" },
+      { "Identity": "StyleCop.Analyzers", "Version": "1.1.118" }
+    ]
+  }
+}"#;
+
+        let mut project = parse(Cursor::new(content), "Api.csproj").unwrap();
+        assert!(needs_evaluation(&project));
+
+        // when
+        let evaluation = LiveEvaluation::parse(output).unwrap();
+        let values = evaluation.merge_into(&mut project);
+
+        // then
+        assert_eq!(
+            values,
+            vec![
+                LiveValue::Property {
+                    name: "TargetFramework".to_string(),
+                    value: "net8.0".to_string()
+                },
+                LiveValue::Property {
+                    name: "IsPackable".to_string(),
+                    value: "false".to_string()
+                },
+                LiveValue::PackageReference {
+                    name: "Serilog".to_string(),
+                    version: "3.1.1".to_string()
+                },
+                LiveValue::PackageReference {
+                    name: "StyleCop.Analyzers".to_string(),
+                    version: "1.1.118".to_string()
+                },
+                LiveValue::ProjectReference {
+                    path: PathBuf::from("../Shared/Shared.csproj")
+                },
+            ]
+        );
+
+        assert_eq!(project.target_framework().unwrap(), "net8.0");
+        assert_eq!(project.assembly_name(), "Shop.Api");
+        assert_eq!(project.package_references()[0].version(), "3.1.1");
+        assert_eq!(project.project_references().len(), 1);
+        assert_eq!(project.project_references()[0].name(), "Shared");
+        assert!(!needs_evaluation(&project));
+    }
+
+    #[test]
+    pub fn match_unresolved_paths() {
+        assert!(matches_unresolved(
+            "$(SharedDir)/Shared/Shared.csproj",
+            "../Shared/Shared.csproj"
+        ));
+        assert!(matches_unresolved(
+            "src/$(Name)/$(Name).csproj",
+            "src/Api/Api.csproj"
+        ));
+        assert!(!matches_unresolved(
+            "$(SharedDir)/Shared.csproj",
+            "../Other/Other.csproj"
+        ));
+        assert!(!matches_unresolved("src/$(Name).csproj", "test/Api.csproj"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    pub fn kill_evaluations_past_the_timeout() {
+        use std::{fs, os::unix::fs::PermissionsExt, time::Duration};
+
+        use super::{MsBuildError, MsBuildEvaluator};
+
+        // given
+        let directory = tempfile::tempdir().unwrap();
+        let dotnet = directory.path().join("dotnet");
+        fs::write(&dotnet, "#!/bin/sh\nexec sleep 10\n").unwrap();
+        fs::set_permissions(&dotnet, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut evaluator = MsBuildEvaluator::new();
+        evaluator.set_dotnet(dotnet);
+        evaluator.set_timeout(Duration::from_millis(200));
+
+        // when
+        let result = evaluator.evaluate("Api.csproj");
+
+        // then
+        assert!(matches!(result, Err(MsBuildError::TimedOut(_))));
+    }
+}
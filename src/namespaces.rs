@@ -0,0 +1,292 @@
+//! A heuristic mapping from packages to the root namespaces they likely provide, to find which
+//! package provides a namespace and which package references are not used by any namespace.
+
+use std::{collections::BTreeMap, io};
+
+use crate::{packages_folder::GlobalPackagesFolder, PackageReference, Project};
+
+/// The suffixes of package names whose types usually live in the namespace of the package
+/// without the suffix (ex: `Polly.Core` provides `Polly`).
+const NAMESPACE_SUFFIXES: [&str; 2] = [".Abstractions", ".Core"];
+
+/// Represents where the namespaces of a package come from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NamespaceSource {
+    /// The names of the assemblies of the package, in the global packages folder.
+    Assembly,
+    /// The name of the package.
+    Convention,
+}
+
+/// Represents the root namespaces a package likely provides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackageNamespaces {
+    namespaces: Vec<String>,
+    source: NamespaceSource,
+}
+
+impl PackageNamespaces {
+    /// Returns the root namespaces, sorted and without duplicates.
+    pub fn namespaces(&self) -> &Vec<String> {
+        &self.namespaces
+    }
+
+    /// Returns where the namespaces come from.
+    pub fn source(&self) -> NamespaceSource {
+        self.source
+    }
+}
+
+/// Represents a mapping from package names to the root namespaces they likely provide.
+///
+/// Package names are compared ignoring case, like NuGet does.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::namespaces::NamespaceMap;
+///
+/// let mut map = NamespaceMap::new();
+/// map.insert_convention("Serilog");
+/// map.insert_convention("Serilog.Sinks.Console");
+/// map.insert_convention("Polly.Core");
+///
+/// assert_eq!(
+///     map.packages_providing("Serilog.Sinks.SystemConsole.Themes"),
+///     vec!["Serilog"]
+/// );
+/// assert_eq!(map.packages_providing("Polly.Retry"), vec!["Polly.Core"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceMap {
+    packages: BTreeMap<String, PackageNamespaces>,
+}
+
+impl NamespaceMap {
+    /// Creates a new empty `NamespaceMap`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the mapping of every package referenced by `projects`.
+    ///
+    /// When `folder` is given and has the referenced version of a package, the namespaces are
+    /// the names of its assemblies. Otherwise, they are guessed from the name of the package.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a package directory could not be read.
+    pub fn build(
+        projects: &[Project],
+        folder: Option<&GlobalPackagesFolder>,
+    ) -> Result<Self, io::Error> {
+        let mut map = Self::new();
+
+        for reference in projects
+            .iter()
+            .flat_map(|project| project.package_references())
+        {
+            if map.get(reference.name()).is_some() {
+                continue;
+            }
+
+            let assemblies = match folder {
+                Some(folder) => folder.assembly_names(reference.name(), reference.version())?,
+                None => vec![],
+            };
+
+            if assemblies.is_empty() {
+                map.insert_convention(reference.name());
+            } else {
+                map.insert(reference.name(), assemblies, NamespaceSource::Assembly);
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Sets the root namespaces of a package, replacing the previous ones.
+    pub fn insert<S>(&mut self, package: &str, namespaces: Vec<S>, source: NamespaceSource)
+    where
+        S: Into<String>,
+    {
+        let mut namespaces: Vec<String> = namespaces.into_iter().map(Into::into).collect();
+        namespaces.sort();
+        namespaces.dedup();
+
+        self.remove(package);
+        self.packages.insert(
+            package.to_string(),
+            PackageNamespaces { namespaces, source },
+        );
+    }
+
+    /// Sets the root namespaces of a package from its name: the name itself and, for names
+    /// ending in `.Abstractions` or `.Core`, the name without that suffix.
+    pub fn insert_convention(&mut self, package: &str) {
+        let mut namespaces = vec![package.to_string()];
+
+        namespaces.extend(NAMESPACE_SUFFIXES.iter().filter_map(|suffix| {
+            let prefix = package.strip_suffix(suffix)?;
+            (!prefix.is_empty()).then(|| prefix.to_string())
+        }));
+
+        self.insert(package, namespaces, NamespaceSource::Convention);
+    }
+
+    /// Returns the root namespaces of a package, if it is in the mapping.
+    pub fn get(&self, package: &str) -> Option<&PackageNamespaces> {
+        self.packages
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(package))
+            .map(|(_, namespaces)| namespaces)
+    }
+
+    /// Returns the packages and their root namespaces, by package name.
+    pub fn packages(&self) -> &BTreeMap<String, PackageNamespaces> {
+        &self.packages
+    }
+
+    /// Returns the packages that likely provide `namespace`, the most specific first.
+    ///
+    /// A package provides a namespace when one of its root namespaces is the namespace or one of
+    /// its parents (ex: `Serilog` provides `Serilog.Events`), ignoring case. Packages whose
+    /// longest matching root namespace is the same are sorted by name.
+    pub fn packages_providing(&self, namespace: &str) -> Vec<&String> {
+        let mut providers: Vec<(usize, &String)> = self
+            .packages
+            .iter()
+            .filter_map(|(package, namespaces)| {
+                let longest = namespaces
+                    .namespaces
+                    .iter()
+                    .filter(|root| is_within(namespace, root))
+                    .map(String::len)
+                    .max()?;
+
+                Some((longest, package))
+            })
+            .collect();
+
+        providers.sort_by(|(a, a_name), (b, b_name)| b.cmp(a).then(a_name.cmp(b_name)));
+
+        providers.into_iter().map(|(_, package)| package).collect()
+    }
+
+    /// Returns the package references of the project that do not provide any of the
+    /// `used_namespaces` (ex: the namespaces of its `using` directives).
+    ///
+    /// Packages missing from the mapping are never returned, since nothing is known about them.
+    /// Packages without namespaces of their own (ex: analyzers or meta-packages) are returned
+    /// too, so the result is a list of candidates to review rather than to remove.
+    pub fn unused_packages<'a, I, S>(
+        &self,
+        project: &'a Project,
+        used_namespaces: I,
+    ) -> Vec<&'a PackageReference>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let used: Vec<S> = used_namespaces.into_iter().collect();
+
+        project
+            .package_references()
+            .iter()
+            .filter(|reference| {
+                self.get(reference.name()).is_some_and(|namespaces| {
+                    !namespaces.namespaces.iter().any(|root| {
+                        used.iter()
+                            .any(|namespace| is_within(namespace.as_ref(), root))
+                    })
+                })
+            })
+            .collect()
+    }
+
+    fn remove(&mut self, package: &str) {
+        self.packages
+            .retain(|name, _| !name.eq_ignore_ascii_case(package));
+    }
+}
+
+/// Returns `true` if `namespace` is `root` or one of its children, ignoring case.
+fn is_within(namespace: &str, root: &str) -> bool {
+    match namespace.get(..root.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(root) => {
+            namespace.len() == root.len() || namespace[root.len()..].starts_with('.')
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, io::Cursor};
+
+    use tempfile::tempdir;
+
+    use crate::{packages_folder::GlobalPackagesFolder, parser::parse};
+
+    use super::{NamespaceMap, NamespaceSource};
+
+    #[test]
+    pub fn map_packages_to_namespaces() {
+        // given
+        let project = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Newtonsoft.Json" Version="13.0.3" />
+    <PackageReference Include="Polly.Core" Version="8.4.1" />
+    <PackageReference Include="Serilog" Version="3.1.1" />
+    <PackageReference Include="Serilog.Sinks.Console" Version="5.0.1" />
+  </ItemGroup>
+</Project>"#;
+
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("newtonsoft.json/13.0.3/lib/net6.0")).unwrap();
+        fs::create_dir_all(root.join("newtonsoft.json/13.0.3/lib/netstandard2.0")).unwrap();
+        fs::write(
+            root.join("newtonsoft.json/13.0.3/lib/net6.0/Newtonsoft.Json.dll"),
+            "",
+        )
+        .unwrap();
+        fs::write(
+            root.join("newtonsoft.json/13.0.3/lib/netstandard2.0/Newtonsoft.Json.dll"),
+            "",
+        )
+        .unwrap();
+
+        let project = parse(Cursor::new(project), "Api.csproj").unwrap();
+        let folder = GlobalPackagesFolder::new(root.to_path_buf());
+
+        // when
+        let map = NamespaceMap::build(std::slice::from_ref(&project), Some(&folder)).unwrap();
+
+        // then
+        let json = map.get("newtonsoft.json").unwrap();
+        assert_eq!(json.namespaces(), &vec!["Newtonsoft.Json".to_string()]);
+        assert_eq!(json.source(), NamespaceSource::Assembly);
+        assert_eq!(
+            map.get("Polly.Core").unwrap().namespaces(),
+            &vec!["Polly".to_string(), "Polly.Core".to_string()]
+        );
+        assert_eq!(
+            map.packages_providing("Serilog.Sinks.Console.Themes"),
+            vec!["Serilog.Sinks.Console", "Serilog"]
+        );
+        assert!(map.packages_providing("SerilogTimings").is_empty());
+
+        let unused: Vec<&str> = map
+            .unused_packages(&project, ["System.Linq", "Polly.Retry", "Serilog.Events"])
+            .iter()
+            .map(|reference| reference.name().as_str())
+            .collect();
+        assert_eq!(unused, vec!["Newtonsoft.Json", "Serilog.Sinks.Console"]);
+
+        dir.close().unwrap();
+    }
+}
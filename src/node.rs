@@ -0,0 +1,57 @@
+//! Node.js bindings built with napi-rs, so editor extensions and JavaScript tools can use the
+//! library in process instead of spawning the command line tool for every query.
+//!
+//! The results are returned as JSON strings, in the shape of the serialization of the library
+//! types.
+//!
+//! Build the addon with `napi build --features node`, or with
+//! `cargo rustc --lib --crate-type cdylib --features node`.
+
+use napi::{Error, Result};
+use napi_derive::napi;
+
+use crate::{graph::DependencyGraph, search, workspace::Workspace, Project};
+
+/// Returns the paths of the project files under `root`.
+#[napi(js_name = "searchProjects")]
+pub fn search_projects(root: String) -> Result<Vec<String>> {
+    let paths = search::search_projects(&root).map_err(error)?;
+
+    Ok(paths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect())
+}
+
+/// Parses the project file at `path` and returns the project as JSON.
+#[napi(js_name = "parseProject")]
+pub fn parse_project(path: String) -> Result<String> {
+    let project = Project::new(path).map_err(error)?;
+
+    serde_json::to_string(&project).map_err(error)
+}
+
+/// Loads every project under `root` and returns the workspace as JSON.
+#[napi(js_name = "loadWorkspace")]
+pub fn load_workspace(root: String) -> Result<String> {
+    let workspace = Workspace::load(root).map_err(error)?;
+
+    serde_json::to_string(&workspace).map_err(error)
+}
+
+/// Loads every project under `root` and returns its dependency graph as JSON, with the package
+/// nodes when `include_packages` is `true`.
+#[napi(js_name = "dependencyGraph")]
+pub fn dependency_graph(root: String, include_packages: bool) -> Result<String> {
+    let workspace = Workspace::load(root).map_err(error)?;
+    let graph = DependencyGraph::from_workspace(&workspace, include_packages);
+
+    serde_json::to_string(&graph).map_err(error)
+}
+
+fn error<E>(error: E) -> Error
+where
+    E: std::error::Error,
+{
+    Error::from_reason(error.to_string())
+}
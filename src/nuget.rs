@@ -0,0 +1,1138 @@
+//! A client for NuGet V3 feeds, used to look up package metadata such as the latest versions.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    nuget_config::{FeedCredentials, PackageSource},
+    nuspec::{folder_version, Nuspec},
+    parser::ParseError,
+    suggestions::{FindingKind, PackageFinding},
+    validation::{Severity, Violation},
+    version::{NuGetVersion, VersionDelta},
+    Project,
+};
+
+/// The service index of the official nuget.org feed.
+pub const NUGET_ORG_SERVICE_INDEX: &str = "https://api.nuget.org/v3/index.json";
+
+/// The rule id used for violations about deprecated packages.
+pub const DEPRECATED_PACKAGE_RULE_ID: &str = "DL0101";
+
+const PACKAGE_BASE_ADDRESS: [&str; 1] = ["PackageBaseAddress/3.0.0"];
+const REGISTRATIONS_BASE_URL: [&str; 3] = [
+    "RegistrationsBaseUrl/3.6.0",
+    "RegistrationsBaseUrl/3.4.0",
+    "RegistrationsBaseUrl",
+];
+
+/// A blocking HTTP client for a NuGet V3 feed.
+///
+/// The client works with nuget.org and with private feeds such as Azure Artifacts and GitHub
+/// Packages. Credentials are only sent to the host of the feed's service index.
+pub struct NugetClient {
+    agent: ureq::Agent,
+    service_index: String,
+    credentials: Option<FeedCredentials>,
+    resources: OnceLock<Vec<ServiceResource>>,
+    cache: Option<HttpCache>,
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+/// An on-disk cache for the responses of a NuGet feed.
+///
+/// Responses are stored with their `ETag`, so stale entries are revalidated with a conditional
+/// request instead of being downloaded again. Entries younger than the time to live are used
+/// without any request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpCache {
+    directory: PathBuf,
+    time_to_live: Duration,
+}
+
+impl NugetClient {
+    /// Creates a new `NugetClient` for the nuget.org feed.
+    pub fn new() -> Self {
+        Self::with_service_index(NUGET_ORG_SERVICE_INDEX.to_string())
+    }
+
+    /// Creates a new `NugetClient` for the feed with the given service index URL
+    /// (ex: "https://api.nuget.org/v3/index.json").
+    pub fn with_service_index(service_index: String) -> Self {
+        Self::with_credentials(service_index, None)
+    }
+
+    /// Creates a new `NugetClient` for an authenticated feed, using basic authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_index` - The service index URL of the feed.
+    /// * `credentials` - The credentials of the feed, if it requires authentication.
+    pub fn with_credentials(service_index: String, credentials: Option<FeedCredentials>) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().build(),
+            service_index,
+            credentials,
+            resources: OnceLock::new(),
+            cache: None,
+            min_interval: Duration::ZERO,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Sets the on-disk cache used for the feed responses.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::nuget::{HttpCache, NugetClient};
+    /// use std::time::Duration;
+    ///
+    /// let mut client = NugetClient::new();
+    /// client.set_cache(Some(HttpCache::new(".cache/nuget".into(), Duration::from_secs(3600))));
+    /// client.set_rate_limit(10);
+    /// ```
+    pub fn set_cache(&mut self, cache: Option<HttpCache>) {
+        self.cache = cache;
+    }
+
+    /// Limits the number of requests sent to the feed per second. `0` disables the limit.
+    pub fn set_rate_limit(&mut self, requests_per_second: u32) {
+        self.min_interval = match requests_per_second {
+            0 => Duration::ZERO,
+            requests => Duration::from_secs(1) / requests,
+        };
+    }
+
+    /// Creates a new `NugetClient` for a package source read from a `NuGet.config` file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{nuget::NugetClient, nuget_config::NugetConfig};
+    ///
+    /// let config = NugetConfig::load("path/to/NuGet.config")?;
+    /// let client = NugetClient::from_source(config.source("company").unwrap());
+    ///
+    /// let latest = client.latest_version("Company.Core", false)?;
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_source(source: &PackageSource) -> Self {
+        Self::with_credentials(source.url().clone(), source.credentials().cloned())
+    }
+
+    /// Returns the service index URL of the feed.
+    pub fn service_index(&self) -> &String {
+        &self.service_index
+    }
+
+    /// Returns every listed version of the package published in the feed, in ascending order.
+    ///
+    /// The versions are read from the registration index, since the package content index also
+    /// lists the unlisted versions, which NuGet does not offer as updates. Versions that could not
+    /// be parsed are ignored. An empty list is returned if the package does not exist in the feed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the feed could not be queried.
+    pub fn versions(&self, package: &str) -> Result<Vec<NuGetVersion>, NugetError> {
+        let mut versions: Vec<NuGetVersion> = self
+            .registration_leaves(package)?
+            .into_iter()
+            .filter(|leaf| leaf.catalog_entry.listed)
+            .filter_map(|leaf| leaf.catalog_entry.version.parse().ok())
+            .collect();
+
+        versions.sort();
+        versions.dedup();
+
+        Ok(versions)
+    }
+
+    /// Returns the latest version of the package, if the package exists in the feed.
+    ///
+    /// # Arguments
+    ///
+    /// * `package` - The name of the package.
+    /// * `include_prerelease` - Whether prerelease versions should be considered.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the feed could not be queried.
+    pub fn latest_version(
+        &self,
+        package: &str,
+        include_prerelease: bool,
+    ) -> Result<Option<NuGetVersion>, NugetError> {
+        Ok(self
+            .versions(package)?
+            .into_iter()
+            .rfind(|version| include_prerelease || !version.is_prerelease()))
+    }
+
+    /// Returns every package reference of the projects that has a newer version in the feed.
+    ///
+    /// Each package is looked up only once, even if it is referenced by many projects. Package
+    /// references whose version is not a plain version (ex: floating versions or ranges) are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the feed could not be queried.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{nuget::NugetClient, Project};
+    ///
+    /// let project = Project::new("path/to/MyProject.csproj")?;
+    ///
+    /// for outdated in NugetClient::new().outdated(&[project], false)? {
+    ///     println!("{}: {} -> {}", outdated.name(), outdated.current(), outdated.latest());
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn outdated(
+        &self,
+        projects: &[Project],
+        include_prerelease: bool,
+    ) -> Result<Vec<OutdatedPackage>, NugetError> {
+        let mut latest_versions: HashMap<String, Option<NuGetVersion>> = HashMap::new();
+        let mut outdated = vec![];
+
+        for project in projects {
+            for package_reference in project.package_references() {
+                let Ok(current) = package_reference.version().parse::<NuGetVersion>() else {
+                    continue;
+                };
+
+                let key = package_reference.name().to_lowercase();
+
+                let latest = match latest_versions.get(&key) {
+                    Some(latest) => latest.clone(),
+                    None => {
+                        let latest =
+                            self.latest_version(package_reference.name(), include_prerelease)?;
+                        latest_versions.insert(key, latest.clone());
+
+                        latest
+                    }
+                };
+
+                let Some(latest) = latest else {
+                    continue;
+                };
+
+                if let Some(delta) = current.delta(&latest).filter(|_| current < latest) {
+                    outdated.push(OutdatedPackage {
+                        project: project.path().clone(),
+                        name: package_reference.name().to_string(),
+                        current,
+                        latest,
+                        delta,
+                    });
+                }
+            }
+        }
+
+        Ok(outdated)
+    }
+
+    /// Returns the deprecation information of a package version, if it is marked as deprecated.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the feed could not be queried.
+    pub fn deprecation(
+        &self,
+        package: &str,
+        version: &NuGetVersion,
+    ) -> Result<Option<PackageDeprecation>, NugetError> {
+        let leaf = self.registration_leaves(package)?.into_iter().find(|leaf| {
+            leaf.catalog_entry
+                .version
+                .parse::<NuGetVersion>()
+                .is_ok_and(|leaf_version| leaf_version == *version)
+        });
+
+        Ok(leaf.and_then(|leaf| leaf.catalog_entry.deprecation))
+    }
+
+    /// Returns every package reference of the projects whose version is marked as deprecated.
+    ///
+    /// Each package version is looked up only once, even if it is referenced by many projects.
+    /// Package references whose version is not a plain version are ignored.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the feed could not be queried.
+    pub fn deprecated(&self, projects: &[Project]) -> Result<Vec<DeprecatedPackage>, NugetError> {
+        let mut deprecations: HashMap<(String, String), Option<PackageDeprecation>> =
+            HashMap::new();
+        let mut deprecated = vec![];
+
+        for project in projects {
+            for package_reference in project.package_references() {
+                let Ok(version) = package_reference.version().parse::<NuGetVersion>() else {
+                    continue;
+                };
+
+                let key = (
+                    package_reference.name().to_lowercase(),
+                    version.to_string().to_lowercase(),
+                );
+
+                let deprecation = match deprecations.get(&key) {
+                    Some(deprecation) => deprecation.clone(),
+                    None => {
+                        let deprecation = self.deprecation(package_reference.name(), &version)?;
+                        deprecations.insert(key, deprecation.clone());
+
+                        deprecation
+                    }
+                };
+
+                if let Some(deprecation) = deprecation {
+                    deprecated.push(DeprecatedPackage {
+                        project: project.path().clone(),
+                        name: package_reference.name().to_string(),
+                        version,
+                        deprecation,
+                    });
+                }
+            }
+        }
+
+        Ok(deprecated)
+    }
+
+    /// Downloads and parses the `.nuspec` of a package version, if it exists in the feed.
+    ///
+    /// The version is normalized like in the feed URLs (ex: `1.0` is downloaded as `1.0.0`).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the feed could not be queried or if the `.nuspec`
+    /// could not be parsed.
+    pub fn nuspec(&self, package: &str, version: &str) -> Result<Option<Nuspec>, NugetError> {
+        let package = package.to_lowercase();
+
+        let url = format!(
+            "{}{}/{}/{}.nuspec",
+            self.resource_url(&PACKAGE_BASE_ADDRESS)?,
+            package,
+            folder_version(version),
+            package
+        );
+
+        match self.fetch(&url)? {
+            Some(content) => Ok(Some(Nuspec::parse(content.as_bytes())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Attaches the license of every package reference that does not have one yet, downloading
+    /// the `.nuspec` of each package version from the feed.
+    ///
+    /// Use `nuspec::attach_licenses` first to avoid downloads for packages already present in the
+    /// global packages folder.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the feed could not be queried or if a `.nuspec` could
+    /// not be parsed.
+    pub fn attach_licenses(&self, projects: &mut [Project]) -> Result<(), NugetError> {
+        let mut nuspecs: HashMap<(String, String), Option<Nuspec>> = HashMap::new();
+
+        for project in projects {
+            for package_reference in project.package_references_mut() {
+                if package_reference.license().is_some() {
+                    continue;
+                }
+
+                let key = (
+                    package_reference.name().to_lowercase(),
+                    package_reference.version().to_lowercase(),
+                );
+
+                if !nuspecs.contains_key(&key) {
+                    let nuspec =
+                        self.nuspec(package_reference.name(), package_reference.version())?;
+                    nuspecs.insert(key.clone(), nuspec);
+                }
+
+                let license = nuspecs[&key]
+                    .as_ref()
+                    .and_then(|nuspec| nuspec.license().cloned());
+
+                package_reference.set_license(license);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the versions of the package in its registration index, downloading the pages that
+    /// are not inlined in the index.
+    fn registration_leaves(&self, package: &str) -> Result<Vec<RegistrationLeaf>, NugetError> {
+        let url = format!(
+            "{}{}/index.json",
+            self.resource_url(&REGISTRATIONS_BASE_URL)?,
+            package.to_lowercase()
+        );
+
+        let Some(index) = self.get_json::<RegistrationIndex>(&url)? else {
+            return Ok(vec![]);
+        };
+
+        let mut leaves = vec![];
+
+        for page in index.items {
+            match page.items {
+                Some(items) => leaves.extend(items),
+                None => {
+                    if let Some(page) = self.get_json::<RegistrationPage>(&page.id)? {
+                        leaves.extend(page.items.unwrap_or_default());
+                    }
+                }
+            }
+        }
+
+        Ok(leaves)
+    }
+
+    fn resource_url(&self, resource_types: &[&'static str]) -> Result<String, NugetError> {
+        let resources = match self.resources.get() {
+            Some(resources) => resources,
+            None => {
+                let index = self
+                    .get_json::<ServiceIndex>(&self.service_index)?
+                    .ok_or(NugetError::ResourceNotFound(resource_types[0]))?;
+
+                self.resources.get_or_init(|| index.resources)
+            }
+        };
+
+        let mut url = resource_types
+            .iter()
+            .find_map(|resource_type| {
+                resources
+                    .iter()
+                    .find(|resource| resource.resource_type == *resource_type)
+            })
+            .map(|resource| resource.id.clone())
+            .ok_or(NugetError::ResourceNotFound(resource_types[0]))?;
+
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+
+        Ok(url)
+    }
+
+    fn fetch(&self, url: &str) -> Result<Option<String>, NugetError> {
+        let cached = self.cache.as_ref().and_then(|cache| cache.get(url));
+
+        if let (Some(cache), Some(entry)) = (&self.cache, &cached) {
+            if entry.age() < cache.time_to_live {
+                return Ok(Some(entry.body.clone()));
+            }
+        }
+
+        self.throttle();
+
+        let mut request = self.get(url);
+
+        if let Some(etag) = cached.as_ref().and_then(|entry| entry.etag.as_ref()) {
+            request = request.set("If-None-Match", etag);
+        }
+
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, _)) => return Ok(None),
+            Err(error) => return Err(NugetError::RequestError(Box::new(error))),
+        };
+
+        let entry = match (response.status(), cached) {
+            (304, Some(cached)) => CacheEntry {
+                fetched_at: now(),
+                ..cached
+            },
+            _ => CacheEntry {
+                url: url.to_string(),
+                etag: response.header("ETag").map(str::to_string),
+                fetched_at: now(),
+                body: response.into_string()?,
+            },
+        };
+
+        if let Some(cache) = &self.cache {
+            cache.put(&entry)?;
+        }
+
+        Ok(Some(entry.body))
+    }
+
+    fn throttle(&self) {
+        if self.min_interval.is_zero() {
+            return;
+        }
+
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(elapsed) = last_request.map(|last| last.elapsed()) {
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+
+        *last_request = Some(Instant::now());
+    }
+
+    fn get(&self, url: &str) -> ureq::Request {
+        let request = self.agent.get(url);
+
+        match &self.credentials {
+            Some(credentials) if host(url) == host(&self.service_index) => {
+                let token = STANDARD.encode(format!(
+                    "{}:{}",
+                    credentials.username(),
+                    credentials.password()
+                ));
+
+                request.set("Authorization", &format!("Basic {token}"))
+            }
+            _ => request,
+        }
+    }
+
+    fn get_json<T>(&self, url: &str) -> Result<Option<T>, NugetError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        match self.fetch(url)? {
+            Some(content) => Ok(Some(serde_json::from_str(&content)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl HttpCache {
+    /// Creates a new `HttpCache` storing the responses in the given directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory where the responses are stored. It is created when needed.
+    /// * `time_to_live` - For how long a response is used without revalidating it with the feed.
+    pub fn new(directory: PathBuf, time_to_live: Duration) -> Self {
+        Self {
+            directory,
+            time_to_live,
+        }
+    }
+
+    /// Returns the directory where the responses are stored.
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Returns for how long a response is used without revalidating it with the feed.
+    pub fn time_to_live(&self) -> Duration {
+        self.time_to_live
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        // FNV-1a, stable across Rust versions, unlike the standard library hasher
+        let hash = url.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+
+        self.directory.join(format!("{hash:016x}.json"))
+    }
+
+    fn get(&self, url: &str) -> Option<CacheEntry> {
+        let content = fs::read_to_string(self.entry_path(url)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+
+        (entry.url == url).then_some(entry)
+    }
+
+    fn put(&self, entry: &CacheEntry) -> Result<(), NugetError> {
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.entry_path(&entry.url), serde_json::to_vec(entry)?)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    fetched_at: u64,
+    body: String,
+}
+
+impl CacheEntry {
+    fn age(&self) -> Duration {
+        Duration::from_secs(now().saturating_sub(self.fetched_at))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+impl Default for NugetClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn host(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+
+    rest.split(['/', '?', '#']).next()
+}
+
+/// Represents a package reference that has a newer version available in the feed.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OutdatedPackage {
+    project: PathBuf,
+    name: String,
+    current: NuGetVersion,
+    latest: NuGetVersion,
+    delta: VersionDelta,
+}
+
+impl OutdatedPackage {
+    /// Returns the path of the project that references the package.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the name of the package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the version currently referenced by the project.
+    pub fn current(&self) -> &NuGetVersion {
+        &self.current
+    }
+
+    /// Returns the latest version available in the feed.
+    pub fn latest(&self) -> &NuGetVersion {
+        &self.latest
+    }
+
+    /// Returns which part of the version changed between the current and the latest versions.
+    pub fn delta(&self) -> VersionDelta {
+        self.delta
+    }
+
+    /// Converts the outdated package into a `PackageFinding`, used to suggest updates.
+    pub fn to_finding(&self) -> PackageFinding {
+        PackageFinding::new(
+            self.project.clone(),
+            self.name.clone(),
+            self.current.clone(),
+            FindingKind::Outdated {
+                latest: self.latest.clone(),
+            },
+        )
+    }
+}
+
+/// Represents the deprecation information of a package version.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageDeprecation {
+    #[serde(default)]
+    reasons: Vec<String>,
+    message: Option<String>,
+    alternate_package: Option<AlternatePackage>,
+}
+
+impl PackageDeprecation {
+    /// Returns the reasons of the deprecation (ex: "Legacy", "CriticalBugs", "Other").
+    pub fn reasons(&self) -> &Vec<String> {
+        &self.reasons
+    }
+
+    /// Returns the message written by the package author, if any.
+    pub fn message(&self) -> Option<&String> {
+        self.message.as_ref()
+    }
+
+    /// Returns the package suggested as an alternative, if any.
+    pub fn alternate_package(&self) -> Option<&AlternatePackage> {
+        self.alternate_package.as_ref()
+    }
+}
+
+/// Represents the package suggested as an alternative to a deprecated package.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Deserialize)]
+pub struct AlternatePackage {
+    #[serde(rename = "id")]
+    name: String,
+    range: Option<String>,
+}
+
+impl AlternatePackage {
+    /// Returns the name of the alternative package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the version range of the alternative package, if any.
+    pub fn range(&self) -> Option<&String> {
+        self.range.as_ref()
+    }
+}
+
+/// Represents a package reference whose version is marked as deprecated in the feed.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Deserialize)]
+pub struct DeprecatedPackage {
+    project: PathBuf,
+    name: String,
+    version: NuGetVersion,
+    deprecation: PackageDeprecation,
+}
+
+impl DeprecatedPackage {
+    /// Returns the path of the project that references the package.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the name of the package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the version referenced by the project.
+    pub fn version(&self) -> &NuGetVersion {
+        &self.version
+    }
+
+    /// Returns the deprecation information.
+    pub fn deprecation(&self) -> &PackageDeprecation {
+        &self.deprecation
+    }
+
+    /// Converts the deprecated package into a `PackageFinding`, used to suggest updates.
+    pub fn to_finding(&self) -> PackageFinding {
+        PackageFinding::new(
+            self.project.clone(),
+            self.name.clone(),
+            self.version.clone(),
+            FindingKind::Deprecated {
+                alternative: self
+                    .deprecation
+                    .alternate_package
+                    .as_ref()
+                    .map(|alternate| alternate.name.clone()),
+            },
+        )
+    }
+
+    /// Converts the deprecated package into a `Violation`, so it can be added to a `Report`.
+    pub fn to_violation(&self) -> Violation {
+        let mut message = format!(
+            "package '{}' {} is deprecated ({})",
+            self.name,
+            self.version,
+            self.deprecation.reasons.join(", ")
+        );
+
+        if let Some(alternate) = &self.deprecation.alternate_package {
+            message.push_str(&format!(", use '{}' instead", alternate.name));
+        }
+
+        Violation::new(
+            DEPRECATED_PACKAGE_RULE_ID.to_string(),
+            Severity::Warning,
+            message,
+            self.project.clone(),
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct ServiceIndex {
+    resources: Vec<ServiceResource>,
+}
+
+#[derive(Deserialize)]
+struct RegistrationIndex {
+    items: Vec<RegistrationPage>,
+}
+
+#[derive(Deserialize)]
+struct RegistrationPage {
+    #[serde(rename = "@id")]
+    id: String,
+    items: Option<Vec<RegistrationLeaf>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RegistrationLeaf {
+    catalog_entry: CatalogEntry,
+}
+
+#[derive(Deserialize)]
+struct CatalogEntry {
+    version: String,
+    #[serde(default = "listed_by_default")]
+    listed: bool,
+    deprecation: Option<PackageDeprecation>,
+}
+
+/// Feeds may omit `listed` from the catalog entries, which are listed unless marked otherwise.
+fn listed_by_default() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct ServiceResource {
+    #[serde(rename = "@id")]
+    id: String,
+    #[serde(rename = "@type")]
+    resource_type: String,
+}
+
+/// Represents errors that can occur while querying a NuGet feed.
+#[derive(Debug, Error)]
+pub enum NugetError {
+    /// The HTTP request to the feed failed.
+    #[error("the request to the feed failed: {0}")]
+    RequestError(Box<ureq::Error>),
+    /// An I/O error occurred while reading the response or the cache.
+    #[error("there was an error while reading the feed response")]
+    IoError(#[from] io::Error),
+    /// The response of the feed could not be deserialized.
+    #[error("there was an error while deserializing the feed response")]
+    DeserializationError(#[from] serde_json::Error),
+    /// A file downloaded from the feed could not be parsed.
+    #[error("there was an error while parsing a file downloaded from the feed")]
+    ParseError(#[from] ParseError),
+    /// The feed does not provide a resource required by the client.
+    #[error("the feed does not provide the resource {0}")]
+    ResourceNotFound(&'static str),
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[test]
+    pub fn cache_entries_are_stored_per_url() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let cache = HttpCache::new(dir.path().join("cache"), Duration::from_secs(60));
+
+        let entry = CacheEntry {
+            url: "https://api.nuget.org/v3/index.json".to_string(),
+            etag: Some("\"abc\"".to_string()),
+            fetched_at: now(),
+            body: "{}".to_string(),
+        };
+
+        // when
+        cache.put(&entry).unwrap();
+
+        // then
+        let cached = cache.get("https://api.nuget.org/v3/index.json").unwrap();
+        assert_eq!(cached.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(cached.body, "{}");
+        assert!(cached.age() < Duration::from_secs(60));
+        assert!(cache.get("https://api.nuget.org/v3/other.json").is_none());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    pub fn credentials_are_only_sent_to_the_feed_host() {
+        assert_eq!(
+            host("https://pkgs.dev.azure.com/company/_packaging/feed/nuget/v3/index.json"),
+            Some("pkgs.dev.azure.com")
+        );
+        assert_eq!(
+            host("https://nuget.pkg.github.com?x=1"),
+            Some("nuget.pkg.github.com")
+        );
+        assert_eq!(host("not a url"), None);
+    }
+
+    #[test]
+    pub fn deserialize_registration_leaf_with_deprecation() {
+        // given
+        let content = r#"{
+            "@id": "https://api.nuget.org/v3/registration5-semver1/foo/index.json",
+            "items": [{
+                "catalogEntry": {
+                    "version": "1.0.0",
+                    "deprecation": {
+                        "reasons": ["Legacy"],
+                        "alternatePackage": { "id": "Bar", "range": "[2.0.0, )" }
+                    }
+                }
+            }]
+        }"#;
+
+        // when
+        let page: RegistrationPage = serde_json::from_str(content).unwrap();
+
+        // then
+        let deprecation = page.items.unwrap()[0]
+            .catalog_entry
+            .deprecation
+            .clone()
+            .unwrap();
+
+        let deprecated = DeprecatedPackage {
+            project: PathBuf::from("Api.csproj"),
+            name: "Foo".to_string(),
+            version: "1.0.0".parse().unwrap(),
+            deprecation,
+        };
+
+        assert_eq!(
+            deprecated.to_violation().message(),
+            "package 'Foo' 1.0.0 is deprecated (Legacy), use 'Bar' instead"
+        );
+    }
+
+    /// Returns a client for a feed whose responses are already in its cache, so it does not
+    /// send any request.
+    ///
+    /// The versions of each package are a JSON array of versions, or of catalog entries for the
+    /// versions with more details (ex: `{"version": "2.0.0", "listed": false}`).
+    fn stub_feed(cache: &std::path::Path, versions: &[(&str, &str)]) -> NugetClient {
+        let service_index = "https://feed.test/v3/index.json";
+        let cache = HttpCache::new(cache.to_path_buf(), Duration::from_secs(3600));
+
+        stub_response(
+            &cache,
+            service_index,
+            r#"{"resources": [
+                {"@id": "https://feed.test/v3/flat", "@type": "PackageBaseAddress/3.0.0"},
+                {"@id": "https://feed.test/v3/registrations", "@type": "RegistrationsBaseUrl/3.6.0"}
+            ]}"#,
+        );
+
+        for (package, versions) in versions {
+            let url = format!("https://feed.test/v3/registrations/{package}/index.json");
+            let leaves: Vec<Value> = serde_json::from_str::<Vec<Value>>(versions)
+                .unwrap()
+                .into_iter()
+                .map(|entry| match entry {
+                    Value::String(version) => json!({ "version": version }),
+                    entry => entry,
+                })
+                .map(|entry| json!({ "catalogEntry": entry }))
+                .collect();
+
+            let index = json!({ "items": [{ "@id": url, "items": leaves }] });
+            stub_response(&cache, &url, &index.to_string());
+        }
+
+        let mut client = NugetClient::with_service_index(service_index.to_string());
+        client.set_cache(Some(cache));
+
+        client
+    }
+
+    /// Stores the response of the feed to a URL in the cache.
+    fn stub_response(cache: &HttpCache, url: &str, body: &str) {
+        let entry = CacheEntry {
+            url: url.to_string(),
+            etag: None,
+            fetched_at: now(),
+            body: body.to_string(),
+        };
+
+        cache.put(&entry).unwrap();
+    }
+
+    #[test]
+    pub fn nuspec_is_downloaded_for_the_normalized_version() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let client = stub_feed(dir.path(), &[]);
+
+        stub_response(
+            client.cache.as_ref().unwrap(),
+            "https://feed.test/v3/flat/serilog/1.0.0/serilog.nuspec",
+            r#"<package>
+  <metadata>
+    <id>Serilog</id>
+    <version>1.0.0</version>
+    <license type="expression">Apache-2.0</license>
+  </metadata>
+</package>"#,
+        );
+
+        // when
+        let nuspec = client.nuspec("Serilog", "1.0").unwrap();
+
+        // then
+        assert!(nuspec.unwrap().license().is_some());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    pub fn latest_version_includes_prereleases_when_requested() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let client = stub_feed(
+            dir.path(),
+            &[(
+                "serilog",
+                r#"["1.0.0", "2.0.0-beta.1", "1.10.0", "not a version", "1.2.0", "2.0.0-alpha"]"#,
+            )],
+        );
+
+        // when
+        let versions = client.versions("Serilog").unwrap();
+
+        // then
+        let versions: Vec<String> = versions.iter().map(|version| version.to_string()).collect();
+        assert_eq!(
+            versions,
+            vec!["1.0.0", "1.2.0", "1.10.0", "2.0.0-alpha", "2.0.0-beta.1"]
+        );
+
+        assert_eq!(
+            client.latest_version("Serilog", false).unwrap().unwrap(),
+            "1.10.0".parse().unwrap()
+        );
+        assert_eq!(
+            client.latest_version("Serilog", true).unwrap().unwrap(),
+            "2.0.0-beta.1".parse().unwrap()
+        );
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    pub fn unlisted_versions_are_not_offered() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let client = stub_feed(
+            dir.path(),
+            &[(
+                "polly",
+                r#"["7.2.4", {"version": "8.0.0", "listed": false}, {"version": "7.2.5", "listed": true}]"#,
+            )],
+        );
+
+        // when
+        let versions = client.versions("Polly").unwrap();
+
+        // then
+        let versions: Vec<String> = versions.iter().map(|version| version.to_string()).collect();
+        assert_eq!(versions, vec!["7.2.4", "7.2.5"]);
+
+        assert_eq!(
+            client.latest_version("Polly", true).unwrap().unwrap(),
+            "7.2.5".parse().unwrap()
+        );
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    pub fn outdated_packages_are_compared_by_version() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let client = stub_feed(
+            dir.path(),
+            &[
+                ("serilog", r#"["1.2.0", "1.10.0", "2.0.0-beta.1"]"#),
+                ("polly", r#"["7.2.4", "8.4.1"]"#),
+                ("moq", r#"["4.20.0"]"#),
+            ],
+        );
+
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="1.2.0" />
+    <PackageReference Include="Polly" Version="8.4.1" />
+    <PackageReference Include="Moq" Version="4.*" />
+  </ItemGroup>
+</Project>"#;
+        let prerelease = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="2.0.0-alpha" />
+    <PackageReference Include="Polly" Version="7.2.4" />
+  </ItemGroup>
+</Project>"#;
+        let projects = vec![
+            crate::parser::parse(io::Cursor::new(content), "Api.csproj").unwrap(),
+            crate::parser::parse(io::Cursor::new(prerelease), "Jobs.csproj").unwrap(),
+        ];
+
+        // when
+        let stable = client.outdated(&projects, false).unwrap();
+        let prereleases = client.outdated(&projects, true).unwrap();
+
+        // then
+        let summary = |outdated: &[OutdatedPackage]| {
+            outdated
+                .iter()
+                .map(|package| {
+                    format!(
+                        "{} {} {} -> {} ({:?})",
+                        package.project().display(),
+                        package.name(),
+                        package.current(),
+                        package.latest(),
+                        package.delta()
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            summary(&stable),
+            vec![
+                "Api.csproj Serilog 1.2.0 -> 1.10.0 (Minor)",
+                "Jobs.csproj Polly 7.2.4 -> 8.4.1 (Major)",
+            ]
+        );
+        assert_eq!(
+            summary(&prereleases),
+            vec![
+                "Api.csproj Serilog 1.2.0 -> 2.0.0-beta.1 (Major)",
+                "Jobs.csproj Serilog 2.0.0-alpha -> 2.0.0-beta.1 (Patch)",
+                "Jobs.csproj Polly 7.2.4 -> 8.4.1 (Major)",
+            ]
+        );
+
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,334 @@
+//! Reading of package sources and their credentials from `NuGet.config` files.
+
+use std::{
+    env,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use spex::{parsing::XmlReader, xml::Element};
+
+use crate::parser::ParseError;
+
+const CONFIG_FILE_NAMES: [&str; 3] = ["nuget.config", "NuGet.config", "NuGet.Config"];
+const CREDENTIALS_ENV_PREFIX: &str = "NuGetPackageSourceCredentials_";
+
+/// Represents the package sources declared in a `NuGet.config` file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NugetConfig {
+    sources: Vec<PackageSource>,
+}
+
+/// Represents a package source (feed) declared in a `NuGet.config` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageSource {
+    name: String,
+    url: String,
+    credentials: Option<FeedCredentials>,
+}
+
+/// Represents the credentials used to authenticate against a feed.
+#[derive(Clone, PartialEq)]
+pub struct FeedCredentials {
+    username: String,
+    password: String,
+}
+
+impl NugetConfig {
+    /// Parses the content of a `NuGet.config` file.
+    ///
+    /// Only clear text passwords (`ClearTextPassword`) are supported, since encrypted passwords
+    /// can only be decrypted on the machine that created them. Environment variables referenced
+    /// as `%NAME%` in the credentials are expanded.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be read or parsed.
+    pub fn parse<R>(reader: R) -> Result<Self, ParseError>
+    where
+        R: Read,
+    {
+        let document = XmlReader::parse_auto(reader)?;
+        let mut sources: Vec<PackageSource> = vec![];
+
+        if let Some(package_sources) = child(document.root(), "packageSources") {
+            for element in package_sources.elements() {
+                match element.name().local_part() {
+                    "add" => {
+                        let name = attribute(element, "key")?;
+                        let url = attribute(element, "value")?;
+
+                        sources.retain(|source| !source.name.eq_ignore_ascii_case(&name));
+                        sources.push(PackageSource {
+                            name,
+                            url,
+                            credentials: None,
+                        });
+                    }
+                    "clear" => sources.clear(),
+                    _ => (),
+                }
+            }
+        }
+
+        if let Some(credentials) = child(document.root(), "packageSourceCredentials") {
+            for element in credentials.elements() {
+                let name = element.name().local_part().replace("_x0020_", " ");
+
+                let Some(source) = sources
+                    .iter_mut()
+                    .find(|source| source.name.eq_ignore_ascii_case(&name))
+                else {
+                    continue;
+                };
+
+                let mut username = None;
+                let mut password = None;
+
+                for setting in element.elements() {
+                    match setting.att_opt("key") {
+                        Some("Username") => username = Some(attribute(setting, "value")?),
+                        Some("ClearTextPassword") => password = Some(attribute(setting, "value")?),
+                        _ => (),
+                    }
+                }
+
+                if let (Some(username), Some(password)) = (username, password) {
+                    source.credentials = Some(FeedCredentials::new(
+                        expand_env(&username),
+                        expand_env(&password),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self { sources })
+    }
+
+    /// Reads a `NuGet.config` file and applies the credentials set through environment variables.
+    ///
+    /// See [`NugetConfig::parse`] and [`NugetConfig::apply_env_credentials`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn load<P>(path: P) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut config = Self::parse(File::open(path)?)?;
+        config.apply_env_credentials();
+
+        Ok(config)
+    }
+
+    /// Returns the path of the closest `NuGet.config` file, looking in the directory and in each
+    /// of its parents.
+    pub fn find<P>(directory: P) -> Option<PathBuf>
+    where
+        P: AsRef<Path>,
+    {
+        directory.as_ref().ancestors().find_map(|dir| {
+            CONFIG_FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file())
+        })
+    }
+
+    /// Sets the credentials of the sources using the `NuGetPackageSourceCredentials_{name}`
+    /// environment variables, in the `Username=...;Password=...` format also understood by the
+    /// dotnet CLI. Credentials from the environment take precedence over the file.
+    pub fn apply_env_credentials(&mut self) {
+        for source in &mut self.sources {
+            let variable = format!("{CREDENTIALS_ENV_PREFIX}{}", source.name);
+
+            if let Some(credentials) = env::var(variable)
+                .ok()
+                .and_then(|value| FeedCredentials::from_env_value(&value))
+            {
+                source.credentials = Some(credentials);
+            }
+        }
+    }
+
+    /// Returns the package sources, in declaration order.
+    pub fn sources(&self) -> &Vec<PackageSource> {
+        &self.sources
+    }
+
+    /// Returns the package source with the given name (case insensitive), if any.
+    pub fn source(&self, name: &str) -> Option<&PackageSource> {
+        self.sources
+            .iter()
+            .find(|source| source.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl PackageSource {
+    /// Creates a new `PackageSource` instance.
+    pub fn new(name: String, url: String, credentials: Option<FeedCredentials>) -> Self {
+        Self {
+            name,
+            url,
+            credentials,
+        }
+    }
+
+    /// Returns the name of the source.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the URL of the source (for V3 feeds, the service index URL).
+    pub fn url(&self) -> &String {
+        &self.url
+    }
+
+    /// Returns the credentials of the source, if any.
+    pub fn credentials(&self) -> Option<&FeedCredentials> {
+        self.credentials.as_ref()
+    }
+}
+
+impl FeedCredentials {
+    /// Creates a new `FeedCredentials` instance. For Azure Artifacts and GitHub Packages, the
+    /// password is a personal access token.
+    pub fn new(username: String, password: String) -> Self {
+        Self { username, password }
+    }
+
+    /// Returns the username.
+    pub fn username(&self) -> &String {
+        &self.username
+    }
+
+    /// Returns the password.
+    pub fn password(&self) -> &String {
+        &self.password
+    }
+
+    fn from_env_value(value: &str) -> Option<Self> {
+        let mut username = None;
+        let mut password = None;
+
+        for pair in value.split(';') {
+            match pair.split_once('=') {
+                Some((key, value)) if key.trim().eq_ignore_ascii_case("username") => {
+                    username = Some(value.to_string())
+                }
+                Some((key, value)) if key.trim().eq_ignore_ascii_case("password") => {
+                    password = Some(value.to_string())
+                }
+                _ => (),
+            }
+        }
+
+        Some(Self::new(username?, password?))
+    }
+}
+
+impl std::fmt::Debug for FeedCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FeedCredentials")
+            .field("username", &self.username)
+            .field("password", &"***")
+            .finish()
+    }
+}
+
+fn expand_env(value: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find('%') {
+        let Some(length) = rest[start + 1..].find('%') else {
+            break;
+        };
+
+        let name = &rest[start + 1..start + 1 + length];
+
+        match env::var(name) {
+            Ok(variable) if !name.is_empty() => {
+                result.push_str(&rest[..start]);
+                result.push_str(&variable);
+            }
+            _ => result.push_str(&rest[..start + length + 2]),
+        }
+
+        rest = &rest[start + length + 2..];
+    }
+
+    result.push_str(rest);
+
+    result
+}
+
+fn child<'a>(element: &'a Element, name: &str) -> Option<&'a Element> {
+    element
+        .elements()
+        .find(|child| child.name().local_part() == name)
+}
+
+fn attribute(element: &Element, name: &str) -> Result<String, ParseError> {
+    element
+        .att_req(name)
+        .map(str::to_string)
+        .map_err(|_| ParseError::DeserializationError)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    pub fn parse_sources_and_credentials() {
+        // given
+        let content = r#"<?xml version="1.0" encoding="utf-8"?>
+<configuration>
+  <packageSources>
+    <add key="ignored" value="https://ignored.example.com/v3/index.json" />
+    <clear />
+    <add key="nuget.org" value="https://api.nuget.org/v3/index.json" />
+    <add key="Company Feed" value="https://pkgs.dev.azure.com/company/_packaging/feed/nuget/v3/index.json" />
+  </packageSources>
+  <packageSourceCredentials>
+    <Company_x0020_Feed>
+      <add key="Username" value="build" />
+      <add key="ClearTextPassword" value="%DOTNET_LENS_TEST_UNSET_VARIABLE%" />
+    </Company_x0020_Feed>
+  </packageSourceCredentials>
+</configuration>
+"#;
+
+        // when
+        let config = NugetConfig::parse(Cursor::new(content)).unwrap();
+
+        // then
+        let names: Vec<&str> = config.sources().iter().map(|s| s.name().as_str()).collect();
+        assert_eq!(names, vec!["nuget.org", "Company Feed"]);
+
+        assert!(config.source("nuget.org").unwrap().credentials().is_none());
+
+        let credentials = config
+            .source("company feed")
+            .unwrap()
+            .credentials()
+            .unwrap();
+        assert_eq!(credentials.username(), "build");
+        assert_eq!(credentials.password(), "%DOTNET_LENS_TEST_UNSET_VARIABLE%");
+    }
+
+    #[test]
+    pub fn parse_env_credentials() {
+        let credentials = FeedCredentials::from_env_value("Username=me;Password=token").unwrap();
+
+        assert_eq!(credentials.username(), "me");
+        assert_eq!(credentials.password(), "token");
+        assert!(FeedCredentials::from_env_value("Username=me").is_none());
+        assert!(!format!("{credentials:?}").contains("token"));
+    }
+}
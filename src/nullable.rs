@@ -0,0 +1,196 @@
+//! Adoption of nullable reference types across the projects of a workspace, from their `Nullable`
+//! property, grouped by directory to track the rollout in large repositories.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{workspace::Workspace, Project, ProjectLanguage};
+
+/// Represents the nullable context of a project, from its `Nullable` property.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NullableStatus {
+    /// The project enables nullable annotations and warnings (`enable`).
+    Enabled,
+    /// The project only enables the annotations or the warnings (`annotations` or `warnings`).
+    Partial,
+    /// The project disables nullable reference types (`disable`).
+    Disabled,
+    /// The project does not set `Nullable`, so the compiler default (disabled) applies, unless it
+    /// is set by an imported file.
+    Unset,
+}
+
+impl NullableStatus {
+    /// Returns the nullable context of a project. Unknown values are considered unset.
+    pub fn of(project: &Project) -> Self {
+        match project
+            .strictness()
+            .nullable()
+            .map(|value| value.trim().to_lowercase())
+            .as_deref()
+        {
+            Some("enable") => NullableStatus::Enabled,
+            Some("annotations") | Some("warnings") => NullableStatus::Partial,
+            Some("disable") => NullableStatus::Disabled,
+            _ => NullableStatus::Unset,
+        }
+    }
+}
+
+/// Represents the nullable contexts of the projects of a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NullableGroup {
+    directory: String,
+    projects: Vec<(PathBuf, NullableStatus)>,
+}
+
+impl NullableGroup {
+    /// Returns the directory of the group, relative to the root of the workspace, with forward
+    /// slashes (`.` for the root).
+    pub fn directory(&self) -> &String {
+        &self.directory
+    }
+
+    /// Returns the paths of the projects of the group, with their nullable context.
+    pub fn projects(&self) -> &Vec<(PathBuf, NullableStatus)> {
+        &self.projects
+    }
+
+    /// Returns the number of projects of the group with the nullable context.
+    pub fn count(&self, status: NullableStatus) -> usize {
+        self.projects
+            .iter()
+            .filter(|(_, project_status)| *project_status == status)
+            .count()
+    }
+
+    /// Returns the share of the projects of the group enabling nullable reference types, between
+    /// `0.0` and `1.0`.
+    pub fn adoption(&self) -> f64 {
+        if self.projects.is_empty() {
+            return 0.0;
+        }
+
+        self.count(NullableStatus::Enabled) as f64 / self.projects.len() as f64
+    }
+}
+
+/// Returns the nullable contexts of the C# projects of a workspace, grouped by the first `depth`
+/// directories of their paths relative to the root (ex: `src/Payments` for
+/// `src/Payments/Api/Api.csproj` with a depth of 2), sorted by directory.
+///
+/// Only the `Nullable` property of the project files is read: the values set by imported files,
+/// such as `Directory.Build.props`, and the `#nullable` directives of the source files are
+/// ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{nullable, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// for group in nullable::nullable_adoption(&workspace, 2) {
+///     println!("{}: {:.0}%", group.directory(), group.adoption() * 100.0);
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn nullable_adoption(workspace: &Workspace, depth: usize) -> Vec<NullableGroup> {
+    let mut groups: BTreeMap<String, Vec<(PathBuf, NullableStatus)>> = BTreeMap::new();
+
+    for project in workspace
+        .projects()
+        .iter()
+        .filter(|project| project.language() == ProjectLanguage::CSharp)
+    {
+        let relative_path = workspace.relative_path(project.path());
+        let mut directories: Vec<&str> = relative_path.split('/').collect();
+        directories.pop();
+        directories.truncate(depth);
+
+        let directory = match directories.join("/") {
+            directory if directory.is_empty() => ".".to_string(),
+            directory => directory,
+        };
+
+        groups
+            .entry(directory)
+            .or_default()
+            .push((project.path().clone(), NullableStatus::of(project)));
+    }
+
+    groups
+        .into_iter()
+        .map(|(directory, projects)| NullableGroup {
+            directory,
+            projects,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::workspace::WorkspaceBuilder;
+
+    use super::{nullable_adoption, NullableStatus};
+
+    fn project(nullable: Option<&str>) -> String {
+        let property = nullable
+            .map(|value| format!("<Nullable>{value}</Nullable>"))
+            .unwrap_or_default();
+
+        format!(
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    {property}
+  </PropertyGroup>
+</Project>"#
+        )
+    }
+
+    #[test]
+    pub fn group_nullable_adoption_by_directory() {
+        // given
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project("src/Payments/Api/Api.csproj", &project(Some("enable")));
+        builder.add_project(
+            "src/Payments/Domain/Domain.csproj",
+            &project(Some("Warnings")),
+        );
+        builder.add_project("src/Orders/Api/Api.csproj", &project(Some("disable")));
+        builder.add_project("src/Orders/Jobs/Jobs.csproj", &project(None));
+        builder.add_project("src/Orders/Model/Model.fsproj", &project(Some("enable")));
+        builder.add_project("Tools.csproj", &project(Some("enable")));
+        let workspace = builder.build().unwrap();
+
+        // when
+        let groups = nullable_adoption(&workspace, 2);
+
+        // then
+        let summary: Vec<(&str, usize, usize, usize, usize)> = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.directory().as_str(),
+                    group.count(NullableStatus::Enabled),
+                    group.count(NullableStatus::Partial),
+                    group.count(NullableStatus::Disabled),
+                    group.count(NullableStatus::Unset),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (".", 1, 0, 0, 0),
+                ("src/Orders", 0, 0, 1, 1),
+                ("src/Payments", 1, 1, 0, 0),
+            ]
+        );
+        assert_eq!(groups[2].adoption(), 0.5);
+    }
+}
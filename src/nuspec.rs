@@ -0,0 +1,431 @@
+//! Reading of NuGet package metadata (`.nuspec` files).
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use spex::{parsing::XmlReader, xml::Element};
+
+use crate::{parser::ParseError, version::NuGetVersion, PackageLicense, Project};
+
+/// Represents the metadata of a NuGet package, as declared in its `.nuspec` file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nuspec {
+    id: String,
+    version: String,
+    license: Option<PackageLicense>,
+    dependency_groups: Vec<DependencyGroup>,
+}
+
+/// Represents the dependencies of a package for a target framework.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DependencyGroup {
+    target_framework: Option<String>,
+    dependencies: Vec<PackageDependency>,
+}
+
+/// Represents a dependency of a package on another package.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackageDependency {
+    id: String,
+    version_range: String,
+}
+
+impl Nuspec {
+    /// Parses the content of a `.nuspec` file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be read or if the `id` or the
+    /// `version` of the package are missing.
+    pub fn parse<R>(reader: R) -> Result<Self, ParseError>
+    where
+        R: Read,
+    {
+        let document = XmlReader::parse_auto(reader)?;
+
+        let metadata =
+            child(document.root(), "metadata").ok_or(ParseError::DeserializationError)?;
+
+        let id = child_text(metadata, "id")?.ok_or(ParseError::DeserializationError)?;
+        let version = child_text(metadata, "version")?.ok_or(ParseError::DeserializationError)?;
+
+        let license = match child(metadata, "license") {
+            Some(license) => {
+                let value = license.text()?.trim().to_string();
+
+                match license.att_opt("type") {
+                    Some("file") => Some(PackageLicense::File(value)),
+                    _ => Some(PackageLicense::Expression(value)),
+                }
+            }
+            None => child_text(metadata, "licenseUrl")?.map(PackageLicense::Url),
+        };
+
+        let mut dependency_groups = vec![];
+
+        if let Some(dependencies) = child(metadata, "dependencies") {
+            let mut ungrouped = vec![];
+
+            for element in dependencies.elements() {
+                match element.name().local_part() {
+                    "group" => dependency_groups.push(DependencyGroup {
+                        target_framework: element.att_opt("targetFramework").map(str::to_string),
+                        dependencies: parse_dependencies(element)?,
+                    }),
+                    "dependency" => ungrouped.push(parse_dependency(element)?),
+                    _ => (),
+                }
+            }
+
+            if !ungrouped.is_empty() {
+                dependency_groups.push(DependencyGroup {
+                    target_framework: None,
+                    dependencies: ungrouped,
+                });
+            }
+        }
+
+        Ok(Self {
+            id,
+            version,
+            license,
+            dependency_groups,
+        })
+    }
+
+    /// Returns the id (name) of the package.
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Returns the version of the package.
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+
+    /// Returns the license of the package, if any.
+    pub fn license(&self) -> Option<&PackageLicense> {
+        self.license.as_ref()
+    }
+
+    /// Returns the dependency groups of the package.
+    pub fn dependency_groups(&self) -> &Vec<DependencyGroup> {
+        &self.dependency_groups
+    }
+
+    /// Returns the dependencies of the package for a target framework (ex: "net8.0").
+    ///
+    /// The group declared for the same target framework is used when it exists, otherwise the
+    /// group without a target framework. When none of them exist, or when no target framework is
+    /// given, the dependencies of every group are returned, which can over-approximate the real
+    /// dependencies.
+    pub fn dependencies_for(&self, target_framework: Option<&str>) -> Vec<&PackageDependency> {
+        let target_framework = target_framework.map(normalize_target_framework);
+
+        let group = self
+            .dependency_groups
+            .iter()
+            .find(|group| {
+                target_framework.is_some()
+                    && group
+                        .target_framework
+                        .as_deref()
+                        .map(normalize_target_framework)
+                        == target_framework
+            })
+            .or_else(|| {
+                self.dependency_groups
+                    .iter()
+                    .find(|group| group.target_framework.is_none())
+            });
+
+        let mut dependencies: Vec<&PackageDependency> = match group {
+            Some(group) => group.dependencies.iter().collect(),
+            None => self
+                .dependency_groups
+                .iter()
+                .flat_map(|group| &group.dependencies)
+                .collect(),
+        };
+
+        let mut seen = HashSet::new();
+        dependencies.retain(|dependency| seen.insert(dependency.id.to_lowercase()));
+
+        dependencies
+    }
+}
+
+impl DependencyGroup {
+    /// Returns the target framework of the group, if any.
+    pub fn target_framework(&self) -> Option<&String> {
+        self.target_framework.as_ref()
+    }
+
+    /// Returns the dependencies of the group.
+    pub fn dependencies(&self) -> &Vec<PackageDependency> {
+        &self.dependencies
+    }
+}
+
+impl PackageDependency {
+    /// Returns the id (name) of the package depended upon.
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Returns the version range of the dependency (ex: "[8.0.0, )").
+    pub fn version_range(&self) -> &String {
+        &self.version_range
+    }
+}
+
+/// Returns the path of a package's `.nuspec` inside a NuGet global packages folder.
+///
+/// The global packages folder stores the packages as `{id}/{version}/{id}.nuspec`, with the id
+/// and the normalized version (see [`NuGetVersion::normalized`]) in lowercase, so a reference to
+/// version `1.0` is found in the `1.0.0` folder.
+pub fn global_packages_path<P>(global_packages_folder: P, name: &str, version: &str) -> PathBuf
+where
+    P: AsRef<Path>,
+{
+    let name = name.to_lowercase();
+
+    global_packages_folder
+        .as_ref()
+        .join(&name)
+        .join(folder_version(version))
+        .join(format!("{name}.nuspec"))
+}
+
+/// Returns the name of the folder of a package version in the global packages folder, or the
+/// version in lowercase if it could not be parsed.
+pub(crate) fn folder_version(version: &str) -> String {
+    version
+        .parse::<NuGetVersion>()
+        .map(|version| version.normalized())
+        .unwrap_or_else(|_| version.to_string())
+        .to_lowercase()
+}
+
+/// Attaches the license of every package reference using the `.nuspec` files found in a NuGet
+/// global packages folder (ex: "~/.nuget/packages").
+///
+/// Package references whose package is not in the folder are left untouched.
+///
+/// # Errors
+///
+/// This function will return an error if a `.nuspec` file could not be read or parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{nuspec, Project};
+///
+/// let mut projects = vec![Project::new("path/to/MyProject.csproj")?];
+///
+/// nuspec::attach_licenses(&mut projects, "/home/me/.nuget/packages")?;
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn attach_licenses<P>(
+    projects: &mut [Project],
+    global_packages_folder: P,
+) -> Result<(), ParseError>
+where
+    P: AsRef<Path>,
+{
+    for project in projects {
+        for package_reference in project.package_references_mut() {
+            let path = global_packages_path(
+                &global_packages_folder,
+                package_reference.name(),
+                package_reference.version(),
+            );
+
+            if !path.is_file() {
+                continue;
+            }
+
+            let nuspec = Nuspec::parse(File::open(path)?)?;
+            package_reference.set_license(nuspec.license);
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_dependencies(group: &Element) -> Result<Vec<PackageDependency>, ParseError> {
+    group
+        .elements()
+        .filter(|element| element.name().local_part() == "dependency")
+        .map(parse_dependency)
+        .collect()
+}
+
+fn parse_dependency(element: &Element) -> Result<PackageDependency, ParseError> {
+    Ok(PackageDependency {
+        id: element
+            .att_req("id")
+            .map_err(|_| ParseError::DeserializationError)?
+            .to_string(),
+        version_range: element.att_opt("version").unwrap_or("0.0.0").to_string(),
+    })
+}
+
+/// Normalizes the target framework names used by nuspec files (ex: ".NETStandard2.0") to the
+/// short names used by project files (ex: "netstandard2.0").
+fn normalize_target_framework(target_framework: &str) -> String {
+    let lower = target_framework.to_lowercase();
+
+    if let Some(version) = lower.strip_prefix(".netstandard") {
+        format!("netstandard{version}")
+    } else if let Some(version) = lower.strip_prefix(".netcoreapp") {
+        format!("netcoreapp{version}")
+    } else if let Some(version) = lower.strip_prefix(".netframework") {
+        format!("net{}", version.replace('.', ""))
+    } else {
+        lower
+    }
+}
+
+fn child<'a>(element: &'a Element, name: &str) -> Option<&'a Element> {
+    element
+        .elements()
+        .find(|child| child.name().local_part() == name)
+}
+
+fn child_text(element: &Element, name: &str) -> Result<Option<String>, ParseError> {
+    match child(element, name) {
+        Some(child) => Ok(Some(child.text()?.trim().to_string())),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use io::Cursor;
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    pub fn parse_nuspec_with_license_expression() {
+        // given
+        let content = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://schemas.microsoft.com/packaging/2013/05/nuspec.xsd">
+  <metadata>
+    <id>Serilog</id>
+    <version>3.1.1</version>
+    <license type="expression">Apache-2.0</license>
+    <licenseUrl>https://licenses.nuget.org/Apache-2.0</licenseUrl>
+  </metadata>
+</package>
+"#;
+
+        // when
+        let nuspec = Nuspec::parse(Cursor::new(content)).unwrap();
+
+        // then
+        assert_eq!(nuspec.id(), "Serilog");
+        assert_eq!(nuspec.version(), "3.1.1");
+        assert_eq!(
+            nuspec.license(),
+            Some(&PackageLicense::Expression("Apache-2.0".to_string()))
+        );
+    }
+
+    #[test]
+    pub fn parse_nuspec_with_license_url() {
+        // given
+        let content = r#"
+<package>
+  <metadata>
+    <id>Old.Package</id>
+    <version>1.0.0</version>
+    <licenseUrl>https://example.com/license</licenseUrl>
+  </metadata>
+</package>
+"#;
+
+        // when
+        let nuspec = Nuspec::parse(Cursor::new(content)).unwrap();
+
+        // then
+        assert_eq!(
+            nuspec.license(),
+            Some(&PackageLicense::Url(
+                "https://example.com/license".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    pub fn parse_nuspec_dependency_groups() {
+        // given
+        let content = r#"
+<package>
+  <metadata>
+    <id>Serilog.Sinks.Console</id>
+    <version>5.0.1</version>
+    <dependencies>
+      <group targetFramework=".NETFramework4.6.2">
+        <dependency id="Serilog" version="3.1.1" />
+        <dependency id="System.Memory" version="4.5.5" />
+      </group>
+      <group targetFramework="net8.0">
+        <dependency id="Serilog" version="[3.1.1, )" />
+      </group>
+    </dependencies>
+  </metadata>
+</package>
+"#;
+
+        // when
+        let nuspec = Nuspec::parse(Cursor::new(content)).unwrap();
+
+        // then
+        assert_eq!(nuspec.dependency_groups().len(), 2);
+
+        let net8: Vec<&str> = nuspec
+            .dependencies_for(Some("net8.0"))
+            .iter()
+            .map(|d| d.id().as_str())
+            .collect();
+        assert_eq!(net8, vec!["Serilog"]);
+
+        let net462 = nuspec.dependencies_for(Some("net462"));
+        assert_eq!(net462.len(), 2);
+        assert_eq!(net462[1].version_range(), "4.5.5");
+
+        assert_eq!(nuspec.dependencies_for(None).len(), 2);
+    }
+
+    #[test]
+    pub fn global_packages_path_is_lowercase() {
+        let path = global_packages_path("/packages", "Serilog", "3.1.1-RC");
+
+        assert_eq!(
+            path,
+            PathBuf::from("/packages/serilog/3.1.1-rc/serilog.nuspec")
+        );
+    }
+
+    #[test]
+    pub fn global_packages_path_uses_the_normalized_version() {
+        assert_eq!(
+            global_packages_path("/packages", "Serilog", "3.1"),
+            PathBuf::from("/packages/serilog/3.1.0/serilog.nuspec")
+        );
+        assert_eq!(
+            global_packages_path("/packages", "Serilog", "3.1.1.0+build"),
+            PathBuf::from("/packages/serilog/3.1.1/serilog.nuspec")
+        );
+    }
+}
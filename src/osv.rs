@@ -0,0 +1,531 @@
+//! A client for the [OSV](https://osv.dev) database, used to find known vulnerabilities in the
+//! referenced packages.
+
+use std::{collections::HashMap, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    suggestions::{FindingKind, PackageFinding},
+    validation::{Severity, Violation},
+    version::NuGetVersion,
+    Project,
+};
+
+/// The query endpoint of the public OSV API.
+pub const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+
+/// The rule id used for violations about vulnerable packages.
+pub const VULNERABLE_PACKAGE_RULE_ID: &str = "DL0102";
+
+const NUGET_ECOSYSTEM: &str = "NuGet";
+
+/// A blocking HTTP client for the OSV API.
+pub struct OsvClient {
+    agent: ureq::Agent,
+    query_url: String,
+}
+
+impl OsvClient {
+    /// Creates a new `OsvClient` for the public OSV API.
+    pub fn new() -> Self {
+        Self::with_query_url(OSV_QUERY_URL.to_string())
+    }
+
+    /// Creates a new `OsvClient` that sends the queries to the given URL, useful for mirrors.
+    pub fn with_query_url(query_url: String) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().build(),
+            query_url,
+        }
+    }
+
+    /// Returns the known vulnerabilities affecting the given package version.
+    ///
+    /// Every page of results is requested, so packages with many advisories are not truncated.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the OSV API could not be queried.
+    pub fn vulnerabilities(
+        &self,
+        package: &str,
+        version: &NuGetVersion,
+    ) -> Result<Vec<Vulnerability>, OsvError> {
+        let mut vulnerabilities = vec![];
+        let mut page_token = None;
+
+        loop {
+            let query = Query {
+                package: QueryPackage {
+                    name: package,
+                    ecosystem: NUGET_ECOSYSTEM,
+                },
+                version: version.to_string(),
+                page_token,
+            };
+
+            let response: QueryResponse = self
+                .agent
+                .post(&self.query_url)
+                .send_json(query)
+                .map_err(|error| OsvError::RequestError(Box::new(error)))?
+                .into_json()?;
+
+            vulnerabilities.extend(
+                response
+                    .vulns
+                    .into_iter()
+                    .map(|vulnerability| Vulnerability::from_osv(vulnerability, package)),
+            );
+
+            match response.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => return Ok(vulnerabilities),
+            }
+        }
+    }
+
+    /// Returns every package reference of the projects affected by known vulnerabilities.
+    ///
+    /// Each package version is looked up only once, even if it is referenced by many projects.
+    /// Package references whose version is not a plain version are ignored.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the OSV API could not be queried.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{osv::OsvClient, Project};
+    ///
+    /// let project = Project::new("path/to/MyProject.csproj")?;
+    ///
+    /// for vulnerable in OsvClient::new().scan(&[project])? {
+    ///     for vulnerability in vulnerable.vulnerabilities() {
+    ///         println!("{} {}: {}", vulnerable.name(), vulnerable.version(), vulnerability.id());
+    ///     }
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn scan(&self, projects: &[Project]) -> Result<Vec<VulnerablePackage>, OsvError> {
+        let mut known: HashMap<(String, String), Vec<Vulnerability>> = HashMap::new();
+        let mut vulnerable = vec![];
+
+        for project in projects {
+            for package_reference in project.package_references() {
+                let Ok(version) = package_reference.version().parse::<NuGetVersion>() else {
+                    continue;
+                };
+
+                let key = (
+                    package_reference.name().to_lowercase(),
+                    version.to_string().to_lowercase(),
+                );
+
+                let vulnerabilities = match known.get(&key) {
+                    Some(vulnerabilities) => vulnerabilities.clone(),
+                    None => {
+                        let vulnerabilities =
+                            self.vulnerabilities(package_reference.name(), &version)?;
+                        known.insert(key, vulnerabilities.clone());
+
+                        vulnerabilities
+                    }
+                };
+
+                if !vulnerabilities.is_empty() {
+                    vulnerable.push(VulnerablePackage {
+                        project: project.path().clone(),
+                        name: package_reference.name().to_string(),
+                        version,
+                        vulnerabilities,
+                    });
+                }
+            }
+        }
+
+        Ok(vulnerable)
+    }
+}
+
+impl Default for OsvClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Represents the severity of a vulnerability, as rated by the advisory database.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum VulnerabilitySeverity {
+    Unknown,
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+/// Represents a known vulnerability.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vulnerability {
+    id: String,
+    aliases: Vec<String>,
+    summary: Option<String>,
+    severity: VulnerabilitySeverity,
+    fixed_versions: Vec<String>,
+}
+
+impl Vulnerability {
+    /// Returns the id of the vulnerability in the OSV database (ex: "GHSA-5crp-9r3c-p9vr").
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Returns the other ids of the vulnerability (ex: "CVE-2024-21907").
+    pub fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    /// Returns the CVE ids of the vulnerability.
+    pub fn cves(&self) -> Vec<&String> {
+        std::iter::once(&self.id)
+            .chain(&self.aliases)
+            .filter(|id| id.starts_with("CVE-"))
+            .collect()
+    }
+
+    /// Returns a short summary of the vulnerability, if any.
+    pub fn summary(&self) -> Option<&String> {
+        self.summary.as_ref()
+    }
+
+    /// Returns the severity of the vulnerability.
+    pub fn severity(&self) -> VulnerabilitySeverity {
+        self.severity
+    }
+
+    /// Returns the versions of the package that fix the vulnerability.
+    pub fn fixed_versions(&self) -> &Vec<String> {
+        &self.fixed_versions
+    }
+
+    /// Converts an advisory of the OSV database, keeping the fixed versions of `package` only,
+    /// since an advisory may affect several packages.
+    fn from_osv(value: OsvVulnerability, package: &str) -> Self {
+        let severity = match value
+            .database_specific
+            .and_then(|database| database.severity)
+            .map(|severity| severity.to_uppercase())
+            .as_deref()
+        {
+            Some("LOW") => VulnerabilitySeverity::Low,
+            Some("MODERATE") | Some("MEDIUM") => VulnerabilitySeverity::Moderate,
+            Some("HIGH") => VulnerabilitySeverity::High,
+            Some("CRITICAL") => VulnerabilitySeverity::Critical,
+            _ => VulnerabilitySeverity::Unknown,
+        };
+
+        let fixed_versions = value
+            .affected
+            .into_iter()
+            .filter(|affected| {
+                affected.package.as_ref().is_none_or(|affected| {
+                    affected.ecosystem == NUGET_ECOSYSTEM
+                        && affected.name.eq_ignore_ascii_case(package)
+                })
+            })
+            .flat_map(|affected| affected.ranges)
+            .flat_map(|range| range.events)
+            .filter_map(|event| event.fixed)
+            .collect();
+
+        Self {
+            id: value.id,
+            aliases: value.aliases,
+            summary: value.summary,
+            severity,
+            fixed_versions,
+        }
+    }
+}
+
+/// Represents a package reference affected by known vulnerabilities.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VulnerablePackage {
+    project: PathBuf,
+    name: String,
+    version: NuGetVersion,
+    vulnerabilities: Vec<Vulnerability>,
+}
+
+impl VulnerablePackage {
+    /// Returns the path of the project that references the package.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the name of the package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the version referenced by the project.
+    pub fn version(&self) -> &NuGetVersion {
+        &self.version
+    }
+
+    /// Returns the vulnerabilities affecting the package version.
+    pub fn vulnerabilities(&self) -> &Vec<Vulnerability> {
+        &self.vulnerabilities
+    }
+
+    /// Returns the highest severity among the vulnerabilities.
+    pub fn max_severity(&self) -> VulnerabilitySeverity {
+        self.vulnerabilities
+            .iter()
+            .map(|vulnerability| vulnerability.severity)
+            .max()
+            .unwrap_or(VulnerabilitySeverity::Unknown)
+    }
+
+    /// Returns the lowest version above the current one that fixes every vulnerability, if all
+    /// of them have a known fix.
+    pub fn fixed_in(&self) -> Option<NuGetVersion> {
+        self.vulnerabilities
+            .iter()
+            .map(|vulnerability| {
+                vulnerability
+                    .fixed_versions
+                    .iter()
+                    .filter_map(|version| version.parse::<NuGetVersion>().ok())
+                    .filter(|version| *version > self.version)
+                    .min()
+            })
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .max()
+    }
+
+    /// Converts the vulnerable package into a `PackageFinding`, used to suggest updates.
+    pub fn to_finding(&self) -> PackageFinding {
+        PackageFinding::new(
+            self.project.clone(),
+            self.name.clone(),
+            self.version.clone(),
+            FindingKind::Vulnerable {
+                ids: self
+                    .vulnerabilities
+                    .iter()
+                    .map(|vulnerability| vulnerability.id.clone())
+                    .collect(),
+                fixed_in: self.fixed_in(),
+            },
+        )
+    }
+
+    /// Converts the vulnerable package into a `Violation`, so it can be added to a `Report`.
+    ///
+    /// High and critical vulnerabilities are reported as errors, the others as warnings.
+    pub fn to_violation(&self) -> Violation {
+        let severity = match self.max_severity() {
+            VulnerabilitySeverity::High | VulnerabilitySeverity::Critical => Severity::Error,
+            _ => Severity::Warning,
+        };
+
+        let ids: Vec<&str> = self
+            .vulnerabilities
+            .iter()
+            .map(|vulnerability| {
+                vulnerability
+                    .cves()
+                    .first()
+                    .map_or(vulnerability.id.as_str(), |cve| cve.as_str())
+            })
+            .collect();
+
+        Violation::new(
+            VULNERABLE_PACKAGE_RULE_ID.to_string(),
+            severity,
+            format!(
+                "package '{}' {} has known vulnerabilities: {}",
+                self.name,
+                self.version,
+                ids.join(", ")
+            ),
+            self.project.clone(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct Query<'a> {
+    package: QueryPackage<'a>,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_token: Option<String>,
+}
+
+#[derive(Serialize)]
+struct QueryPackage<'a> {
+    name: &'a str,
+    ecosystem: &'static str,
+}
+
+#[derive(Deserialize)]
+struct QueryResponse {
+    #[serde(default)]
+    vulns: Vec<OsvVulnerability>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OsvVulnerability {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    summary: Option<String>,
+    database_specific: Option<DatabaseSpecific>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+}
+
+#[derive(Deserialize)]
+struct Affected {
+    package: Option<AffectedPackage>,
+    #[serde(default)]
+    ranges: Vec<AffectedRange>,
+}
+
+#[derive(Deserialize)]
+struct AffectedPackage {
+    name: String,
+    ecosystem: String,
+}
+
+#[derive(Deserialize)]
+struct AffectedRange {
+    #[serde(default)]
+    events: Vec<RangeEvent>,
+}
+
+#[derive(Deserialize)]
+struct RangeEvent {
+    fixed: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DatabaseSpecific {
+    severity: Option<String>,
+}
+
+/// Represents errors that can occur while querying the OSV API.
+#[derive(Debug, Error)]
+pub enum OsvError {
+    /// The HTTP request to the OSV API failed.
+    #[error("the request to the OSV API failed: {0}")]
+    RequestError(Box<ureq::Error>),
+    /// An I/O error occurred while reading the response.
+    #[error("there was an error while reading the OSV API response")]
+    IoError(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn deserialize_query_response() {
+        // given
+        let content = r#"{
+            "vulns": [{
+                "id": "GHSA-5crp-9r3c-p9vr",
+                "aliases": ["CVE-2024-21907"],
+                "summary": "Improper Handling of Exceptional Conditions in Newtonsoft.Json",
+                "database_specific": { "severity": "HIGH" },
+                "affected": [{
+                    "ranges": [{ "type": "ECOSYSTEM", "events": [{ "introduced": "0" }, { "fixed": "13.0.1" }] }]
+                }]
+            }]
+        }"#;
+
+        // when
+        let response: QueryResponse = serde_json::from_str(content).unwrap();
+
+        // then
+        let vulnerable = VulnerablePackage {
+            project: PathBuf::from("Api.csproj"),
+            name: "Newtonsoft.Json".to_string(),
+            version: "12.0.1".parse().unwrap(),
+            vulnerabilities: response
+                .vulns
+                .into_iter()
+                .map(|vulnerability| Vulnerability::from_osv(vulnerability, "Newtonsoft.Json"))
+                .collect(),
+        };
+
+        assert_eq!(vulnerable.max_severity(), VulnerabilitySeverity::High);
+        assert_eq!(vulnerable.fixed_in(), Some("13.0.1".parse().unwrap()));
+
+        let violation = vulnerable.to_violation();
+        assert_eq!(violation.severity(), Severity::Error);
+        assert_eq!(
+            violation.message(),
+            "package 'Newtonsoft.Json' 12.0.1 has known vulnerabilities: CVE-2024-21907"
+        );
+    }
+
+    #[test]
+    pub fn keep_the_fixed_versions_of_the_queried_package() {
+        // given
+        let content = r#"{
+            "vulns": [{
+                "id": "GHSA-7jgj-8wvc-jh57",
+                "affected": [
+                    {
+                        "package": { "name": "System.Text.Encodings.Web", "ecosystem": "NuGet" },
+                        "ranges": [{ "type": "ECOSYSTEM", "events": [{ "introduced": "4.0.0" }, { "fixed": "4.5.1" }] }]
+                    },
+                    {
+                        "package": { "name": "Microsoft.AspNetCore.App.Runtime.linux-x64", "ecosystem": "NuGet" },
+                        "ranges": [{ "type": "ECOSYSTEM", "events": [{ "introduced": "5.0.0" }, { "fixed": "5.0.4" }] }]
+                    },
+                    {
+                        "package": { "name": "system.text.encodings.web", "ecosystem": "npm" },
+                        "ranges": [{ "type": "SEMVER", "events": [{ "introduced": "0" }, { "fixed": "9.9.9" }] }]
+                    }
+                ]
+            }],
+            "next_page_token": "page-2"
+        }"#;
+
+        // when
+        let response: QueryResponse = serde_json::from_str(content).unwrap();
+
+        // then
+        assert_eq!(response.next_page_token.as_deref(), Some("page-2"));
+
+        let vulnerabilities: Vec<Vulnerability> = response
+            .vulns
+            .into_iter()
+            .map(|vulnerability| {
+                Vulnerability::from_osv(vulnerability, "System.Text.Encodings.Web")
+            })
+            .collect();
+
+        assert_eq!(
+            vulnerabilities[0].fixed_versions(),
+            &vec!["4.5.1".to_string()]
+        );
+    }
+
+    #[test]
+    pub fn empty_query_response() {
+        let response: QueryResponse = serde_json::from_str("{}").unwrap();
+
+        assert!(response.vulns.is_empty());
+        assert!(response.next_page_token.is_none());
+    }
+}
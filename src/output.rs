@@ -0,0 +1,7 @@
+//! Output formats for validation results and package listings.
+
+#[cfg(feature = "serde")]
+pub mod dotnet_list;
+pub mod github;
+#[cfg(feature = "serde")]
+pub mod sarif;
@@ -0,0 +1,352 @@
+use std::{io::Write, path::PathBuf};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    assets::ProjectAssets,
+    export::{ExportError, Exporter},
+    graph::DependencyGraph,
+    parser::ParseError,
+    version::VersionRange,
+    workspace::Workspace,
+    PackageReference, Project,
+};
+
+const VERSION: u32 = 1;
+const TRANSITIVE_PARAMETERS: &str = "--include-transitive";
+
+/// Serializes the package references of the projects like `dotnet list package
+/// --include-transitive --format json`, so scripts reading that output can use dotnet-lens
+/// without restoring the projects.
+///
+/// Every project lists its packages by target framework, with the `requestedVersion` as declared
+/// in the project file. When the project was restored, the target frameworks, the
+/// `resolvedVersion` and the transitive packages are read from its restore graph (see
+/// [`ProjectAssets`]). Otherwise, the lowest version accepted by the requested range is reported
+/// as resolved and no transitive packages are listed. The `parameters` are
+/// `--include-transitive` only when at least one project was restored, since the transitive
+/// packages are unknown otherwise.
+///
+/// Projects without any package only have a `path`, and the packages of a project whose target
+/// framework is unknown are listed under an empty `framework`.
+///
+/// # Errors
+///
+/// This function will return an error if the restore graph of a project could not be read, or
+/// if the document could not be written.
+///
+/// # Arguments
+///
+/// * `writer` - The writer where the document will be written.
+/// * `projects` - The projects whose packages are listed.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::output::dotnet_list;
+///
+/// let mut output = vec![];
+/// dotnet_list::write(&mut output, &[]).unwrap();
+/// ```
+pub fn write<W>(writer: W, projects: &[Project]) -> Result<(), DotnetListError>
+where
+    W: Write,
+{
+    let document = build_document(projects)?;
+
+    Ok(serde_json::to_writer_pretty(writer, &document)?)
+}
+
+/// Serializes the package references of the projects like `dotnet list package
+/// --include-transitive --format json`.
+///
+/// See [`write`] for more details.
+pub fn to_string(projects: &[Project]) -> Result<String, DotnetListError> {
+    Ok(serde_json::to_string_pretty(&build_document(projects)?)?)
+}
+
+fn build_document(projects: &[Project]) -> Result<Document<'_>, DotnetListError> {
+    let mut restored = false;
+    let mut listed = vec![];
+
+    for project in projects {
+        let assets =
+            ProjectAssets::for_project(project).map_err(|source| DotnetListError::AssetsError {
+                path: project.path().clone(),
+                source,
+            })?;
+
+        restored |= assets.is_some();
+
+        listed.push(ListedProject {
+            path: project.path().to_string_lossy().to_string(),
+            frameworks: frameworks(project, assets.as_ref()),
+        });
+    }
+
+    Ok(Document {
+        version: VERSION,
+        parameters: if restored { TRANSITIVE_PARAMETERS } else { "" },
+        projects: listed,
+    })
+}
+
+fn frameworks<'a>(project: &'a Project, assets: Option<&ProjectAssets>) -> Vec<Framework<'a>> {
+    if project.package_references().is_empty() {
+        return vec![];
+    }
+
+    let mut names: Vec<String> = match assets {
+        Some(assets) => assets.target_frameworks().cloned().collect(),
+        None => project.target_frameworks().into_iter().cloned().collect(),
+    };
+
+    if names.is_empty() {
+        names.push(String::new());
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            let references: Vec<&PackageReference> = project
+                .package_references()
+                .iter()
+                .filter(|reference| name.is_empty() || reference.applies_to(&name))
+                .collect();
+
+            let resolved = assets.and_then(|assets| assets.resolved(&name));
+            let requested = assets.and_then(|assets| assets.requested(&name));
+
+            let top_level_packages = references
+                .iter()
+                .map(|reference| {
+                    let requested_version = match reference.version().as_str() {
+                        "" => requested
+                            .and_then(|requested| find(requested.iter(), reference.name()))
+                            .cloned()
+                            .unwrap_or_default(),
+                        version => version.to_string(),
+                    };
+
+                    let resolved_version = resolved
+                        .and_then(|resolved| find(resolved.iter(), reference.name()))
+                        .cloned()
+                        .or_else(|| lowest_version(&requested_version))
+                        .unwrap_or_else(|| requested_version.clone());
+
+                    TopLevelPackage {
+                        id: reference.name(),
+                        requested_version,
+                        resolved_version,
+                    }
+                })
+                .collect();
+
+            let transitive_packages = resolved
+                .into_iter()
+                .flatten()
+                .filter(|(package, _)| {
+                    !references
+                        .iter()
+                        .any(|reference| reference.name().eq_ignore_ascii_case(package))
+                })
+                .map(|(package, version)| TransitivePackage {
+                    id: package.clone(),
+                    resolved_version: version.clone(),
+                })
+                .collect();
+
+            Framework {
+                framework: name,
+                top_level_packages,
+                transitive_packages,
+            }
+        })
+        .collect()
+}
+
+/// Returns the value of the package, comparing the names ignoring case like NuGet does.
+fn find<'a, I>(mut packages: I, name: &str) -> Option<&'a String>
+where
+    I: Iterator<Item = (&'a String, &'a String)>,
+{
+    packages
+        .find(|(package, _)| package.eq_ignore_ascii_case(name))
+        .map(|(_, version)| version)
+}
+
+fn lowest_version(range: &str) -> Option<String> {
+    let range: VersionRange = range.parse().ok()?;
+
+    range.min().map(|min| min.to_string())
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    version: u32,
+    parameters: &'a str,
+    projects: Vec<ListedProject<'a>>,
+}
+
+#[derive(Serialize)]
+struct ListedProject<'a> {
+    path: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    frameworks: Vec<Framework<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Framework<'a> {
+    framework: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    top_level_packages: Vec<TopLevelPackage<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    transitive_packages: Vec<TransitivePackage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TopLevelPackage<'a> {
+    id: &'a str,
+    requested_version: String,
+    resolved_version: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TransitivePackage {
+    id: String,
+    resolved_version: String,
+}
+
+/// An [`Exporter`] writing the packages of the projects of the workspace like `dotnet list
+/// package --format json` (see [`write`]), followed by a newline. The graph is not used.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DotnetListExporter;
+
+impl Exporter for DotnetListExporter {
+    fn name(&self) -> &str {
+        "dotnet-json"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        _graph: &DependencyGraph,
+        workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        match write(&mut *writer, workspace.projects()) {
+            Ok(()) => {}
+            Err(DotnetListError::Json(error)) => return Err(error.into()),
+            Err(error) => return Err(ExportError::Other(Box::new(error))),
+        }
+        writeln!(writer)?;
+
+        Ok(())
+    }
+}
+
+/// Represents errors that can occur while listing the packages of the projects.
+#[derive(Debug, Error)]
+pub enum DotnetListError {
+    /// The restore graph of a project could not be read.
+    #[error("the restore graph of {path:?} could not be read")]
+    AssetsError {
+        path: PathBuf,
+        #[source]
+        source: ParseError,
+    },
+    /// The document could not be serialized as JSON.
+    #[error("the document could not be serialized as JSON")]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, io::Cursor};
+
+    use serde_json::{json, Value};
+    use tempfile::tempdir;
+
+    use crate::parser::parse;
+
+    use super::{to_string, DotnetListError};
+
+    #[test]
+    pub fn list_packages_like_dotnet() {
+        // given
+        let dir = tempdir().unwrap();
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.1.1" />
+    <PackageReference Include="Polly" Version="[8.0,9.0)" />
+  </ItemGroup>
+</Project>"#;
+
+        let restored_path = dir.path().join("Api/Api.csproj");
+        fs::create_dir_all(dir.path().join("Api/obj")).unwrap();
+        fs::write(
+            dir.path().join("Api/obj/project.assets.json"),
+            r#"{
+  "targets": {
+    "net8.0": {
+      "Serilog/3.1.1": { "type": "package" },
+      "Polly/8.4.1": { "type": "package" },
+      "Polly.Core/8.4.1": { "type": "package" }
+    }
+  },
+  "project": { "frameworks": { "net8.0": { "dependencies": {} } } }
+}"#,
+        )
+        .unwrap();
+
+        let projects = vec![
+            parse(Cursor::new(content), &restored_path).unwrap(),
+            parse(Cursor::new(content), dir.path().join("Web/Web.csproj")).unwrap(),
+            parse(
+                Cursor::new(r#"<Project Sdk="Microsoft.NET.Sdk" />"#),
+                dir.path().join("Core/Core.csproj"),
+            )
+            .unwrap(),
+        ];
+
+        // when
+        let document: Value = serde_json::from_str(&to_string(&projects).unwrap()).unwrap();
+
+        // then
+        assert_eq!(document["version"], 1);
+        assert_eq!(document["parameters"], "--include-transitive");
+
+        assert_eq!(
+            document["projects"][0]["frameworks"],
+            json!([{
+                "framework": "net8.0",
+                "topLevelPackages": [
+                    { "id": "Serilog", "requestedVersion": "3.1.1", "resolvedVersion": "3.1.1" },
+                    { "id": "Polly", "requestedVersion": "[8.0,9.0)", "resolvedVersion": "8.4.1" }
+                ],
+                "transitivePackages": [{ "id": "Polly.Core", "resolvedVersion": "8.4.1" }]
+            }])
+        );
+
+        let unrestored = &document["projects"][1]["frameworks"][0];
+        assert_eq!(unrestored["topLevelPackages"][1]["resolvedVersion"], "8.0");
+        assert!(unrestored.get("transitivePackages").is_none());
+
+        assert!(document["projects"][2].get("frameworks").is_none());
+
+        let unrestored: Value = serde_json::from_str(&to_string(&projects[1..]).unwrap()).unwrap();
+        assert_eq!(unrestored["parameters"], "");
+
+        fs::write(dir.path().join("Api/obj/project.assets.json"), "{").unwrap();
+        assert!(matches!(
+            to_string(&projects),
+            Err(DotnetListError::AssetsError { .. })
+        ));
+    }
+}
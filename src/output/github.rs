@@ -0,0 +1,194 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use crate::{
+    export::{ExportError, Exporter},
+    graph::DependencyGraph,
+    validation::{default_rules, validate, Rule, Severity, Violation},
+    workspace::Workspace,
+};
+
+/// Writes the validation violations as GitHub Actions workflow commands.
+///
+/// Every violation becomes a `::error`, `::warning` or `::notice` command pointing to the
+/// project file, so they are shown inline on pull requests. The annotations of the violations
+/// with a line (see [`Violation::line`]) point to that line, and the others are attached to the
+/// whole file. The built-in rules do not track lines, so their annotations are file-level.
+///
+/// # Arguments
+///
+/// * `writer` - The writer where the workflow commands will be written.
+/// * `violations` - The violations found during validation.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::output::github;
+/// use dotnet_lens::validation::{Severity, Violation};
+/// use std::path::PathBuf;
+///
+/// let violation = Violation::new(
+///     "DL0001".to_string(),
+///     Severity::Warning,
+///     "project 'Api' does not declare a target framework".to_string(),
+///     PathBuf::from("src/Api/Api.csproj"),
+/// );
+///
+/// let mut output = vec![];
+/// github::write(&mut output, &[violation]).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "::warning file=src/Api/Api.csproj,title=DL0001::project 'Api' does not declare a target framework\n"
+/// );
+/// ```
+pub fn write<W>(mut writer: W, violations: &[Violation]) -> io::Result<()>
+where
+    W: Write,
+{
+    for violation in violations {
+        writeln!(writer, "{}", annotation(violation))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the validation violations as GitHub Actions workflow commands, one per line.
+///
+/// See [`write`] for more details.
+pub fn to_string(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(|violation| annotation(violation) + "\n")
+        .collect()
+}
+
+fn annotation(violation: &Violation) -> String {
+    let command = match violation.severity() {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "notice",
+    };
+
+    let file = violation.path().to_string_lossy().replace('\\', "/");
+    let line = violation
+        .line()
+        .map(|line| format!(",line={line}"))
+        .unwrap_or_default();
+
+    format!(
+        "::{} file={}{},title={}::{}",
+        command,
+        escape_property(&file),
+        line,
+        escape_property(violation.rule_id()),
+        escape_data(violation.message())
+    )
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// An [`Exporter`] validating the projects of the workspace and writing the violations as
+/// GitHub Actions workflow commands (see [`write`]). The graph is not used.
+pub struct GithubExporter {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl GithubExporter {
+    /// Creates a new `GithubExporter` validating the projects with the given rules.
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Self {
+        Self { rules }
+    }
+}
+
+impl Default for GithubExporter {
+    /// Creates a new `GithubExporter` validating the projects with the built-in rules.
+    fn default() -> Self {
+        Self::new(default_rules())
+    }
+}
+
+impl fmt::Debug for GithubExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GithubExporter")
+            .field(
+                "rules",
+                &self.rules.iter().map(|rule| rule.id()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Exporter for GithubExporter {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        _graph: &DependencyGraph,
+        workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        let violations = validate(workspace.projects(), &self.rules);
+
+        Ok(write(writer, &violations)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    pub fn annotations_are_escaped() {
+        // given
+        let mut located = Violation::new(
+            "DL0003".to_string(),
+            Severity::Note,
+            "just a note".to_string(),
+            PathBuf::from("C:/Core.csproj"),
+        );
+        located.set_line(Some(12));
+
+        let violations = vec![
+            Violation::new(
+                "DL0002".to_string(),
+                Severity::Error,
+                "100% broken\nsecond line".to_string(),
+                PathBuf::from("src\\Api,Old\\Api.csproj"),
+            ),
+            Violation::new(
+                "DL0003".to_string(),
+                Severity::Note,
+                "just a note".to_string(),
+                PathBuf::from("C:/Core.csproj"),
+            ),
+            located,
+        ];
+
+        // when
+        let output = to_string(&violations);
+
+        // then
+        assert_eq!(
+            output,
+            "::error file=src/Api%2COld/Api.csproj,title=DL0002::100%25 broken%0Asecond line\n\
+             ::notice file=C%3A/Core.csproj,title=DL0003::just a note\n\
+             ::notice file=C%3A/Core.csproj,line=12,title=DL0003::just a note\n"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use std::{fmt, io::Write};
+
+use serde::Serialize;
+
+use crate::{
+    export::{ExportError, Exporter},
+    graph::DependencyGraph,
+    validation::{default_rules, validate, Rule, Severity, Violation},
+    workspace::Workspace,
+};
+
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Serializes the validation violations as a SARIF 2.1.0 log.
+///
+/// The rules are used to fill the tool's rule descriptors, so code scanning tools can show a
+/// description for every rule id referenced by the results. The results of the violations with
+/// a line (see [`Violation::line`]) have a region starting at that line, the others refer to the
+/// whole project file.
+///
+/// # Arguments
+///
+/// * `writer` - The writer where the SARIF log will be written.
+/// * `violations` - The violations found during validation.
+/// * `rules` - The rules that were used during validation.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{output::sarif, validation::default_rules};
+///
+/// let mut output = vec![];
+/// sarif::write(&mut output, &[], &default_rules()).unwrap();
+/// ```
+pub fn write<W>(
+    writer: W,
+    violations: &[Violation],
+    rules: &[Box<dyn Rule>],
+) -> Result<(), serde_json::Error>
+where
+    W: Write,
+{
+    serde_json::to_writer_pretty(writer, &build_log(violations, rules))
+}
+
+/// Serializes the validation violations as a SARIF 2.1.0 log string.
+///
+/// See [`write`] for more details.
+pub fn to_string(
+    violations: &[Violation],
+    rules: &[Box<dyn Rule>],
+) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&build_log(violations, rules))
+}
+
+fn build_log<'a>(violations: &'a [Violation], rules: &'a [Box<dyn Rule>]) -> SarifLog<'a> {
+    let rules = rules
+        .iter()
+        .map(|rule| ReportingDescriptor {
+            id: rule.id(),
+            short_description: Message {
+                text: rule.description().to_string(),
+            },
+        })
+        .collect();
+
+    let results = violations
+        .iter()
+        .map(|violation| SarifResult {
+            rule_id: violation.rule_id(),
+            level: level(violation.severity()),
+            message: Message {
+                text: violation.message().to_string(),
+            },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: violation.path().to_string_lossy().replace('\\', "/"),
+                    },
+                    region: violation.line().map(|start_line| Region { start_line }),
+                },
+            }],
+        })
+        .collect();
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: env!("CARGO_PKG_REPOSITORY"),
+                    rules,
+                },
+            },
+            results,
+        }],
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+    }
+}
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run<'a>>,
+}
+
+#[derive(Serialize)]
+struct Run<'a> {
+    tool: Tool,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: &'static str,
+    short_description: Message,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    rule_id: &'a str,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u32,
+}
+
+/// An [`Exporter`] validating the projects of the workspace and writing the violations as a
+/// SARIF 2.1.0 log (see [`write`]), followed by a newline. The graph is not used.
+pub struct SarifExporter {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl SarifExporter {
+    /// Creates a new `SarifExporter` validating the projects with the given rules.
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Self {
+        Self { rules }
+    }
+}
+
+impl Default for SarifExporter {
+    /// Creates a new `SarifExporter` validating the projects with the built-in rules.
+    fn default() -> Self {
+        Self::new(default_rules())
+    }
+}
+
+impl fmt::Debug for SarifExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SarifExporter")
+            .field(
+                "rules",
+                &self.rules.iter().map(|rule| rule.id()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Exporter for SarifExporter {
+    fn name(&self) -> &str {
+        "sarif"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        _graph: &DependencyGraph,
+        workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        let violations = validate(workspace.projects(), &self.rules);
+
+        write(&mut *writer, &violations, &self.rules)?;
+        writeln!(writer)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::validation::{default_rules, Severity, Violation};
+
+    use super::*;
+
+    #[test]
+    pub fn sarif_contains_results_and_rules() {
+        // given
+        let mut duplicate = Violation::new(
+            "DL0002".to_string(),
+            Severity::Error,
+            "package 'Serilog' is referenced more than once by project 'Api'".to_string(),
+            PathBuf::from("src/Api/Api.csproj"),
+        );
+        duplicate.set_line(Some(12));
+
+        let violations = vec![
+            Violation::new(
+                "DL0001".to_string(),
+                Severity::Warning,
+                "project 'Api' does not declare a target framework".to_string(),
+                PathBuf::from("src\\Api\\Api.csproj"),
+            ),
+            duplicate,
+        ];
+
+        // when
+        let sarif = to_string(&violations, &default_rules()).unwrap();
+
+        // then
+        let log: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+        let run = &log["runs"][0];
+
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["name"], "dotnet-lens");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "DL0001");
+        assert_eq!(run["results"][0]["ruleId"], "DL0001");
+        assert_eq!(run["results"][0]["level"], "warning");
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/Api/Api.csproj"
+        );
+        assert!(run["results"][0]["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+        assert_eq!(
+            run["results"][1]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            12
+        );
+    }
+}
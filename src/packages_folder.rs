@@ -0,0 +1,423 @@
+//! Offline access to the NuGet global packages folder (ex: "~/.nuget/packages").
+
+use std::{
+    collections::{HashSet, VecDeque},
+    env, fs,
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    nuspec::{self, Nuspec},
+    parser::ParseError,
+    version::{NuGetVersion, VersionRange},
+    PackageReference, Project,
+};
+
+/// Represents a NuGet global packages folder, where restored packages are extracted.
+///
+/// It allows reading package metadata and resolving transitive package dependencies without any
+/// network access, for air-gapped build environments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalPackagesFolder {
+    root: PathBuf,
+}
+
+/// Represents a package found while resolving transitive dependencies.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResolvedPackage {
+    name: String,
+    version: String,
+    required_by: String,
+    installed: bool,
+}
+
+impl GlobalPackagesFolder {
+    /// Creates a new `GlobalPackagesFolder` for the given directory.
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Returns the global packages folder used by NuGet on this machine.
+    ///
+    /// The `NUGET_PACKAGES` environment variable is used when it is set, otherwise
+    /// `.nuget/packages` inside the user's home directory. Returns `None` if the home directory
+    /// could not be determined.
+    pub fn from_env() -> Option<Self> {
+        if let Some(path) = env::var_os("NUGET_PACKAGES").filter(|path| !path.is_empty()) {
+            return Some(Self::new(PathBuf::from(path)));
+        }
+
+        env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(|home| Self::new(Path::new(&home).join(".nuget").join("packages")))
+    }
+
+    /// Returns the path of the folder.
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    /// Returns the versions of the package present in the folder, in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the package directory could not be read.
+    pub fn installed_versions(&self, name: &str) -> Result<Vec<NuGetVersion>, io::Error> {
+        let package_dir = self.root.join(name.to_lowercase());
+
+        if !package_dir.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut versions = vec![];
+
+        for entry in fs::read_dir(package_dir)? {
+            let entry = entry?;
+
+            if let Some(version) = entry
+                .file_name()
+                .to_str()
+                .and_then(|version| version.parse::<NuGetVersion>().ok())
+            {
+                versions.push(version);
+            }
+        }
+
+        versions.sort();
+
+        Ok(versions)
+    }
+
+    /// Returns the `.nuspec` of a package version, if the package is present in the folder.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the `.nuspec` could not be read or parsed.
+    pub fn nuspec(&self, name: &str, version: &str) -> Result<Option<Nuspec>, ParseError> {
+        let path = nuspec::global_packages_path(&self.root, name, version);
+
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        Nuspec::parse(File::open(path)?).map(Some)
+    }
+
+    /// Returns the names of the assemblies of a package version (ex: `Newtonsoft.Json`), found in
+    /// its `lib` and `ref` folders for every target framework, sorted and without duplicates.
+    ///
+    /// Returns an empty list if the package is not present in the folder or only has other
+    /// assets (ex: analyzers or build files).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a package directory could not be read.
+    pub fn assembly_names(&self, name: &str, version: &str) -> Result<Vec<String>, io::Error> {
+        let package_dir = self
+            .root
+            .join(name.to_lowercase())
+            .join(nuspec::folder_version(version));
+
+        let mut names = vec![];
+
+        for folder in ["lib", "ref"] {
+            let folder = package_dir.join(folder);
+            if !folder.is_dir() {
+                continue;
+            }
+
+            for framework in fs::read_dir(folder)? {
+                let framework = framework?.path();
+                if !framework.is_dir() {
+                    continue;
+                }
+
+                for file in fs::read_dir(framework)? {
+                    let file_name = file?.file_name();
+                    let file_name = file_name.to_string_lossy();
+
+                    if let Some(name) = file_name
+                        .strip_suffix(".dll")
+                        .filter(|name| !names.iter().any(|known: &String| known == name))
+                    {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Resolves the transitive package dependencies of a package version.
+    ///
+    /// For each dependency, the lowest installed version that satisfies the version range is
+    /// used, like NuGet does. Dependencies without any satisfying version in the folder are
+    /// returned with `installed() == false` and the minimum version of their range, and their
+    /// own dependencies are not resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the package.
+    /// * `version` - The version of the package.
+    /// * `target_framework` - The target framework used to choose the dependency groups.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a `.nuspec` could not be read or parsed.
+    pub fn transitive_dependencies(
+        &self,
+        name: &str,
+        version: &str,
+        target_framework: Option<&str>,
+    ) -> Result<Vec<ResolvedPackage>, ParseError> {
+        let mut resolved = vec![];
+        let mut visited = HashSet::from([name.to_lowercase()]);
+        let mut queue = VecDeque::from([(name.to_string(), version.to_string())]);
+
+        while let Some((name, version)) = queue.pop_front() {
+            let Some(nuspec) = self.nuspec(&name, &version)? else {
+                continue;
+            };
+
+            for dependency in nuspec.dependencies_for(target_framework) {
+                if !visited.insert(dependency.id().to_lowercase()) {
+                    continue;
+                }
+
+                let range = dependency.version_range().parse::<VersionRange>().ok();
+
+                let installed = match &range {
+                    Some(range) => self
+                        .installed_versions(dependency.id())?
+                        .into_iter()
+                        .find(|version| range.satisfies(version)),
+                    None => None,
+                };
+
+                let resolved_version = match (&installed, &range) {
+                    (Some(installed), _) => installed.to_string(),
+                    (None, Some(range)) => range
+                        .min()
+                        .map_or_else(|| dependency.version_range().clone(), |v| v.to_string()),
+                    (None, None) => dependency.version_range().clone(),
+                };
+
+                if installed.is_some() {
+                    queue.push_back((dependency.id().clone(), resolved_version.clone()));
+                }
+
+                resolved.push(ResolvedPackage {
+                    name: dependency.id().clone(),
+                    version: resolved_version,
+                    required_by: name.clone(),
+                    installed: installed.is_some(),
+                });
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Resolves the transitive package dependencies of every package referenced by a project,
+    /// using the project's target framework.
+    ///
+    /// Packages directly referenced by the project are not included in the result.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a `.nuspec` could not be read or parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{packages_folder::GlobalPackagesFolder, Project};
+    ///
+    /// let project = Project::new("path/to/MyProject.csproj")?;
+    /// let folder = GlobalPackagesFolder::from_env().unwrap();
+    ///
+    /// for package in folder.project_transitive_dependencies(&project)? {
+    ///     println!("{} {} (required by {})", package.name(), package.version(), package.required_by());
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn project_transitive_dependencies(
+        &self,
+        project: &Project,
+    ) -> Result<Vec<ResolvedPackage>, ParseError> {
+        let direct: HashSet<String> = project
+            .package_references()
+            .iter()
+            .map(|package_reference| package_reference.name().to_lowercase())
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut resolved = vec![];
+
+        for package_reference in project.package_references() {
+            for package in self.transitive_dependencies(
+                package_reference.name(),
+                package_reference.version(),
+                project.target_framework().map(|tfm| tfm.as_str()),
+            )? {
+                let key = package.name.to_lowercase();
+
+                if !direct.contains(&key) && seen.insert(key) {
+                    resolved.push(package);
+                }
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Returns the direct and transitive packages of a project with their licenses attached,
+    /// read from the `.nuspec` files of the folder.
+    ///
+    /// The result is meant to be used with `license::assess`. Packages missing from the folder
+    /// are returned without a license.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a `.nuspec` could not be read or parsed.
+    pub fn license_closure(&self, project: &Project) -> Result<Vec<PackageReference>, ParseError> {
+        let transitive = self.project_transitive_dependencies(project)?;
+
+        let packages = project
+            .package_references()
+            .iter()
+            .map(|package_reference| {
+                (
+                    package_reference.name().to_string(),
+                    package_reference.version().to_string(),
+                )
+            })
+            .chain(
+                transitive
+                    .into_iter()
+                    .map(|package| (package.name, package.version)),
+            );
+
+        let mut closure = vec![];
+
+        for (name, version) in packages {
+            let license = self
+                .nuspec(&name, &version)?
+                .and_then(|nuspec| nuspec.license().cloned());
+
+            let mut package_reference = PackageReference::new(name, version);
+            package_reference.set_license(license);
+
+            closure.push(package_reference);
+        }
+
+        Ok(closure)
+    }
+}
+
+impl ResolvedPackage {
+    /// Returns the name of the package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the resolved version of the package.
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+
+    /// Returns the name of the package that depends on this package.
+    pub fn required_by(&self) -> &String {
+        &self.required_by
+    }
+
+    /// Returns `true` if the resolved version is present in the global packages folder.
+    pub fn installed(&self) -> bool {
+        self.installed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn write_nuspec(root: &Path, name: &str, version: &str, dependencies: &[(&str, &str)]) {
+        let path = nuspec::global_packages_path(root, name, version);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let dependencies: String = dependencies
+            .iter()
+            .map(|(id, range)| format!(r#"<dependency id="{id}" version="{range}" />"#))
+            .collect();
+
+        fs::write(
+            path,
+            format!(
+                r#"<package><metadata><id>{name}</id><version>{version}</version><dependencies><group targetFramework="net8.0">{dependencies}</group></dependencies></metadata></package>"#
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    pub fn resolve_transitive_dependencies() {
+        // given
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        write_nuspec(root, "A", "1.0.0", &[("B", "[2.0.0, )"), ("C", "1.0.0")]);
+        write_nuspec(root, "B", "2.1.0", &[("D", "[1.0.0]")]);
+        write_nuspec(root, "B", "3.0.0", &[]);
+        write_nuspec(root, "D", "1.0.0", &[]);
+
+        let folder = GlobalPackagesFolder::new(root.to_path_buf());
+
+        // when
+        let resolved = folder
+            .transitive_dependencies("A", "1.0.0", Some("net8.0"))
+            .unwrap();
+
+        // then
+        let summary: Vec<(&str, &str, &str, bool)> = resolved
+            .iter()
+            .map(|p| {
+                (
+                    p.name().as_str(),
+                    p.version().as_str(),
+                    p.required_by().as_str(),
+                    p.installed(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("B", "2.1.0", "A", true),
+                ("C", "1.0.0", "A", false),
+                ("D", "1.0.0", "B", true),
+            ]
+        );
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    pub fn missing_package_has_no_nuspec() {
+        let dir = tempdir().unwrap();
+        let folder = GlobalPackagesFolder::new(dir.path().to_path_buf());
+
+        assert!(folder.nuspec("Serilog", "3.1.1").unwrap().is_none());
+        assert!(folder.installed_versions("Serilog").unwrap().is_empty());
+
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+//! The packaging properties of a project (`IsPackable`, `GeneratePackageOnBuild` and
+//! `PackAsTool`), to enumerate the projects producing NuGet packages or dotnet tools.
+
+use std::path::PathBuf;
+
+use crate::Project;
+
+/// The package referenced by test projects, whose SDK makes them not packable by default.
+const TEST_SDK_PACKAGE: &str = "Microsoft.NET.Test.Sdk";
+
+/// Represents the packaging properties of a project, as set in the project file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackagingMetadata {
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    is_packable: Option<bool>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    generate_package_on_build: Option<bool>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pack_as_tool: Option<bool>,
+}
+
+impl PackagingMetadata {
+    /// Creates a new `PackagingMetadata` without any property.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the project can be packed (`IsPackable`), if the project sets it.
+    pub fn is_packable(&self) -> Option<bool> {
+        self.is_packable
+    }
+
+    /// Sets whether the project can be packed.
+    pub fn set_is_packable(&mut self, is_packable: Option<bool>) {
+        self.is_packable = is_packable;
+    }
+
+    /// Returns whether the project is packed on every build (`GeneratePackageOnBuild`), if the
+    /// project sets it.
+    pub fn generate_package_on_build(&self) -> Option<bool> {
+        self.generate_package_on_build
+    }
+
+    /// Sets whether the project is packed on every build.
+    pub fn set_generate_package_on_build(&mut self, generate_package_on_build: Option<bool>) {
+        self.generate_package_on_build = generate_package_on_build;
+    }
+
+    /// Returns whether the project is packed as a dotnet tool (`PackAsTool`), if the project
+    /// sets it.
+    pub fn pack_as_tool(&self) -> Option<bool> {
+        self.pack_as_tool
+    }
+
+    /// Sets whether the project is packed as a dotnet tool.
+    pub fn set_pack_as_tool(&mut self, pack_as_tool: Option<bool>) {
+        self.pack_as_tool = pack_as_tool;
+    }
+
+    /// Returns `true` if the project does not set any packaging property.
+    pub fn is_empty(&self) -> bool {
+        self.is_packable.is_none()
+            && self.generate_package_on_build.is_none()
+            && self.pack_as_tool.is_none()
+    }
+}
+
+/// Represents what `dotnet pack` produces for a project.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PackageOutput {
+    /// A NuGet package with a library.
+    Package,
+    /// A NuGet package with a dotnet tool.
+    Tool,
+}
+
+/// Represents a project that produces a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackableProject {
+    project: PathBuf,
+    output: PackageOutput,
+    packed_on_build: bool,
+}
+
+impl PackableProject {
+    /// Returns the path of the project.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns what the project produces.
+    pub fn output(&self) -> PackageOutput {
+        self.output
+    }
+
+    /// Returns `true` if the package is produced by every build, not only by `dotnet pack`.
+    pub fn packed_on_build(&self) -> bool {
+        self.packed_on_build
+    }
+}
+
+/// Returns what `dotnet pack` produces for `project`, or `None` if it is not packable.
+///
+/// Projects are packable unless they set `IsPackable` to `false`, or reference the test SDK
+/// without setting `IsPackable`.
+pub fn package_output(project: &Project) -> Option<PackageOutput> {
+    let packaging = project.packaging();
+
+    if !packaging
+        .is_packable()
+        .unwrap_or_else(|| !is_test_project(project))
+    {
+        return None;
+    }
+
+    match packaging.pack_as_tool() {
+        Some(true) => Some(PackageOutput::Tool),
+        _ => Some(PackageOutput::Package),
+    }
+}
+
+/// Returns `true` if the project references the test SDK (`Microsoft.NET.Test.Sdk`).
+pub(crate) fn is_test_project(project: &Project) -> bool {
+    project
+        .package_references()
+        .iter()
+        .any(|reference| reference.name().eq_ignore_ascii_case(TEST_SDK_PACKAGE))
+}
+
+/// Returns the projects that produce a package or a dotnet tool, in the order of `projects`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{
+///     packaging::{packable_projects, PackageOutput},
+///     workspace::Workspace,
+/// };
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// for packable in packable_projects(workspace.projects()) {
+///     if packable.output() == PackageOutput::Tool {
+///         println!("tool: {}", packable.project().display());
+///     }
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn packable_projects(projects: &[Project]) -> Vec<PackableProject> {
+    projects
+        .iter()
+        .filter_map(|project| {
+            Some(PackableProject {
+                project: project.path().clone(),
+                output: package_output(project)?,
+                packed_on_build: project
+                    .packaging()
+                    .generate_package_on_build()
+                    .unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Cursor, path::PathBuf};
+
+    use crate::parser::parse;
+
+    use super::{packable_projects, PackageOutput};
+
+    #[test]
+    pub fn enumerate_packable_projects() {
+        // given
+        let library = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <GeneratePackageOnBuild>true</GeneratePackageOnBuild>
+  </PropertyGroup>
+</Project>"#;
+        let tool = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <PackAsTool>true</PackAsTool>
+  </PropertyGroup>
+</Project>"#;
+        let tests = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Microsoft.NET.Test.Sdk" Version="17.10.0" />
+  </ItemGroup>
+</Project>"#;
+        let internal = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <IsPackable>false</IsPackable>
+  </PropertyGroup>
+</Project>"#;
+
+        let projects = vec![
+            parse(Cursor::new(library), "Library.csproj").unwrap(),
+            parse(Cursor::new(tool), "Tool.csproj").unwrap(),
+            parse(Cursor::new(tests), "Library.Tests.csproj").unwrap(),
+            parse(Cursor::new(internal), "Internal.csproj").unwrap(),
+        ];
+
+        // when
+        let packable = packable_projects(&projects);
+
+        // then
+        let summary: Vec<(PathBuf, PackageOutput, bool)> = packable
+            .iter()
+            .map(|p| (p.project().clone(), p.output(), p.packed_on_build()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    PathBuf::from("Library.csproj"),
+                    PackageOutput::Package,
+                    true
+                ),
+                (PathBuf::from("Tool.csproj"), PackageOutput::Tool, false),
+            ]
+        );
+    }
+}
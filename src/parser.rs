@@ -3,12 +3,29 @@ use spex::{
     xml::{Element, XmlDocument},
 };
 use std::{
+    borrow::Cow,
+    fs::File,
     io::{self, Read},
     path::{Path, PathBuf},
 };
 use thiserror::Error;
 
-use crate::{PackageReference, Project, ProjectLanguage, ProjectReference};
+use crate::{
+    central_packages, compose,
+    condition::{both, combine, evaluate, ConditionalProperty, Properties},
+    intern::Interner,
+    migration,
+    vfs::{FileSystem, MemoryFileSystem, OsFileSystem},
+    PackageReference, Project, ProjectLanguage, ProjectReference, VALID_EXTENSIONS,
+};
+
+/// The property the tags of a project are read from, unless [`TAGS_PROPERTY_SETTING`] is set.
+pub const DEFAULT_TAGS_PROPERTY: &str = "ProjectTags";
+
+/// The property naming another property to read the tags of a project from (ex:
+/// `DotnetLensTagsProperty=Owners`), set with the other properties passed to
+/// [`parse_with_properties`] or by the project itself.
+pub const TAGS_PROPERTY_SETTING: &str = "DotnetLensTagsProperty";
 
 /// Parses a .NET project file and extracts project information.
 ///
@@ -30,7 +47,7 @@ use crate::{PackageReference, Project, ProjectLanguage, ProjectReference};
 ///
 /// This function returns a `ParseError` in the following cases:
 /// * If the path is a directory.
-/// * If the file is not a recognized project file type (.csproj, .fsproj, .vbproj).
+/// * If the file does not have one of the [`VALID_EXTENSIONS`].
 /// * If the file does not have a name.
 /// * If there is an error reading or deserializing the file.
 ///
@@ -47,12 +64,105 @@ use crate::{PackageReference, Project, ProjectLanguage, ProjectReference};
 /// println!("Parsed project: {:?}", project);
 /// ```
 pub fn parse<R, P>(reader: R, path: P) -> Result<Project, ParseError>
+where
+    R: Read,
+    P: AsRef<Path>,
+{
+    parse_with_properties(reader, path, &Properties::new())
+}
+
+/// Parses a .NET project file like [`parse`], evaluating its conditions with the given
+/// `properties` (ex: `Configuration=Release`), as if they were passed to the build.
+///
+/// The given properties are global, like the `-p` options of `dotnet build`: the project file
+/// cannot change them. The item groups and items whose conditions evaluate to `false` are
+/// skipped, and the ones whose conditions cannot be evaluated are kept with their conditions.
+///
+/// # Errors
+///
+/// This function returns a `ParseError` in the same cases as [`parse`].
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{condition::Properties, parser::parse_with_properties};
+/// use std::io::Cursor;
+///
+/// let content = r#"<Project>
+///   <PropertyGroup Condition="'$(Configuration)' == 'Release'">
+///     <TargetFramework>net8.0</TargetFramework>
+///   </PropertyGroup>
+/// </Project>"#;
+///
+/// let mut properties = Properties::new();
+/// properties.set("Configuration", "Release");
+///
+/// let project = parse_with_properties(Cursor::new(content), "Api.csproj", &properties).unwrap();
+///
+/// assert_eq!(project.target_framework().unwrap(), "net8.0");
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(path = %path.as_ref().display()))
+)]
+pub fn parse_with_properties<R, P>(
+    reader: R,
+    path: P,
+    properties: &Properties,
+) -> Result<Project, ParseError>
+where
+    R: Read,
+    P: AsRef<Path>,
+{
+    parse_in(reader, path, properties, &OsFileSystem, &Interner::new())
+}
+
+/// Parses a .NET project file like [`parse_with_properties`], reading the files next to it (ex:
+/// the compose files of a Docker Compose project) from `files` instead of the local disk.
+///
+/// The names and versions of the references are interned with `strings`, so the projects parsed
+/// with the same interner share them.
+///
+/// # Errors
+///
+/// This function returns a `ParseError` in the same cases as [`parse`], or if one of the files
+/// next to the project could not be read.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{
+///     condition::Properties, intern::Interner, parser::parse_in, vfs::MemoryFileSystem,
+/// };
+///
+/// let mut files = MemoryFileSystem::new();
+/// files.add_file("/repo/src/Api/Api.csproj", "<Project />");
+/// files.add_file(
+///     "/repo/docker-compose.yml",
+///     "services:\n  api:\n    build:\n      context: .\n      dockerfile: src/Api/Dockerfile\n",
+/// );
+///
+/// let content = "<Project Sdk=\"Microsoft.Docker.Sdk\" />";
+/// let path = "/repo/docker-compose.dcproj";
+///
+/// let strings = Interner::new();
+/// let project = parse_in(content.as_bytes(), path, &Properties::new(), &files, &strings).unwrap();
+///
+/// assert_eq!(project.project_references()[0].name(), "Api");
+/// ```
+pub fn parse_in<R, P>(
+    reader: R,
+    path: P,
+    properties: &Properties,
+    files: &dyn FileSystem,
+    strings: &Interner,
+) -> Result<Project, ParseError>
 where
     R: Read,
     P: AsRef<Path>,
 {
     let path = path.as_ref();
-    if path.is_dir() {
+    if files.is_dir(path) {
         return Err(ParseError::PathIsNotAFile);
     }
 
@@ -63,16 +173,181 @@ where
 
     let name = Project::get_project_name(path).ok_or(ParseError::FileDoesNotHaveAName)?;
 
-    let mut project = Project {
+    parse_project(
+        reader,
         name,
-        language: language.unwrap(),
-        path: path.to_owned(),
-        target_framework: None,
-        project_references: vec![],
-        package_references: vec![],
-    };
+        language.unwrap(),
+        path.to_owned(),
+        properties,
+        files,
+        strings,
+    )
+}
+
+/// Opens and parses the project file at `path` like [`parse_in`], reading the files next to it
+/// from the local disk.
+pub(crate) fn parse_file(
+    path: &Path,
+    properties: &Properties,
+    strings: &Interner,
+) -> Result<Project, ParseError> {
+    parse_in(File::open(path)?, path, properties, &OsFileSystem, strings)
+}
+
+/// Parses .NET project content that does not come from a project file, such as the standard
+/// input or the output of `git show`.
+///
+/// Since there is no file, the name and language of the project must be provided. The path of
+/// the project is set to the name with the extension of the language (ex: "MyProject.csproj").
+/// The files next to a project (ex: `Directory.Packages.props`) are not read, since the content
+/// is not in a directory.
+///
+/// # Errors
+///
+/// This function returns a `ParseError` if the content could not be read or deserialized.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{parser::parse_as, ProjectLanguage};
+/// use std::io::Cursor;
+///
+/// let content = "<Project><PropertyGroup><TargetFramework>net8.0</TargetFramework></PropertyGroup></Project>";
+/// let project = parse_as(Cursor::new(content), "MyProject", ProjectLanguage::CSharp).unwrap();
+///
+/// assert_eq!(project.path().to_str(), Some("MyProject.csproj"));
+/// ```
+pub fn parse_as<R>(reader: R, name: &str, language: ProjectLanguage) -> Result<Project, ParseError>
+where
+    R: Read,
+{
+    parse_as_with_properties(reader, name, language, &Properties::new())
+}
+
+/// Parses .NET project content that does not come from a project file like [`parse_as`],
+/// evaluating the conditions with the given global properties like
+/// [`parse_with_properties`].
+///
+/// # Errors
+///
+/// This function returns a `ParseError` if the content could not be read or deserialized.
+pub fn parse_as_with_properties<R>(
+    reader: R,
+    name: &str,
+    language: ProjectLanguage,
+    properties: &Properties,
+) -> Result<Project, ParseError>
+where
+    R: Read,
+{
+    let path = PathBuf::from(format!("{}.{}", name, language.extension()));
+
+    parse_project(
+        reader,
+        name.to_string(),
+        language,
+        path,
+        properties,
+        &MemoryFileSystem::new(),
+        &Interner::new(),
+    )
+}
+
+/// A parser for a type of project file, registered for a file extension in a
+/// [`crate::parser_registry::ParserRegistry`] so workspaces can load project types that are not
+/// built in.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{
+///     parser::{ParseError, ProjectParser},
+///     Project, ProjectLanguage, ProjectReference,
+/// };
+/// use std::{
+///     io::{BufRead, BufReader, Read},
+///     path::{Path, PathBuf},
+/// };
+///
+/// /// Reads the projects referenced by a file listing one path per line.
+/// struct ListParser;
+///
+/// impl ProjectParser for ListParser {
+///     fn parse(&self, reader: &mut dyn Read, path: &Path) -> Result<Project, ParseError> {
+///         let name = Project::get_project_name(path).ok_or(ParseError::FileDoesNotHaveAName)?;
+///         let mut project = Project::empty(name, ProjectLanguage::CSharp, path.to_path_buf());
+///
+///         for line in BufReader::new(reader).lines() {
+///             let reference = PathBuf::from(line?.trim());
+///             let name = Project::get_project_name(&reference).unwrap_or_default();
+///
+///             project.add_project_reference(ProjectReference::new(name, reference));
+///         }
+///
+///         Ok(project)
+///     }
+/// }
+/// ```
+pub trait ProjectParser: Send + Sync {
+    /// Parses the content of the project file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a `ParseError` if the content could not be read or parsed.
+    fn parse(&self, reader: &mut dyn Read, path: &Path) -> Result<Project, ParseError>;
+}
+
+/// A [`ProjectParser`] reading MSBuild project files with another extension (ex: `.sqlproj`,
+/// `.esproj`) like the built-in ones, as projects of the given language.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MsBuildParser {
+    language: ProjectLanguage,
+}
+
+impl MsBuildParser {
+    /// Creates a new `MsBuildParser` for projects of the given language.
+    pub fn new(language: ProjectLanguage) -> Self {
+        Self { language }
+    }
+}
+
+impl ProjectParser for MsBuildParser {
+    fn parse(&self, reader: &mut dyn Read, path: &Path) -> Result<Project, ParseError> {
+        let name = Project::get_project_name(path).ok_or(ParseError::FileDoesNotHaveAName)?;
+
+        parse_project(
+            reader,
+            name,
+            self.language,
+            path.to_owned(),
+            &Properties::new(),
+            &OsFileSystem,
+            &Interner::new(),
+        )
+    }
+}
+
+fn parse_project<R>(
+    reader: R,
+    name: String,
+    language: ProjectLanguage,
+    path: PathBuf,
+    properties: &Properties,
+    files: &dyn FileSystem,
+    strings: &Interner,
+) -> Result<Project, ParseError>
+where
+    R: Read,
+{
+    let mut project = Project::empty(name, language, path);
+
+    let document = XmlReader::parse_auto(reader)?;
+    fill_project_based_on_xml(&mut project, document, properties, strings)?;
+    let packages_config = migration::find_packages_config(project.path(), files);
+    project.migration.set_packages_config(packages_config);
 
-    fill_project_based_on_xml(&mut project, XmlReader::parse_auto(reader)?)?;
+    central_packages::resolve_versions(&mut project, files, strings)?;
+    compose::add_service_references(&mut project, files)?;
 
     Ok(project)
 }
@@ -80,65 +355,349 @@ where
 fn fill_project_based_on_xml(
     project: &mut Project,
     document: XmlDocument,
+    properties: &Properties,
+    strings: &Interner,
 ) -> Result<(), ParseError> {
-    for element in document.root().elements() {
-        match element.name().local_part() {
-            "PropertyGroup" => handle_property_group(project, element)?,
-            "ItemGroup" => handle_item_group(project, element)?,
-            _ => (),
+    let mut evaluated = properties.clone();
+    let mut target_frameworks = TargetFrameworkValues::default();
+
+    project.migration.set_sdks(project_sdks(document.root()));
+
+    for element in document
+        .root()
+        .elements()
+        .filter(|element| element.name().local_part() == "PropertyGroup")
+    {
+        handle_property_group(
+            project,
+            element,
+            properties,
+            &mut evaluated,
+            &mut target_frameworks,
+        )?;
+    }
+
+    // like MSBuild, the items are evaluated once every property is
+    for element in document
+        .root()
+        .elements()
+        .filter(|element| element.name().local_part() == "ItemGroup")
+    {
+        handle_item_group(project, element, &evaluated, strings)?;
+    }
+
+    let TargetFrameworkValues { single, multiple } = target_frameworks;
+
+    // like MSBuild, `TargetFrameworks` is ignored when `TargetFramework` is set
+    if single.is_empty() {
+        project.target_framework = multiple.first().cloned();
+        project.target_frameworks = multiple;
+    } else {
+        project.target_framework = single.first().cloned();
+
+        if single.len() > 1 {
+            project.target_framework_candidates = single;
         }
     }
 
     Ok(())
 }
 
-fn handle_property_group(project: &mut Project, element: &Element) -> Result<(), ParseError> {
-    // currently, the target framework is the only information that we look in the
-    // PropertyGroup tag
-    if project.target_framework.is_some() {
-        return Ok(());
+/// Returns the MSBuild project SDKs of a project file, from the `Sdk` attribute of its `Project`
+/// element, its `Sdk` elements and its imports with an `Sdk` attribute.
+fn project_sdks(root: &Element) -> Vec<String> {
+    let imports = root
+        .elements()
+        .filter(|element| element.name().local_part() == "ImportGroup")
+        .flat_map(|group| group.elements());
+
+    let declared =
+        root.elements()
+            .chain(imports)
+            .filter_map(|element| match element.name().local_part() {
+                "Sdk" => element.att_opt("Name"),
+                "Import" => element.att_opt("Sdk"),
+                _ => None,
+            });
+
+    let mut sdks: Vec<String> = vec![];
+
+    for sdk in root
+        .att_opt("Sdk")
+        .into_iter()
+        .flat_map(|sdks| sdks.split(';'))
+        .chain(declared)
+        .map(str::trim)
+    {
+        if !sdk.is_empty() && !sdks.iter().any(|known| known == sdk) {
+            sdks.push(sdk.to_string());
+        }
     }
 
-    project.target_framework = element
-        .opt("TargetFramework")
-        .text()?
-        .map(|target| target.to_string());
+    sdks
+}
+
+/// The values of `TargetFramework` (`single`) and `TargetFrameworks` (`multiple`) evaluated so far.
+#[derive(Default)]
+struct TargetFrameworkValues {
+    single: Vec<String>,
+    multiple: Vec<String>,
+}
+
+/// Evaluates the properties of a group in order, like MSBuild does.
+///
+/// The `global` properties, given by the caller, cannot be changed by the project: their
+/// assignments are ignored.
+///
+/// `target_frameworks` holds the possible values of `TargetFramework` and the frameworks of
+/// `TargetFrameworks`: a value set under conditions that are `true` replaces them, and a value set
+/// under conditions that could not be evaluated is added to them.
+fn handle_property_group(
+    project: &mut Project,
+    element: &Element,
+    global: &Properties,
+    properties: &mut Properties,
+    target_frameworks: &mut TargetFrameworkValues,
+) -> Result<(), ParseError> {
+    let group_condition = element
+        .att_opt("Condition")
+        .map_or(Some(true), |condition| evaluate(condition, properties));
+
+    for property in element.elements() {
+        let name = property.name().local_part();
+        let value = property.text()?.trim();
+
+        if let Some(raw_condition) =
+            combine(element.att_opt("Condition"), property.att_opt("Condition"))
+        {
+            project
+                .conditional_properties
+                .push(ConditionalProperty::new(
+                    name.to_string(),
+                    value.to_string(),
+                    raw_condition,
+                ));
+        }
+
+        let condition = property
+            .att_opt("Condition")
+            .map_or(Some(true), |condition| evaluate(condition, properties));
+
+        let condition = both(group_condition, condition);
+        if condition == Some(false) || global.get(name).is_some() {
+            continue;
+        }
+
+        let expanded = properties.expand(value);
+
+        match (condition, &expanded) {
+            (Some(true), Some(expanded)) => properties.set(name, expanded),
+            _ => properties.unset(name),
+        }
+
+        // the values are kept as written when they could not be expanded
+        let value = expanded.unwrap_or_else(|| value.to_string());
+
+        let tags_property = properties
+            .get(TAGS_PROPERTY_SETTING)
+            .unwrap_or(DEFAULT_TAGS_PROPERTY);
+
+        if name.eq_ignore_ascii_case(tags_property) {
+            project.tags = parse_tags(&value);
+        }
+
+        match name {
+            "TargetFramework" | "TargetFrameworks" => {
+                let values: Vec<String> = value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+                    .collect();
+
+                let known = match name {
+                    "TargetFramework" => &mut target_frameworks.single,
+                    _ => &mut target_frameworks.multiple,
+                };
+
+                if condition == Some(true) {
+                    *known = values;
+                } else {
+                    for value in values {
+                        if !known.contains(&value) {
+                            known.push(value);
+                        }
+                    }
+                }
+            }
+            "PackageLicenseExpression" => project.license_expression = Some(value),
+            "RepositoryUrl" => project.repository.set_url(Some(value)),
+            "RepositoryType" => project.repository.set_repository_type(Some(value)),
+            "PublishRepositoryUrl" => project
+                .repository
+                .set_publish_url(Some(value.eq_ignore_ascii_case("true"))),
+            "IsPackable" => project
+                .packaging
+                .set_is_packable(Some(value.eq_ignore_ascii_case("true"))),
+            "GeneratePackageOnBuild" => project
+                .packaging
+                .set_generate_package_on_build(Some(value.eq_ignore_ascii_case("true"))),
+            "PackAsTool" => project
+                .packaging
+                .set_pack_as_tool(Some(value.eq_ignore_ascii_case("true"))),
+            "TreatWarningsAsErrors" => project
+                .strictness
+                .set_treat_warnings_as_errors(parse_bool(&value)),
+            "NoWarn" => {
+                let mut codes = parse_tags(&value);
+                codes.retain(|code| !code.contains("$("));
+
+                // `$(NoWarn)` could not be expanded, so the codes are added to the known ones
+                if value.to_lowercase().contains("$(nowarn)") {
+                    let mut no_warn = project.strictness.no_warn().clone();
+
+                    for code in codes {
+                        if !no_warn
+                            .iter()
+                            .any(|known| known.eq_ignore_ascii_case(&code))
+                        {
+                            no_warn.push(code);
+                        }
+                    }
+
+                    codes = no_warn;
+                }
+
+                project.strictness.set_no_warn(codes);
+            }
+            "AnalysisLevel" => project.strictness.set_analysis_level(Some(value)),
+            "Nullable" => project.strictness.set_nullable(Some(value)),
+            "AssemblyName" => project.assembly.set_name(Some(value)),
+            "AssemblyVersion" => project.assembly.set_version(Some(value)),
+            "FileVersion" => project.assembly.set_file_version(Some(value)),
+            "InformationalVersion" => project.assembly.set_informational_version(Some(value)),
+            "UseMaui" => project.workloads.set_use_maui(parse_bool(&value)),
+            "RunAOTCompilation" => project
+                .workloads
+                .set_run_aot_compilation(parse_bool(&value)),
+            "WasmBuildNative" => project.workloads.set_wasm_build_native(parse_bool(&value)),
+            "IsAspireHost" => project.workloads.set_is_aspire_host(parse_bool(&value)),
+            "TargetFrameworkVersion" => project.migration.set_target_framework_version(Some(value)),
+            "RuntimeIdentifier" | "RuntimeIdentifiers" => {
+                let mut runtime_identifiers = project.workloads.runtime_identifiers().clone();
+
+                for identifier in value.split(';').map(str::trim) {
+                    let identifier = identifier.to_lowercase();
+
+                    if !identifier.is_empty() && !runtime_identifiers.contains(&identifier) {
+                        runtime_identifiers.push(identifier);
+                    }
+                }
+
+                project
+                    .workloads
+                    .set_runtime_identifiers(runtime_identifiers);
+            }
+            _ => (),
+        }
+    }
 
     Ok(())
 }
 
-fn handle_item_group(project: &mut Project, element: &Element) -> Result<(), ParseError> {
+/// Reads the references of an item group, skipping the group and the items whose conditions
+/// evaluate to `false` with the `properties` of the project.
+fn handle_item_group(
+    project: &mut Project,
+    element: &Element,
+    properties: &Properties,
+    strings: &Interner,
+) -> Result<(), ParseError> {
+    let group_condition = element.att_opt("Condition");
+    if group_condition.and_then(|condition| evaluate(condition, properties)) == Some(false) {
+        return Ok(());
+    }
+
+    let group_target_framework = group_condition.and_then(target_framework_condition);
+
     for item in element.elements() {
+        if item
+            .att_opt("Condition")
+            .and_then(|condition| evaluate(condition, properties))
+            == Some(false)
+        {
+            continue;
+        }
+
+        let condition = combine(group_condition, item.att_opt("Condition"));
+
+        // both conditions have to be true, so an item conditioned on another target framework
+        // than its group never applies
+        let target_framework = match (
+            item.att_opt("Condition")
+                .and_then(target_framework_condition),
+            &group_target_framework,
+        ) {
+            (Some(item), Some(group)) if !item.eq_ignore_ascii_case(group) => continue,
+            (item, group) => item.or_else(|| group.clone()),
+        };
+
         match item.name().local_part() {
             "ProjectReference" => {
                 let attr_content = item
                     .att_req("Include")
-                    .map_err(|_| ParseError::DeserializationError)?
-                    .replace("\\", "/");
+                    .map_err(|_| ParseError::DeserializationError)?;
 
-                let path = PathBuf::from(attr_content);
+                let path = PathBuf::from(normalize_separators(attr_content).into_owned());
 
-                let name =
-                    Project::get_project_name(&path).ok_or(ParseError::FileDoesNotHaveAName)?;
+                let mut reference = ProjectReference::from_path(path, strings)
+                    .ok_or(ParseError::FileDoesNotHaveAName)?;
+                reference.set_target_framework(target_framework);
+                reference.set_condition(condition);
+                reference.set_output_item_type(owned_metadata(item, "OutputItemType")?);
+                reference.set_reference_output_assembly(owned_metadata(
+                    item,
+                    "ReferenceOutputAssembly",
+                )?);
 
-                project
-                    .project_references
-                    .push(ProjectReference::new(name, path));
+                project.project_references.push(reference);
             }
             "PackageReference" => {
                 let name = item
                     .att_req("Include")
-                    .map_err(|_| ParseError::DeserializationError)?
-                    .to_string();
-
-                let version = item
-                    .att_req("Version")
-                    .map_err(|_| ParseError::DeserializationError)?
-                    .to_string();
-
-                project
-                    .package_references
-                    .push(PackageReference::new(name, version));
+                    .map_err(|_| ParseError::DeserializationError)?;
+
+                // without a version, the package is versioned centrally (see `central_packages`)
+                let version = match item_metadata(item, "VersionOverride")? {
+                    Some(version) => Some(version),
+                    None => item_metadata(item, "Version")?,
+                };
+
+                let mut reference = PackageReference::from_shared(
+                    strings.intern(name),
+                    strings.intern(version.unwrap_or_default()),
+                );
+                reference.set_target_framework(target_framework);
+                reference.set_private_assets(owned_metadata(item, "PrivateAssets")?);
+                reference.set_include_assets(owned_metadata(item, "IncludeAssets")?);
+                reference.set_exclude_assets(owned_metadata(item, "ExcludeAssets")?);
+                reference.set_condition(condition);
+
+                project.package_references.push(reference);
+            }
+            // the assemblies of the global assembly cache are referenced without a path
+            "Reference" if item_metadata(item, "HintPath")?.is_none() => {
+                if let Some(name) = item
+                    .att_opt("Include")
+                    .and_then(|include| include.split(',').next())
+                {
+                    project.migration.add_gac_reference(name.trim().to_string());
+                }
+            }
+            "COMReference" | "COMFileReference" => {
+                if let Some(include) = item.att_opt("Include") {
+                    project.migration.add_com_reference(include.to_string());
+                }
             }
             _ => (),
         }
@@ -147,6 +706,81 @@ fn handle_item_group(project: &mut Project, element: &Element) -> Result<(), Par
     Ok(())
 }
 
+/// Returns the metadata of an item, written either as an attribute or as a child element,
+/// borrowed from the document until it is stored.
+fn item_metadata<'a>(item: &'a Element, name: &str) -> Result<Option<&'a str>, ParseError> {
+    if let Some(value) = item.att_opt(name) {
+        return Ok(Some(value.trim()));
+    }
+
+    for child in item.elements() {
+        if child.name().local_part() == name {
+            return Ok(Some(child.text()?.trim()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns the metadata of an item like [`item_metadata`], copied to be stored in a reference.
+fn owned_metadata(item: &Element, name: &str) -> Result<Option<String>, ParseError> {
+    Ok(item_metadata(item, name)?.map(str::to_string))
+}
+
+/// Returns whether a boolean property is `true`, or `None` if its value could not be expanded.
+fn parse_bool(value: &str) -> Option<bool> {
+    (!value.contains("$(")).then(|| value.eq_ignore_ascii_case("true"))
+}
+
+/// Splits a list of tags separated by `;` or `,`, without empty entries and duplicates.
+fn parse_tags(value: &str) -> Vec<String> {
+    let mut tags: Vec<String> = vec![];
+
+    for tag in value.split([';', ',']).map(str::trim) {
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+
+    tags
+}
+
+/// Returns the target framework of a condition comparing it with a literal, such as
+/// `'$(TargetFramework)' == 'net48'`. Other conditions are not evaluated and return `None`.
+fn target_framework_condition(condition: &str) -> Option<String> {
+    let (left, right) = condition.split_once("==")?;
+
+    let unquote = |side: &str| {
+        let side = side.trim();
+        side.strip_prefix('\'')
+            .and_then(|side| side.strip_suffix('\''))
+            .unwrap_or(side)
+            .trim()
+            .to_string()
+    };
+
+    let (left, right) = (unquote(left), unquote(right));
+    let is_property = |side: &str| side.eq_ignore_ascii_case("$(TargetFramework)");
+
+    let value = match (is_property(&left), is_property(&right)) {
+        (true, false) => right,
+        (false, true) => left,
+        _ => return None,
+    };
+
+    (!value.is_empty() && !value.contains(['$', '\'', '(', ')'])).then_some(value)
+}
+
+/// Replaces the Windows separators of a path written in a project file, borrowing the attribute
+/// when there is nothing to replace, which is the common case outside of Visual Studio solutions.
+fn normalize_separators(path: &str) -> Cow<'_, str> {
+    if path.contains('\\') {
+        Cow::Owned(path.replace('\\', "/"))
+    } else {
+        Cow::Borrowed(path)
+    }
+}
+
 /// Represents errors that can occur during project file parsing.
 #[derive(Debug, Error)]
 pub enum ParseError {
@@ -159,8 +793,11 @@ pub enum ParseError {
     /// The provided path is a directory, not a file.
     #[error("the path is a directory")]
     PathIsNotAFile,
-    /// The file is not a recognized project file type (.csproj, .fsproj, .vbproj).
-    #[error("the file is not a project (.csproj, .fsproj, .vbproj)")]
+    /// The file does not have one of the [`VALID_EXTENSIONS`].
+    #[error(
+        "the file is not a project (expected one of the extensions {})",
+        VALID_EXTENSIONS.join(", ")
+    )]
     FileIsNotAProject,
     /// The file does not have a name.
     #[error("the file does not have a name")]
@@ -220,26 +857,32 @@ mod test {
         let parsed_project = parse(Cursor::new(content), project_path).unwrap();
 
         // then
-        let expected_project = Project {
-            name: "TestProject".to_string(),
-            path: PathBuf::from(project_path),
-            language: ProjectLanguage::CSharp,
-            target_framework: Some("net8.0".to_string()),
-            project_references: vec![ProjectReference {
-                name: "FsharpConsole".to_string(),
-                path: PathBuf::from("../FsharpConsole/FsharpConsole.fsproj"),
-            }],
-            package_references: vec![
-                PackageReference {
-                    name: "Microsoft.Extensions.Configuration".to_string(),
-                    version: "8.0.0".to_string(),
-                },
-                PackageReference {
-                    name: "Microsoft.Extensions.Hosting".to_string(),
-                    version: "8.0.0".to_string(),
-                },
-            ],
-        };
+        let mut expected_project = Project::empty(
+            "TestProject".to_string(),
+            ProjectLanguage::CSharp,
+            PathBuf::from(project_path),
+        );
+        expected_project
+            .migration
+            .set_sdks(vec!["Microsoft.NET.Sdk".to_string()]);
+        expected_project.target_framework = Some("net8.0".to_string());
+        expected_project
+            .strictness
+            .set_nullable(Some("enable".to_string()));
+        expected_project.project_references = vec![ProjectReference::new(
+            "FsharpConsole".to_string(),
+            PathBuf::from("../FsharpConsole/FsharpConsole.fsproj"),
+        )];
+        expected_project.package_references = vec![
+            PackageReference::new(
+                "Microsoft.Extensions.Configuration".to_string(),
+                "8.0.0".to_string(),
+            ),
+            PackageReference::new(
+                "Microsoft.Extensions.Hosting".to_string(),
+                "8.0.0".to_string(),
+            ),
+        ];
 
         assert_eq!(parsed_project, expected_project);
     }
@@ -276,20 +919,23 @@ mod test {
         let parsed_project = parse(Cursor::new(content), project_path).unwrap();
 
         // then
-        let expected_project = Project {
-            name: "TestProject".to_string(),
-            path: PathBuf::from(project_path),
-            language: ProjectLanguage::FSharp,
-            target_framework: Some("net8.0".to_string()),
-            project_references: vec![ProjectReference {
-                name: "VbConsole".to_string(),
-                path: PathBuf::from("../VbConsole/VbConsole.vbproj"),
-            }],
-            package_references: vec![PackageReference {
-                name: "Microsoft.Extensions.Configuration".to_string(),
-                version: "8.0.0".to_string(),
-            }],
-        };
+        let mut expected_project = Project::empty(
+            "TestProject".to_string(),
+            ProjectLanguage::FSharp,
+            PathBuf::from(project_path),
+        );
+        expected_project
+            .migration
+            .set_sdks(vec!["Microsoft.NET.Sdk".to_string()]);
+        expected_project.target_framework = Some("net8.0".to_string());
+        expected_project.project_references = vec![ProjectReference::new(
+            "VbConsole".to_string(),
+            PathBuf::from("../VbConsole/VbConsole.vbproj"),
+        )];
+        expected_project.package_references = vec![PackageReference::new(
+            "Microsoft.Extensions.Configuration".to_string(),
+            "8.0.0".to_string(),
+        )];
 
         assert_eq!(parsed_project, expected_project);
     }
@@ -323,24 +969,136 @@ mod test {
         let parsed_project = parse(Cursor::new(content), project_path).unwrap();
 
         // then
-        let expected_project = Project {
-            name: "TestProject".to_string(),
-            path: PathBuf::from(project_path),
-            language: ProjectLanguage::VB,
-            target_framework: Some("net8.0".to_string()),
-            project_references: vec![ProjectReference {
-                name: "FsharpConsole".to_string(),
-                path: PathBuf::from("../FsharpConsole/FsharpConsole.fsproj"),
-            }],
-            package_references: vec![PackageReference {
-                name: "Microsoft.Extensions.Configuration".to_string(),
-                version: "8.0.0".to_string(),
-            }],
-        };
+        let mut expected_project = Project::empty(
+            "TestProject".to_string(),
+            ProjectLanguage::VB,
+            PathBuf::from(project_path),
+        );
+        expected_project
+            .migration
+            .set_sdks(vec!["Microsoft.NET.Sdk".to_string()]);
+        expected_project.target_framework = Some("net8.0".to_string());
+        expected_project.project_references = vec![ProjectReference::new(
+            "FsharpConsole".to_string(),
+            PathBuf::from("../FsharpConsole/FsharpConsole.fsproj"),
+        )];
+        expected_project.package_references = vec![PackageReference::new(
+            "Microsoft.Extensions.Configuration".to_string(),
+            "8.0.0".to_string(),
+        )];
 
         assert_eq!(parsed_project, expected_project);
     }
 
+    #[test]
+    pub fn parse_license_expression() {
+        // given
+        let content = r#"
+<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <PackageLicenseExpression>MIT</PackageLicenseExpression>
+  </PropertyGroup>
+</Project>
+"#;
+
+        // when
+        let project = parse(Cursor::new(content), "./TestProject.vbproj").unwrap();
+
+        // then
+        assert_eq!(project.license_expression().unwrap(), "MIT");
+    }
+
+    #[test]
+    pub fn parse_wix_installer() {
+        // given
+        let content = r#"
+<Project Sdk="WixToolset.Sdk/5.0.1">
+  <PropertyGroup>
+    <OutputName>ShopSetup</OutputName>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <PackageReference Include="WixToolset.UI.wixext" Version="5.0.1" />
+  </ItemGroup>
+
+  <ItemGroup>
+    <ProjectReference Include="..\Shop.Desktop\Shop.Desktop.csproj" Publish="true" />
+  </ItemGroup>
+</Project>
+"#;
+
+        // when
+        let project = parse(Cursor::new(content), "Installer/Shop.Setup.wixproj").unwrap();
+
+        // then
+        assert_eq!(project.language(), ProjectLanguage::Wix);
+        assert!(project.language().is_deployment());
+        assert_eq!(project.target_framework(), None);
+        assert_eq!(project.project_references()[0].name(), "Shop.Desktop");
+        assert_eq!(
+            project.project_references()[0].path(),
+            &PathBuf::from("../Shop.Desktop/Shop.Desktop.csproj")
+        );
+        assert_eq!(
+            project.package_references()[0].name(),
+            "WixToolset.UI.wixext"
+        );
+    }
+
+    #[test]
+    pub fn parse_content_with_assumed_name() {
+        // given
+        let content = r#"
+<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>
+"#;
+
+        // when
+        let parsed_project =
+            parse_as(Cursor::new(content), "FromStdin", ProjectLanguage::FSharp).unwrap();
+
+        // then
+        assert_eq!(parsed_project.name(), "FromStdin");
+        assert_eq!(parsed_project.path(), &PathBuf::from("FromStdin.fsproj"));
+        assert_eq!(parsed_project.language(), ProjectLanguage::FSharp);
+        assert_eq!(
+            parsed_project.target_framework(),
+            Some(&"net8.0".to_string())
+        );
+    }
+
+    #[test]
+    pub fn parse_content_with_assumed_name_and_properties() {
+        // given
+        let content = r#"
+<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup Condition="'$(Configuration)' == 'Release'">
+    <PackageReference Include="Serilog" />
+  </ItemGroup>
+</Project>
+"#;
+
+        let mut properties = Properties::new();
+        properties.set("Configuration", "Release");
+
+        // when
+        let parsed_project = parse_as_with_properties(
+            Cursor::new(content),
+            "FromStdin",
+            ProjectLanguage::CSharp,
+            &properties,
+        )
+        .unwrap();
+
+        // then
+        assert_eq!(parsed_project.package_references()[0].name(), "Serilog");
+        assert_eq!(parsed_project.package_references()[0].version(), "");
+    }
+
     #[test]
     pub fn invalid_xml() {
         // given
@@ -383,7 +1141,7 @@ mod test {
 <Project Sdk="Microsoft.NET.Sdk">
 
   <ItemGroup>
-    <PackageReference Include="Microsoft.Extensions.Configuration" />
+    <PackageReference Version="8.0.0" />
   </ItemGroup>
 
   <ItemGroup>
@@ -467,4 +1225,373 @@ mod test {
 
         unreachable!()
     }
+
+    #[test]
+    pub fn borrow_paths_without_windows_separators() {
+        // given
+        let unix = "../Core/Core.csproj";
+        let windows = "..\\Core\\Core.csproj";
+
+        // when
+        let unix_path = normalize_separators(unix);
+        let windows_path = normalize_separators(windows);
+
+        // then
+        assert!(matches!(unix_path, Cow::Borrowed("../Core/Core.csproj")));
+        assert!(matches!(windows_path, Cow::Owned(_)));
+        assert_eq!(windows_path, "../Core/Core.csproj");
+    }
+
+    #[test]
+    pub fn parse_target_framework_conditions() {
+        // given
+        let content = r#"
+<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFrameworks>net8.0;net48</TargetFrameworks>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.1.1" />
+  </ItemGroup>
+
+  <ItemGroup Condition=" '$(TargetFramework)' == 'net48' ">
+    <PackageReference Include="System.ValueTuple" Version="4.5.0" />
+    <ProjectReference Include="..\Legacy\Legacy.csproj" />
+    <PackageReference Include="Polly" Version="8.4.1" Condition="'net8.0'=='$(TargetFramework)'" />
+    <PackageReference Include="Microsoft.Bcl.AsyncInterfaces" Version="8.0.0" Condition="'NET48'=='$(TargetFramework)'" />
+  </ItemGroup>
+
+  <ItemGroup Condition="'$(Configuration)' == 'Debug'">
+    <PackageReference Include="Debugger" Version="1.0.0" />
+  </ItemGroup>
+</Project>"#;
+
+        // when
+        let project = parse(Cursor::new(content), "Api.csproj").unwrap();
+
+        // then
+        let conditions: Vec<(&str, Option<&str>)> = project
+            .package_references()
+            .iter()
+            .map(|reference| {
+                (
+                    reference.name().as_str(),
+                    reference.target_framework().map(|t| t.as_str()),
+                )
+            })
+            .collect();
+        let net8: Vec<&str> = project
+            .package_references_for("net8.0")
+            .map(|reference| reference.name().as_str())
+            .collect();
+
+        assert_eq!(
+            conditions,
+            vec![
+                ("Serilog", None),
+                ("System.ValueTuple", Some("net48")),
+                ("Microsoft.Bcl.AsyncInterfaces", Some("NET48")),
+                ("Debugger", None),
+            ]
+        );
+        assert_eq!(net8, vec!["Serilog", "Debugger"]);
+        assert_eq!(project.target_frameworks(), vec!["net8.0", "net48"]);
+        assert_eq!(
+            project.project_references()[0].target_framework(),
+            Some(&"net48".to_string())
+        );
+        assert_eq!(project.project_references_for("net8.0").count(), 0);
+    }
+
+    #[test]
+    pub fn keep_raw_conditions() {
+        // given
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <Nullable Condition="'$(CI)' == 'true'">enable</Nullable>
+  </PropertyGroup>
+  <PropertyGroup Condition="'$(Configuration)' == 'Debug'">
+    <DefineConstants>$(DefineConstants);TRACE</DefineConstants>
+  </PropertyGroup>
+  <ItemGroup Condition="Exists('local.props')">
+    <ProjectReference Include="..\Local\Local.csproj" />
+    <PackageReference Include="Polly" Version="8.4.1" Condition="'$(UsePolly)' != 'false'" />
+  </ItemGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.1.1" />
+  </ItemGroup>
+</Project>"#;
+
+        // when
+        let project = parse(Cursor::new(content), "Api.csproj").unwrap();
+
+        // then
+        let properties: Vec<(&str, &str, &str)> = project
+            .conditional_properties()
+            .iter()
+            .map(|property| {
+                (
+                    property.name().as_str(),
+                    property.value().as_str(),
+                    property.condition().as_str(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            properties,
+            vec![
+                ("Nullable", "enable", "'$(CI)' == 'true'"),
+                (
+                    "DefineConstants",
+                    "$(DefineConstants);TRACE",
+                    "'$(Configuration)' == 'Debug'"
+                ),
+            ]
+        );
+        assert_eq!(
+            project.project_references()[0].condition().unwrap(),
+            "Exists('local.props')"
+        );
+        assert_eq!(
+            project.package_references()[0].condition().unwrap(),
+            "(Exists('local.props')) and ('$(UsePolly)' != 'false')"
+        );
+        assert!(project.package_references()[1].condition().is_none());
+    }
+
+    #[test]
+    pub fn evaluate_conditions_with_given_properties() {
+        // given
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+  <PropertyGroup Condition="'$(Configuration)' == 'Legacy'">
+    <TargetFramework>net48</TargetFramework>
+  </PropertyGroup>
+</Project>"#;
+
+        let mut legacy = Properties::new();
+        legacy.set("Configuration", "Legacy");
+
+        let mut release = Properties::new();
+        release.set("Configuration", "Release");
+
+        // when
+        let legacy = parse_with_properties(Cursor::new(content), "Api.csproj", &legacy).unwrap();
+        let release = parse_with_properties(Cursor::new(content), "Api.csproj", &release).unwrap();
+
+        // then
+        assert_eq!(legacy.target_framework().unwrap(), "net48");
+        assert!(legacy.target_framework_candidates().is_empty());
+
+        assert_eq!(release.target_framework().unwrap(), "net8.0");
+        assert!(release.target_framework_candidates().is_empty());
+    }
+
+    #[test]
+    pub fn evaluate_item_conditions_with_global_properties() {
+        // given
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <Configuration>Debug</Configuration>
+  </PropertyGroup>
+  <ItemGroup Condition="'$(Configuration)' == 'Debug'">
+    <PackageReference Include="Microsoft.VisualStudio.Azure.Containers.Tools.Targets" Version="1.19.6" />
+  </ItemGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.1.1" />
+    <PackageReference Include="Serilog.Sinks.Seq" Version="6.0.0" Condition="'$(Configuration)' == 'Release'" />
+    <PackageReference Include="Serilog.Sinks.File" Version="5.0.0" Condition="'$(Platform)' == 'x64'" />
+  </ItemGroup>
+</Project>"#;
+
+        let mut release = Properties::new();
+        release.set("Configuration", "Release");
+
+        // when
+        let default = parse(Cursor::new(content), "Api.csproj").unwrap();
+        let release = parse_with_properties(Cursor::new(content), "Api.csproj", &release).unwrap();
+
+        // then
+        let names = |project: &Project| -> Vec<String> {
+            project
+                .package_references()
+                .iter()
+                .map(|reference| reference.name().clone())
+                .collect()
+        };
+
+        assert_eq!(
+            names(&default),
+            vec![
+                "Microsoft.VisualStudio.Azure.Containers.Tools.Targets",
+                "Serilog",
+                "Serilog.Sinks.File"
+            ]
+        );
+        assert_eq!(
+            names(&release),
+            vec!["Serilog", "Serilog.Sinks.Seq", "Serilog.Sinks.File"]
+        );
+        assert_eq!(
+            release.package_references()[2].condition().unwrap(),
+            "'$(Platform)' == 'x64'"
+        );
+    }
+
+    #[test]
+    pub fn evaluate_conditional_target_frameworks() {
+        // given
+        let content = |groups: &str| {
+            format!(
+                r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net6.0</TargetFramework>
+    <Modern>true</Modern>
+  </PropertyGroup>
+  {groups}
+</Project>"#
+            )
+        };
+
+        let overridden = content(
+            r#"<PropertyGroup Condition="'$(Modern)' == 'true'">
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+  <PropertyGroup>
+    <TargetFramework Condition="'$(Modern)' != 'true'">net48</TargetFramework>
+  </PropertyGroup>"#,
+        );
+        let ambiguous = content(
+            r#"<PropertyGroup Condition="'$(Configuration)' == 'Legacy'">
+    <TargetFramework>net48</TargetFramework>
+  </PropertyGroup>"#,
+        );
+
+        // when
+        let overridden = parse(Cursor::new(overridden), "Api.csproj").unwrap();
+        let ambiguous = parse(Cursor::new(ambiguous), "Api.csproj").unwrap();
+
+        // then
+        assert_eq!(overridden.target_framework().unwrap(), "net8.0");
+        assert!(overridden.target_framework_candidates().is_empty());
+
+        assert_eq!(ambiguous.target_framework().unwrap(), "net6.0");
+        assert_eq!(
+            ambiguous.target_framework_candidates(),
+            &vec!["net6.0".to_string(), "net48".to_string()]
+        );
+    }
+
+    #[test]
+    pub fn parse_project_tags() {
+        // given
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <ProjectTags>payments; backend;;Payments</ProjectTags>
+    <Owners>team-checkout,team-platform</Owners>
+  </PropertyGroup>
+</Project>"#;
+
+        let mut properties = Properties::new();
+        properties.set("DotnetLensTagsProperty", "Owners");
+
+        // when
+        let tagged = parse(Cursor::new(content), "Api.csproj").unwrap();
+        let owned = parse_with_properties(Cursor::new(content), "Api.csproj", &properties).unwrap();
+
+        // then
+        assert_eq!(
+            tagged.tags(),
+            &vec!["payments".to_string(), "backend".to_string()]
+        );
+        assert!(tagged.has_tag("Backend"));
+        assert_eq!(
+            owned.tags(),
+            &vec!["team-checkout".to_string(), "team-platform".to_string()]
+        );
+    }
+
+    #[test]
+    pub fn parse_package_assets() {
+        // given
+        let content = r#"
+<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Microsoft.SourceLink.GitHub" Version="8.0.0" PrivateAssets="All" />
+    <PackageReference Include="StyleCop.Analyzers" Version="1.1.118">
+      <PrivateAssets>all</PrivateAssets>
+      <IncludeAssets>runtime; build; native; contentfiles; analyzers</IncludeAssets>
+    </PackageReference>
+    <PackageReference Include="Contracts" Version="1.0.0" ExcludeAssets="runtime" />
+  </ItemGroup>
+</Project>"#;
+
+        // when
+        let project = parse(Cursor::new(content), "Api.csproj").unwrap();
+
+        // then
+        let assets: Vec<(Option<&str>, Option<&str>, Option<&str>)> = project
+            .package_references()
+            .iter()
+            .map(|reference| {
+                (
+                    reference.private_assets().map(|a| a.as_str()),
+                    reference.include_assets().map(|a| a.as_str()),
+                    reference.exclude_assets().map(|a| a.as_str()),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            assets,
+            vec![
+                (Some("All"), None, None),
+                (
+                    Some("all"),
+                    Some("runtime; build; native; contentfiles; analyzers"),
+                    None
+                ),
+                (None, None, Some("runtime")),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn parse_project_reference_output() {
+        // given
+        let content = r#"
+<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="..\Generators\Generators.csproj" OutputItemType="Analyzer" ReferenceOutputAssembly="false" />
+    <ProjectReference Include="..\Tools\Tools.csproj">
+      <ReferenceOutputAssembly>false</ReferenceOutputAssembly>
+    </ProjectReference>
+  </ItemGroup>
+</Project>"#;
+
+        // when
+        let project = parse(Cursor::new(content), "Api.csproj").unwrap();
+
+        // then
+        let outputs: Vec<(Option<&str>, Option<&str>)> = project
+            .project_references()
+            .iter()
+            .map(|reference| {
+                (
+                    reference.output_item_type().map(|a| a.as_str()),
+                    reference.reference_output_assembly().map(|a| a.as_str()),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            outputs,
+            vec![(Some("Analyzer"), Some("false")), (None, Some("false"))]
+        );
+    }
 }
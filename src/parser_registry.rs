@@ -0,0 +1,200 @@
+//! A registry of parsers for project types that are not built in, by file extension.
+
+use std::{
+    ffi::OsStr,
+    fmt,
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    condition::Properties,
+    intern::Interner,
+    parser::{self, ParseError, ProjectParser},
+    search, Project, VALID_EXTENSIONS,
+};
+
+/// Represents the parsers used to search and parse project files, by file extension.
+///
+/// The extensions of the built-in project types (`.csproj`, `.fsproj`, `.vbproj`, `.dcproj`,
+/// `.sfproj`, `.wixproj`) are parsed with [`Project::new`] unless a parser is registered for
+/// them. Extensions are compared ignoring case.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{
+///     parser::MsBuildParser, parser_registry::ParserRegistry, workspace::Workspace,
+///     ProjectLanguage,
+/// };
+///
+/// let mut parsers = ParserRegistry::new();
+/// parsers.register("sqlproj", MsBuildParser::new(ProjectLanguage::CSharp));
+///
+/// let workspace = Workspace::load_with_parsers("path/to/repository", &parsers)?;
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct ParserRegistry {
+    parsers: Vec<(String, Arc<dyn ProjectParser>)>,
+}
+
+impl ParserRegistry {
+    /// Creates a new `ParserRegistry` with only the built-in project types.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a parser for the files with the extension, without a `.` (ex: `sqlproj`),
+    /// replacing the parser previously registered for it.
+    pub fn register<P>(&mut self, extension: &str, parser: P)
+    where
+        P: ProjectParser + 'static,
+    {
+        let extension = extension.trim_start_matches('.');
+
+        self.parsers
+            .retain(|(registered, _)| !registered.eq_ignore_ascii_case(extension));
+        self.parsers.push((extension.to_string(), Arc::new(parser)));
+    }
+
+    /// Returns the extensions with a registered parser, in the order they were registered.
+    pub fn extensions(&self) -> impl Iterator<Item = &str> {
+        self.parsers.iter().map(|(extension, _)| extension.as_str())
+    }
+
+    /// Returns the parser registered for the extension, if any.
+    pub fn parser(&self, extension: &OsStr) -> Option<&dyn ProjectParser> {
+        self.parsers
+            .iter()
+            .find(|(registered, _)| extension.eq_ignore_ascii_case(registered))
+            .map(|(_, parser)| parser.as_ref())
+    }
+
+    /// Returns `true` if the extension is built in or has a registered parser.
+    pub fn handles(&self, extension: &OsStr) -> bool {
+        VALID_EXTENSIONS.iter().any(|ext| *ext == extension) || self.parser(extension).is_some()
+    }
+
+    /// Returns `true` if the path is a project file that [`ParserRegistry::search`] would find
+    /// (see [`search::is_project_file`]).
+    pub fn is_project_file<P>(&self, path: &P) -> bool
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        path.extension()
+            .is_some_and(|extension| self.handles(extension))
+            && !search::is_in_blocked_directory(path)
+    }
+
+    /// Searches recursively for the project files of the built-in and registered types, like
+    /// [`search::search_projects`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a directory could not be read.
+    pub fn search<P>(&self, path: &P) -> Result<Vec<PathBuf>, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        search::search_files(path, &|extension| self.handles(extension))
+    }
+
+    /// Parses a project file with the parser registered for its extension, or with
+    /// [`Project::new`] for the built-in types.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn parse_file<P>(&self, path: P) -> Result<Project, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        self.parse_file_with(path.as_ref(), &Interner::new())
+    }
+
+    /// Parses the project file at `path` like [`ParserRegistry::parse_file`], sharing its names
+    /// and versions with the other projects parsed with `strings`.
+    pub(crate) fn parse_file_with(
+        &self,
+        path: &Path,
+        strings: &Interner,
+    ) -> Result<Project, ParseError> {
+        match path
+            .extension()
+            .and_then(|extension| self.parser(extension))
+        {
+            Some(parser) => {
+                let mut project = parser.parse(&mut File::open(path)?, path)?;
+                strings.share(&mut project);
+
+                Ok(project)
+            }
+            None => parser::parse_file(path, &Properties::new(), strings),
+        }
+    }
+}
+
+impl fmt::Debug for ParserRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParserRegistry")
+            .field("extensions", &self.extensions().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{parser::MsBuildParser, workspace::Workspace, ProjectLanguage};
+
+    use super::ParserRegistry;
+
+    #[test]
+    pub fn load_custom_project_types() {
+        // given
+        let dir = tempdir().unwrap();
+        let project = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="../Database/Database.SQLPROJ" />
+  </ItemGroup>
+</Project>"#;
+
+        fs::create_dir_all(dir.path().join("Api")).unwrap();
+        fs::create_dir_all(dir.path().join("Database")).unwrap();
+        fs::write(dir.path().join("Api/Api.csproj"), project).unwrap();
+        fs::write(
+            dir.path().join("Database/Database.SQLPROJ"),
+            r#"<Project Sdk="Microsoft.Build.Sql" />"#,
+        )
+        .unwrap();
+
+        let mut parsers = ParserRegistry::new();
+        parsers.register(".sqlproj", MsBuildParser::new(ProjectLanguage::CSharp));
+
+        // when
+        let builtin = Workspace::load(dir.path()).unwrap();
+        let workspace = Workspace::load_with_parsers(dir.path(), &parsers).unwrap();
+
+        // then
+        assert_eq!(builtin.projects().len(), 1);
+        assert_eq!(parsers.extensions().collect::<Vec<_>>(), vec!["sqlproj"]);
+
+        let names: Vec<&str> = workspace
+            .projects()
+            .iter()
+            .map(|project| project.name().as_str())
+            .collect();
+        assert_eq!(names, vec!["Api", "Database"]);
+        assert!(parsers.is_project_file(&"Database/Database.sqlproj"));
+        assert!(!parsers.is_project_file(&"bin/Database.sqlproj"));
+    }
+}
@@ -0,0 +1,526 @@
+//! A small expression language filtering the projects of a workspace, such as
+//! `kind == test && tfm startswith 'net4'` or `package('Serilog') && !path contains 'legacy'`.
+//!
+//! A query combines comparisons and predicates with `&&`, `||`, `!` and parentheses:
+//!
+//! - `<field> <operator> <value>` compares a field of the project with a value, where the
+//!   operator is `==`, `!=`, `contains`, `startswith` or `endswith`. The value is quoted with
+//!   `'` or `"`, or a bare word (ex: `test`, `net8.0`).
+//! - `package('<name>')` and `package('<name>', '<version>')` match the projects referencing a
+//!   package, and `reference('<name>')` the projects referencing a project.
+//!
+//! The fields are `name`, `path`, `language` (ex: `csharp` or `csproj`), `tfm` (every candidate
+//! target framework), `kind` (`test`, `package` or `tool`), `assembly`, `license` and `tag`.
+//! Comparisons ignore case, and a field with several values matches if one of them does (`!=`
+//! matches if none of them is equal).
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::{
+    packaging::{self, PackageOutput},
+    Project,
+};
+
+/// Represents a parsed query.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{parser::parse, query::Query};
+/// use std::io::Cursor;
+///
+/// let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+///   <PropertyGroup>
+///     <TargetFramework>net48</TargetFramework>
+///   </PropertyGroup>
+///   <ItemGroup>
+///     <PackageReference Include="Serilog" Version="3.1.1" />
+///   </ItemGroup>
+/// </Project>"#;
+///
+/// let project = parse(Cursor::new(content), "src/Api/Api.csproj").unwrap();
+///
+/// let query: Query = "tfm startswith 'net4' && package('serilog')".parse().unwrap();
+/// assert!(query.matches(&project));
+///
+/// let query: Query = "!path contains 'legacy' && language == fsharp".parse().unwrap();
+/// assert!(!query.matches(&project));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    expression: Expression,
+}
+
+impl Query {
+    /// Parses a query.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the query is not valid, with the position of the
+    /// problem.
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        let tokens = tokenize(query)?;
+        let mut parser = QueryParser {
+            tokens: &tokens,
+            position: 0,
+        };
+
+        let expression = parser.or()?;
+
+        if let Some((position, _)) = tokens.get(parser.position) {
+            return Err(QueryError::UnexpectedToken(*position));
+        }
+
+        Ok(Self { expression })
+    }
+
+    /// Returns `true` if the project matches the query.
+    pub fn matches(&self, project: &Project) -> bool {
+        self.expression.matches(project)
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Represents errors that can occur while parsing a query.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum QueryError {
+    /// A string is not closed.
+    #[error("the string at position {0} is not closed")]
+    UnterminatedString(usize),
+    /// A character is not part of the language.
+    #[error("unexpected character '{character}' at position {position}")]
+    UnexpectedCharacter { character: char, position: usize },
+    /// A token is not valid where it is.
+    #[error("unexpected token at position {0}")]
+    UnexpectedToken(usize),
+    /// The query ends in the middle of an expression.
+    #[error("the query ends unexpectedly")]
+    UnexpectedEnd,
+    /// A comparison uses an unknown field.
+    #[error("unknown field '{0}'")]
+    UnknownField(String),
+    /// A predicate is unknown or has the wrong number of arguments.
+    #[error("unknown predicate '{0}'")]
+    UnknownPredicate(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expression {
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Comparison {
+        field: Field,
+        operator: Operator,
+        value: String,
+    },
+    Package {
+        name: String,
+        version: Option<String>,
+    },
+    Reference(String),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Field {
+    Name,
+    Path,
+    Language,
+    TargetFramework,
+    Kind,
+    Assembly,
+    License,
+    Tag,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Operator {
+    Equals,
+    NotEquals,
+    Contains,
+    StartsWith,
+    EndsWith,
+}
+
+impl Expression {
+    fn matches(&self, project: &Project) -> bool {
+        match self {
+            Expression::And(left, right) => left.matches(project) && right.matches(project),
+            Expression::Or(left, right) => left.matches(project) || right.matches(project),
+            Expression::Not(expression) => !expression.matches(project),
+            Expression::Comparison {
+                field,
+                operator,
+                value,
+            } => {
+                let values = field.values(project);
+                let value = value.to_lowercase();
+
+                let mut matching =
+                    values
+                        .iter()
+                        .map(|v| v.to_lowercase())
+                        .filter(|v| match operator {
+                            Operator::Equals | Operator::NotEquals => *v == value,
+                            Operator::Contains => v.contains(&value),
+                            Operator::StartsWith => v.starts_with(&value),
+                            Operator::EndsWith => v.ends_with(&value),
+                        });
+
+                match operator {
+                    Operator::NotEquals => matching.next().is_none(),
+                    _ => matching.next().is_some(),
+                }
+            }
+            Expression::Package { name, version } => {
+                project.package_references().iter().any(|reference| {
+                    reference.name().eq_ignore_ascii_case(name)
+                        && version
+                            .as_ref()
+                            .is_none_or(|version| reference.version().eq_ignore_ascii_case(version))
+                })
+            }
+            Expression::Reference(name) => project
+                .project_references()
+                .iter()
+                .any(|reference| reference.name().eq_ignore_ascii_case(name)),
+        }
+    }
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "name" => Some(Field::Name),
+            "path" => Some(Field::Path),
+            "language" => Some(Field::Language),
+            "tfm" => Some(Field::TargetFramework),
+            "kind" => Some(Field::Kind),
+            "assembly" => Some(Field::Assembly),
+            "license" => Some(Field::License),
+            "tag" => Some(Field::Tag),
+            _ => None,
+        }
+    }
+
+    fn values(&self, project: &Project) -> Vec<String> {
+        match self {
+            Field::Name => vec![project.name().clone()],
+            Field::Path => vec![project.path().to_string_lossy().replace('\\', "/")],
+            Field::Language => vec![
+                project.language().label().to_string(),
+                project.language().extension().to_string(),
+            ],
+            Field::TargetFramework => project.target_frameworks().into_iter().cloned().collect(),
+            Field::Kind => {
+                let test = packaging::is_test_project(project).then_some("test");
+                let output = packaging::package_output(project).map(|output| match output {
+                    PackageOutput::Package => "package",
+                    PackageOutput::Tool => "tool",
+                });
+
+                test.into_iter().chain(output).map(str::to_string).collect()
+            }
+            Field::Assembly => vec![project.assembly_name().to_string()],
+            Field::License => project.license_expression().cloned().into_iter().collect(),
+            Field::Tag => project.tags().clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A quoted string.
+    String(String),
+    /// An unquoted word, such as a field, an operator like `contains` or a value.
+    Word(String),
+    Equals,
+    NotEquals,
+    And,
+    Or,
+    Not,
+    Comma,
+    OpenParenthesis,
+    CloseParenthesis,
+}
+
+/// Splits the query into tokens, with their position.
+fn tokenize(query: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let mut tokens = vec![];
+    let mut chars = query.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '\'' | '"' => {
+                let start = index + 1;
+                let end = query[start..]
+                    .find(c)
+                    .ok_or(QueryError::UnterminatedString(index))?
+                    + start;
+
+                while chars.next_if(|(i, _)| *i <= end).is_some() {}
+
+                Token::String(query[start..end].to_string())
+            }
+            '(' => Token::OpenParenthesis,
+            ')' => Token::CloseParenthesis,
+            ',' => Token::Comma,
+            '=' if chars.next_if(|(_, next)| *next == '=').is_some() => Token::Equals,
+            '!' if chars.next_if(|(_, next)| *next == '=').is_some() => Token::NotEquals,
+            '!' => Token::Not,
+            '&' if chars.next_if(|(_, next)| *next == '&').is_some() => Token::And,
+            '|' if chars.next_if(|(_, next)| *next == '|').is_some() => Token::Or,
+            c if is_word_character(c) => {
+                let mut word = c.to_string();
+
+                while let Some((_, next)) = chars.next_if(|(_, next)| is_word_character(*next)) {
+                    word.push(next);
+                }
+
+                Token::Word(word)
+            }
+            character => {
+                return Err(QueryError::UnexpectedCharacter {
+                    character,
+                    position: index,
+                })
+            }
+        };
+
+        tokens.push((index, token));
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_character(c: char) -> bool {
+    c.is_alphanumeric() || "._-/*".contains(c)
+}
+
+struct QueryParser<'a> {
+    tokens: &'a [(usize, Token)],
+    position: usize,
+}
+
+impl QueryParser<'_> {
+    fn next_if(&mut self, token: &Token) -> bool {
+        let matches = self.tokens.get(self.position).map(|(_, t)| t) == Some(token);
+
+        if matches {
+            self.position += 1;
+        }
+
+        matches
+    }
+
+    /// Returns the next token with its position, or an error at the end of the query.
+    fn next(&mut self) -> Result<(usize, Token), QueryError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(QueryError::UnexpectedEnd)?;
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), QueryError> {
+        if self.next_if(token) {
+            return Ok(());
+        }
+
+        match self.tokens.get(self.position) {
+            Some((position, _)) => Err(QueryError::UnexpectedToken(*position)),
+            None => Err(QueryError::UnexpectedEnd),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expression, QueryError> {
+        let mut result = self.and()?;
+
+        while self.next_if(&Token::Or) {
+            result = Expression::Or(Box::new(result), Box::new(self.and()?));
+        }
+
+        Ok(result)
+    }
+
+    fn and(&mut self) -> Result<Expression, QueryError> {
+        let mut result = self.not()?;
+
+        while self.next_if(&Token::And) {
+            result = Expression::And(Box::new(result), Box::new(self.not()?));
+        }
+
+        Ok(result)
+    }
+
+    fn not(&mut self) -> Result<Expression, QueryError> {
+        if self.next_if(&Token::Not) {
+            return Ok(Expression::Not(Box::new(self.not()?)));
+        }
+
+        self.term()
+    }
+
+    fn term(&mut self) -> Result<Expression, QueryError> {
+        let (position, token) = self.next()?;
+
+        let word = match token {
+            Token::OpenParenthesis => {
+                let expression = self.or()?;
+                self.expect(&Token::CloseParenthesis)?;
+
+                return Ok(expression);
+            }
+            Token::Word(word) => word,
+            _ => return Err(QueryError::UnexpectedToken(position)),
+        };
+
+        if self.next_if(&Token::OpenParenthesis) {
+            return self.predicate(word);
+        }
+
+        let field = Field::parse(&word).ok_or(QueryError::UnknownField(word))?;
+
+        let (position, token) = self.next()?;
+        let operator = match token {
+            Token::Equals => Operator::Equals,
+            Token::NotEquals => Operator::NotEquals,
+            Token::Word(word) => match word.to_ascii_lowercase().as_str() {
+                "contains" => Operator::Contains,
+                "startswith" => Operator::StartsWith,
+                "endswith" => Operator::EndsWith,
+                _ => return Err(QueryError::UnexpectedToken(position)),
+            },
+            _ => return Err(QueryError::UnexpectedToken(position)),
+        };
+
+        Ok(Expression::Comparison {
+            field,
+            operator,
+            value: self.value()?,
+        })
+    }
+
+    /// Parses the arguments of a predicate, after its opening parenthesis.
+    fn predicate(&mut self, name: String) -> Result<Expression, QueryError> {
+        let mut arguments = vec![self.value()?];
+
+        while self.next_if(&Token::Comma) {
+            arguments.push(self.value()?);
+        }
+
+        self.expect(&Token::CloseParenthesis)?;
+
+        let mut arguments = arguments.into_iter();
+
+        match (
+            name.to_ascii_lowercase().as_str(),
+            arguments.next(),
+            arguments.next(),
+            arguments.next(),
+        ) {
+            ("package", Some(name), version, None) => Ok(Expression::Package { name, version }),
+            ("reference", Some(name), None, None) => Ok(Expression::Reference(name)),
+            _ => Err(QueryError::UnknownPredicate(name)),
+        }
+    }
+
+    fn value(&mut self) -> Result<String, QueryError> {
+        match self.next()? {
+            (_, Token::String(value) | Token::Word(value)) => Ok(value),
+            (position, _) => Err(QueryError::UnexpectedToken(position)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::parser::parse;
+
+    use super::{Query, QueryError};
+
+    #[test]
+    pub fn filter_projects_with_queries() {
+        // given
+        let api = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.1.1" />
+    <ProjectReference Include="..\Domain\Domain.csproj" />
+  </ItemGroup>
+</Project>"#;
+        let tests = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net48</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Microsoft.NET.Test.Sdk" Version="17.10.0" />
+  </ItemGroup>
+</Project>"#;
+
+        let projects = [
+            parse(Cursor::new(api), "src/Api/Api.csproj").unwrap(),
+            parse(Cursor::new(tests), "legacy/Api.Tests/Api.Tests.csproj").unwrap(),
+        ];
+
+        let names = |query: &str| -> Vec<&str> {
+            let query = Query::parse(query).unwrap();
+
+            projects
+                .iter()
+                .filter(|project| query.matches(project))
+                .map(|project| project.name().as_str())
+                .collect()
+        };
+
+        // when / then
+        assert_eq!(
+            names("kind == test && tfm startswith 'net4'"),
+            vec!["Api.Tests"]
+        );
+        assert_eq!(
+            names("package('Serilog') && !path contains 'legacy'"),
+            vec!["Api"]
+        );
+        assert_eq!(names("package(\"serilog\", '3.0.0')"), Vec::<&str>::new());
+        assert_eq!(
+            names("(reference(Domain) || kind != package) && language == csproj"),
+            vec!["Api", "Api.Tests"]
+        );
+        assert_eq!(names("name endswith .tests"), vec!["Api.Tests"]);
+
+        assert_eq!(
+            Query::parse("tfm startswith"),
+            Err(QueryError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Query::parse("version == '1.0'"),
+            Err(QueryError::UnknownField("version".to_string()))
+        );
+        assert_eq!(
+            Query::parse("name == 'Api' name"),
+            Err(QueryError::UnexpectedToken(14))
+        );
+        assert_eq!(
+            Query::parse("name == 'Api"),
+            Err(QueryError::UnterminatedString(8))
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! A registry of parsed projects keyed by canonical path, so a project reachable from several
+//! roots or through several paths is parsed only once.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::SystemTime,
+};
+
+use crate::{
+    condition::Properties,
+    intern::Interner,
+    parser::{self, ParseError},
+    search,
+    workspace::{Workspace, WorkspaceError},
+    Project,
+};
+
+/// A registry sharing the projects parsed by several loads.
+///
+/// Projects are keyed by their canonical path, so the same file reached through a symbolic
+/// link or a path with `..` components maps to the same entry. An entry is parsed again when
+/// the modification time or the length of its file changed. The registry can be shared between
+/// threads.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::registry::ProjectRegistry;
+///
+/// let registry = ProjectRegistry::new();
+///
+/// // the projects of the shop are parsed once, even though both roots contain them
+/// let everything = registry.load("repos")?;
+/// let shop = registry.load("repos/shop")?;
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct ProjectRegistry {
+    projects: Mutex<HashMap<PathBuf, RegistryEntry>>,
+    /// The names and versions shared by the projects of the registry.
+    strings: Interner,
+}
+
+#[derive(Debug)]
+struct RegistryEntry {
+    modified: Option<SystemTime>,
+    len: u64,
+    project: Arc<Project>,
+}
+
+impl RegistryEntry {
+    fn is_fresh(&self, metadata: &fs::Metadata) -> bool {
+        self.modified == metadata.modified().ok() && self.len == metadata.len()
+    }
+}
+
+impl ProjectRegistry {
+    /// Creates an empty `ProjectRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the project at `path`, parsing it if it is not in the registry yet or if its file
+    /// changed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be found or parsed.
+    pub fn get_or_parse<P>(&self, path: P) -> Result<Arc<Project>, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        let key = fs::canonicalize(path.as_ref())?;
+        let metadata = fs::metadata(&key)?;
+
+        if let Some(entry) = self.lock().get(&key) {
+            if entry.is_fresh(&metadata) {
+                return Ok(entry.project.clone());
+            }
+        }
+
+        // parsed without holding the lock, so other threads are not blocked; if two threads
+        // parse the same version of a project, the first one registered wins
+        let entry = RegistryEntry {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+            project: Arc::new(parser::parse_file(
+                path.as_ref(),
+                &Properties::new(),
+                &self.strings,
+            )?),
+        };
+
+        let mut projects = self.lock();
+
+        match projects.get(&key) {
+            Some(registered) if registered.is_fresh(&metadata) => Ok(registered.project.clone()),
+            _ => {
+                let project = entry.project.clone();
+                projects.insert(key, entry);
+
+                Ok(project)
+            }
+        }
+    }
+
+    /// Returns the project at `path` if it is in the registry and its file did not change.
+    pub fn get<P>(&self, path: P) -> Option<Arc<Project>>
+    where
+        P: AsRef<Path>,
+    {
+        let key = fs::canonicalize(path).ok()?;
+        let metadata = fs::metadata(&key).ok()?;
+
+        self.lock()
+            .get(&key)
+            .filter(|entry| entry.is_fresh(&metadata))
+            .map(|entry| entry.project.clone())
+    }
+
+    /// Removes the project at `path` from the registry, so it is parsed again the next time it
+    /// is requested (ex: after the file changed).
+    pub fn remove<P>(&self, path: P) -> Option<Arc<Project>>
+    where
+        P: AsRef<Path>,
+    {
+        let key = fs::canonicalize(path).ok()?;
+
+        self.lock().remove(&key).map(|entry| entry.project)
+    }
+
+    /// Returns the number of projects in the registry.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if the registry does not have any project.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Searches every project under `root` like [`Workspace::load`], parsing only the projects
+    /// that are not in the registry yet or that changed.
+    ///
+    /// A workspace owns its projects, so they are copied out of the registry. See
+    /// [`ProjectRegistry::load_shared`] to read them without copying them.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read or if one of the
+    /// projects could not be parsed.
+    pub fn load<P>(&self, root: P) -> Result<Workspace, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        Workspace::load_with(root, |path, _| {
+            self.get_or_parse(path).map(|project| (*project).clone())
+        })
+    }
+
+    /// Searches every project under `root` like [`ProjectRegistry::load`], returning the
+    /// projects shared with the registry, sorted by path, instead of copying them into a
+    /// workspace.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read or if one of the
+    /// projects could not be parsed.
+    pub fn load_shared<P>(&self, root: P) -> Result<Vec<Arc<Project>>, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut paths = search::search_projects(&root)?;
+        paths.sort();
+
+        paths
+            .into_iter()
+            .map(|path| {
+                self.get_or_parse(&path)
+                    .map_err(|source| WorkspaceError::ParseError { path, source })
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, RegistryEntry>> {
+        self.projects
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, sync::Arc};
+
+    use tempfile::tempdir;
+
+    use super::ProjectRegistry;
+
+    #[test]
+    pub fn parse_each_project_once() {
+        // given
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        fs::create_dir_all(root.join("src/Api")).unwrap();
+        fs::create_dir_all(root.join("tools/Cli")).unwrap();
+        fs::write(root.join("src/Api/Api.csproj"), "<Project></Project>").unwrap();
+        fs::write(root.join("tools/Cli/Cli.csproj"), "<Project></Project>").unwrap();
+
+        let registry = ProjectRegistry::new();
+
+        // when
+        let everything = registry.load(root).unwrap();
+        let src = registry.load(root.join("src")).unwrap();
+
+        let first = registry
+            .get_or_parse(root.join("src/Api/Api.csproj"))
+            .unwrap();
+        let second = registry
+            .get_or_parse(root.join("tools/../src/Api/Api.csproj"))
+            .unwrap();
+
+        let shared = registry.load_shared(root).unwrap();
+
+        // then
+        assert_eq!(everything.projects().len(), 2);
+        assert_eq!(src.projects()[0], everything.projects()[0]);
+        assert_eq!(registry.len(), 2);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(Arc::ptr_eq(&shared[0], &first));
+
+        // a changed file is parsed again
+        fs::write(
+            root.join("src/Api/Api.csproj"),
+            "<Project><PropertyGroup><TargetFramework>net8.0</TargetFramework></PropertyGroup></Project>",
+        )
+        .unwrap();
+        let changed = registry
+            .get_or_parse(root.join("src/Api/Api.csproj"))
+            .unwrap();
+
+        assert_eq!(changed.target_framework().unwrap(), "net8.0");
+
+        registry.remove(root.join("src/Api/Api.csproj"));
+        assert!(registry.get(root.join("src/Api/Api.csproj")).is_none());
+
+        dir.close().unwrap();
+    }
+}
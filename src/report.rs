@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use crate::validation::{Severity, Violation};
+
+/// Aggregates the results of one or more validation runs.
+///
+/// A `Report` keeps every violation along with a summary of how many were found by severity and by
+/// rule, giving CI integrations a single object to consume.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::report::Report;
+/// use dotnet_lens::validation::{default_rules, validate};
+///
+/// let report = Report::from(validate(&[], &default_rules()));
+///
+/// assert!(!report.has_errors());
+/// assert_eq!(report.exit_code(), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    summary: Summary,
+    violations: Vec<Violation>,
+}
+
+/// Represents the counts of violations of a `Report`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    total: usize,
+    errors: usize,
+    warnings: usize,
+    notes: usize,
+    by_rule: BTreeMap<String, usize>,
+}
+
+impl Report {
+    /// Creates a new empty `Report`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the violations of a validation run to the report.
+    pub fn add_violations<I>(&mut self, violations: I)
+    where
+        I: IntoIterator<Item = Violation>,
+    {
+        for violation in violations {
+            self.summary.add(&violation);
+            self.violations.push(violation);
+        }
+    }
+
+    /// Returns the summary of the report.
+    pub fn summary(&self) -> &Summary {
+        &self.summary
+    }
+
+    /// Returns a reference to the list of violations.
+    pub fn violations(&self) -> &Vec<Violation> {
+        &self.violations
+    }
+
+    /// Returns `true` if the report contains at least one violation with `Severity::Error`.
+    pub fn has_errors(&self) -> bool {
+        self.summary.errors > 0
+    }
+
+    /// Returns `true` if the report contains a violation at least as severe as `severity`.
+    pub fn has_violations_at_least(&self, severity: Severity) -> bool {
+        self.violations.iter().any(|v| v.severity() <= severity)
+    }
+
+    /// Returns the suggested process exit code: `1` if the report has errors, `0` otherwise.
+    pub fn exit_code(&self) -> i32 {
+        if self.has_errors() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Serializes the report as pretty printed JSON.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl From<Vec<Violation>> for Report {
+    fn from(violations: Vec<Violation>) -> Self {
+        let mut report = Self::new();
+        report.add_violations(violations);
+
+        report
+    }
+}
+
+impl Summary {
+    fn add(&mut self, violation: &Violation) {
+        self.total += 1;
+
+        match violation.severity() {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Note => self.notes += 1,
+        }
+
+        *self.by_rule.entry(violation.rule_id().clone()).or_default() += 1;
+    }
+
+    /// Returns the total number of violations.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of violations with `Severity::Error`.
+    pub fn errors(&self) -> usize {
+        self.errors
+    }
+
+    /// Returns the number of violations with `Severity::Warning`.
+    pub fn warnings(&self) -> usize {
+        self.warnings
+    }
+
+    /// Returns the number of violations with `Severity::Note`.
+    pub fn notes(&self) -> usize {
+        self.notes
+    }
+
+    /// Returns the number of violations found by each rule, keyed by rule id.
+    pub fn by_rule(&self) -> &BTreeMap<String, usize> {
+        &self.by_rule
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn violation(rule_id: &str, severity: Severity) -> Violation {
+        Violation::new(
+            rule_id.to_string(),
+            severity,
+            "message".to_string(),
+            PathBuf::from("Api.csproj"),
+        )
+    }
+
+    #[test]
+    pub fn summary_counts_by_severity_and_rule() {
+        // given
+        let mut report = Report::from(vec![
+            violation("DL0001", Severity::Warning),
+            violation("DL0002", Severity::Error),
+        ]);
+
+        // when
+        report.add_violations(vec![violation("DL0001", Severity::Warning)]);
+
+        // then
+        let summary = report.summary();
+        assert_eq!(summary.total(), 3);
+        assert_eq!(summary.errors(), 1);
+        assert_eq!(summary.warnings(), 2);
+        assert_eq!(summary.notes(), 0);
+        assert_eq!(summary.by_rule()["DL0001"], 2);
+        assert!(report.has_errors());
+        assert_eq!(report.exit_code(), 1);
+    }
+
+    #[test]
+    pub fn report_without_errors() {
+        // given
+        let report = Report::from(vec![violation("DL0003", Severity::Note)]);
+
+        // then
+        assert!(!report.has_errors());
+        assert!(!report.has_violations_at_least(Severity::Warning));
+        assert!(report.has_violations_at_least(Severity::Note));
+        assert_eq!(report.exit_code(), 0);
+    }
+}
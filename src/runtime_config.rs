@@ -0,0 +1,318 @@
+//! Reading of the runtime configuration of a project, from the `runtimeconfig.template.json` file
+//! next to it or from the `<name>.runtimeconfig.json` file generated in its output.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{parser::ParseError, Project};
+
+/// The name of the checked-in file merged into the generated runtime configuration.
+const TEMPLATE_FILE_NAME: &str = "runtimeconfig.template.json";
+
+/// The deepest directory under `bin` searched for generated files (ex:
+/// `bin/Release/net8.0/win-x64`).
+const MAX_OUTPUT_DEPTH: usize = 3;
+
+/// Represents a shared framework the application runs on (ex: `Microsoft.NETCore.App` 8.0.0).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeFramework {
+    name: String,
+    version: Option<String>,
+}
+
+/// Represents the runtime options of a `runtimeconfig.json` or `runtimeconfig.template.json`
+/// file.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::runtime_config::RuntimeConfig;
+/// use std::io::Cursor;
+///
+/// let content = r#"{
+///   "runtimeOptions": {
+///     "tfm": "net8.0",
+///     "framework": { "name": "Microsoft.NETCore.App", "version": "8.0.0" },
+///     "configProperties": { "System.GC.Server": true }
+///   }
+/// }"#;
+///
+/// let config = RuntimeConfig::parse(Cursor::new(content)).unwrap();
+///
+/// assert_eq!(config.frameworks()[0].name(), "Microsoft.NETCore.App");
+/// assert_eq!(config.properties()["System.GC.Server"], "true");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RuntimeConfig {
+    target_framework: Option<String>,
+    frameworks: Vec<RuntimeFramework>,
+    roll_forward: Option<String>,
+    properties: BTreeMap<String, String>,
+}
+
+impl RuntimeFramework {
+    /// Creates a new `RuntimeFramework`.
+    pub fn new(name: String, version: Option<String>) -> Self {
+        Self { name, version }
+    }
+
+    /// Returns the name of the framework.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the version of the framework, which templates usually leave to the SDK.
+    pub fn version(&self) -> Option<&String> {
+        self.version.as_ref()
+    }
+}
+
+impl RuntimeConfig {
+    /// Parses the content of a `runtimeconfig.json` file, or of a `runtimeconfig.template.json`
+    /// file where the options are not nested in `runtimeOptions`.
+    ///
+    /// The frameworks are read from `framework`, `frameworks` and, for self-contained
+    /// applications, `includedFrameworks`. The values of `configProperties` are kept as written,
+    /// without the quotes of strings.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be read or is not a JSON
+    /// object.
+    pub fn parse<R>(reader: R) -> Result<Self, ParseError>
+    where
+        R: Read,
+    {
+        let document: Value =
+            serde_json::from_reader(reader).map_err(|_| ParseError::DeserializationError)?;
+        let options = document.get("runtimeOptions").unwrap_or(&document);
+
+        if !options.is_object() {
+            return Err(ParseError::DeserializationError);
+        }
+
+        let frameworks = options
+            .get("framework")
+            .into_iter()
+            .chain(
+                ["frameworks", "includedFrameworks"]
+                    .iter()
+                    .filter_map(|key| options.get(key)?.as_array())
+                    .flatten(),
+            )
+            .filter_map(|framework| {
+                Some(RuntimeFramework::new(
+                    framework.get("name")?.as_str()?.to_string(),
+                    framework
+                        .get("version")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                ))
+            })
+            .collect();
+
+        let properties = options
+            .get("configProperties")
+            .and_then(Value::as_object)
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, value)| (name.clone(), property_value(value)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            target_framework: string(options, "tfm"),
+            frameworks,
+            roll_forward: string(options, "rollForward"),
+            properties,
+        })
+    }
+
+    /// Reads a `runtimeconfig.json` or `runtimeconfig.template.json` file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn load<P>(path: P) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse(File::open(path)?)
+    }
+
+    /// Returns the runtime configuration file of the project: the checked-in
+    /// `runtimeconfig.template.json` next to it, or else the `<assembly>.runtimeconfig.json` file
+    /// generated under its `bin` directory (the first one by path, when it was built for several
+    /// configurations).
+    pub fn find(project: &Project) -> Option<PathBuf> {
+        let directory = project.path().parent().unwrap_or(Path::new(""));
+
+        let template = directory.join(TEMPLATE_FILE_NAME);
+        if template.is_file() {
+            return Some(template);
+        }
+
+        let file_name = format!("{}.runtimeconfig.json", project.assembly_name());
+        let mut generated = vec![];
+        search(&directory.join("bin"), &file_name, 0, &mut generated);
+
+        generated.sort();
+        generated.into_iter().next()
+    }
+
+    /// Reads the runtime configuration of the project (see [`RuntimeConfig::find`]), or returns
+    /// `None` if it does not have one.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn for_project(project: &Project) -> Result<Option<Self>, ParseError> {
+        Self::find(project).map(Self::load).transpose()
+    }
+
+    /// Returns the target framework (`tfm`), if any.
+    pub fn target_framework(&self) -> Option<&String> {
+        self.target_framework.as_ref()
+    }
+
+    /// Returns the shared frameworks the application runs on.
+    pub fn frameworks(&self) -> &Vec<RuntimeFramework> {
+        &self.frameworks
+    }
+
+    /// Returns the roll forward policy (`rollForward`, ex: "LatestMinor"), if any.
+    pub fn roll_forward(&self) -> Option<&String> {
+        self.roll_forward.as_ref()
+    }
+
+    /// Returns the runtime knobs (`configProperties`, ex: `System.GC.Server`) by name.
+    pub fn properties(&self) -> &BTreeMap<String, String> {
+        &self.properties
+    }
+}
+
+fn string(options: &Value, key: &str) -> Option<String> {
+    options.get(key)?.as_str().map(str::to_string)
+}
+
+fn property_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Collects the files named `file_name` under `directory`, up to [`MAX_OUTPUT_DEPTH`].
+fn search(directory: &Path, file_name: &str, depth: usize, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            if depth < MAX_OUTPUT_DEPTH {
+                search(&path, file_name, depth + 1, found);
+            }
+        } else if entry.file_name().to_string_lossy() == file_name {
+            found.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, io::Cursor};
+
+    use tempfile::tempdir;
+
+    use crate::Project;
+
+    use super::{RuntimeConfig, RuntimeFramework};
+
+    #[test]
+    pub fn parse_runtime_config() {
+        // given
+        let content = r#"{
+  "runtimeOptions": {
+    "tfm": "net8.0",
+    "rollForward": "LatestMinor",
+    "frameworks": [
+      { "name": "Microsoft.NETCore.App", "version": "8.0.0" },
+      { "name": "Microsoft.AspNetCore.App", "version": "8.0.0" }
+    ],
+    "configProperties": {
+      "System.GC.Server": true,
+      "System.Globalization.Invariant": "false",
+      "System.Threading.ThreadPool.MinThreads": 8
+    }
+  }
+}"#;
+
+        // when
+        let config = RuntimeConfig::parse(Cursor::new(content)).unwrap();
+
+        // then
+        assert_eq!(config.target_framework().unwrap(), "net8.0");
+        assert_eq!(config.roll_forward().unwrap(), "LatestMinor");
+        assert_eq!(
+            config.frameworks()[1],
+            RuntimeFramework::new(
+                "Microsoft.AspNetCore.App".to_string(),
+                Some("8.0.0".to_string())
+            )
+        );
+        assert_eq!(config.properties()["System.GC.Server"], "true");
+        assert_eq!(
+            config.properties()["System.Globalization.Invariant"],
+            "false"
+        );
+        assert_eq!(
+            config.properties()["System.Threading.ThreadPool.MinThreads"],
+            "8"
+        );
+    }
+
+    #[test]
+    pub fn find_template_before_generated_file() {
+        // given
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        fs::create_dir_all(root.join("Api/bin/Release/net8.0")).unwrap();
+        fs::write(root.join("Api/Api.csproj"), "<Project></Project>").unwrap();
+        fs::write(
+            root.join("Api/bin/Release/net8.0/Api.runtimeconfig.json"),
+            r#"{ "runtimeOptions": { "tfm": "net8.0" } }"#,
+        )
+        .unwrap();
+
+        let project = Project::new(root.join("Api/Api.csproj")).unwrap();
+
+        // when
+        let generated = RuntimeConfig::for_project(&project).unwrap().unwrap();
+
+        fs::write(
+            root.join("Api/runtimeconfig.template.json"),
+            r#"{ "configProperties": { "System.GC.Concurrent": false } }"#,
+        )
+        .unwrap();
+        let template = RuntimeConfig::for_project(&project).unwrap().unwrap();
+
+        // then
+        assert_eq!(generated.target_framework().unwrap(), "net8.0");
+        assert_eq!(template.properties()["System.GC.Concurrent"], "false");
+
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+//! Software bill of materials (SBOM) formats for the packages of a workspace.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::workspace::Workspace;
+
+pub mod cyclonedx;
+pub mod spdx;
+
+/// The name of the tool recorded in the SBOM metadata.
+pub const TOOL_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// The version of the tool recorded in the SBOM metadata.
+pub const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Represents the document metadata that changes between SBOM generations.
+///
+/// Both values are optional so reproducible documents can be generated: omitting the timestamp
+/// and using [`stable_serial_number`] produces byte-for-byte identical output for the same
+/// workspace.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SbomOptions {
+    serial_number: Option<String>,
+    timestamp: Option<String>,
+}
+
+impl SbomOptions {
+    /// Creates new `SbomOptions`.
+    ///
+    /// # Arguments
+    ///
+    /// * `serial_number` - The UUID identifying the document (ex: from [`random_serial_number`]).
+    /// * `timestamp` - The RFC 3339 creation time of the document (ex: from [`timestamp`]).
+    pub fn new(serial_number: Option<String>, timestamp: Option<String>) -> Self {
+        Self {
+            serial_number,
+            timestamp,
+        }
+    }
+
+    /// Creates options with a random serial number and the current time.
+    pub fn generated() -> Self {
+        Self::new(
+            Some(random_serial_number()),
+            Some(timestamp(SystemTime::now())),
+        )
+    }
+
+    /// Creates options with a serial number derived from the workspace content and no timestamp.
+    pub fn stable(workspace: &Workspace) -> Self {
+        Self::new(Some(stable_serial_number(workspace)), None)
+    }
+
+    /// Returns the serial number of the document.
+    pub fn serial_number(&self) -> Option<&String> {
+        self.serial_number.as_ref()
+    }
+
+    /// Returns the creation time of the document.
+    pub fn timestamp(&self) -> Option<&String> {
+        self.timestamp.as_ref()
+    }
+}
+
+/// Returns the package URL (purl) of a NuGet package.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::sbom::purl;
+///
+/// assert_eq!(purl("Newtonsoft.Json", "13.0.3"), "pkg:nuget/Newtonsoft.Json@13.0.3");
+/// ```
+pub fn purl(name: &str, version: &str) -> String {
+    format!("pkg:nuget/{}@{}", name, version)
+}
+
+/// Returns a UUID derived from the projects and packages of the workspace.
+///
+/// The same workspace always produces the same serial number, regardless of where it is
+/// located on disk.
+pub fn stable_serial_number(workspace: &Workspace) -> String {
+    let mut content = String::new();
+
+    for project in workspace.projects() {
+        content += &workspace.relative_path(project.path());
+        content.push('\n');
+
+        for reference in project.project_references() {
+            content += &format!(" {}\n", reference.path().display());
+        }
+
+        for package_reference in project.package_references() {
+            content += &format!(
+                " {} {:?}\n",
+                purl(package_reference.name(), package_reference.version()),
+                package_reference.license()
+            );
+        }
+    }
+
+    let high = fnv1a(0xcbf29ce484222325, content.as_bytes());
+    let low = fnv1a(high, content.as_bytes());
+
+    uuid(high, low)
+}
+
+/// Returns a random UUID.
+pub fn random_serial_number() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos());
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(nanos);
+    hasher.write_u32(std::process::id());
+    let high = hasher.finish();
+
+    hasher.write_u64(high);
+    let low = hasher.finish();
+
+    uuid(high, low)
+}
+
+/// Formats a time as an RFC 3339 UTC timestamp (ex: `2024-07-21T13:45:00Z`).
+pub fn timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    let days = (seconds / 86_400) as i64;
+    let seconds_of_day = seconds % 86_400;
+
+    // converts days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Returns the name of the directory of the workspace, used to name the document.
+fn document_name(workspace: &Workspace) -> String {
+    let root = workspace
+        .root()
+        .canonicalize()
+        .unwrap_or_else(|_| workspace.root().clone());
+
+    root.file_name().map_or("workspace".to_string(), |name| {
+        name.to_string_lossy().to_string()
+    })
+}
+
+fn fnv1a(offset: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(offset, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Formats 128 bits as a version 8 (custom) UUID.
+fn uuid(high: u64, low: u64) -> String {
+    let high = (high & !0xf000) | 0x8000;
+    let low = (low & !(0b11 << 62)) | (0b10 << 62);
+
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{
+        graph::test::{project, workspace},
+        workspace::Workspace,
+    };
+
+    use super::*;
+
+    #[test]
+    pub fn stable_serial_number_depends_only_on_content() {
+        // given
+        let workspace = workspace();
+        let moved = Workspace::from_projects(
+            "/other",
+            vec![
+                project(
+                    "/other/src/Api/Api.csproj",
+                    &["../Domain/Domain.csproj"],
+                    &[("Serilog", "3.1.1")],
+                ),
+                project(
+                    "/other/src/Domain/Domain.csproj",
+                    &[],
+                    &[("Serilog", "3.1.1")],
+                ),
+            ],
+        );
+
+        // when
+        let serial_number = stable_serial_number(&workspace);
+
+        // then
+        assert_eq!(serial_number, stable_serial_number(&moved));
+        assert_eq!(serial_number.len(), 36);
+        assert_eq!(&serial_number[14..15], "8");
+        assert_ne!(
+            serial_number,
+            stable_serial_number(&Workspace::from_projects("/repo", vec![]))
+        );
+    }
+
+    #[test]
+    pub fn format_timestamp() {
+        assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            timestamp(UNIX_EPOCH + Duration::from_secs(1_709_210_096)),
+            "2024-02-29T12:34:56Z"
+        );
+    }
+}
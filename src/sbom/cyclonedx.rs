@@ -0,0 +1,310 @@
+use std::{collections::BTreeMap, io::Write};
+
+use serde::Serialize;
+
+use crate::{
+    export::{ExportError, Exporter},
+    graph::DependencyGraph,
+    workspace::Workspace,
+    PackageLicense,
+};
+
+use super::{document_name, purl, SbomOptions, TOOL_NAME, TOOL_VERSION};
+
+const SPEC_VERSION: &str = "1.5";
+
+/// Writes the projects and packages of a workspace as a CycloneDX 1.5 JSON document.
+///
+/// Every project is an `application` component identified by its path relative to the
+/// workspace root, and every package version is a `library` component identified by its purl.
+/// Licenses are included when they were attached to the package references (see
+/// `nuspec::attach_licenses`). The serial number and timestamp come from `options`.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{sbom::{cyclonedx, SbomOptions}, workspace::Workspace};
+///
+/// let workspace = Workspace::from_projects("path/to/repository", vec![]);
+///
+/// let mut output = vec![];
+/// cyclonedx::write(&mut output, &workspace, &SbomOptions::stable(&workspace)).unwrap();
+/// ```
+pub fn write<W>(
+    writer: W,
+    workspace: &Workspace,
+    options: &SbomOptions,
+) -> Result<(), serde_json::Error>
+where
+    W: Write,
+{
+    serde_json::to_writer_pretty(writer, &build_bom(workspace, options))
+}
+
+/// Serializes the projects and packages of a workspace as a CycloneDX 1.5 JSON string.
+///
+/// See [`write`] for more details.
+pub fn to_string(
+    workspace: &Workspace,
+    options: &SbomOptions,
+) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&build_bom(workspace, options))
+}
+
+fn build_bom(workspace: &Workspace, options: &SbomOptions) -> Bom {
+    let mut components = vec![];
+    let mut packages: BTreeMap<String, Component> = BTreeMap::new();
+    let mut dependencies = vec![];
+
+    for project in workspace.projects() {
+        let bom_ref = workspace.relative_path(project.path());
+
+        let mut depends_on: Vec<String> = project
+            .project_references()
+            .iter()
+            .filter_map(|reference| workspace.resolve_reference(project, reference))
+            .map(|target| workspace.relative_path(target.path()))
+            .collect();
+
+        for package_reference in project.package_references() {
+            let purl = purl(package_reference.name(), package_reference.version());
+
+            packages.entry(purl.clone()).or_insert_with(|| Component {
+                kind: "library",
+                bom_ref: purl.clone(),
+                name: package_reference.name().to_string(),
+                version: Some(package_reference.version().to_string()),
+                purl: Some(purl.clone()),
+                licenses: package_reference
+                    .license()
+                    .map(|license| vec![LicenseChoice::from(license)]),
+            });
+
+            depends_on.push(purl);
+        }
+
+        depends_on.sort();
+        depends_on.dedup();
+
+        components.push(Component {
+            kind: "application",
+            bom_ref: bom_ref.clone(),
+            name: project.name().clone(),
+            version: None,
+            purl: None,
+            licenses: project
+                .license_expression()
+                .map(|expression| vec![LicenseChoice::Expression(expression.clone())]),
+        });
+
+        dependencies.push(Dependency {
+            reference: bom_ref,
+            depends_on,
+        });
+    }
+
+    components.extend(packages.into_values());
+
+    let name = document_name(workspace);
+
+    Bom {
+        bom_format: "CycloneDX",
+        spec_version: SPEC_VERSION,
+        serial_number: options
+            .serial_number()
+            .map(|serial_number| format!("urn:uuid:{}", serial_number)),
+        version: 1,
+        metadata: Metadata {
+            timestamp: options.timestamp().cloned(),
+            tools: Tools {
+                components: vec![Component {
+                    kind: "application",
+                    bom_ref: format!("{}@{}", TOOL_NAME, TOOL_VERSION),
+                    name: TOOL_NAME.to_string(),
+                    version: Some(TOOL_VERSION.to_string()),
+                    purl: None,
+                    licenses: None,
+                }],
+            },
+            component: Component {
+                kind: "application",
+                bom_ref: name.clone(),
+                name,
+                version: None,
+                purl: None,
+                licenses: None,
+            },
+        },
+        components,
+        dependencies,
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    serial_number: Option<String>,
+    version: u32,
+    metadata: Metadata,
+    components: Vec<Component>,
+    dependencies: Vec<Dependency>,
+}
+
+#[derive(Serialize)]
+struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+    tools: Tools,
+    component: Component,
+}
+
+#[derive(Serialize)]
+struct Tools {
+    components: Vec<Component>,
+}
+
+#[derive(Serialize)]
+struct Component {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    licenses: Option<Vec<LicenseChoice>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+enum LicenseChoice {
+    Expression(String),
+    License(License),
+}
+
+#[derive(Serialize)]
+struct License {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+impl From<&PackageLicense> for LicenseChoice {
+    fn from(license: &PackageLicense) -> Self {
+        match license {
+            PackageLicense::Expression(expression) => Self::Expression(expression.clone()),
+            // license files are not available outside of the package, so only the name is kept
+            PackageLicense::File(file) => Self::License(License {
+                name: file.clone(),
+                url: None,
+            }),
+            PackageLicense::Url(url) => Self::License(License {
+                name: url.clone(),
+                url: Some(url.clone()),
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Dependency {
+    #[serde(rename = "ref")]
+    reference: String,
+    depends_on: Vec<String>,
+}
+
+/// An [`Exporter`] writing the projects and packages of the workspace as a CycloneDX 1.5 JSON
+/// document (see [`write`]), followed by a newline. The graph is not used.
+///
+/// Without options, the document gets the stable serial number of the workspace and no
+/// timestamp (see [`SbomOptions::stable`]).
+#[derive(Debug, Clone, Default)]
+pub struct CycloneDxExporter {
+    options: Option<SbomOptions>,
+}
+
+impl CycloneDxExporter {
+    /// Creates a new `CycloneDxExporter` with the given options.
+    pub fn new(options: SbomOptions) -> Self {
+        Self {
+            options: Some(options),
+        }
+    }
+}
+
+impl Exporter for CycloneDxExporter {
+    fn name(&self) -> &str {
+        "cyclonedx"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        _graph: &DependencyGraph,
+        workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        let options = match &self.options {
+            Some(options) => options.clone(),
+            None => SbomOptions::stable(workspace),
+        };
+
+        write(&mut *writer, workspace, &options)?;
+        writeln!(writer)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{graph::test::workspace, sbom::SbomOptions, PackageLicense};
+
+    use super::to_string;
+
+    #[test]
+    pub fn bom_contains_components_and_dependencies() {
+        // given
+        let mut workspace = workspace();
+        workspace.projects_mut()[0].package_references_mut()[0]
+            .set_license(Some(PackageLicense::Expression("Apache-2.0".to_string())));
+
+        // when
+        let options = SbomOptions::new(
+            Some("8a5c2f31-6f0e-4c2b-8d1e-0f9a7b6c5d4e".to_string()),
+            Some("2024-07-21T13:45:00Z".to_string()),
+        );
+        let bom = to_string(&workspace, &options).unwrap();
+
+        // then
+        let json: serde_json::Value = serde_json::from_str(&bom).unwrap();
+
+        assert_eq!(json["bomFormat"], "CycloneDX");
+        assert_eq!(
+            json["serialNumber"],
+            "urn:uuid:8a5c2f31-6f0e-4c2b-8d1e-0f9a7b6c5d4e"
+        );
+        assert_eq!(json["metadata"]["timestamp"], "2024-07-21T13:45:00Z");
+        assert_eq!(
+            json["metadata"]["tools"]["components"][0]["name"],
+            "dotnet-lens"
+        );
+        assert_eq!(json["metadata"]["component"]["name"], "repo");
+
+        let components = json["components"].as_array().unwrap();
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[2]["purl"], "pkg:nuget/Serilog@3.1.1");
+        assert_eq!(components[2]["licenses"][0]["expression"], "Apache-2.0");
+
+        assert_eq!(
+            json["dependencies"][0]["dependsOn"],
+            serde_json::json!(["pkg:nuget/Serilog@3.1.1", "src/Domain/Domain.csproj"])
+        );
+    }
+}
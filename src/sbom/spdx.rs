@@ -0,0 +1,304 @@
+use std::{collections::BTreeMap, io::Write};
+
+use serde::Serialize;
+
+use crate::{
+    export::{ExportError, Exporter},
+    graph::DependencyGraph,
+    workspace::Workspace,
+    PackageLicense,
+};
+
+use super::{document_name, purl, stable_serial_number, SbomOptions, TOOL_NAME, TOOL_VERSION};
+
+const SPDX_VERSION: &str = "SPDX-2.3";
+
+/// The creation time used when the options do not have a timestamp, since SPDX requires one.
+const DEFAULT_TIMESTAMP: &str = "1970-01-01T00:00:00Z";
+
+/// Writes the projects and packages of a workspace as an SPDX 2.3 JSON document.
+///
+/// Every project and package version is an SPDX package, linked with `DEPENDS_ON`
+/// relationships. Package licenses are declared when they were attached to the package
+/// references (see `nuspec::attach_licenses`) and are `NOASSERTION` otherwise.
+///
+/// The document namespace uses the serial number from `options`, or the stable serial number
+/// of the workspace when none was given.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{sbom::{spdx, SbomOptions}, workspace::Workspace};
+///
+/// let workspace = Workspace::from_projects("path/to/repository", vec![]);
+///
+/// let mut output = vec![];
+/// spdx::write(&mut output, &workspace, &SbomOptions::stable(&workspace)).unwrap();
+/// ```
+pub fn write<W>(
+    writer: W,
+    workspace: &Workspace,
+    options: &SbomOptions,
+) -> Result<(), serde_json::Error>
+where
+    W: Write,
+{
+    serde_json::to_writer_pretty(writer, &build_document(workspace, options))
+}
+
+/// Serializes the projects and packages of a workspace as an SPDX 2.3 JSON string.
+///
+/// See [`write`] for more details.
+pub fn to_string(
+    workspace: &Workspace,
+    options: &SbomOptions,
+) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&build_document(workspace, options))
+}
+
+fn build_document(workspace: &Workspace, options: &SbomOptions) -> Document {
+    let mut packages = vec![];
+    let mut package_versions: BTreeMap<String, Package> = BTreeMap::new();
+    let mut relationships = vec![];
+    let mut describes = vec![];
+
+    for project in workspace.projects() {
+        let id = spdx_id("Project", &workspace.relative_path(project.path()));
+
+        for reference in project.project_references() {
+            if let Some(target) = workspace.resolve_reference(project, reference) {
+                relationships.push(Relationship::depends_on(
+                    &id,
+                    spdx_id("Project", &workspace.relative_path(target.path())),
+                ));
+            }
+        }
+
+        for package_reference in project.package_references() {
+            let purl = purl(package_reference.name(), package_reference.version());
+            let package_id = spdx_id("Package", &purl);
+
+            package_versions
+                .entry(purl.clone())
+                .or_insert_with(|| Package {
+                    id: package_id.clone(),
+                    name: package_reference.name().to_string(),
+                    version_info: Some(package_reference.version().to_string()),
+                    download_location: "NOASSERTION",
+                    license_declared: license_declared(package_reference.license()),
+                    external_refs: vec![ExternalRef {
+                        reference_category: "PACKAGE-MANAGER",
+                        reference_type: "purl",
+                        reference_locator: purl,
+                    }],
+                });
+
+            relationships.push(Relationship::depends_on(&id, package_id));
+        }
+
+        describes.push(id.clone());
+        packages.push(Package {
+            id,
+            name: project.name().clone(),
+            version_info: None,
+            download_location: "NOASSERTION",
+            license_declared: project
+                .license_expression()
+                .cloned()
+                .unwrap_or("NOASSERTION".to_string()),
+            external_refs: vec![],
+        });
+    }
+
+    packages.extend(package_versions.into_values());
+
+    let name = document_name(workspace);
+    let serial_number = options
+        .serial_number()
+        .cloned()
+        .unwrap_or_else(|| stable_serial_number(workspace));
+
+    Document {
+        spdx_version: SPDX_VERSION,
+        data_license: "CC0-1.0",
+        id: "SPDXRef-DOCUMENT",
+        document_namespace: format!("https://spdx.org/spdxdocs/{}-{}", name, serial_number),
+        name,
+        creation_info: CreationInfo {
+            created: options
+                .timestamp()
+                .cloned()
+                .unwrap_or(DEFAULT_TIMESTAMP.to_string()),
+            creators: vec![format!("Tool: {}-{}", TOOL_NAME, TOOL_VERSION)],
+        },
+        document_describes: describes,
+        packages,
+        relationships,
+    }
+}
+
+/// Returns an SPDX identifier, which may only contain letters, numbers, `.` and `-`.
+fn spdx_id(prefix: &str, value: &str) -> String {
+    let value: String = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    format!("SPDXRef-{}-{}", prefix, value)
+}
+
+fn license_declared(license: Option<&PackageLicense>) -> String {
+    match license {
+        Some(PackageLicense::Expression(expression)) => expression.clone(),
+        _ => "NOASSERTION".to_string(),
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    spdx_version: &'static str,
+    data_license: &'static str,
+    #[serde(rename = "SPDXID")]
+    id: &'static str,
+    name: String,
+    document_namespace: String,
+    creation_info: CreationInfo,
+    document_describes: Vec<String>,
+    packages: Vec<Package>,
+    relationships: Vec<Relationship>,
+}
+
+#[derive(Serialize)]
+struct CreationInfo {
+    created: String,
+    creators: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Package {
+    #[serde(rename = "SPDXID")]
+    id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_info: Option<String>,
+    download_location: &'static str,
+    license_declared: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_refs: Vec<ExternalRef>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalRef {
+    reference_category: &'static str,
+    reference_type: &'static str,
+    reference_locator: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Relationship {
+    spdx_element_id: String,
+    relationship_type: &'static str,
+    related_spdx_element: String,
+}
+
+impl Relationship {
+    fn depends_on(from: &str, to: String) -> Self {
+        Self {
+            spdx_element_id: from.to_string(),
+            relationship_type: "DEPENDS_ON",
+            related_spdx_element: to,
+        }
+    }
+}
+
+/// An [`Exporter`] writing the projects and packages of the workspace as an SPDX 2.3 JSON
+/// document (see [`write`]), followed by a newline. The graph is not used.
+///
+/// Without options, the document gets the stable serial number of the workspace and no
+/// timestamp (see [`SbomOptions::stable`]).
+#[derive(Debug, Clone, Default)]
+pub struct SpdxExporter {
+    options: Option<SbomOptions>,
+}
+
+impl SpdxExporter {
+    /// Creates a new `SpdxExporter` with the given options.
+    pub fn new(options: SbomOptions) -> Self {
+        Self {
+            options: Some(options),
+        }
+    }
+}
+
+impl Exporter for SpdxExporter {
+    fn name(&self) -> &str {
+        "spdx"
+    }
+
+    fn export(
+        &self,
+        writer: &mut dyn Write,
+        _graph: &DependencyGraph,
+        workspace: &Workspace,
+    ) -> Result<(), ExportError> {
+        let options = match &self.options {
+            Some(options) => options.clone(),
+            None => SbomOptions::stable(workspace),
+        };
+
+        write(&mut *writer, workspace, &options)?;
+        writeln!(writer)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{graph::test::workspace, sbom::SbomOptions};
+
+    use super::to_string;
+
+    #[test]
+    pub fn spdx_contains_packages_and_relationships() {
+        // given
+        let workspace = workspace();
+
+        // when
+        let document = to_string(&workspace, &SbomOptions::stable(&workspace)).unwrap();
+
+        // then
+        let json: serde_json::Value = serde_json::from_str(&document).unwrap();
+
+        assert_eq!(json["spdxVersion"], "SPDX-2.3");
+        assert_eq!(json["creationInfo"]["created"], "1970-01-01T00:00:00Z");
+        assert_eq!(json["packages"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            json["packages"][2]["SPDXID"],
+            "SPDXRef-Package-pkg-nuget-Serilog-3.1.1"
+        );
+        assert_eq!(
+            json["relationships"][0],
+            serde_json::json!({
+                "spdxElementId": "SPDXRef-Project-src-Api-Api.csproj",
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": "SPDXRef-Project-src-Domain-Domain.csproj"
+            })
+        );
+
+        assert_eq!(
+            document,
+            to_string(&workspace, &SbomOptions::stable(&workspace)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! The serialized shape of [`Project`], kept apart from the struct so internal refactors do not
+//! change the JSON read by downstream consumers.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assembly::AssemblyMetadata, condition::ConditionalProperty, migration::MigrationMetadata,
+    packaging::PackagingMetadata, source_link::RepositoryMetadata, strictness::StrictnessMetadata,
+    workloads::WorkloadMetadata, PackageReference, Project, ProjectLanguage, ProjectReference,
+    PROJECT_SCHEMA_VERSION,
+};
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ProjectDocument {
+    #[serde(rename = "schema_version", default = "first_version")]
+    schema_version: u32,
+    #[serde(rename = "name")]
+    name: String,
+    #[serde(rename = "language")]
+    language: ProjectLanguage,
+    #[serde(rename = "path")]
+    path: PathBuf,
+    #[serde(rename = "target_framework", default)]
+    target_framework: Option<String>,
+    #[serde(rename = "license_expression", default)]
+    license_expression: Option<String>,
+    #[serde(
+        rename = "target_framework_candidates",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    target_framework_candidates: Vec<String>,
+    #[serde(
+        rename = "target_frameworks",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    target_frameworks: Vec<String>,
+    #[serde(
+        rename = "repository",
+        default,
+        skip_serializing_if = "RepositoryMetadata::is_empty"
+    )]
+    repository: RepositoryMetadata,
+    #[serde(
+        rename = "assembly",
+        default,
+        skip_serializing_if = "AssemblyMetadata::is_empty"
+    )]
+    assembly: AssemblyMetadata,
+    #[serde(
+        rename = "packaging",
+        default,
+        skip_serializing_if = "PackagingMetadata::is_empty"
+    )]
+    packaging: PackagingMetadata,
+    #[serde(
+        rename = "strictness",
+        default,
+        skip_serializing_if = "StrictnessMetadata::is_empty"
+    )]
+    strictness: StrictnessMetadata,
+    #[serde(
+        rename = "workloads",
+        default,
+        skip_serializing_if = "WorkloadMetadata::is_empty"
+    )]
+    workloads: WorkloadMetadata,
+    #[serde(
+        rename = "migration",
+        default,
+        skip_serializing_if = "MigrationMetadata::is_empty"
+    )]
+    migration: MigrationMetadata,
+    #[serde(rename = "tags", default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(
+        rename = "conditional_properties",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    conditional_properties: Vec<ConditionalProperty>,
+    #[serde(rename = "project_references", default)]
+    project_references: Vec<ProjectReference>,
+    #[serde(rename = "package_references", default)]
+    package_references: Vec<PackageReference>,
+}
+
+/// Documents written before the schema was versioned have the shape of the first version.
+fn first_version() -> u32 {
+    1
+}
+
+impl From<Project> for ProjectDocument {
+    fn from(project: Project) -> Self {
+        Self {
+            schema_version: PROJECT_SCHEMA_VERSION,
+            name: project.name,
+            language: project.language,
+            path: project.path,
+            target_framework: project.target_framework,
+            license_expression: project.license_expression,
+            target_framework_candidates: project.target_framework_candidates,
+            target_frameworks: project.target_frameworks,
+            repository: project.repository,
+            assembly: project.assembly,
+            packaging: project.packaging,
+            strictness: project.strictness,
+            workloads: project.workloads,
+            migration: project.migration,
+            tags: project.tags,
+            conditional_properties: project.conditional_properties,
+            project_references: project.project_references,
+            package_references: project.package_references,
+        }
+    }
+}
+
+impl TryFrom<ProjectDocument> for Project {
+    type Error = String;
+
+    fn try_from(document: ProjectDocument) -> Result<Self, Self::Error> {
+        if document.schema_version > PROJECT_SCHEMA_VERSION {
+            return Err(format!(
+                "the project schema version {} is newer than the supported version {}",
+                document.schema_version, PROJECT_SCHEMA_VERSION
+            ));
+        }
+
+        Ok(Self {
+            name: document.name,
+            language: document.language,
+            path: document.path,
+            target_framework: document.target_framework,
+            license_expression: document.license_expression,
+            target_framework_candidates: document.target_framework_candidates,
+            target_frameworks: document.target_frameworks,
+            repository: document.repository,
+            assembly: document.assembly,
+            packaging: document.packaging,
+            strictness: document.strictness,
+            workloads: document.workloads,
+            migration: document.migration,
+            tags: document.tags,
+            conditional_properties: document.conditional_properties,
+            project_references: document.project_references,
+            package_references: document.package_references,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::{PackageReference, Project, ProjectLanguage};
+
+    #[test]
+    pub fn serialize_with_stable_shape() {
+        // given
+        let mut project = Project::empty(
+            "Api".to_string(),
+            ProjectLanguage::CSharp,
+            PathBuf::from("src/Api/Api.csproj"),
+        );
+        project.target_framework = Some("net8.0".to_string());
+        project.add_package_reference(PackageReference::new(
+            "Serilog".to_string(),
+            "3.1.1".to_string(),
+        ));
+
+        // when
+        let json = serde_json::to_value(&project).unwrap();
+
+        // then
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "schema_version": 1,
+                "name": "Api",
+                "language": "CSharp",
+                "path": "src/Api/Api.csproj",
+                "target_framework": "net8.0",
+                "license_expression": null,
+                "project_references": [],
+                "package_references": [
+                    { "name": "Serilog", "version": "3.1.1", "license": null }
+                ]
+            })
+        );
+        assert_eq!(serde_json::from_value::<Project>(json).unwrap(), project);
+    }
+
+    #[test]
+    pub fn deserialize_unversioned_document() {
+        let project: Project = serde_json::from_str(
+            r#"{"name":"Api","language":"FSharp","path":"src/Api/Api.fsproj"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(project.language(), ProjectLanguage::FSharp);
+        assert!(project.target_framework().is_none());
+        assert!(project.package_references().is_empty());
+    }
+
+    #[test]
+    pub fn reject_newer_schema_version() {
+        let result = serde_json::from_str::<Project>(
+            r#"{"schema_version":2,"name":"Api","language":"CSharp","path":"Api.csproj"}"#,
+        );
+
+        assert!(result.is_err());
+    }
+}
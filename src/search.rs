@@ -1,9 +1,10 @@
 use std::{
+    ffi::OsStr,
     fs, io,
     path::{Path, PathBuf},
 };
 
-use crate::VALID_EXTENSIONS;
+use crate::{ignore::IgnoreFile, VALID_EXTENSIONS};
 
 const BLOCKED_DIRS: [&str; 3] = ["bin", ".git", "obj"];
 
@@ -11,7 +12,9 @@ const BLOCKED_DIRS: [&str; 3] = ["bin", ".git", "obj"];
 ///
 /// This function traverses the directory tree starting from the specified path,
 /// looking for files with an extension contained in the const `VALID_EXTENSIONS`.
-/// It skips the directories `bin`, `.git`, and `obj` for performance reasons.
+/// It skips the directories `bin`, `.git`, and `obj` for performance reasons, and the
+/// directories and files excluded by the `.dotnetlensignore` file of the searched directory
+/// (see [`IgnoreFile`]).
 ///
 /// # Arguments
 ///
@@ -35,13 +38,42 @@ const BLOCKED_DIRS: [&str; 3] = ["bin", ".git", "obj"];
 ///     Err(e) => eprintln!("Error: {:?}", e),
 /// }
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(directory = %path.as_ref().display()))
+)]
 pub fn search_projects<P>(path: &P) -> Result<Vec<PathBuf>, io::Error>
 where
     P: AsRef<Path>,
 {
-    let mut results = Vec::new();
+    search_files(path, &is_valid_extension)
+}
 
-    let path = path.as_ref();
+/// Searches recursively for the files whose extension is accepted by `is_project`, skipping the
+/// same directories as [`search_projects`] and the paths excluded by the ignore file of `path`
+/// (see [`IgnoreFile`]).
+pub(crate) fn search_files<P>(
+    path: &P,
+    is_project: &dyn Fn(&OsStr) -> bool,
+) -> Result<Vec<PathBuf>, io::Error>
+where
+    P: AsRef<Path>,
+{
+    let root = path.as_ref();
+    let ignore = IgnoreFile::load(root)?;
+
+    walk(root, root, &ignore, is_project)
+}
+
+fn walk(
+    root: &Path,
+    path: &Path,
+    ignore: &IgnoreFile,
+    is_project: &dyn Fn(&OsStr) -> bool,
+) -> Result<Vec<PathBuf>, io::Error> {
+    let mut results = Vec::new();
+    #[cfg(feature = "tracing")]
+    let mut skipped_files = 0;
 
     for entry in fs::read_dir(path)? {
         let entry = entry?;
@@ -49,19 +81,65 @@ where
         let file_type = entry.file_type()?;
 
         let entry_path = entry.path();
+        let relative = entry_path.strip_prefix(root).unwrap_or(&entry_path);
 
-        if file_type.is_dir() && !BLOCKED_DIRS.iter().any(|dir| entry_path.ends_with(dir)) {
-            results.append(&mut search_projects(&entry_path)?);
+        if file_type.is_dir() {
+            if BLOCKED_DIRS.iter().any(|dir| entry_path.ends_with(dir))
+                || ignore.is_ignored_directory(relative)
+            {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(directory = %entry_path.display(), "skipped directory");
+            } else {
+                results.append(&mut walk(root, &entry_path, ignore, is_project)?);
+            }
 
             continue;
         }
 
-        if let Some(entry_extension) = entry_path.extension() {
-            if VALID_EXTENSIONS.iter().any(|ext| *ext == entry_extension) {
+        match entry_path.extension() {
+            Some(extension) if is_project(extension) && !ignore.is_ignored(relative) => {
                 results.push(entry_path);
             }
+            #[cfg(feature = "tracing")]
+            _ => skipped_files += 1,
+            #[cfg(not(feature = "tracing"))]
+            _ => (),
         }
     }
 
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        projects = results.len(),
+        skipped_files,
+        "searched directory"
+    );
+
     Ok(results)
 }
+
+/// Returns `true` if the path is a project file that `search_projects` would find, that is, it
+/// has a valid extension and is not inside one of the skipped directories.
+///
+/// The path should be relative to the searched directory, otherwise a skipped directory above
+/// it (ex: `/home/user/bin/repo`) would exclude the file.
+pub fn is_project_file<P>(path: &P) -> bool
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+
+    path.extension().is_some_and(is_valid_extension) && !is_in_blocked_directory(path)
+}
+
+/// Returns `true` if the path is inside one of the directories skipped by [`search_projects`].
+pub(crate) fn is_in_blocked_directory(path: &Path) -> bool {
+    path.parent().is_some_and(|parent| {
+        parent
+            .components()
+            .any(|component| BLOCKED_DIRS.iter().any(|dir| component.as_os_str() == *dir))
+    })
+}
+
+fn is_valid_extension(extension: &OsStr) -> bool {
+    VALID_EXTENSIONS.iter().any(|ext| *ext == extension)
+}
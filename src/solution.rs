@@ -0,0 +1,875 @@
+//! Reading of the projects, solution folders and build configurations declared in Visual Studio
+//! solution files (`.sln`), and comparison of the solutions with the projects on disk.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    parser::ParseError,
+    search,
+    workspace::{normalize_path, Workspace},
+    ProjectLanguage,
+};
+
+/// The project type GUID of solution folders.
+pub const SOLUTION_FOLDER_TYPE_GUID: &str = "2150E333-8FDC-42A3-9474-1A3956D46DE8";
+
+/// The well-known project type GUIDs, in uppercase without braces, and the kind of project they
+/// identify.
+///
+/// Visual Studio writes different GUIDs for the same language depending on the project system:
+/// the legacy one (ex: `FAE04EC0-...` for C#) and the SDK-style one (ex: `9A19103F-...`).
+pub const PROJECT_TYPE_GUIDS: [(&str, ProjectKind); 17] = [
+    (SOLUTION_FOLDER_TYPE_GUID, ProjectKind::SolutionFolder),
+    ("FAE04EC0-301F-11D3-BF4B-00C04F79EFBC", ProjectKind::CSharp),
+    ("9A19103F-16F7-4668-BE54-9A1E7A4F7556", ProjectKind::CSharp),
+    ("F2A71F9B-5D33-465A-A702-920D77279786", ProjectKind::FSharp),
+    ("6EC3EE1D-3C4E-46DD-8F32-0CC8E7565705", ProjectKind::FSharp),
+    ("F184B08F-C81C-45F6-A57F-5ABD9991F28F", ProjectKind::VB),
+    ("778DAE3C-4631-46EA-AA77-85C1314464D9", ProjectKind::VB),
+    ("8BC9CEB8-8B4A-11D0-8D11-00A0C91BC942", ProjectKind::Cpp),
+    (
+        "D954291E-2A0B-460D-934E-DC6B0785DB48",
+        ProjectKind::SharedProject,
+    ),
+    ("E24C65DC-7377-472B-9ABA-BC803B73C61A", ProjectKind::WebSite),
+    (
+        "8BB2217D-0F2D-49D1-97BC-3654ED321F3B",
+        ProjectKind::AspNetCoreXproj,
+    ),
+    (
+        "00D1A9C2-B5F0-4AF3-8072-F6C62B433612",
+        ProjectKind::Database,
+    ),
+    (
+        "E53339B2-1760-4266-BCC7-CA923CBCF16C",
+        ProjectKind::DockerCompose,
+    ),
+    (
+        "A07B5EB6-E848-4116-A8D0-A826331D98C6",
+        ProjectKind::ServiceFabric,
+    ),
+    ("930C7802-8A8C-48F9-8165-68863BCCD9DD", ProjectKind::Wix),
+    (
+        "54435603-DBB4-11D2-8724-00A0C9A8B90C",
+        ProjectKind::SetupProject,
+    ),
+    ("888888A0-9F3D-457C-B088-3A5042F75D52", ProjectKind::Python),
+];
+
+/// Represents a Visual Studio solution file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::solution::Solution;
+///
+/// let solution = Solution::load("path/to/App.sln")?;
+///
+/// for project in solution.projects().iter().filter(|p| !p.is_folder()) {
+///     println!("{} ({:?})", project.name(), solution.folder(project));
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Solution {
+    path: PathBuf,
+    projects: Vec<SolutionProject>,
+    configurations: Vec<ConfigurationPlatform>,
+    project_configurations: Vec<ProjectConfiguration>,
+}
+
+/// Represents a project or a solution folder declared in a solution.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolutionProject {
+    name: String,
+    path: PathBuf,
+    guid: String,
+    type_guid: String,
+    parent: Option<String>,
+}
+
+/// Represents the kind of a solution entry, identified by its project type GUID (see
+/// [`PROJECT_TYPE_GUIDS`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProjectKind {
+    SolutionFolder,
+    CSharp,
+    FSharp,
+    VB,
+    /// A C++ project (`.vcxproj`).
+    Cpp,
+    /// A shared project (`.shproj`), whose files are compiled into the projects importing it.
+    SharedProject,
+    /// An ASP.NET web site, a folder without a project file.
+    WebSite,
+    /// An ASP.NET Core project of the `project.json` era (`.xproj`).
+    AspNetCoreXproj,
+    /// A SQL Server database project (`.sqlproj`).
+    Database,
+    DockerCompose,
+    ServiceFabric,
+    Wix,
+    /// A Visual Studio installer project (`.vdproj`).
+    SetupProject,
+    /// A Python project (`.pyproj`).
+    Python,
+}
+
+impl ProjectKind {
+    /// Returns the kind of project identified by a project type GUID, with or without braces
+    /// and in any case, if it is well known.
+    pub fn from_type_guid(type_guid: &str) -> Option<Self> {
+        let type_guid = guid(type_guid);
+
+        PROJECT_TYPE_GUIDS
+            .iter()
+            .find(|(known, _)| *known == type_guid)
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Returns a friendly name for the kind of project (ex: `C#`, `Solution folder`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SolutionFolder => "Solution folder",
+            Self::CSharp => "C#",
+            Self::FSharp => "F#",
+            Self::VB => "Visual Basic",
+            Self::Cpp => "C++",
+            Self::SharedProject => "Shared project",
+            Self::WebSite => "Web site",
+            Self::AspNetCoreXproj => "ASP.NET Core (xproj)",
+            Self::Database => "SQL Server database",
+            Self::DockerCompose => "Docker Compose",
+            Self::ServiceFabric => "Service Fabric",
+            Self::Wix => "WiX installer",
+            Self::SetupProject => "Setup project",
+            Self::Python => "Python",
+        }
+    }
+
+    /// Returns the language of the projects of this kind that dotnet-lens reads, if any.
+    pub fn language(&self) -> Option<ProjectLanguage> {
+        match self {
+            Self::CSharp => Some(ProjectLanguage::CSharp),
+            Self::FSharp => Some(ProjectLanguage::FSharp),
+            Self::VB => Some(ProjectLanguage::VB),
+            Self::DockerCompose => Some(ProjectLanguage::DockerCompose),
+            Self::ServiceFabric => Some(ProjectLanguage::ServiceFabric),
+            Self::Wix => Some(ProjectLanguage::Wix),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ProjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Represents a configuration and platform pair (ex: `Release|Any CPU`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigurationPlatform {
+    configuration: String,
+    platform: String,
+}
+
+/// Represents how a project is built in a configuration of its solution, as declared in the
+/// `ProjectConfigurationPlatforms` section.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectConfiguration {
+    project: String,
+    solution_configuration: ConfigurationPlatform,
+    active: Option<ConfigurationPlatform>,
+    build: bool,
+    deploy: bool,
+}
+
+/// Represents the differences between the projects found on disk and the projects listed in the
+/// solutions of a repository.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolutionDrift {
+    unlisted: Vec<PathBuf>,
+    missing: Vec<MissingProject>,
+}
+
+/// Represents a solution entry pointing at a project that does not exist.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MissingProject {
+    solution: PathBuf,
+    name: String,
+    path: PathBuf,
+}
+
+impl Solution {
+    /// Parses the content of a solution file located at `path`.
+    ///
+    /// GUIDs are stored in uppercase without braces. Project paths are kept as written in the
+    /// solution, with forward slashes.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content could not be read or if a project
+    /// declaration is malformed.
+    pub fn parse<R, P>(reader: R, path: P) -> Result<Self, ParseError>
+    where
+        R: BufRead,
+        P: AsRef<Path>,
+    {
+        let mut projects = vec![];
+        let mut parents: HashMap<String, String> = HashMap::new();
+        let mut configurations = vec![];
+        let mut project_configurations: Vec<ProjectConfiguration> = vec![];
+        let mut section = None;
+
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+
+            if let Some(declaration) = line.strip_prefix("Project(") {
+                projects.push(parse_project(declaration)?);
+            } else if let Some(name) = line.strip_prefix("GlobalSection(") {
+                section = name.split_once(')').map(|(name, _)| name.to_string());
+            } else if line.starts_with("EndGlobalSection") {
+                section = None;
+            } else if let Some((key, value)) = line.split_once('=') {
+                match section.as_deref() {
+                    Some("NestedProjects") => {
+                        parents.insert(guid(key), guid(value));
+                    }
+                    Some("SolutionConfigurationPlatforms") => {
+                        configurations.push(ConfigurationPlatform::parse(key));
+                    }
+                    Some("ProjectConfigurationPlatforms") => {
+                        add_project_configuration(&mut project_configurations, key, value);
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        for project in &mut projects {
+            project.parent = parents.remove(&project.guid);
+        }
+
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            projects,
+            configurations,
+            project_configurations,
+        })
+    }
+
+    /// Reads and parses a solution file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be read or parsed.
+    pub fn load<P>(path: P) -> Result<Self, ParseError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse(BufReader::new(File::open(&path)?), path)
+    }
+
+    /// Returns the path of the solution file.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns the projects and solution folders declared in the solution.
+    pub fn projects(&self) -> &Vec<SolutionProject> {
+        &self.projects
+    }
+
+    /// Returns the path of a project of the solution, resolved relative to the solution
+    /// directory.
+    pub fn project_path(&self, project: &SolutionProject) -> PathBuf {
+        let directory = self.path.parent().unwrap_or(Path::new(""));
+
+        normalize_path(&directory.join(&project.path))
+    }
+
+    /// Returns the project of the solution at `path`.
+    pub fn find<P>(&self, path: P) -> Option<&SolutionProject>
+    where
+        P: AsRef<Path>,
+    {
+        let path = normalize_path(path.as_ref());
+
+        self.projects
+            .iter()
+            .find(|project| !project.is_folder() && self.project_path(project) == path)
+    }
+
+    /// Returns the solution folders containing a project, from the outermost to the innermost,
+    /// joined with `/` (ex: `src/Core`). Returns `None` for projects at the solution root.
+    pub fn folder(&self, project: &SolutionProject) -> Option<String> {
+        let mut folders = vec![];
+        let mut parent = project.parent.as_ref();
+
+        while let Some(guid) = parent {
+            let Some(folder) = self.projects.iter().find(|p| &p.guid == guid) else {
+                break;
+            };
+
+            // guards against malformed solutions where folders are nested in each other
+            if folders.len() > self.projects.len() {
+                break;
+            }
+
+            folders.push(folder.name.as_str());
+            parent = folder.parent.as_ref();
+        }
+
+        if folders.is_empty() {
+            return None;
+        }
+
+        folders.reverse();
+
+        Some(folders.join("/"))
+    }
+
+    /// Returns the configurations of the solution (ex: `Debug|Any CPU`, `Release|Any CPU`), as
+    /// declared in the `SolutionConfigurationPlatforms` section.
+    pub fn configurations(&self) -> &Vec<ConfigurationPlatform> {
+        &self.configurations
+    }
+
+    /// Returns the configurations of a project, one for every solution configuration mapping
+    /// it.
+    pub fn project_configurations(&self, project: &SolutionProject) -> Vec<&ProjectConfiguration> {
+        self.project_configurations
+            .iter()
+            .filter(|configuration| configuration.project == project.guid)
+            .collect()
+    }
+
+    /// Returns how a project is built in a solution configuration, if the solution maps it.
+    ///
+    /// Configurations are compared ignoring case and platforms ignoring case and spaces, like
+    /// Visual Studio does, so `Release|AnyCPU` finds `Release|Any CPU`.
+    pub fn project_configuration(
+        &self,
+        project: &SolutionProject,
+        configuration: &ConfigurationPlatform,
+    ) -> Option<&ProjectConfiguration> {
+        self.project_configurations.iter().find(|mapping| {
+            mapping.project == project.guid && mapping.solution_configuration.matches(configuration)
+        })
+    }
+
+    /// Returns `true` if a project is built in a solution configuration.
+    pub fn builds(&self, project: &SolutionProject, configuration: &ConfigurationPlatform) -> bool {
+        self.project_configuration(project, configuration)
+            .is_some_and(|mapping| mapping.build)
+    }
+
+    /// Returns the projects of the solution that are not built in a solution configuration,
+    /// either because their `Build.0` entry is missing or because they are not mapped to it at
+    /// all. Solution folders are never returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::solution::{ConfigurationPlatform, Solution};
+    ///
+    /// let solution = Solution::load("path/to/App.sln")?;
+    /// let release = ConfigurationPlatform::new("Release", "Any CPU");
+    ///
+    /// for project in solution.excluded_projects(&release) {
+    ///     println!("{} is not built in {release}", project.name());
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn excluded_projects(
+        &self,
+        configuration: &ConfigurationPlatform,
+    ) -> Vec<&SolutionProject> {
+        self.projects
+            .iter()
+            .filter(|project| !project.is_folder() && !self.builds(project, configuration))
+            .collect()
+    }
+}
+
+impl SolutionProject {
+    /// Returns the name of the project or solution folder.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the path of the project as written in the solution. For solution folders, the
+    /// path is the folder name.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns the GUID identifying the project in the solution.
+    pub fn guid(&self) -> &String {
+        &self.guid
+    }
+
+    /// Returns the GUID of the project type (ex: C#, solution folder).
+    pub fn type_guid(&self) -> &String {
+        &self.type_guid
+    }
+
+    /// Returns the GUID of the solution folder containing the project, if any.
+    pub fn parent(&self) -> Option<&String> {
+        self.parent.as_ref()
+    }
+
+    /// Returns the kind of the project, if its project type GUID is well known.
+    pub fn kind(&self) -> Option<ProjectKind> {
+        ProjectKind::from_type_guid(&self.type_guid)
+    }
+
+    /// Returns `true` if this is a solution folder instead of a project.
+    pub fn is_folder(&self) -> bool {
+        self.type_guid == SOLUTION_FOLDER_TYPE_GUID
+    }
+}
+
+impl ConfigurationPlatform {
+    /// Creates a new `ConfigurationPlatform`.
+    pub fn new(configuration: &str, platform: &str) -> Self {
+        Self {
+            configuration: configuration.to_string(),
+            platform: platform.to_string(),
+        }
+    }
+
+    /// Returns the name of the configuration (ex: `Release`).
+    pub fn configuration(&self) -> &String {
+        &self.configuration
+    }
+
+    /// Returns the name of the platform (ex: `Any CPU`, `x64`).
+    pub fn platform(&self) -> &String {
+        &self.platform
+    }
+
+    /// Returns `true` if both pairs name the same configuration and platform, comparing the
+    /// configurations ignoring case and the platforms ignoring case and spaces.
+    pub fn matches(&self, other: &ConfigurationPlatform) -> bool {
+        let platform = |value: &str| value.replace(' ', "").to_lowercase();
+
+        self.configuration
+            .eq_ignore_ascii_case(&other.configuration)
+            && platform(&self.platform) == platform(&other.platform)
+    }
+
+    /// Parses `Configuration|Platform`.
+    fn parse(value: &str) -> Self {
+        let (configuration, platform) = value.trim().split_once('|').unwrap_or((value.trim(), ""));
+
+        Self::new(configuration, platform)
+    }
+}
+
+impl fmt::Display for ConfigurationPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}|{}", self.configuration, self.platform)
+    }
+}
+
+impl ProjectConfiguration {
+    /// Returns the GUID of the project.
+    pub fn project(&self) -> &String {
+        &self.project
+    }
+
+    /// Returns the solution configuration.
+    pub fn solution_configuration(&self) -> &ConfigurationPlatform {
+        &self.solution_configuration
+    }
+
+    /// Returns the configuration of the project used in the solution configuration (its
+    /// `ActiveCfg`), if it is set. It can differ from the solution configuration (ex: a project
+    /// built as `Debug|x64` in `Release|Any CPU`).
+    pub fn active(&self) -> Option<&ConfigurationPlatform> {
+        self.active.as_ref()
+    }
+
+    /// Returns `true` if the project is built in the solution configuration.
+    pub fn build(&self) -> bool {
+        self.build
+    }
+
+    /// Returns `true` if the project is deployed in the solution configuration.
+    pub fn deploy(&self) -> bool {
+        self.deploy
+    }
+}
+
+/// Adds an entry of the `ProjectConfigurationPlatforms` section, such as
+/// `{GUID}.Release|Any CPU.ActiveCfg = Release|Any CPU` or `{GUID}.Release|Any CPU.Build.0 = ...`,
+/// to the configuration of the project it belongs to.
+fn add_project_configuration(
+    configurations: &mut Vec<ProjectConfiguration>,
+    key: &str,
+    value: &str,
+) {
+    let Some((project, entry)) = key.trim().split_once('.') else {
+        return;
+    };
+    let project = guid(project);
+
+    let (solution_configuration, property) = if let Some(value) = entry.strip_suffix(".ActiveCfg") {
+        (value, "ActiveCfg")
+    } else if let Some(value) = entry.strip_suffix(".Build.0") {
+        (value, "Build")
+    } else if let Some(value) = entry.strip_suffix(".Deploy.0") {
+        (value, "Deploy")
+    } else {
+        return;
+    };
+    let solution_configuration = ConfigurationPlatform::parse(solution_configuration);
+
+    let index = match configurations.iter().position(|configuration| {
+        configuration.project == project
+            && configuration.solution_configuration == solution_configuration
+    }) {
+        Some(index) => index,
+        None => {
+            configurations.push(ProjectConfiguration {
+                project,
+                solution_configuration,
+                active: None,
+                build: false,
+                deploy: false,
+            });
+            configurations.len() - 1
+        }
+    };
+    let configuration = &mut configurations[index];
+
+    match property {
+        "ActiveCfg" => configuration.active = Some(ConfigurationPlatform::parse(value)),
+        "Build" => configuration.build = true,
+        _ => configuration.deploy = true,
+    }
+}
+
+impl SolutionDrift {
+    /// Returns `true` if every project on disk is listed in a solution and every solution entry
+    /// exists.
+    pub fn is_empty(&self) -> bool {
+        self.unlisted.is_empty() && self.missing.is_empty()
+    }
+
+    /// Returns the paths of the projects of the workspace that are not listed in any solution.
+    pub fn unlisted(&self) -> &Vec<PathBuf> {
+        &self.unlisted
+    }
+
+    /// Returns the solution entries pointing at projects that do not exist.
+    pub fn missing(&self) -> &Vec<MissingProject> {
+        &self.missing
+    }
+}
+
+impl MissingProject {
+    /// Returns the path of the solution listing the project.
+    pub fn solution(&self) -> &PathBuf {
+        &self.solution
+    }
+
+    /// Returns the name of the project in the solution.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the path of the project, resolved relative to the solution directory.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+/// Searches recursively for solution files (`.sln`) in the given directory, skipping the same
+/// directories as [`search::search_projects`].
+///
+/// # Errors
+///
+/// This function will return an error if a directory could not be read.
+pub fn search_solutions<P>(path: &P) -> Result<Vec<PathBuf>, io::Error>
+where
+    P: AsRef<Path>,
+{
+    search::search_files(path, &|extension| extension.eq_ignore_ascii_case("sln"))
+}
+
+/// Compares the projects of a workspace with the projects listed in the solutions: the projects
+/// on disk missing from every solution, and the solution entries pointing at files that are
+/// neither in the workspace nor on disk.
+///
+/// Paths are compared after normalization, so the workspace and the solutions should be loaded
+/// from paths of the same kind (both absolute or both relative to the same directory).
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{
+///     solution::{drift, search_solutions, Solution},
+///     workspace::Workspace,
+/// };
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// let solutions = search_solutions(&"path/to/repository")?
+///     .into_iter()
+///     .map(Solution::load)
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// for path in drift(&workspace, &solutions).unlisted() {
+///     println!("{} is not in any solution", path.display());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn drift(workspace: &Workspace, solutions: &[Solution]) -> SolutionDrift {
+    let mut listed = vec![];
+    let mut missing = vec![];
+
+    for solution in solutions {
+        for project in solution.projects.iter().filter(|p| !p.is_folder()) {
+            let path = solution.project_path(project);
+
+            if workspace.project_by_path(&path).is_none() && !path.exists() {
+                missing.push(MissingProject {
+                    solution: solution.path.clone(),
+                    name: project.name.clone(),
+                    path: path.clone(),
+                });
+            }
+
+            listed.push(path);
+        }
+    }
+
+    let unlisted = workspace
+        .projects()
+        .iter()
+        .map(|project| normalize_path(project.path()))
+        .filter(|path| !listed.contains(path))
+        .collect();
+
+    SolutionDrift { unlisted, missing }
+}
+
+/// Parses `("{TYPE}") = "Name", "Path", "{GUID}"`, the part of a project declaration after
+/// `Project(`.
+fn parse_project(declaration: &str) -> Result<SolutionProject, ParseError> {
+    let values: Vec<&str> = declaration.split('"').skip(1).step_by(2).collect();
+
+    let [type_guid, name, path, project_guid] = values[..] else {
+        return Err(ParseError::DeserializationError);
+    };
+
+    Ok(SolutionProject {
+        name: name.to_string(),
+        path: PathBuf::from(path.replace('\\', "/")),
+        guid: guid(project_guid),
+        type_guid: guid(type_guid),
+        parent: None,
+    })
+}
+
+fn guid(value: &str) -> String {
+    value
+        .trim()
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .to_uppercase()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, io::Cursor, path::PathBuf};
+
+    use tempfile::tempdir;
+
+    use crate::workspace::Workspace;
+
+    use super::{drift, search_solutions, ConfigurationPlatform, ProjectKind, Solution};
+
+    const SOLUTION: &str = r#"
+Microsoft Visual Studio Solution File, Format Version 12.00
+Project("{2150E333-8FDC-42A3-9474-1A3956D46DE8}") = "src", "src", "{A0000000-0000-0000-0000-000000000001}"
+EndProject
+Project("{2150E333-8FDC-42A3-9474-1A3956D46DE8}") = "Core", "Core", "{A0000000-0000-0000-0000-000000000002}"
+EndProject
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Domain", "src\Domain\Domain.csproj", "{b0000000-0000-0000-0000-000000000001}"
+EndProject
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Tools", "tools\Tools.csproj", "{B0000000-0000-0000-0000-000000000002}"
+EndProject
+Global
+	GlobalSection(SolutionConfigurationPlatforms) = preSolution
+		Debug|Any CPU = Debug|Any CPU
+		Release|Any CPU = Release|Any CPU
+	EndGlobalSection
+	GlobalSection(ProjectConfigurationPlatforms) = postSolution
+		{B0000000-0000-0000-0000-000000000001}.Debug|Any CPU.ActiveCfg = Debug|Any CPU
+		{B0000000-0000-0000-0000-000000000001}.Debug|Any CPU.Build.0 = Debug|Any CPU
+		{B0000000-0000-0000-0000-000000000001}.Release|Any CPU.ActiveCfg = Release|Any CPU
+		{B0000000-0000-0000-0000-000000000001}.Release|Any CPU.Build.0 = Release|Any CPU
+		{B0000000-0000-0000-0000-000000000002}.Debug|Any CPU.ActiveCfg = Debug|x64
+		{B0000000-0000-0000-0000-000000000002}.Debug|Any CPU.Build.0 = Debug|x64
+		{B0000000-0000-0000-0000-000000000002}.Release|Any CPU.ActiveCfg = Release|x64
+	EndGlobalSection
+	GlobalSection(NestedProjects) = preSolution
+		{A0000000-0000-0000-0000-000000000002} = {A0000000-0000-0000-0000-000000000001}
+		{B0000000-0000-0000-0000-000000000001} = {A0000000-0000-0000-0000-000000000002}
+	EndGlobalSection
+EndGlobal
+"#;
+
+    #[test]
+    pub fn parse_projects_and_folders() {
+        // given
+        let reader = Cursor::new(SOLUTION);
+
+        // when
+        let solution = Solution::parse(reader, "/repo/App.sln").unwrap();
+
+        // then
+        let domain = &solution.projects()[2];
+
+        assert_eq!(solution.projects().len(), 4);
+        assert!(solution.projects()[0].is_folder());
+        assert!(!domain.is_folder());
+        assert_eq!(domain.guid(), "B0000000-0000-0000-0000-000000000001");
+        assert_eq!(domain.type_guid(), "FAE04EC0-301F-11D3-BF4B-00C04F79EFBC");
+        assert_eq!(domain.kind(), Some(ProjectKind::CSharp));
+        assert_eq!(
+            solution.projects()[0].kind(),
+            Some(ProjectKind::SolutionFolder)
+        );
+        assert_eq!(
+            ProjectKind::from_type_guid("{9a19103f-16f7-4668-be54-9a1e7a4f7556}"),
+            Some(ProjectKind::CSharp)
+        );
+        assert_eq!(
+            solution.project_path(domain),
+            PathBuf::from("/repo/src/Domain/Domain.csproj")
+        );
+        assert_eq!(solution.folder(domain), Some("src/Core".to_string()));
+        assert_eq!(solution.folder(&solution.projects()[3]), None);
+        assert_eq!(
+            solution.find("/repo/tools/Tools.csproj").unwrap().name(),
+            "Tools"
+        );
+    }
+
+    #[test]
+    pub fn parse_configuration_matrix() {
+        // given
+        let reader = Cursor::new(SOLUTION);
+
+        // when
+        let solution = Solution::parse(reader, "/repo/App.sln").unwrap();
+
+        // then
+        let release = ConfigurationPlatform::new("Release", "AnyCPU");
+        let domain = &solution.projects()[2];
+        let tools = &solution.projects()[3];
+
+        let configurations: Vec<String> = solution
+            .configurations()
+            .iter()
+            .map(|configuration| configuration.to_string())
+            .collect();
+        assert_eq!(configurations, vec!["Debug|Any CPU", "Release|Any CPU"]);
+
+        assert_eq!(solution.project_configurations(tools).len(), 2);
+        assert_eq!(
+            solution
+                .project_configuration(tools, &release)
+                .and_then(|mapping| mapping.active())
+                .map(|active| active.to_string()),
+            Some("Release|x64".to_string())
+        );
+        assert!(solution.builds(domain, &release));
+        assert!(!solution.builds(tools, &release));
+        assert!(solution.builds(tools, &ConfigurationPlatform::new("debug", "Any CPU")));
+
+        let excluded: Vec<&str> = solution
+            .excluded_projects(&release)
+            .iter()
+            .map(|project| project.name().as_str())
+            .collect();
+        assert_eq!(excluded, vec!["Tools"]);
+    }
+
+    #[test]
+    pub fn detect_solution_drift() {
+        // given
+        let dir = tempdir().unwrap();
+        let project = r#"<Project Sdk="Microsoft.NET.Sdk" />"#;
+
+        for name in ["Api", "Core", "Scratch"] {
+            fs::create_dir_all(dir.path().join(name)).unwrap();
+            fs::write(dir.path().join(format!("{name}/{name}.csproj")), project).unwrap();
+        }
+
+        fs::write(
+            dir.path().join("App.sln"),
+            r#"
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Api", "Api\Api.csproj", "{C0000000-0000-0000-0000-000000000001}"
+EndProject
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Old", "Old\Old.csproj", "{C0000000-0000-0000-0000-000000000002}"
+EndProject
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("Core/Core.sln"),
+            r#"
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Core", "Core.csproj", "{C0000000-0000-0000-0000-000000000003}"
+EndProject
+"#,
+        )
+        .unwrap();
+
+        let workspace = Workspace::load(dir.path()).unwrap();
+        let solutions: Vec<Solution> = search_solutions(&dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|path| Solution::load(path).unwrap())
+            .collect();
+
+        // when
+        let drift = drift(&workspace, &solutions);
+
+        // then
+        assert_eq!(solutions.len(), 2);
+        assert!(!drift.is_empty());
+        assert_eq!(
+            drift.unlisted(),
+            &vec![dir.path().join("Scratch/Scratch.csproj")]
+        );
+        assert_eq!(drift.missing().len(), 1);
+        assert_eq!(drift.missing()[0].name(), "Old");
+        assert_eq!(
+            drift.missing()[0].path(),
+            &dir.path().join("Old/Old.csproj")
+        );
+        assert_eq!(drift.missing()[0].solution(), &dir.path().join("App.sln"));
+    }
+}
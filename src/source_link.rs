@@ -0,0 +1,211 @@
+//! Repository metadata (`RepositoryUrl`, `RepositoryType`, `PublishRepositoryUrl`) and SourceLink
+//! package usage, to audit which projects produce source-linked packages.
+
+use std::path::PathBuf;
+
+use crate::Project;
+
+/// The prefix of the SourceLink packages (ex: `Microsoft.SourceLink.GitHub`).
+pub const SOURCE_LINK_PACKAGE_PREFIX: &str = "Microsoft.SourceLink.";
+
+/// Represents the repository properties of a project.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RepositoryMetadata {
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    url: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    repository_type: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    publish_url: Option<bool>,
+}
+
+impl RepositoryMetadata {
+    /// Creates a new `RepositoryMetadata` without any property.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the URL of the repository (`RepositoryUrl`), if any.
+    pub fn url(&self) -> Option<&String> {
+        self.url.as_ref()
+    }
+
+    /// Sets the URL of the repository.
+    pub fn set_url(&mut self, url: Option<String>) {
+        self.url = url;
+    }
+
+    /// Returns the type of the repository (`RepositoryType`, ex: "git"), if any.
+    pub fn repository_type(&self) -> Option<&String> {
+        self.repository_type.as_ref()
+    }
+
+    /// Sets the type of the repository.
+    pub fn set_repository_type(&mut self, repository_type: Option<String>) {
+        self.repository_type = repository_type;
+    }
+
+    /// Returns whether the repository URL is published in the package (`PublishRepositoryUrl`),
+    /// if the project sets it.
+    pub fn publish_url(&self) -> Option<bool> {
+        self.publish_url
+    }
+
+    /// Sets whether the repository URL is published in the package.
+    pub fn set_publish_url(&mut self, publish_url: Option<bool>) {
+        self.publish_url = publish_url;
+    }
+
+    /// Returns `true` if the project does not set any repository property.
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none() && self.repository_type.is_none() && self.publish_url.is_none()
+    }
+}
+
+/// Represents the SourceLink configuration of a project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceLinkStatus {
+    project: PathBuf,
+    repository: RepositoryMetadata,
+    packages: Vec<String>,
+}
+
+impl SourceLinkStatus {
+    /// Returns the SourceLink configuration of the project.
+    pub fn new(project: &Project) -> Self {
+        Self {
+            project: project.path().clone(),
+            repository: project.repository().clone(),
+            packages: project
+                .package_references()
+                .iter()
+                .map(|reference| reference.name())
+                .filter(|name| is_source_link_package(name))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Returns the path of the project.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the repository properties of the project.
+    pub fn repository(&self) -> &RepositoryMetadata {
+        &self.repository
+    }
+
+    /// Returns the SourceLink packages referenced by the project.
+    pub fn packages(&self) -> &Vec<String> {
+        &self.packages
+    }
+
+    /// Returns `true` if the packages of the project link to their sources: it references a
+    /// SourceLink package and publishes its repository URL.
+    ///
+    /// The .NET 8 SDK and later include SourceLink without a package reference, which cannot be
+    /// detected from the project file: check [`RepositoryMetadata::publish_url`] alone for the
+    /// projects built with them.
+    pub fn is_source_linked(&self) -> bool {
+        !self.packages.is_empty() && self.repository.publish_url == Some(true)
+    }
+}
+
+/// Returns `true` if `name` is a SourceLink package, ignoring case.
+pub fn is_source_link_package(name: &str) -> bool {
+    name.len() > SOURCE_LINK_PACKAGE_PREFIX.len()
+        && name
+            .get(..SOURCE_LINK_PACKAGE_PREFIX.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(SOURCE_LINK_PACKAGE_PREFIX))
+}
+
+/// Returns the SourceLink configuration of every project.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::{parser::parse, source_link::audit};
+/// use std::io::Cursor;
+///
+/// let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+///   <PropertyGroup>
+///     <RepositoryUrl>https://github.com/contoso/shop</RepositoryUrl>
+///     <PublishRepositoryUrl>true</PublishRepositoryUrl>
+///   </PropertyGroup>
+///   <ItemGroup>
+///     <PackageReference Include="Microsoft.SourceLink.GitHub" Version="8.0.0" />
+///   </ItemGroup>
+/// </Project>"#;
+///
+/// let project = parse(Cursor::new(content), "Shop.csproj").unwrap();
+/// let statuses = audit(&[project]);
+///
+/// assert!(statuses[0].is_source_linked());
+/// ```
+pub fn audit(projects: &[Project]) -> Vec<SourceLinkStatus> {
+    projects.iter().map(SourceLinkStatus::new).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::parser::parse;
+
+    use super::audit;
+
+    #[test]
+    pub fn audit_source_link_configuration() {
+        // given
+        let linked = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <RepositoryUrl>https://github.com/contoso/shop</RepositoryUrl>
+    <RepositoryType>git</RepositoryType>
+    <PublishRepositoryUrl>True</PublishRepositoryUrl>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="microsoft.sourcelink.github" Version="8.0.0" />
+    <PackageReference Include="Serilog" Version="3.1.1" />
+  </ItemGroup>
+</Project>"#;
+        let unpublished = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <RepositoryUrl>https://github.com/contoso/shop</RepositoryUrl>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Microsoft.SourceLink.GitHub" Version="8.0.0" />
+  </ItemGroup>
+</Project>"#;
+
+        let projects = vec![
+            parse(Cursor::new(linked), "Linked.csproj").unwrap(),
+            parse(Cursor::new(unpublished), "Unpublished.csproj").unwrap(),
+        ];
+
+        // when
+        let statuses = audit(&projects);
+
+        // then
+        assert!(statuses[0].is_source_linked());
+        assert_eq!(statuses[0].packages(), &vec!["microsoft.sourcelink.github"]);
+        assert_eq!(
+            statuses[0].repository().repository_type(),
+            Some(&"git".to_string())
+        );
+
+        assert!(!statuses[1].is_source_linked());
+        assert_eq!(statuses[1].repository().publish_url(), None);
+    }
+}
@@ -0,0 +1,256 @@
+//! The build strictness properties of a project (`TreatWarningsAsErrors`, `NoWarn`,
+//! `AnalysisLevel` and `Nullable`), and a report of the warning suppressions and analysis levels
+//! of a workspace, to track the code quality configuration of the projects centrally.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::workspace::Workspace;
+
+/// Represents the build strictness properties of a project, as set in the project file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrictnessMetadata {
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    treat_warnings_as_errors: Option<bool>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    no_warn: Vec<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    analysis_level: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    nullable: Option<String>,
+}
+
+impl StrictnessMetadata {
+    /// Creates a new `StrictnessMetadata` without any property.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the warnings fail the build (`TreatWarningsAsErrors`), if the project
+    /// sets it.
+    pub fn treat_warnings_as_errors(&self) -> Option<bool> {
+        self.treat_warnings_as_errors
+    }
+
+    /// Sets whether the warnings fail the build.
+    pub fn set_treat_warnings_as_errors(&mut self, treat_warnings_as_errors: Option<bool>) {
+        self.treat_warnings_as_errors = treat_warnings_as_errors;
+    }
+
+    /// Returns the codes of the suppressed warnings (`NoWarn`, ex: `CS1591`), in the order they
+    /// are written and without duplicates. Codes that reference other properties are skipped.
+    pub fn no_warn(&self) -> &Vec<String> {
+        &self.no_warn
+    }
+
+    /// Sets the codes of the suppressed warnings.
+    pub fn set_no_warn(&mut self, no_warn: Vec<String>) {
+        self.no_warn = no_warn;
+    }
+
+    /// Returns the version of the code analysis rules (`AnalysisLevel`, ex: `latest-recommended`),
+    /// if the project sets it.
+    pub fn analysis_level(&self) -> Option<&String> {
+        self.analysis_level.as_ref()
+    }
+
+    /// Sets the version of the code analysis rules.
+    pub fn set_analysis_level(&mut self, analysis_level: Option<String>) {
+        self.analysis_level = analysis_level;
+    }
+
+    /// Returns the nullable context of the project (`Nullable`, ex: `enable` or `warnings`), if
+    /// the project sets it.
+    pub fn nullable(&self) -> Option<&String> {
+        self.nullable.as_ref()
+    }
+
+    /// Sets the nullable context of the project.
+    pub fn set_nullable(&mut self, nullable: Option<String>) {
+        self.nullable = nullable;
+    }
+
+    /// Returns `true` if the project does not set any strictness property.
+    pub fn is_empty(&self) -> bool {
+        self.treat_warnings_as_errors.is_none()
+            && self.no_warn.is_empty()
+            && self.analysis_level.is_none()
+            && self.nullable.is_none()
+    }
+}
+
+/// Represents the warning suppressions and analysis levels of the projects of a workspace.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{strictness::StrictnessReport, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+/// let report = StrictnessReport::from_workspace(&workspace);
+///
+/// for (code, projects) in report.suppressed_warnings() {
+///     println!("{code} is suppressed by {} projects", projects.len());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrictnessReport {
+    projects: usize,
+    treating_warnings_as_errors: Vec<PathBuf>,
+    suppressed_warnings: BTreeMap<String, Vec<PathBuf>>,
+    analysis_levels: BTreeMap<String, Vec<PathBuf>>,
+}
+
+impl StrictnessReport {
+    /// Creates the report of the projects of a workspace.
+    ///
+    /// Only the project files are read: properties set by imported files, such as
+    /// `Directory.Build.props`, are not seen.
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let mut report = Self {
+            projects: workspace.projects().len(),
+            ..Self::default()
+        };
+
+        for project in workspace.projects() {
+            let strictness = project.strictness();
+            let path = project.path();
+
+            if strictness.treat_warnings_as_errors == Some(true) {
+                report.treating_warnings_as_errors.push(path.clone());
+            }
+
+            for code in &strictness.no_warn {
+                report
+                    .suppressed_warnings
+                    .entry(code.to_uppercase())
+                    .or_default()
+                    .push(path.clone());
+            }
+
+            if let Some(level) = &strictness.analysis_level {
+                report
+                    .analysis_levels
+                    .entry(level.to_lowercase())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+
+        report
+    }
+
+    /// Returns the number of projects of the workspace.
+    pub fn projects(&self) -> usize {
+        self.projects
+    }
+
+    /// Returns the paths of the projects setting `TreatWarningsAsErrors` to `true`.
+    pub fn treating_warnings_as_errors(&self) -> &Vec<PathBuf> {
+        &self.treating_warnings_as_errors
+    }
+
+    /// Returns the paths of the projects suppressing each warning, by uppercase warning code.
+    pub fn suppressed_warnings(&self) -> &BTreeMap<String, Vec<PathBuf>> {
+        &self.suppressed_warnings
+    }
+
+    /// Returns the paths of the projects setting each analysis level, by lowercase level. The
+    /// projects that do not set it use the default level of their SDK.
+    pub fn analysis_levels(&self) -> &BTreeMap<String, Vec<PathBuf>> {
+        &self.analysis_levels
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::workspace::WorkspaceBuilder;
+
+    use super::StrictnessReport;
+
+    #[test]
+    pub fn report_strictness_of_workspace() {
+        // given
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project(
+            "Api/Api.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <PropertyGroup>
+    <TreatWarningsAsErrors>true</TreatWarningsAsErrors>
+    <NoWarn>$(NoWarn);CS1591</NoWarn>
+    <AnalysisLevel>latest-Recommended</AnalysisLevel>
+  </PropertyGroup>
+  <PropertyGroup Condition="'$(Configuration)' == 'Debug'">
+    <NoWarn>$(NoWarn),cs0618;$(LegacyWarnings)</NoWarn>
+  </PropertyGroup>
+</Project>"#,
+        );
+        builder.add_project(
+            "Domain/Domain.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TreatWarningsAsErrors>false</TreatWarningsAsErrors>
+    <NoWarn>CS1591;NU1701</NoWarn>
+    <NoWarn>CS1591</NoWarn>
+  </PropertyGroup>
+</Project>"#,
+        );
+        builder.add_project(
+            "Worker/Worker.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk.Worker">
+  <PropertyGroup>
+    <TreatWarningsAsErrors>$(ContinuousIntegrationBuild)</TreatWarningsAsErrors>
+  </PropertyGroup>
+</Project>"#,
+        );
+        let workspace = builder.build().unwrap();
+
+        // when
+        let report = StrictnessReport::from_workspace(&workspace);
+
+        // then
+        let api = PathBuf::from("/repo/Api/Api.csproj");
+        let domain = PathBuf::from("/repo/Domain/Domain.csproj");
+
+        assert_eq!(
+            workspace.projects()[0].strictness().no_warn(),
+            &vec!["CS1591".to_string(), "cs0618".to_string()]
+        );
+        assert_eq!(
+            workspace.projects()[2]
+                .strictness()
+                .treat_warnings_as_errors(),
+            None
+        );
+        assert_eq!(report.projects(), 3);
+        assert_eq!(report.treating_warnings_as_errors(), &vec![api.clone()]);
+        assert_eq!(
+            report.suppressed_warnings().iter().collect::<Vec<_>>(),
+            vec![
+                (&"CS0618".to_string(), &vec![api.clone()]),
+                (&"CS1591".to_string(), &vec![api.clone(), domain]),
+            ]
+        );
+        assert_eq!(
+            report.analysis_levels().iter().collect::<Vec<_>>(),
+            vec![(&"latest-recommended".to_string(), &vec![api])]
+        );
+    }
+}
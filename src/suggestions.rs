@@ -0,0 +1,376 @@
+//! Suggestions of package updates, combining outdated, deprecated and vulnerable package data.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    central_packages::CentralPackageVersions,
+    parser::ParseError,
+    version::{NuGetVersion, VersionDelta},
+};
+
+/// Represents something found about a package referenced by a project, such as a newer version
+/// being available in the feed or a known vulnerability.
+///
+/// Findings are usually created from the results of the `nuget` and `osv` modules with their
+/// `to_finding` methods.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackageFinding {
+    project: PathBuf,
+    name: String,
+    version: NuGetVersion,
+    kind: FindingKind,
+}
+
+/// Represents the kind of a `PackageFinding`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FindingKind {
+    /// A newer version of the package is available.
+    Outdated { latest: NuGetVersion },
+    /// The package version is deprecated, optionally with an alternative package.
+    Deprecated { alternative: Option<String> },
+    /// The package version has known vulnerabilities, optionally fixed in a later version.
+    Vulnerable {
+        ids: Vec<String>,
+        fixed_in: Option<NuGetVersion>,
+    },
+}
+
+/// Represents the file that must be changed to apply an `UpdateSuggestion`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdateTarget {
+    /// The version is declared in the project file.
+    Project(PathBuf),
+    /// The version is declared centrally, in a `Directory.Packages.props` file.
+    CentralPackageVersions(PathBuf),
+}
+
+/// Represents why an update is suggested.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdateReason {
+    Outdated(VersionDelta),
+    Deprecated,
+    Vulnerable(Vec<String>),
+}
+
+/// Represents a concrete version bump of a package.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateSuggestion {
+    target: UpdateTarget,
+    package: String,
+    current: NuGetVersion,
+    suggested: NuGetVersion,
+    reasons: Vec<UpdateReason>,
+    projects: Vec<PathBuf>,
+}
+
+impl PackageFinding {
+    /// Creates a new `PackageFinding` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The path of the project that references the package.
+    /// * `name` - The name of the package.
+    /// * `version` - The version referenced by the project.
+    /// * `kind` - What was found about the package.
+    pub fn new(project: PathBuf, name: String, version: NuGetVersion, kind: FindingKind) -> Self {
+        Self {
+            project,
+            name,
+            version,
+            kind,
+        }
+    }
+
+    /// Returns the path of the project that references the package.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the name of the package.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the version referenced by the project.
+    pub fn version(&self) -> &NuGetVersion {
+        &self.version
+    }
+
+    /// Returns what was found about the package.
+    pub fn kind(&self) -> &FindingKind {
+        &self.kind
+    }
+}
+
+impl UpdateSuggestion {
+    /// Returns the file that must be changed to apply the suggestion.
+    pub fn target(&self) -> &UpdateTarget {
+        &self.target
+    }
+
+    /// Returns the name of the package.
+    pub fn package(&self) -> &String {
+        &self.package
+    }
+
+    /// Returns the version currently in use.
+    pub fn current(&self) -> &NuGetVersion {
+        &self.current
+    }
+
+    /// Returns the suggested version.
+    pub fn suggested(&self) -> &NuGetVersion {
+        &self.suggested
+    }
+
+    /// Returns why the update is suggested.
+    pub fn reasons(&self) -> &Vec<UpdateReason> {
+        &self.reasons
+    }
+
+    /// Returns the projects affected by the update.
+    pub fn projects(&self) -> &Vec<PathBuf> {
+        &self.projects
+    }
+}
+
+/// Combines the findings into concrete version bumps.
+///
+/// For each package, the suggested version is the highest among the latest version available and
+/// the version that fixes the known vulnerabilities. Findings without a newer version (ex: a
+/// deprecated package that has no newer release) do not produce suggestions, but they are listed
+/// as reasons when another finding produces one.
+///
+/// When a project uses Central Package Management and its `Directory.Packages.props` declares the
+/// package, the suggestion targets that file and lists every affected project once.
+///
+/// # Errors
+///
+/// This function will return an error if a `Directory.Packages.props` file could not be read or
+/// parsed.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::suggestions::{suggest_updates, FindingKind, PackageFinding};
+/// use std::path::PathBuf;
+///
+/// let finding = PackageFinding::new(
+///     PathBuf::from("path/to/MyProject.csproj"),
+///     "Serilog".to_string(),
+///     "2.12.0".parse().unwrap(),
+///     FindingKind::Outdated { latest: "3.1.1".parse().unwrap() },
+/// );
+///
+/// let suggestions = suggest_updates(&[finding])?;
+/// assert_eq!(suggestions[0].suggested().to_string(), "3.1.1");
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn suggest_updates(findings: &[PackageFinding]) -> Result<Vec<UpdateSuggestion>, ParseError> {
+    let mut central_files: HashMap<PathBuf, CentralPackageVersions> = HashMap::new();
+    let mut suggestions: Vec<UpdateSuggestion> = vec![];
+
+    for finding in findings {
+        let key = finding.name.to_lowercase();
+
+        let related: Vec<&PackageFinding> = findings
+            .iter()
+            .filter(|other| other.project == finding.project && other.name.to_lowercase() == key)
+            .collect();
+
+        if !std::ptr::eq(related[0], finding) {
+            continue;
+        }
+
+        let current = finding.version.clone();
+
+        let suggested = related
+            .iter()
+            .filter_map(|finding| match &finding.kind {
+                FindingKind::Outdated { latest } => Some(latest),
+                FindingKind::Vulnerable { fixed_in, .. } => fixed_in.as_ref(),
+                FindingKind::Deprecated { .. } => None,
+            })
+            .filter(|version| **version > current)
+            .max()
+            .cloned();
+
+        let Some(suggested) = suggested else {
+            continue;
+        };
+
+        let reasons: Vec<UpdateReason> = related
+            .iter()
+            .map(|finding| match &finding.kind {
+                FindingKind::Outdated { latest } => {
+                    UpdateReason::Outdated(current.delta(latest).unwrap_or(VersionDelta::Patch))
+                }
+                FindingKind::Deprecated { .. } => UpdateReason::Deprecated,
+                FindingKind::Vulnerable { ids, .. } => UpdateReason::Vulnerable(ids.clone()),
+            })
+            .collect();
+
+        let target = update_target(&mut central_files, &finding.project, &finding.name)?;
+
+        match suggestions
+            .iter_mut()
+            .find(|s| s.target == target && s.package.to_lowercase() == key)
+        {
+            Some(existing) => {
+                if suggested > existing.suggested {
+                    existing.suggested = suggested;
+                }
+
+                for reason in reasons {
+                    if !existing.reasons.contains(&reason) {
+                        existing.reasons.push(reason);
+                    }
+                }
+
+                existing.projects.push(finding.project.clone());
+            }
+            None => suggestions.push(UpdateSuggestion {
+                target,
+                package: finding.name.clone(),
+                current,
+                suggested,
+                reasons,
+                projects: vec![finding.project.clone()],
+            }),
+        }
+    }
+
+    Ok(suggestions)
+}
+
+fn update_target(
+    central_files: &mut HashMap<PathBuf, CentralPackageVersions>,
+    project: &Path,
+    package: &str,
+) -> Result<UpdateTarget, ParseError> {
+    let directory = project.parent().unwrap_or(Path::new(""));
+
+    if let Some(path) = CentralPackageVersions::find(directory) {
+        if !central_files.contains_key(&path) {
+            let central = CentralPackageVersions::load(&path)?;
+            central_files.insert(path.clone(), central);
+        }
+
+        if central_files[&path].version_of(package).is_some() {
+            return Ok(UpdateTarget::CentralPackageVersions(path));
+        }
+    }
+
+    Ok(UpdateTarget::Project(project.to_owned()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn version(value: &str) -> NuGetVersion {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    pub fn combine_findings_of_the_same_package() {
+        // given
+        let project = PathBuf::from("/nonexistent/Api/Api.csproj");
+
+        let findings = vec![
+            PackageFinding::new(
+                project.clone(),
+                "Newtonsoft.Json".to_string(),
+                version("12.0.1"),
+                FindingKind::Vulnerable {
+                    ids: vec!["CVE-2024-21907".to_string()],
+                    fixed_in: Some(version("13.0.1")),
+                },
+            ),
+            PackageFinding::new(
+                project.clone(),
+                "Newtonsoft.Json".to_string(),
+                version("12.0.1"),
+                FindingKind::Outdated {
+                    latest: version("13.0.3"),
+                },
+            ),
+            PackageFinding::new(
+                project.clone(),
+                "Old.Package".to_string(),
+                version("1.0.0"),
+                FindingKind::Deprecated { alternative: None },
+            ),
+        ];
+
+        // when
+        let suggestions = suggest_updates(&findings).unwrap();
+
+        // then
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].target(), &UpdateTarget::Project(project));
+        assert_eq!(suggestions[0].suggested(), &version("13.0.3"));
+        assert_eq!(
+            suggestions[0].reasons(),
+            &vec![
+                UpdateReason::Vulnerable(vec!["CVE-2024-21907".to_string()]),
+                UpdateReason::Outdated(VersionDelta::Major),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn target_central_package_versions() {
+        // given
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+
+        fs::create_dir_all(root.join("src/Api")).unwrap();
+        fs::create_dir_all(root.join("src/Core")).unwrap();
+        fs::write(
+            root.join("Directory.Packages.props"),
+            r#"<Project><ItemGroup><PackageVersion Include="Serilog" Version="2.12.0" /></ItemGroup></Project>"#,
+        )
+        .unwrap();
+
+        let findings: Vec<PackageFinding> = ["src/Api/Api.csproj", "src/Core/Core.csproj"]
+            .iter()
+            .map(|project| {
+                PackageFinding::new(
+                    root.join(project),
+                    "Serilog".to_string(),
+                    version("2.12.0"),
+                    FindingKind::Outdated {
+                        latest: version("3.1.1"),
+                    },
+                )
+            })
+            .collect();
+
+        // when
+        let suggestions = suggest_updates(&findings).unwrap();
+
+        // then
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(
+            suggestions[0].target(),
+            &UpdateTarget::CentralPackageVersions(root.join("Directory.Packages.props"))
+        );
+        assert_eq!(suggestions[0].projects().len(), 2);
+
+        dir.close().unwrap();
+    }
+}
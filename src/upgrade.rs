@@ -0,0 +1,227 @@
+//! Readiness of the projects of a workspace for an upgrade to a target framework (ex: `net9.0`):
+//! a project can be moved to the target framework once every project it references transitively
+//! can be referenced from it.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use crate::{framework::FrameworkRules, workspace::Workspace, Project};
+
+/// Represents the readiness of a project for an upgrade to a target framework.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpgradeReadiness {
+    project: PathBuf,
+    target_frameworks: Vec<String>,
+    compatible: bool,
+    blocking: Vec<PathBuf>,
+}
+
+impl UpgradeReadiness {
+    /// Returns the path of the project.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the target frameworks of the project (see [`Project::target_frameworks`]).
+    pub fn target_frameworks(&self) -> &Vec<String> {
+        &self.target_frameworks
+    }
+
+    /// Returns `true` if the project is already on a target framework that a project targeting
+    /// the target framework of the upgrade can reference.
+    pub fn is_compatible(&self) -> bool {
+        self.compatible
+    }
+
+    /// Returns the paths of the projects to upgrade first: the project itself and the projects it
+    /// references transitively that are not compatible, in dependency order (a project comes
+    /// after the projects it references).
+    pub fn blocking(&self) -> &Vec<PathBuf> {
+        &self.blocking
+    }
+
+    /// Returns `true` if the project and the projects it references transitively are compatible.
+    pub fn is_ready(&self) -> bool {
+        self.blocking.is_empty()
+    }
+}
+
+/// Returns the readiness of each project of a workspace for an upgrade to `target_framework`, in
+/// the order of the projects of the workspace.
+///
+/// A project is compatible when one of its target frameworks can be referenced from
+/// `target_framework`, following `rules` (ex: `net8.0` and `netstandard2.0` for `net9.0`, but not
+/// `net48`). Projects whose target framework is unknown are not compatible, since they cannot be
+/// checked.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{framework::FrameworkRules, upgrade, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// for readiness in upgrade::upgrade_readiness(&workspace, "net9.0", &FrameworkRules::new()) {
+///     if !readiness.is_ready() {
+///         println!(
+///             "{} is blocked by {:?}",
+///             readiness.project().display(),
+///             readiness.blocking()
+///         );
+///     }
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn upgrade_readiness(
+    workspace: &Workspace,
+    target_framework: &str,
+    rules: &FrameworkRules,
+) -> Vec<UpgradeReadiness> {
+    let compatible: HashMap<&PathBuf, bool> = workspace
+        .projects()
+        .iter()
+        .map(|project| {
+            let compatible = project
+                .target_frameworks()
+                .iter()
+                .any(|framework| rules.is_compatible(target_framework, framework));
+
+            (project.path(), compatible)
+        })
+        .collect();
+
+    workspace
+        .projects()
+        .iter()
+        .map(|project| {
+            let mut visited = HashSet::new();
+            let mut blocking = vec![];
+            visit(workspace, project, &compatible, &mut visited, &mut blocking);
+
+            UpgradeReadiness {
+                project: project.path().clone(),
+                target_frameworks: project.target_frameworks().into_iter().cloned().collect(),
+                compatible: compatible[project.path()],
+                blocking,
+            }
+        })
+        .collect()
+}
+
+/// Adds the incompatible projects among a project and the projects it references transitively,
+/// after the projects they reference.
+fn visit<'a>(
+    workspace: &'a Workspace,
+    project: &'a Project,
+    compatible: &HashMap<&PathBuf, bool>,
+    visited: &mut HashSet<&'a PathBuf>,
+    blocking: &mut Vec<PathBuf>,
+) {
+    if !visited.insert(project.path()) {
+        return;
+    }
+
+    for reference in project.project_references() {
+        if let Some(referenced) = workspace.resolve_reference(project, reference) {
+            visit(workspace, referenced, compatible, visited, blocking);
+        }
+    }
+
+    if !compatible[project.path()] {
+        blocking.push(project.path().clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{framework::FrameworkRules, workspace::WorkspaceBuilder};
+
+    use super::upgrade_readiness;
+
+    fn project(target_framework: &str, references: &[&str]) -> String {
+        let references: String = references
+            .iter()
+            .map(|reference| format!(r#"<ProjectReference Include="{reference}" />"#))
+            .collect();
+
+        format!(
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>{target_framework}</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>{references}</ItemGroup>
+</Project>"#
+        )
+    }
+
+    #[test]
+    pub fn report_upgrade_readiness() {
+        // given
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project(
+            "Api/Api.csproj",
+            &project(
+                "net8.0",
+                &["../Domain/Domain.csproj", "../Legacy/Legacy.csproj"],
+            ),
+        );
+        builder.add_project(
+            "Domain/Domain.csproj",
+            &project("netstandard2.0", &["../Core/Core.csproj"]),
+        );
+        builder.add_project("Core/Core.csproj", &project("net8.0", &[]));
+        builder.add_project(
+            "Legacy/Legacy.csproj",
+            &project("net48", &["../Interop/Interop.csproj"]),
+        );
+        builder.add_project("Interop/Interop.csproj", &project("net472", &[]));
+        let workspace = builder.build().unwrap();
+
+        // when
+        let readiness = upgrade_readiness(&workspace, "net9.0", &FrameworkRules::new());
+
+        // then
+        let summary: Vec<(String, bool, Vec<String>)> = readiness
+            .iter()
+            .map(|readiness| {
+                (
+                    workspace.relative_path(readiness.project()),
+                    readiness.is_compatible(),
+                    readiness
+                        .blocking()
+                        .iter()
+                        .map(|path| workspace.relative_path(path))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let interop = "Interop/Interop.csproj".to_string();
+        let legacy = "Legacy/Legacy.csproj".to_string();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Api/Api.csproj".to_string(),
+                    true,
+                    vec![interop.clone(), legacy.clone()]
+                ),
+                ("Core/Core.csproj".to_string(), true, vec![]),
+                ("Domain/Domain.csproj".to_string(), true, vec![]),
+                (interop.clone(), false, vec![interop.clone()]),
+                (legacy.clone(), false, vec![interop.clone(), legacy.clone()]),
+            ]
+        );
+        assert!(!readiness[0].is_ready());
+        assert!(readiness[2].is_ready());
+        assert_eq!(
+            readiness[3].target_frameworks(),
+            &vec!["net472".to_string()]
+        );
+    }
+}
@@ -0,0 +1,766 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    framework::FrameworkRules, workspace::normalize_path, PackageReference, Project,
+    ProjectReference,
+};
+
+/// Represents how serious a validation violation is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+/// Represents a problem found by a validation rule in a .NET project.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Violation {
+    rule_id: String,
+    severity: Severity,
+    message: String,
+    path: PathBuf,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    line: Option<u32>,
+}
+
+impl Violation {
+    /// Creates a new `Violation` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule_id` - The id of the rule that produced the violation.
+    /// * `severity` - The severity of the violation.
+    /// * `message` - A human readable description of the problem.
+    /// * `path` - The path of the project file where the problem was found.
+    pub fn new(rule_id: String, severity: Severity, message: String, path: PathBuf) -> Self {
+        Self {
+            rule_id,
+            severity,
+            message,
+            path,
+            line: None,
+        }
+    }
+
+    /// Returns the id of the rule that produced the violation.
+    pub fn rule_id(&self) -> &String {
+        &self.rule_id
+    }
+
+    /// Returns the severity of the violation.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the message describing the violation.
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+
+    /// Returns the path of the project file where the violation was found.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns the line of the project file where the violation was found, starting at 1, if
+    /// the rule knows it.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Sets the line of the project file where the violation was found, starting at 1.
+    pub fn set_line(&mut self, line: Option<u32>) {
+        self.line = line;
+    }
+}
+
+/// A check that can be run against a set of .NET projects.
+pub trait Rule {
+    /// Returns the unique id of the rule (ex: "DL0001").
+    fn id(&self) -> &'static str;
+
+    /// Returns a short description of what the rule checks.
+    fn description(&self) -> &'static str;
+
+    /// Checks the projects and returns every violation found.
+    fn check(&self, projects: &[Project]) -> Vec<Violation>;
+}
+
+/// Runs every rule against the projects and returns all the violations found.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::validation::{default_rules, validate};
+///
+/// let violations = validate(&[], &default_rules());
+/// assert!(violations.is_empty());
+/// ```
+pub fn validate(projects: &[Project], rules: &[Box<dyn Rule>]) -> Vec<Violation> {
+    rules.iter().flat_map(|rule| rule.check(projects)).collect()
+}
+
+/// Returns the built-in validation rules.
+pub fn default_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(MissingTargetFramework),
+        Box::new(DuplicatePackageReference),
+        Box::new(InconsistentPackageVersion),
+        Box::new(IncompatibleProjectReference::default()),
+        Box::new(DuplicateAssemblyName),
+    ]
+}
+
+/// Returns the project `reference` points to, matched by path, or by name when no project has
+/// that path.
+pub(crate) fn referenced_project<'a>(
+    projects: &'a [Project],
+    project: &Project,
+    reference: &ProjectReference,
+) -> Option<&'a Project> {
+    let directory = project.path().parent().unwrap_or(Path::new(""));
+    let path = normalize_path(&directory.join(reference.path()));
+
+    projects
+        .iter()
+        .find(|p| normalize_path(p.path()) == path)
+        .or_else(|| {
+            projects
+                .iter()
+                .find(|p| p.name().eq_ignore_ascii_case(reference.name()))
+        })
+}
+
+/// Flags projects that do not declare a `TargetFramework`, except the deployment projects (see
+/// [`crate::ProjectLanguage::is_deployment`]).
+pub struct MissingTargetFramework;
+
+impl Rule for MissingTargetFramework {
+    fn id(&self) -> &'static str {
+        "DL0001"
+    }
+
+    fn description(&self) -> &'static str {
+        "Project does not declare a target framework"
+    }
+
+    fn check(&self, projects: &[Project]) -> Vec<Violation> {
+        projects
+            .iter()
+            .filter(|project| {
+                project.target_framework().is_none() && !project.language().is_deployment()
+            })
+            .map(|project| {
+                Violation::new(
+                    self.id().to_string(),
+                    Severity::Warning,
+                    format!("project '{}' does not declare a target framework", project.name()),
+                    project.path().clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Flags packages that are referenced more than once by the same project, comparing the package
+/// ids ignoring case like NuGet.
+pub struct DuplicatePackageReference;
+
+impl Rule for DuplicatePackageReference {
+    fn id(&self) -> &'static str {
+        "DL0002"
+    }
+
+    fn description(&self) -> &'static str {
+        "Package is referenced more than once by the same project"
+    }
+
+    fn check(&self, projects: &[Project]) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for project in projects {
+            let mut seen: Vec<&PackageReference> = vec![];
+
+            for package_reference in project.package_references() {
+                let name = package_reference.name();
+
+                // references conditioned on different target frameworks never apply together
+                let duplicate = seen.iter().any(|other| {
+                    other.name().eq_ignore_ascii_case(name)
+                        && match (
+                            other.target_framework(),
+                            package_reference.target_framework(),
+                        ) {
+                            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                            _ => true,
+                        }
+                });
+
+                if duplicate {
+                    violations.push(Violation::new(
+                        self.id().to_string(),
+                        Severity::Error,
+                        format!(
+                            "package '{}' is referenced more than once by project '{}'",
+                            name,
+                            project.name()
+                        ),
+                        project.path().clone(),
+                    ));
+                } else {
+                    seen.push(package_reference);
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+/// Flags packages that are referenced with different versions across projects, comparing the
+/// package ids ignoring case like NuGet.
+///
+/// References without a version (ex: managed by `Directory.Packages.props` without a central
+/// version) are not compared.
+pub struct InconsistentPackageVersion;
+
+impl Rule for InconsistentPackageVersion {
+    fn id(&self) -> &'static str {
+        "DL0003"
+    }
+
+    fn description(&self) -> &'static str {
+        "Package is referenced with different versions across projects"
+    }
+
+    fn check(&self, projects: &[Project]) -> Vec<Violation> {
+        let mut versions: HashMap<String, Vec<&String>> = HashMap::new();
+
+        for package_reference in projects.iter().flat_map(versioned_references) {
+            let entry = versions
+                .entry(package_reference.name().to_lowercase())
+                .or_default();
+
+            if !entry.contains(&package_reference.version()) {
+                entry.push(package_reference.version());
+            }
+        }
+
+        let mut violations = vec![];
+
+        for project in projects {
+            for package_reference in versioned_references(project) {
+                let package_versions = &versions[&package_reference.name().to_lowercase()];
+
+                if package_versions.len() < 2 {
+                    continue;
+                }
+
+                let others: Vec<&str> = package_versions
+                    .iter()
+                    .filter(|version| **version != package_reference.version())
+                    .map(|version| version.as_str())
+                    .collect();
+
+                violations.push(Violation::new(
+                    self.id().to_string(),
+                    Severity::Warning,
+                    format!(
+                        "package '{}' is referenced with version {} but other projects use {}",
+                        package_reference.name(),
+                        package_reference.version(),
+                        others.join(", ")
+                    ),
+                    project.path().clone(),
+                ));
+            }
+        }
+
+        violations
+    }
+}
+
+/// Returns the package references of `project` that declare a version.
+fn versioned_references(project: &Project) -> impl Iterator<Item = &PackageReference> {
+    project
+        .package_references()
+        .iter()
+        .filter(|reference| !reference.version().is_empty())
+}
+
+/// Flags project references to projects without any target framework compatible with a target
+/// framework of the referencing project (ex: a `net48` project referencing a `net8.0` one),
+/// which NuGet reports as `NU1201`.
+///
+/// Multi-targeted projects are checked for each of their frameworks, with the references that
+/// apply to it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IncompatibleProjectReference {
+    frameworks: FrameworkRules,
+}
+
+impl IncompatibleProjectReference {
+    /// Creates a new `IncompatibleProjectReference` checking the target frameworks with
+    /// `frameworks`, which may extend the built-in .NET rules.
+    pub fn new(frameworks: FrameworkRules) -> Self {
+        Self { frameworks }
+    }
+
+    /// Returns the knowledge base used to check the target frameworks.
+    pub fn frameworks(&self) -> &FrameworkRules {
+        &self.frameworks
+    }
+}
+
+impl Rule for IncompatibleProjectReference {
+    fn id(&self) -> &'static str {
+        "DL0004"
+    }
+
+    fn description(&self) -> &'static str {
+        "Project references a project targeting an incompatible framework"
+    }
+
+    fn check(&self, projects: &[Project]) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for project in projects {
+            for framework in project.target_frameworks() {
+                for reference in project.project_references_for(framework) {
+                    let Some(target) = referenced_project(projects, project, reference) else {
+                        continue;
+                    };
+
+                    let target_frameworks = target.target_frameworks();
+
+                    if target_frameworks.is_empty()
+                        || target_frameworks
+                            .iter()
+                            .any(|target| self.frameworks.is_compatible(framework, target))
+                    {
+                        continue;
+                    }
+
+                    let target_frameworks: Vec<&str> =
+                        target_frameworks.iter().map(|t| t.as_str()).collect();
+
+                    violations.push(Violation::new(
+                        self.id().to_string(),
+                        Severity::Error,
+                        format!(
+                            "project '{}' targeting {} references '{}' targeting {}",
+                            project.name(),
+                            framework,
+                            reference.name(),
+                            target_frameworks.join(";")
+                        ),
+                        project.path().clone(),
+                    ));
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+/// Flags projects targeting a framework marked as out of support (see
+/// [`FrameworkRules::add_unsupported`]).
+///
+/// Multi-targeted projects are flagged once for each unsupported framework (see
+/// [`Project::target_frameworks`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsupportedTargetFramework {
+    frameworks: FrameworkRules,
+}
+
+impl UnsupportedTargetFramework {
+    /// Creates a new `UnsupportedTargetFramework` flagging the frameworks marked as out of
+    /// support in `frameworks`.
+    pub fn new(frameworks: FrameworkRules) -> Self {
+        Self { frameworks }
+    }
+
+    /// Returns the knowledge base used to check the target frameworks.
+    pub fn frameworks(&self) -> &FrameworkRules {
+        &self.frameworks
+    }
+}
+
+impl Rule for UnsupportedTargetFramework {
+    fn id(&self) -> &'static str {
+        "DL0005"
+    }
+
+    fn description(&self) -> &'static str {
+        "Project targets a framework that is out of support"
+    }
+
+    fn check(&self, projects: &[Project]) -> Vec<Violation> {
+        projects
+            .iter()
+            .flat_map(|project| {
+                project
+                    .target_frameworks()
+                    .into_iter()
+                    .filter(|framework| self.frameworks.is_unsupported(framework))
+                    .map(move |framework| {
+                        Violation::new(
+                            self.id().to_string(),
+                            Severity::Warning,
+                            format!(
+                                "project '{}' targets {}, which is out of support",
+                                project.name(),
+                                framework
+                            ),
+                            project.path().clone(),
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Flags projects building an assembly with the same name as another project, either set with
+/// `AssemblyName` or from the same project file name (see [`Project::assembly_name`]), which
+/// overwrite each other in output folders and packages. Names are compared ignoring case and the
+/// deployment projects are skipped, since they do not build assemblies.
+pub struct DuplicateAssemblyName;
+
+impl Rule for DuplicateAssemblyName {
+    fn id(&self) -> &'static str {
+        "DL0006"
+    }
+
+    fn description(&self) -> &'static str {
+        "Project builds an assembly with the same name as another project"
+    }
+
+    fn check(&self, projects: &[Project]) -> Vec<Violation> {
+        let projects: Vec<&Project> = projects
+            .iter()
+            .filter(|project| !project.language().is_deployment())
+            .collect();
+
+        let mut assemblies: HashMap<String, Vec<&Project>> = HashMap::new();
+
+        for project in &projects {
+            assemblies
+                .entry(project.assembly_name().to_lowercase())
+                .or_default()
+                .push(project);
+        }
+
+        projects
+            .iter()
+            .filter_map(|project| {
+                let others: Vec<String> = assemblies[&project.assembly_name().to_lowercase()]
+                    .iter()
+                    .filter(|other| other.path() != project.path())
+                    .map(|other| format!("'{}'", other.path().display()))
+                    .collect();
+
+                (!others.is_empty()).then(|| {
+                    Violation::new(
+                        self.id().to_string(),
+                        Severity::Error,
+                        format!(
+                            "project '{}' builds the assembly '{}', also built by {}",
+                            project.name(),
+                            project.assembly_name(),
+                            others.join(", ")
+                        ),
+                        project.path().clone(),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ProjectLanguage;
+
+    use super::*;
+
+    fn project(name: &str, target_framework: Option<&str>, packages: &[(&str, &str)]) -> Project {
+        let mut project = Project::empty(
+            name.to_string(),
+            ProjectLanguage::CSharp,
+            PathBuf::from(format!("src/{name}/{name}.csproj")),
+        );
+        project.target_framework = target_framework.map(|t| t.to_string());
+
+        for (n, v) in packages {
+            project.add_package_reference(PackageReference::new(n.to_string(), v.to_string()));
+        }
+
+        project
+    }
+
+    #[test]
+    pub fn missing_target_framework() {
+        // given
+        let projects = vec![project("Api", None, &[]), project("Core", Some("net8.0"), &[])];
+
+        // when
+        let violations = MissingTargetFramework.check(&projects);
+
+        // then
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_id(), "DL0001");
+        assert_eq!(violations[0].path(), &PathBuf::from("src/Api/Api.csproj"));
+    }
+
+    #[test]
+    pub fn duplicate_package_reference() {
+        // given
+        let projects = vec![project(
+            "Api",
+            Some("net8.0"),
+            &[("Serilog", "3.0.0"), ("Serilog", "3.1.0")],
+        )];
+
+        // when
+        let violations = DuplicatePackageReference.check(&projects);
+
+        // then
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity(), Severity::Error);
+    }
+
+    #[test]
+    pub fn package_referenced_once_per_target_framework() {
+        // given
+        let mut projects = vec![project(
+            "Api",
+            Some("net8.0"),
+            &[("Polly", "7.2.4"), ("Polly", "8.4.1")],
+        )];
+        projects[0].package_references[0].set_target_framework(Some("net48".to_string()));
+        projects[0].package_references[1].set_target_framework(Some("net8.0".to_string()));
+
+        // when
+        let violations = DuplicatePackageReference.check(&projects);
+
+        // then
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    pub fn duplicate_package_reference_ignoring_case() {
+        // given
+        let projects = vec![project(
+            "Api",
+            Some("net8.0"),
+            &[("Serilog", "3.0.0"), ("serilog", "3.0.0")],
+        )];
+
+        // when
+        let violations = DuplicatePackageReference.check(&projects);
+
+        // then
+        assert_eq!(violations.len(), 1);
+    }
+
+    #[test]
+    pub fn inconsistent_package_version() {
+        // given
+        let projects = vec![
+            project("Api", Some("net8.0"), &[("Serilog", "3.0.0")]),
+            project("Core", Some("net8.0"), &[("Serilog", "3.1.0")]),
+            project("Infra", Some("net8.0"), &[("Dapper", "2.1.0")]),
+        ];
+
+        // when
+        let violations = InconsistentPackageVersion.check(&projects);
+
+        // then
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].message(),
+            "package 'Serilog' is referenced with version 3.0.0 but other projects use 3.1.0"
+        );
+    }
+
+    #[test]
+    pub fn inconsistent_package_version_ignoring_case_and_central_versions() {
+        // given
+        let projects = vec![
+            project("Api", Some("net8.0"), &[("Serilog", "3.0.0")]),
+            project("Core", Some("net8.0"), &[("serilog", "3.1.0")]),
+            project("Infra", Some("net8.0"), &[("Serilog", "")]),
+        ];
+
+        // when
+        let violations = InconsistentPackageVersion.check(&projects);
+
+        // then
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[1].message(),
+            "package 'serilog' is referenced with version 3.1.0 but other projects use 3.0.0"
+        );
+    }
+
+    #[test]
+    pub fn incompatible_project_reference() {
+        // given
+        let mut projects = vec![
+            project("Legacy", Some("net48"), &[]),
+            project("Shared", Some("netstandard2.0"), &[]),
+            project("Core", Some("net8.0"), &[]),
+            project("Tools", Some("contoso-tools"), &[]),
+            project("Api", Some("net8.0"), &[]),
+        ];
+        projects[1].target_frameworks = vec!["net8.0".to_string(), "netstandard2.0".to_string()];
+        projects[4].target_frameworks = vec!["net8.0".to_string(), "net48".to_string()];
+
+        let references = [
+            (0, "Shared"),
+            (0, "Core"),
+            (0, "Tools"),
+            (4, "Shared"),
+            (4, "Core"),
+        ];
+        for (from, target) in references {
+            projects[from].add_project_reference(ProjectReference::new(
+                target.to_string(),
+                PathBuf::from(format!("../{target}/{target}.csproj")),
+            ));
+        }
+
+        let mut frameworks = FrameworkRules::new();
+        frameworks.add_compatibility("net48", "contoso-tools");
+
+        // when
+        let violations = IncompatibleProjectReference::new(frameworks).check(&projects);
+
+        // then
+        let messages: Vec<&String> = violations.iter().map(|v| v.message()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "project 'Legacy' targeting net48 references 'Core' targeting net8.0",
+                "project 'Api' targeting net48 references 'Core' targeting net8.0",
+            ]
+        );
+    }
+
+    #[test]
+    pub fn unsupported_target_framework() {
+        // given
+        let projects = vec![
+            project("Api", Some("net5.0"), &[]),
+            project("Core", Some("net8.0"), &[]),
+        ];
+
+        let mut frameworks = FrameworkRules::new();
+        frameworks.add_unsupported("net5.0");
+
+        // when
+        let violations = UnsupportedTargetFramework::new(frameworks).check(&projects);
+
+        // then
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_id(), "DL0005");
+        assert_eq!(violations[0].path(), &PathBuf::from("src/Api/Api.csproj"));
+    }
+
+    #[test]
+    pub fn unsupported_target_frameworks_of_multi_targeted_projects() {
+        // given
+        let mut api = project("Api", None, &[]);
+        api.target_frameworks = vec![
+            "net8.0".to_string(),
+            "net5.0".to_string(),
+            "netcoreapp3.1".to_string(),
+        ];
+
+        let mut frameworks = FrameworkRules::new();
+        frameworks.add_unsupported("net5.0");
+        frameworks.add_unsupported("netcoreapp3.1");
+
+        // when
+        let violations = UnsupportedTargetFramework::new(frameworks).check(&[api]);
+
+        // then
+        assert_eq!(
+            violations
+                .iter()
+                .map(|violation| violation.message().as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "project 'Api' targets net5.0, which is out of support",
+                "project 'Api' targets netcoreapp3.1, which is out of support",
+            ]
+        );
+    }
+
+    #[test]
+    pub fn flag_duplicate_assembly_names() {
+        // given
+        let mut renamed = project("Api.Legacy", Some("net48"), &[]);
+        renamed.path = PathBuf::from("legacy/Api.Legacy/Api.Legacy.csproj");
+        renamed.assembly.set_name(Some("API".to_string()));
+
+        let mut same_file_name = project("Api", Some("net8.0"), &[]);
+        same_file_name.path = PathBuf::from("tools/Api/Api.csproj");
+
+        let mut installer = project("Api", None, &[]);
+        installer.language = ProjectLanguage::Wix;
+        installer.path = PathBuf::from("installer/Api.wixproj");
+
+        let projects = vec![
+            project("Api", Some("net8.0"), &[]),
+            renamed,
+            same_file_name,
+            installer,
+            project("Worker", Some("net8.0"), &[]),
+        ];
+
+        // when
+        let violations = DuplicateAssemblyName.check(&projects);
+
+        // then
+        let paths: Vec<&PathBuf> = violations.iter().map(|v| v.path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                &PathBuf::from("src/Api/Api.csproj"),
+                &PathBuf::from("legacy/Api.Legacy/Api.Legacy.csproj"),
+                &PathBuf::from("tools/Api/Api.csproj"),
+            ]
+        );
+        assert_eq!(violations[0].severity(), Severity::Error);
+        assert_eq!(
+            violations[0].message(),
+            "project 'Api' builds the assembly 'Api', also built by \
+             'legacy/Api.Legacy/Api.Legacy.csproj', 'tools/Api/Api.csproj'"
+        );
+    }
+
+    #[test]
+    pub fn validate_runs_every_rule() {
+        // given
+        let projects = vec![project("Api", None, &[("Serilog", "3.0.0"), ("Serilog", "3.0.0")])];
+
+        // when
+        let violations = validate(&projects, &default_rules());
+
+        // then
+        let rule_ids: Vec<&str> = violations.iter().map(|v| v.rule_id().as_str()).collect();
+        assert_eq!(rule_ids, vec!["DL0001", "DL0002"]);
+    }
+}
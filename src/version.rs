@@ -0,0 +1,468 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+/// Represents a NuGet package version (ex: "8.0.0", "1.2.3.4", "2.0.0-preview.1").
+///
+/// Versions are ordered following the NuGet SemVer 2.0 rules: numeric parts are compared first,
+/// a prerelease version is lower than its release and build metadata is ignored.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct NuGetVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    revision: u64,
+    prerelease: Option<String>,
+    original: String,
+}
+
+/// Represents which part of a version changed between two versions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VersionDelta {
+    Major,
+    Minor,
+    /// The patch, revision or prerelease label changed.
+    Patch,
+}
+
+impl NuGetVersion {
+    /// Returns the major part of the version.
+    pub fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// Returns the minor part of the version.
+    pub fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    /// Returns the patch part of the version.
+    pub fn patch(&self) -> u64 {
+        self.patch
+    }
+
+    /// Returns the revision (fourth) part of the version, `0` when not present.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Returns the prerelease label of the version, if any.
+    pub fn prerelease(&self) -> Option<&str> {
+        self.prerelease.as_deref()
+    }
+
+    /// Returns `true` if the version has a prerelease label.
+    pub fn is_prerelease(&self) -> bool {
+        self.prerelease.is_some()
+    }
+
+    /// Returns the version normalized like NuGet does (ex: "1.0" is "1.0.0", "1.2.3.0" is
+    /// "1.2.3"): three numeric parts, the revision only when it is not `0`, the prerelease label
+    /// and no build metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::version::NuGetVersion;
+    ///
+    /// let version: NuGetVersion = "1.0-beta+abc".parse().unwrap();
+    ///
+    /// assert_eq!(version.normalized(), "1.0.0-beta");
+    /// ```
+    pub fn normalized(&self) -> String {
+        let mut normalized = format!("{}.{}.{}", self.major, self.minor, self.patch);
+
+        if self.revision != 0 {
+            normalized.push_str(&format!(".{}", self.revision));
+        }
+
+        if let Some(prerelease) = &self.prerelease {
+            normalized.push('-');
+            normalized.push_str(prerelease);
+        }
+
+        normalized
+    }
+
+    /// Returns which part changed from `self` to `other`, or `None` if both are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::version::{NuGetVersion, VersionDelta};
+    ///
+    /// let current: NuGetVersion = "6.0.1".parse().unwrap();
+    /// let latest: NuGetVersion = "8.0.0".parse().unwrap();
+    ///
+    /// assert_eq!(current.delta(&latest), Some(VersionDelta::Major));
+    /// ```
+    pub fn delta(&self, other: &NuGetVersion) -> Option<VersionDelta> {
+        if self.major != other.major {
+            Some(VersionDelta::Major)
+        } else if self.minor != other.minor {
+            Some(VersionDelta::Minor)
+        } else if self.cmp(other) != Ordering::Equal {
+            Some(VersionDelta::Patch)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for NuGetVersion {
+    type Err = VersionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        let without_metadata = trimmed.split('+').next().unwrap_or_default();
+
+        let (numbers, prerelease) = match without_metadata.split_once('-') {
+            Some((numbers, prerelease)) if !prerelease.is_empty() => {
+                (numbers, Some(prerelease.to_string()))
+            }
+            Some(_) => return Err(VersionError::InvalidVersion(value.to_string())),
+            None => (without_metadata, None),
+        };
+
+        let parts = numbers
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| VersionError::InvalidVersion(value.to_string()))?;
+
+        if parts.is_empty() || parts.len() > 4 {
+            return Err(VersionError::InvalidVersion(value.to_string()));
+        }
+
+        Ok(Self {
+            major: parts[0],
+            minor: parts.get(1).copied().unwrap_or_default(),
+            patch: parts.get(2).copied().unwrap_or_default(),
+            revision: parts.get(3).copied().unwrap_or_default(),
+            prerelease,
+            original: trimmed.to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for NuGetVersion {
+    type Error = VersionError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<NuGetVersion> for String {
+    fn from(value: NuGetVersion) -> Self {
+        value.original
+    }
+}
+
+impl Display for NuGetVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.original)
+    }
+}
+
+impl PartialEq for NuGetVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for NuGetVersion {}
+
+impl PartialOrd for NuGetVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NuGetVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch, self.revision)
+            .cmp(&(other.major, other.minor, other.patch, other.revision))
+            .then_with(|| match (&self.prerelease, &other.prerelease) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(left), Some(right)) => compare_prerelease(left, right),
+            })
+    }
+}
+
+fn compare_prerelease(left: &str, right: &str) -> Ordering {
+    let mut left_labels = left.split('.');
+    let mut right_labels = right.split('.');
+
+    loop {
+        let ordering = match (left_labels.next(), right_labels.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) => match (l.parse::<u64>(), r.parse::<u64>()) {
+                (Ok(l), Ok(r)) => l.cmp(&r),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => l.to_lowercase().cmp(&r.to_lowercase()),
+            },
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Represents a NuGet version range (ex: "1.0.0", "[1.0,2.0)", "[1.2.3]").
+///
+/// A plain version is interpreted as a minimum inclusive version, following NuGet semantics.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionRange {
+    min: Option<NuGetVersion>,
+    min_inclusive: bool,
+    max: Option<NuGetVersion>,
+    max_inclusive: bool,
+}
+
+impl VersionRange {
+    /// Returns the lower bound of the range, if any.
+    pub fn min(&self) -> Option<&NuGetVersion> {
+        self.min.as_ref()
+    }
+
+    /// Returns the upper bound of the range, if any.
+    pub fn max(&self) -> Option<&NuGetVersion> {
+        self.max.as_ref()
+    }
+
+    /// Returns `true` if the version is inside the range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::version::{NuGetVersion, VersionRange};
+    ///
+    /// let range: VersionRange = "[1.0,2.0)".parse().unwrap();
+    ///
+    /// assert!(range.satisfies(&"1.5.0".parse().unwrap()));
+    /// assert!(!range.satisfies(&"2.0.0".parse().unwrap()));
+    /// ```
+    pub fn satisfies(&self, version: &NuGetVersion) -> bool {
+        let above_min = match &self.min {
+            Some(min) if self.min_inclusive => version >= min,
+            Some(min) => version > min,
+            None => true,
+        };
+
+        let below_max = match &self.max {
+            Some(max) if self.max_inclusive => version <= max,
+            Some(max) => version < max,
+            None => true,
+        };
+
+        above_min && below_max
+    }
+
+    /// Returns `true` if at least one version is inside both ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::version::VersionRange;
+    ///
+    /// let range: VersionRange = "[1.0,2.0)".parse().unwrap();
+    ///
+    /// assert!(range.intersects(&"1.5.0".parse().unwrap()));
+    /// assert!(!range.intersects(&"[2.0.0]".parse().unwrap()));
+    /// ```
+    pub fn intersects(&self, other: &VersionRange) -> bool {
+        // the highest lower bound and the lowest upper bound of both ranges
+        let (min, min_inclusive) = tighter_bound(
+            (self.min.as_ref(), self.min_inclusive),
+            (other.min.as_ref(), other.min_inclusive),
+            Ordering::Greater,
+        );
+        let (max, max_inclusive) = tighter_bound(
+            (self.max.as_ref(), self.max_inclusive),
+            (other.max.as_ref(), other.max_inclusive),
+            Ordering::Less,
+        );
+
+        match (min, max) {
+            (Some(min), Some(max)) => min < max || (min == max && min_inclusive && max_inclusive),
+            _ => true,
+        }
+    }
+}
+
+/// Returns the tighter of two bounds, which is the one ordered `tighter` than the other. A missing
+/// bound is the loosest, and a shared bound is only inclusive if it is inclusive in both ranges.
+fn tighter_bound<'a>(
+    left: (Option<&'a NuGetVersion>, bool),
+    right: (Option<&'a NuGetVersion>, bool),
+    tighter: Ordering,
+) -> (Option<&'a NuGetVersion>, bool) {
+    match (left, right) {
+        ((Some(l), l_inclusive), (Some(r), r_inclusive)) => match l.cmp(r) {
+            Ordering::Equal => (Some(l), l_inclusive && r_inclusive),
+            ordering if ordering == tighter => left,
+            _ => right,
+        },
+        ((None, _), _) => right,
+        (_, (None, _)) => left,
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = VersionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        let invalid = || VersionError::InvalidRange(value.to_string());
+
+        let Some(first) = trimmed.chars().next() else {
+            return Err(invalid());
+        };
+
+        if first != '[' && first != '(' {
+            return Ok(Self {
+                min: Some(trimmed.parse()?),
+                min_inclusive: true,
+                max: None,
+                max_inclusive: false,
+            });
+        }
+
+        let min_inclusive = first == '[';
+        let max_inclusive = match trimmed.chars().last() {
+            Some(']') => true,
+            Some(')') => false,
+            _ => return Err(invalid()),
+        };
+
+        let inner = &trimmed[1..trimmed.len() - 1];
+
+        let parse_bound = |bound: &str| -> Result<Option<NuGetVersion>, VersionError> {
+            let bound = bound.trim();
+
+            if bound.is_empty() {
+                Ok(None)
+            } else {
+                bound.parse().map(Some)
+            }
+        };
+
+        match inner.split_once(',') {
+            Some((min, max)) => Ok(Self {
+                min: parse_bound(min)?,
+                min_inclusive,
+                max: parse_bound(max)?,
+                max_inclusive,
+            }),
+            None if min_inclusive && max_inclusive => {
+                let exact = parse_bound(inner)?.ok_or_else(invalid)?;
+
+                Ok(Self {
+                    min: Some(exact.clone()),
+                    min_inclusive: true,
+                    max: Some(exact),
+                    max_inclusive: true,
+                })
+            }
+            None => Err(invalid()),
+        }
+    }
+}
+
+/// Represents errors that can occur while parsing versions.
+#[derive(Debug, Error)]
+pub enum VersionError {
+    /// The value is not a valid NuGet version.
+    #[error("'{0}' is not a valid version")]
+    InvalidVersion(String),
+    /// The value is not a valid NuGet version range.
+    #[error("'{0}' is not a valid version range")]
+    InvalidRange(String),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn version(value: &str) -> NuGetVersion {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    pub fn parse_versions() {
+        let parsed = version("1.2.3.4-beta.1+abc");
+
+        assert_eq!(parsed.major(), 1);
+        assert_eq!(parsed.minor(), 2);
+        assert_eq!(parsed.patch(), 3);
+        assert_eq!(parsed.revision(), 4);
+        assert_eq!(parsed.prerelease(), Some("beta.1"));
+        assert_eq!(parsed.to_string(), "1.2.3.4-beta.1+abc");
+
+        assert!("1.*".parse::<NuGetVersion>().is_err());
+        assert!("[1.0,2.0)".parse::<NuGetVersion>().is_err());
+        assert!("1.0.0-".parse::<NuGetVersion>().is_err());
+    }
+
+    #[test]
+    pub fn order_versions() {
+        assert!(version("1.0.0-alpha") < version("1.0.0-alpha.1"));
+        assert!(version("1.0.0-alpha.1") < version("1.0.0-alpha.beta"));
+        assert!(version("1.0.0-beta.2") < version("1.0.0-beta.11"));
+        assert!(version("1.0.0-rc.1") < version("1.0.0"));
+        assert!(version("1.0.0") < version("1.0.0.1"));
+        assert_eq!(version("1.0"), version("1.0.0"));
+        assert_eq!(version("1.0.0+build"), version("1.0.0"));
+    }
+
+    #[test]
+    pub fn version_ranges() {
+        let range = |value: &str| value.parse::<VersionRange>().unwrap();
+
+        assert!(range("1.0.0").satisfies(&version("1.0.0")));
+        assert!(range("1.0.0").satisfies(&version("5.0.0")));
+        assert!(!range("1.0.0").satisfies(&version("0.9.0")));
+        assert!(range("[1.2.3]").satisfies(&version("1.2.3")));
+        assert!(!range("[1.2.3]").satisfies(&version("1.2.4")));
+        assert!(!range("(1.0,2.0]").satisfies(&version("1.0")));
+        assert!(range("(1.0,2.0]").satisfies(&version("2.0")));
+        assert!(range("(,2.0)").satisfies(&version("0.1")));
+        assert!(range("[1.0, )").satisfies(&version("99.0")));
+        assert!("(1.0)".parse::<VersionRange>().is_err());
+        assert!("[1.0".parse::<VersionRange>().is_err());
+    }
+
+    #[test]
+    pub fn intersect_version_ranges() {
+        let range = |value: &str| value.parse::<VersionRange>().unwrap();
+
+        assert!(range("1.0.0").intersects(&range("2.0.0")));
+        assert!(range("[1.0,2.0]").intersects(&range("[2.0]")));
+        assert!(!range("[1.0,2.0)").intersects(&range("[2.0]")));
+        assert!(!range("[1.0.0]").intersects(&range("[1.1.0, )")));
+        assert!(range("(,3.0)").intersects(&range("(1.0,)")));
+    }
+
+    #[test]
+    pub fn delta_between_versions() {
+        assert_eq!(version("1.0.0").delta(&version("2.0.0")), Some(VersionDelta::Major));
+        assert_eq!(version("1.0.0").delta(&version("1.1.0")), Some(VersionDelta::Minor));
+        assert_eq!(version("1.0.0").delta(&version("1.0.1")), Some(VersionDelta::Patch));
+        assert_eq!(
+            version("1.0.0-rc.1").delta(&version("1.0.0")),
+            Some(VersionDelta::Patch)
+        );
+        assert_eq!(version("1.0.0").delta(&version("1.0")), None);
+    }
+}
@@ -0,0 +1,144 @@
+//! An abstraction over the files read next to the project files (ex: `Directory.Packages.props`,
+//! `docker-compose.yml`), so projects can be parsed from other sources than the local disk, such
+//! as the in-memory files of a [`crate::workspace::WorkspaceBuilder`].
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::workspace::normalize_path;
+
+/// A source of files read while parsing a project.
+pub trait FileSystem: Send + Sync {
+    /// Returns the content of the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the file does not exist or could not be read.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Returns `true` if there is a file at `path`.
+    fn is_file(&self, path: &Path) -> bool;
+
+    /// Returns `true` if there is a directory at `path`.
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Returns the names of the entries of the directory at `path`, files and directories.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the directory does not exist or could not be read.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+}
+
+/// The files of the local disk.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| PathBuf::from(entry.file_name())))
+            .collect()
+    }
+}
+
+/// Files declared in memory, for tests and for sources that are not checked out on the disk.
+///
+/// The paths are normalized (see [`normalize_path`]), so `src/./Api/../Api/Api.csproj` and
+/// `src/Api/Api.csproj` are the same file. Directories exist as long as they contain a file.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::vfs::{FileSystem, MemoryFileSystem};
+/// use std::path::Path;
+///
+/// let mut files = MemoryFileSystem::new();
+/// files.add_file("/repo/src/Api/Api.csproj", "<Project />");
+///
+/// assert!(files.is_file(Path::new("/repo/src/Api/Api.csproj")));
+/// assert!(files.is_dir(Path::new("/repo/src")));
+/// assert_eq!(files.read_dir(Path::new("/repo/src")).unwrap(), [Path::new("Api")]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryFileSystem {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryFileSystem {
+    /// Creates a new `MemoryFileSystem` without files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file with its content. A file added twice at the same path is replaced.
+    pub fn add_file<P, C>(&mut self, path: P, content: C)
+    where
+        P: AsRef<Path>,
+        C: Into<Vec<u8>>,
+    {
+        self.files
+            .insert(normalize_path(path.as_ref()), content.into());
+    }
+
+    /// Returns the paths of the files, sorted.
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.keys()
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.files
+            .get(&normalize_path(path))
+            .cloned()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(&normalize_path(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        let path = normalize_path(path);
+
+        self.files
+            .keys()
+            .any(|file| file != &path && file.starts_with(&path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let path = normalize_path(path);
+
+        let mut entries: Vec<PathBuf> = self
+            .files
+            .keys()
+            .filter_map(|file| file.strip_prefix(&path).ok())
+            .filter_map(|relative| relative.components().next())
+            .map(|component| PathBuf::from(component.as_os_str()))
+            .collect();
+
+        if entries.is_empty() && !self.is_dir(&path) {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+
+        entries.dedup();
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,60 @@
+//! JavaScript bindings built with `wasm-bindgen`, to parse projects in a browser.
+//!
+//! Build the module for the browser with `wasm-pack build -- --features wasm`, or with
+//! `cargo rustc --lib --crate-type cdylib --target wasm32-unknown-unknown --features wasm`.
+//! The standard library has no filesystem on `wasm32-unknown-unknown`, so the functions reading
+//! files (ex: [`crate::workspace::Workspace::load`]) return errors there; the bindings only work
+//! on the contents passed from JavaScript.
+
+use wasm_bindgen::prelude::*;
+
+use crate::parser;
+
+/// Parses the content of a project file and returns the project as JSON, in the shape of
+/// [`crate::Project`]'s serialization.
+///
+/// The language and name of the project are taken from `path` (ex: `src/Api/Api.csproj`),
+/// which does not need to exist.
+///
+/// ```js
+/// import { parseProject } from "dotnet-lens";
+///
+/// const project = JSON.parse(parseProject(content, "src/Api/Api.csproj"));
+/// console.log(project.package_references);
+/// ```
+#[wasm_bindgen(js_name = parseProject)]
+pub fn parse_project(content: &str, path: &str) -> Result<String, JsError> {
+    project_json(content, path).map_err(|error| JsError::new(&error))
+}
+
+fn project_json(content: &str, path: &str) -> Result<String, String> {
+    let project = parser::parse(content.as_bytes(), path).map_err(|error| error.to_string())?;
+
+    serde_json::to_string(&project).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::project_json;
+
+    #[test]
+    pub fn parse_project_to_json() {
+        // given
+        let content = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#;
+
+        // when
+        let json = project_json(content, "src/Api/Api.csproj").unwrap();
+        let invalid = project_json(content, "README.md");
+
+        // then
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["name"], "Api");
+        assert_eq!(value["target_framework"], "net8.0");
+        assert!(invalid.is_err());
+    }
+}
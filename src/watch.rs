@@ -0,0 +1,318 @@
+//! A filesystem watcher that keeps a workspace up to date and reports what changed in its
+//! projects.
+
+use std::{
+    collections::BTreeSet,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver},
+    time::Duration,
+};
+
+use notify::{
+    event::{EventKind, ModifyKind},
+    Event, RecommendedWatcher, RecursiveMode, Watcher,
+};
+use thiserror::Error;
+
+use crate::{
+    diff::{self, ReferenceChange},
+    workspace::{LoadPolicy, Workspace, WorkspaceError},
+};
+
+/// The time to wait for more events after a change, so saving several files at once is reported
+/// as a single change.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Represents a change in the projects of a watched workspace.
+///
+/// Projects are identified by their path relative to the workspace root.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WatchEvent {
+    /// A project file was created.
+    ProjectAdded(String),
+    /// A project file was modified.
+    ProjectChanged(String),
+    /// A project file was deleted.
+    ProjectRemoved(String),
+    /// A project reference was added to a project that already existed.
+    ReferenceAdded(ReferenceChange),
+    /// A project reference was removed from a project that still exists.
+    ReferenceRemoved(ReferenceChange),
+}
+
+/// Watches the project files of a workspace and refreshes it as they change (see
+/// [`Workspace::refresh`]).
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{watch::WorkspaceWatcher, workspace::Workspace};
+///
+/// let mut watcher = WorkspaceWatcher::new(Workspace::load("path/to/repository")?)?;
+///
+/// loop {
+///     let update = watcher.wait()?;
+///
+///     for event in update.events() {
+///         println!("{:?}", event);
+///     }
+///
+///     for failure in update.failures() {
+///         eprintln!("{}", failure);
+///     }
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct WorkspaceWatcher {
+    workspace: Workspace,
+    receiver: Receiver<notify::Result<Event>>,
+    // the events stop when the watcher is dropped
+    _watcher: RecommendedWatcher,
+}
+
+impl WorkspaceWatcher {
+    /// Starts watching the root of `workspace`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the watcher could not be created.
+    pub fn new(workspace: Workspace) -> Result<Self, WatchError> {
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(workspace.root(), RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            workspace,
+            receiver,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the workspace, as of the last call to [`WorkspaceWatcher::wait`].
+    pub fn workspace(&self) -> &Workspace {
+        &self.workspace
+    }
+
+    /// Consumes the watcher and returns the workspace.
+    pub fn into_workspace(self) -> Workspace {
+        self.workspace
+    }
+
+    /// Waits until project files change, refreshes the workspace and returns the events
+    /// describing the changes, sorted, with the projects that could not be parsed (ex: a file
+    /// saved halfway through an edit), which keep their previous version.
+    ///
+    /// Changes to other files are ignored, so the returned update is never empty.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the watcher failed or if the `.dotnetlensignore`
+    /// file of the root could not be read. The watcher can still be used after an error.
+    pub fn wait(&mut self) -> Result<WatchUpdate, WatchError> {
+        loop {
+            let paths = self.receive()?;
+
+            // only the changed projects are compared, instead of the whole workspace
+            let before = references(&self.workspace, &paths);
+            let (changed, failures) = self
+                .workspace
+                .refresh_with_policy(&paths, LoadPolicy::CollectErrors)?;
+
+            if changed.is_empty() && failures.is_empty() {
+                continue;
+            }
+
+            let after = references(&self.workspace, &changed);
+
+            return Ok(WatchUpdate {
+                events: events(&before, &after),
+                failures,
+            });
+        }
+    }
+
+    /// Waits for a change, then collects the paths of the changes that follow it closely.
+    fn receive(&self) -> Result<Vec<PathBuf>, WatchError> {
+        loop {
+            let event = self
+                .receiver
+                .recv()
+                .map_err(|_| WatchError::Disconnected)??;
+            if !is_change(&event) {
+                continue;
+            }
+
+            let mut paths = event.paths;
+
+            while let Ok(event) = self.receiver.recv_timeout(DEBOUNCE) {
+                let event = event?;
+
+                if is_change(&event) {
+                    paths.extend(event.paths);
+                }
+            }
+
+            return Ok(paths);
+        }
+    }
+}
+
+/// Returns the projects referenced by the project at each path, by relative path, or `None` for
+/// the paths without a project.
+fn references(workspace: &Workspace, paths: &[PathBuf]) -> Vec<(String, Option<BTreeSet<String>>)> {
+    paths
+        .iter()
+        .map(|path| {
+            let references = workspace
+                .project_by_path(path)
+                .map(|project| diff::resolved_references(workspace, project));
+
+            (workspace.relative_path(path), references)
+        })
+        .collect()
+}
+
+/// Returns the events between the references of the changed projects `before` and `after` a
+/// refresh.
+fn events(
+    before: &[(String, Option<BTreeSet<String>>)],
+    after: &[(String, Option<BTreeSet<String>>)],
+) -> Vec<WatchEvent> {
+    let mut events = vec![];
+
+    for (project, references) in after {
+        let previous = before
+            .iter()
+            .find(|(path, _)| path == project)
+            .and_then(|(_, references)| references.as_ref());
+
+        match (previous, references) {
+            (None, Some(_)) => events.push(WatchEvent::ProjectAdded(project.clone())),
+            (Some(_), None) => events.push(WatchEvent::ProjectRemoved(project.clone())),
+            (Some(previous), Some(references)) => {
+                events.push(WatchEvent::ProjectChanged(project.clone()));
+
+                events.extend(references.difference(previous).map(|reference| {
+                    WatchEvent::ReferenceAdded(ReferenceChange::new(
+                        project.clone(),
+                        reference.clone(),
+                    ))
+                }));
+                events.extend(previous.difference(references).map(|reference| {
+                    WatchEvent::ReferenceRemoved(ReferenceChange::new(
+                        project.clone(),
+                        reference.clone(),
+                    ))
+                }));
+            }
+            (None, None) => {}
+        }
+    }
+
+    events.sort();
+    events
+}
+
+/// Returns `false` for events caused by reading the files, which the analysis itself triggers.
+fn is_change(event: &Event) -> bool {
+    !matches!(
+        event.kind,
+        EventKind::Access(_) | EventKind::Modify(ModifyKind::Metadata(_))
+    )
+}
+
+/// Represents the changes found by [`WorkspaceWatcher::wait`].
+#[derive(Debug)]
+pub struct WatchUpdate {
+    events: Vec<WatchEvent>,
+    failures: Vec<WorkspaceError>,
+}
+
+impl WatchUpdate {
+    /// Returns the events describing the changes, sorted.
+    pub fn events(&self) -> &Vec<WatchEvent> {
+        &self.events
+    }
+
+    /// Returns the errors of the changed projects that could not be parsed.
+    pub fn failures(&self) -> &Vec<WorkspaceError> {
+        &self.failures
+    }
+}
+
+/// Represents errors that can occur while watching a workspace.
+#[derive(Debug, Error)]
+pub enum WatchError {
+    /// The filesystem watcher failed.
+    #[error("the filesystem watcher failed")]
+    NotifyError(#[from] notify::Error),
+    /// The filesystem watcher stopped sending events.
+    #[error("the filesystem watcher stopped")]
+    Disconnected,
+    /// The `.dotnetlensignore` file of the root could not be read.
+    #[error(transparent)]
+    WorkspaceError(#[from] WorkspaceError),
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::{diff::ReferenceChange, graph::test::project, workspace::Workspace};
+
+    use super::{events, references, WatchEvent};
+
+    #[test]
+    pub fn describe_refreshed_projects() {
+        // given
+        let before = Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/src/Api/Api.csproj",
+                    &["../Domain/Domain.csproj"],
+                    &[],
+                ),
+                project("/repo/src/Domain/Domain.csproj", &[], &[]),
+            ],
+        );
+        let after = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/src/Api/Api.csproj", &["../Core/Core.csproj"], &[]),
+                project("/repo/src/Core/Core.csproj", &[], &[]),
+            ],
+        );
+
+        let paths = [
+            PathBuf::from("/repo/src/Api/Api.csproj"),
+            PathBuf::from("/repo/src/Core/Core.csproj"),
+            PathBuf::from("/repo/src/Domain/Domain.csproj"),
+        ];
+
+        // when
+        let events = events(&references(&before, &paths), &references(&after, &paths));
+
+        // then
+        let api = "src/Api/Api.csproj".to_string();
+
+        assert_eq!(
+            events,
+            vec![
+                WatchEvent::ProjectAdded("src/Core/Core.csproj".to_string()),
+                WatchEvent::ProjectChanged(api.clone()),
+                WatchEvent::ProjectRemoved("src/Domain/Domain.csproj".to_string()),
+                WatchEvent::ReferenceAdded(ReferenceChange::new(
+                    api.clone(),
+                    "src/Core/Core.csproj".to_string()
+                )),
+                WatchEvent::ReferenceRemoved(ReferenceChange::new(
+                    api,
+                    "src/Domain/Domain.csproj".to_string()
+                )),
+            ]
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! Detection of the optional .NET SDK workloads the projects need to build (ex: `maui-android`,
+//! `wasm-tools`), aggregated over a workspace so CI images can be provisioned with
+//! `dotnet workload install`.
+
+use std::path::PathBuf;
+
+use crate::{
+    framework::{self, TargetFramework},
+    workspace::Workspace,
+    Project,
+};
+
+/// The workloads of the platforms of target framework monikers (ex: `net8.0-android`), without
+/// and with .NET MAUI. The `windows` platform is part of the SDK.
+const PLATFORM_WORKLOADS: [(&str, &str, &str); 6] = [
+    ("android", "android", "maui-android"),
+    ("ios", "ios", "maui-ios"),
+    ("maccatalyst", "maccatalyst", "maui-maccatalyst"),
+    ("macos", "macos", "macos"),
+    ("tvos", "tvos", "tvos"),
+    ("tizen", "tizen", "maui-tizen"),
+];
+
+/// Represents a workload required by the projects of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkloadRequirement {
+    id: String,
+    projects: Vec<PathBuf>,
+}
+
+impl WorkloadRequirement {
+    /// Returns the id of the workload, as passed to `dotnet workload install` (ex: `wasm-tools`).
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Returns the paths of the projects requiring the workload.
+    pub fn projects(&self) -> &Vec<PathBuf> {
+        &self.projects
+    }
+}
+
+/// Represents the properties of a project that require workloads, as set in the project file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkloadMetadata {
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    use_maui: Option<bool>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    run_aot_compilation: Option<bool>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    wasm_build_native: Option<bool>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    is_aspire_host: Option<bool>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    runtime_identifiers: Vec<String>,
+}
+
+impl WorkloadMetadata {
+    /// Creates a new `WorkloadMetadata` without any property.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the project uses .NET MAUI (`UseMaui`), if the project sets it.
+    pub fn use_maui(&self) -> Option<bool> {
+        self.use_maui
+    }
+
+    /// Sets whether the project uses .NET MAUI.
+    pub fn set_use_maui(&mut self, use_maui: Option<bool>) {
+        self.use_maui = use_maui;
+    }
+
+    /// Returns whether the project is compiled ahead of time to WebAssembly
+    /// (`RunAOTCompilation`), if the project sets it.
+    pub fn run_aot_compilation(&self) -> Option<bool> {
+        self.run_aot_compilation
+    }
+
+    /// Sets whether the project is compiled ahead of time to WebAssembly.
+    pub fn set_run_aot_compilation(&mut self, run_aot_compilation: Option<bool>) {
+        self.run_aot_compilation = run_aot_compilation;
+    }
+
+    /// Returns whether the WebAssembly runtime is relinked (`WasmBuildNative`), if the project
+    /// sets it.
+    pub fn wasm_build_native(&self) -> Option<bool> {
+        self.wasm_build_native
+    }
+
+    /// Sets whether the WebAssembly runtime is relinked.
+    pub fn set_wasm_build_native(&mut self, wasm_build_native: Option<bool>) {
+        self.wasm_build_native = wasm_build_native;
+    }
+
+    /// Returns whether the project is a .NET Aspire app host (`IsAspireHost`), if the project
+    /// sets it.
+    pub fn is_aspire_host(&self) -> Option<bool> {
+        self.is_aspire_host
+    }
+
+    /// Sets whether the project is a .NET Aspire app host.
+    pub fn set_is_aspire_host(&mut self, is_aspire_host: Option<bool>) {
+        self.is_aspire_host = is_aspire_host;
+    }
+
+    /// Returns the runtime identifiers of the project (`RuntimeIdentifier` and
+    /// `RuntimeIdentifiers`, ex: `browser-wasm`), in lowercase.
+    pub fn runtime_identifiers(&self) -> &Vec<String> {
+        &self.runtime_identifiers
+    }
+
+    /// Sets the runtime identifiers of the project.
+    pub fn set_runtime_identifiers(&mut self, runtime_identifiers: Vec<String>) {
+        self.runtime_identifiers = runtime_identifiers;
+    }
+
+    /// Returns `true` if the project does not set any property requiring a workload.
+    pub fn is_empty(&self) -> bool {
+        self.use_maui.is_none()
+            && self.run_aot_compilation.is_none()
+            && self.wasm_build_native.is_none()
+            && self.is_aspire_host.is_none()
+            && self.runtime_identifiers.is_empty()
+    }
+}
+
+/// Returns the ids of the workloads a project needs to build, sorted and without duplicates.
+///
+/// The workloads are detected from the platforms of the target frameworks (ex:
+/// `net8.0-ios` requires `ios`, or `maui-ios` with `UseMaui`) and from the properties of the
+/// project file (see [`Project::workloads`]):
+/// - `UseMaui` requires `maui` when no platform is targeted (ex: a MAUI class library);
+/// - `RunAOTCompilation` and `WasmBuildNative` require `wasm-tools`;
+/// - the `browser` platform and the `browser-wasm` runtime identifier, outside of Blazor
+///   WebAssembly projects (referencing `Microsoft.AspNetCore.Components.WebAssembly`), require
+///   `wasm-experimental`;
+/// - the `wasi-wasm` runtime identifier requires `wasi-experimental`;
+/// - `IsAspireHost` requires `aspire`.
+///
+/// The properties are read when the project is parsed, from the project file only: properties
+/// set by imported files, such as `Directory.Build.props`, are not seen.
+pub fn project_workloads(project: &Project) -> Vec<String> {
+    let properties = project.workloads();
+    let enabled = |value: Option<bool>| value == Some(true);
+
+    let use_maui = enabled(properties.use_maui());
+    let mut workloads = vec![];
+
+    let runtime_identifiers = properties.runtime_identifiers();
+
+    let mut targets_platform = false;
+
+    for target_framework in project.target_frameworks() {
+        let Some(platform) = TargetFramework::parse(target_framework)
+            .platform()
+            .map(|platform| framework::platform_name(platform).to_string())
+        else {
+            continue;
+        };
+
+        if platform == "browser" {
+            workloads.push("wasm-experimental");
+            continue;
+        }
+
+        if let Some((_, workload, maui_workload)) = PLATFORM_WORKLOADS
+            .iter()
+            .find(|(name, _, _)| *name == platform)
+        {
+            targets_platform = true;
+            workloads.push(if use_maui { maui_workload } else { workload });
+        } else if platform == "windows" && use_maui {
+            targets_platform = true;
+            workloads.push("maui-windows");
+        }
+    }
+
+    if use_maui && !targets_platform {
+        workloads.push("maui");
+    }
+
+    if enabled(properties.run_aot_compilation()) || enabled(properties.wasm_build_native()) {
+        workloads.push("wasm-tools");
+    }
+
+    let blazor = project.package_references().iter().any(|package| {
+        package
+            .name()
+            .eq_ignore_ascii_case("Microsoft.AspNetCore.Components.WebAssembly")
+    });
+
+    if !blazor && runtime_identifiers.iter().any(|rid| rid == "browser-wasm") {
+        workloads.push("wasm-experimental");
+    }
+
+    if runtime_identifiers.iter().any(|rid| rid == "wasi-wasm") {
+        workloads.push("wasi-experimental");
+    }
+
+    if enabled(properties.is_aspire_host()) {
+        workloads.push("aspire");
+    }
+
+    let mut workloads: Vec<String> = workloads.into_iter().map(str::to_string).collect();
+    workloads.sort();
+    workloads.dedup();
+
+    workloads
+}
+
+/// Returns the workloads required by the projects of a workspace, sorted by id, each with the
+/// projects requiring it (see [`project_workloads`]).
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::{workloads, workspace::Workspace};
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// let ids: Vec<String> = workloads::required_workloads(&workspace)
+///     .into_iter()
+///     .map(|workload| workload.id().clone())
+///     .collect();
+///
+/// println!("dotnet workload install {}", ids.join(" "));
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn required_workloads(workspace: &Workspace) -> Vec<WorkloadRequirement> {
+    let mut requirements: Vec<WorkloadRequirement> = vec![];
+
+    for project in workspace.projects() {
+        for id in project_workloads(project) {
+            match requirements
+                .iter_mut()
+                .find(|requirement| requirement.id == id)
+            {
+                Some(requirement) => requirement.projects.push(project.path().clone()),
+                None => requirements.push(WorkloadRequirement {
+                    id,
+                    projects: vec![project.path().clone()],
+                }),
+            }
+        }
+    }
+
+    requirements.sort_by(|a, b| a.id.cmp(&b.id));
+
+    requirements
+}
+
+#[cfg(test)]
+mod test {
+    use crate::workspace::WorkspaceBuilder;
+
+    use super::required_workloads;
+
+    #[test]
+    pub fn aggregate_required_workloads() {
+        // given
+        let projects = [
+            (
+                "App/App.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFrameworks>net8.0-android;net8.0-ios;net8.0-windows10.0.19041.0</TargetFrameworks>
+    <UseMaui>true</UseMaui>
+  </PropertyGroup>
+</Project>"#,
+            ),
+            (
+                "Controls/Controls.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <UseMaui>true</UseMaui>
+  </PropertyGroup>
+</Project>"#,
+            ),
+            (
+                "Client/Client.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk.BlazorWebAssembly">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <RunAOTCompilation>true</RunAOTCompilation>
+    <RuntimeIdentifier>browser-wasm</RuntimeIdentifier>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Microsoft.AspNetCore.Components.WebAssembly" Version="8.0.0" />
+  </ItemGroup>
+</Project>"#,
+            ),
+            (
+                "Phone/Phone.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0-android</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+            ),
+            (
+                "Api/Api.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+            ),
+        ];
+
+        // the projects only exist in memory, so the properties are read while parsing
+        let mut builder = WorkspaceBuilder::new("/repo");
+        for (path, content) in projects {
+            builder.add_project(path, content);
+        }
+
+        let workspace = builder.build().unwrap();
+
+        // when
+        let workloads = required_workloads(&workspace);
+
+        // then
+        let summary: Vec<(&str, Vec<String>)> = workloads
+            .iter()
+            .map(|workload| {
+                let mut projects: Vec<String> = workload
+                    .projects()
+                    .iter()
+                    .map(|path| workspace.relative_path(path))
+                    .collect();
+                projects.sort();
+
+                (workload.id().as_str(), projects)
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("android", vec!["Phone/Phone.csproj".to_string()]),
+                ("maui", vec!["Controls/Controls.csproj".to_string()]),
+                ("maui-android", vec!["App/App.csproj".to_string()]),
+                ("maui-ios", vec!["App/App.csproj".to_string()]),
+                ("maui-windows", vec!["App/App.csproj".to_string()]),
+                ("wasm-tools", vec!["Client/Client.csproj".to_string()]),
+            ]
+        );
+    }
+}
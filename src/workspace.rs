@@ -0,0 +1,2448 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use crate::cache::ParseCache;
+use crate::{
+    assembly::VersionStamping,
+    condition::Properties,
+    filter::ProjectFilter,
+    graph::DependencyGraph,
+    ignore::IgnoreFile,
+    intern::Interner,
+    packages_folder::GlobalPackagesFolder,
+    parser::{self, ParseError},
+    parser_registry::ParserRegistry,
+    query::Query,
+    registry::ProjectRegistry,
+    search,
+    solution::Solution,
+    version::{NuGetVersion, VersionRange},
+    vfs::{FileSystem, MemoryFileSystem},
+    Project, ProjectLanguage, ProjectReference,
+};
+
+/// Represents every .NET project found under a root directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotnet_lens::workspace::Workspace;
+///
+/// let workspace = Workspace::load("path/to/repository")?;
+///
+/// for project in workspace.projects() {
+///     println!("{}: {} references", project.name(), project.project_references().len());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Concurrency
+///
+/// `Workspace` is `Send` and `Sync`, and every query takes `&self`, so one loaded workspace can
+/// answer queries from several threads behind an `Arc<Workspace>`.
+///
+/// The projects are shared between clones, which makes cloning cheap. A clone is a snapshot:
+/// the methods changing the projects (ex: [`Workspace::refresh`], [`Workspace::projects_mut`])
+/// copy them first if they are shared, so the other clones, and the threads querying them, keep
+/// seeing the projects as they were. A server can refresh a clone and then swap it with the
+/// workspace it shares.
+///
+/// ```no_run
+/// use std::{sync::Arc, thread};
+///
+/// use dotnet_lens::workspace::Workspace;
+///
+/// let workspace = Arc::new(Workspace::load("path/to/repository")?);
+///
+/// let handles: Vec<_> = ["Api", "Domain"]
+///     .into_iter()
+///     .map(|name| {
+///         let workspace = Arc::clone(&workspace);
+///         thread::spawn(move || workspace.project(name).is_some())
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     println!("{}", handle.join().unwrap());
+/// }
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Workspace {
+    root: PathBuf,
+    projects: Arc<Vec<Project>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    path_mappings: Vec<PathMapping>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    reader: ProjectReader,
+    /// The indexes of the projects by lowercase assembly name, built on first use and reset
+    /// when the projects change.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    assemblies: OnceLock<BTreeMap<String, Vec<usize>>>,
+}
+
+impl PartialEq for Workspace {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root
+            && self.projects == other.projects
+            && self.path_mappings == other.path_mappings
+    }
+}
+
+/// How the projects of a workspace were read, so [`Workspace::refresh`] reads the changed
+/// projects with the same properties, parsers and cache.
+#[derive(Clone, Default)]
+pub(crate) enum ProjectReader {
+    #[default]
+    Default,
+    Properties(Properties),
+    Parsers(ParserRegistry),
+    #[cfg(feature = "serde")]
+    Cache(ParseCache),
+}
+
+impl ProjectReader {
+    fn is_project_file(&self, path: &Path) -> bool {
+        match self {
+            ProjectReader::Parsers(parsers) => parsers.is_project_file(&path),
+            _ => search::is_project_file(&path),
+        }
+    }
+
+    fn read(&self, path: &Path, strings: &Interner) -> Result<Project, ParseError> {
+        match self {
+            ProjectReader::Default => parser::parse_file(path, &Properties::new(), strings),
+            ProjectReader::Properties(properties) => parser::parse_file(path, properties, strings),
+            ProjectReader::Parsers(parsers) => parsers.parse_file_with(path, strings),
+            #[cfg(feature = "serde")]
+            ProjectReader::Cache(cache) => cache.parse_with(path, strings),
+        }
+    }
+}
+
+impl std::fmt::Debug for ProjectReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectReader::Default => write!(f, "Default"),
+            ProjectReader::Properties(properties) => {
+                f.debug_tuple("Properties").field(properties).finish()
+            }
+            ProjectReader::Parsers(parsers) => f
+                .debug_tuple("Parsers")
+                .field(&parsers.extensions().collect::<Vec<_>>())
+                .finish(),
+            #[cfg(feature = "serde")]
+            ProjectReader::Cache(cache) => f.debug_tuple("Cache").field(cache).finish(),
+        }
+    }
+}
+
+impl Workspace {
+    /// Searches and parses every project under `root`.
+    ///
+    /// The projects are sorted by path so the result does not depend on the order the
+    /// filesystem lists the directories. See [`Workspace::stream`] to process the projects as
+    /// they are parsed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read or if one of the
+    /// projects could not be parsed.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", skip_all, fields(root = %root.as_ref().display()))
+    )]
+    pub fn load<P>(root: P) -> Result<Self, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_with_reader(root, ProjectReader::Default)
+    }
+
+    /// Searches and parses every project under `root` like [`Workspace::load`], evaluating their
+    /// conditions with the given `properties` (ex: `Configuration=Release`), so the analysis
+    /// reflects the settings of the build.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read or if one of the
+    /// projects could not be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{condition::Properties, workspace::Workspace};
+    ///
+    /// let mut properties = Properties::new();
+    /// properties.set("Configuration", "Release");
+    /// properties.set("CI", "true");
+    ///
+    /// let workspace = Workspace::load_with_properties("path/to/repository", &properties)?;
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load_with_properties<P>(root: P, properties: &Properties) -> Result<Self, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_with_reader(root, ProjectReader::Properties(properties.clone()))
+    }
+
+    /// Searches and parses every project under `root` like [`Workspace::load`], notifying
+    /// `observer` as each project is discovered and parsed, as each project reference is
+    /// resolved and when an error occurs.
+    ///
+    /// The observer can skip projects before they are parsed and decide whether a project that
+    /// fails to parse stops the loading (see [`WorkspaceObserver`]).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read or if one of the
+    /// projects could not be parsed and the observer aborted the loading.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    ///
+    /// use dotnet_lens::workspace::{ErrorAction, Workspace, WorkspaceError, WorkspaceObserver};
+    ///
+    /// #[derive(Default)]
+    /// struct SkipBroken {
+    ///     broken: Vec<String>,
+    /// }
+    ///
+    /// impl WorkspaceObserver for SkipBroken {
+    ///     fn on_project_discovered(&mut self, path: &Path) -> bool {
+    ///         !path.starts_with("path/to/repository/samples")
+    ///     }
+    ///
+    ///     fn on_error(&mut self, error: &WorkspaceError) -> ErrorAction {
+    ///         self.broken.push(error.to_string());
+    ///         ErrorAction::Skip
+    ///     }
+    /// }
+    ///
+    /// let mut observer = SkipBroken::default();
+    /// let workspace = Workspace::load_with_observer("path/to/repository", &mut observer)?;
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load_with_observer<P, O>(root: P, observer: &mut O) -> Result<Self, WorkspaceError>
+    where
+        P: AsRef<Path>,
+        O: WorkspaceObserver + ?Sized,
+    {
+        let mut paths = match search::search_projects(&root) {
+            Ok(paths) => paths,
+            Err(error) => {
+                let error = WorkspaceError::from(error);
+                observer.on_error(&error);
+                return Err(error);
+            }
+        };
+        paths.sort();
+
+        let strings = Interner::new();
+        let mut projects = vec![];
+
+        for path in paths {
+            if !observer.on_project_discovered(&path) {
+                continue;
+            }
+
+            match parser::parse_file(&path, &Properties::new(), &strings) {
+                Ok(project) => {
+                    observer.on_project_parsed(&project);
+                    projects.push(project);
+                }
+                Err(source) => {
+                    let error = WorkspaceError::ParseError { path, source };
+
+                    if observer.on_error(&error) == ErrorAction::Abort {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+
+        let workspace = Self::from_projects(root, projects);
+
+        for project in workspace.projects() {
+            for reference in project.project_references() {
+                let target = workspace.resolve_reference(project, reference);
+                observer.on_reference_resolved(project, reference, target);
+            }
+        }
+
+        Ok(workspace)
+    }
+
+    /// Searches and parses every project under `root` like [`Workspace::load`], with a policy
+    /// deciding what happens when a project could not be parsed.
+    ///
+    /// With [`LoadPolicy::FailFast`], the first project that could not be parsed stops the
+    /// loading, like [`Workspace::load`]. With [`LoadPolicy::CollectErrors`], the workspace
+    /// contains every project that was parsed, and the failures are returned with it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read, or if one of the
+    /// projects could not be parsed with [`LoadPolicy::FailFast`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::workspace::{LoadPolicy, Workspace};
+    ///
+    /// let loaded = Workspace::load_with_policy("path/to/repository", LoadPolicy::CollectErrors)?;
+    ///
+    /// for failure in loaded.failures() {
+    ///     eprintln!("{}", failure);
+    /// }
+    ///
+    /// let workspace = loaded.into_workspace();
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load_with_policy<P>(
+        root: P,
+        policy: LoadPolicy,
+    ) -> Result<LoadedWorkspace, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut projects = vec![];
+        let mut failures = vec![];
+
+        for project in Self::stream_with(&root, |path, strings| {
+            parser::parse_file(path, &Properties::new(), strings)
+        })? {
+            match project {
+                Ok(project) => projects.push(project),
+                Err(error) if policy == LoadPolicy::CollectErrors => failures.push(error),
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(LoadedWorkspace {
+            workspace: Self::from_projects(root, projects),
+            failures,
+        })
+    }
+
+    /// Searches the projects under `root` and returns an iterator that parses them one by one.
+    ///
+    /// Unlike [`Workspace::load`], the projects are available as soon as they are parsed, which
+    /// lets callers start processing large repositories before the whole scan finishes. The
+    /// projects are returned sorted by path, and a project that fails to parse does not stop
+    /// the iteration.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::workspace::Workspace;
+    ///
+    /// for project in Workspace::stream("path/to/repository")? {
+    ///     match project {
+    ///         Ok(project) => println!("{}", project.name()),
+    ///         Err(e) => eprintln!("{}", e),
+    ///     }
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn stream<P>(
+        root: P,
+    ) -> Result<impl Iterator<Item = Result<Project, WorkspaceError>>, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::stream_with(root, |path, strings| {
+            parser::parse_file(path, &Properties::new(), strings)
+        })
+    }
+
+    /// Searches and parses every project under `root` like [`Workspace::load`], mapping the
+    /// project files into memory instead of reading them through a buffer (see
+    /// [`Project::map`]).
+    ///
+    /// # Safety
+    ///
+    /// The project files must not be modified or truncated while the workspace is loaded (see
+    /// [`Project::map`]).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read or if one of the
+    /// projects could not be parsed.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn load_mapped<P>(root: P) -> Result<Self, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        // SAFETY: the caller guarantees the project files are not modified while they are parsed
+        Self::load_with(root, |path, strings| unsafe {
+            Project::map_with(path, strings)
+        })
+    }
+
+    /// Searches and parses every project under `root` like [`Workspace::load`], including the
+    /// files with an extension registered in `parsers`, which are parsed by their registered
+    /// parser.
+    ///
+    /// [`Workspace::refresh`] parses the changed files with the same parsers.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory could not be read or if one of the
+    /// projects could not be parsed.
+    pub fn load_with_parsers<P>(root: P, parsers: &ParserRegistry) -> Result<Self, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut paths = parsers.search(&root)?;
+        paths.sort();
+
+        let strings = Interner::new();
+        let projects = paths
+            .into_iter()
+            .map(|path| {
+                parsers
+                    .parse_file_with(&path, &strings)
+                    .map_err(|source| WorkspaceError::ParseError { path, source })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut workspace = Self::from_projects(root, projects);
+        workspace.reader = ProjectReader::Parsers(parsers.clone());
+
+        Ok(workspace)
+    }
+
+    /// Searches every project under `root` and reads them with `reader`, which is kept to read
+    /// the projects again when the workspace is refreshed.
+    pub(crate) fn load_with_reader<P>(
+        root: P,
+        reader: ProjectReader,
+    ) -> Result<Self, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut workspace = Self::load_with(root, |path, strings| reader.read(path, strings))?;
+        workspace.reader = reader;
+
+        Ok(workspace)
+    }
+
+    /// Searches every project under `root` and reads them with `read`, for the loaders that do
+    /// not parse the files directly (ex: [`crate::cache::ParseCache`]).
+    ///
+    /// The projects are read with the same [`Interner`], so they share their names and versions.
+    pub(crate) fn load_with<P, F>(root: P, read: F) -> Result<Self, WorkspaceError>
+    where
+        P: AsRef<Path>,
+        F: Fn(&Path, &Interner) -> Result<Project, ParseError>,
+    {
+        let projects = Self::stream_with(&root, read)?.collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::from_projects(root, projects))
+    }
+
+    fn stream_with<P, F>(
+        root: P,
+        read: F,
+    ) -> Result<impl Iterator<Item = Result<Project, WorkspaceError>>, WorkspaceError>
+    where
+        P: AsRef<Path>,
+        F: Fn(&Path, &Interner) -> Result<Project, ParseError>,
+    {
+        let mut paths = search::search_projects(&root)?;
+        paths.sort();
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(projects = paths.len(), "found projects");
+
+        let strings = Interner::new();
+
+        Ok(paths.into_iter().map(move |path| {
+            read(&path, &strings).map_err(|source| WorkspaceError::ParseError { path, source })
+        }))
+    }
+
+    /// Creates a `Workspace` from projects that were already parsed.
+    pub fn from_projects<P>(root: P, projects: Vec<Project>) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            projects: Arc::new(projects),
+            path_mappings: vec![],
+            reader: ProjectReader::Default,
+            assemblies: OnceLock::new(),
+        }
+    }
+
+    /// Loads the projects of several roots, for example repositories referencing each other
+    /// through submodules, into one workspace.
+    ///
+    /// See [`Workspace::merge`] for how the workspaces are combined, and
+    /// [`Workspace::add_path_mapping`] to resolve the references between the roots. Projects
+    /// found under several roots (ex: nested roots) are only parsed once.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if one of the roots could not be loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::workspace::{PathMapping, Workspace};
+    ///
+    /// let mut workspace = Workspace::load_many(&["repos/platform", "repos/shop"])?;
+    ///
+    /// // the shop references the platform through a submodule checked out in `external/platform`
+    /// workspace.add_path_mapping(PathMapping::new(
+    ///     "repos/shop/external/platform",
+    ///     "repos/platform",
+    /// ));
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load_many<P>(roots: &[P]) -> Result<Self, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut roots = roots.iter();
+
+        let Some(first) = roots.next() else {
+            return Ok(Self::from_projects("", vec![]));
+        };
+
+        let registry = ProjectRegistry::new();
+        let mut workspace = registry.load(first)?;
+
+        for root in roots {
+            workspace.merge(registry.load(root)?);
+        }
+
+        Ok(workspace)
+    }
+
+    /// Adds the projects and path mappings of another workspace to this workspace.
+    ///
+    /// The root becomes the deepest directory containing both roots, so project ids stay
+    /// unique. Projects with the same path are only kept once.
+    pub fn merge(&mut self, other: Workspace) {
+        self.root = common_ancestor(&self.root, &other.root);
+
+        for project in Arc::unwrap_or_clone(other.projects) {
+            if self.project_by_path(project.path()).is_none() {
+                self.projects_mut().push(project);
+            }
+        }
+
+        self.projects_mut().sort_by(|a, b| a.path().cmp(b.path()));
+        self.path_mappings.extend(other.path_mappings);
+    }
+
+    /// Returns the path mappings used to resolve project references.
+    pub fn path_mappings(&self) -> &Vec<PathMapping> {
+        &self.path_mappings
+    }
+
+    /// Adds a path mapping used to resolve the project references that do not point to a project
+    /// of the workspace.
+    pub fn add_path_mapping(&mut self, mapping: PathMapping) {
+        self.path_mappings.push(mapping);
+    }
+
+    /// Returns the root directory of the workspace.
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    /// Returns a reference to the list of projects.
+    pub fn projects(&self) -> &Vec<Project> {
+        &self.projects
+    }
+
+    /// Returns a mutable reference to the list of projects, copying them first if they are
+    /// shared with a clone of the workspace.
+    pub fn projects_mut(&mut self) -> &mut Vec<Project> {
+        self.assemblies.take();
+
+        Arc::make_mut(&mut self.projects)
+    }
+
+    /// Returns the first project with the given name, ignoring case.
+    pub fn project(&self, name: &str) -> Option<&Project> {
+        self.projects
+            .iter()
+            .find(|project| project.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns a workspace with the projects matching the filter, keeping only their package
+    /// references matching it, so the listings, graphs and reports built from it are filtered
+    /// too.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{
+    ///     filter::{Matcher, ProjectFilter},
+    ///     graph::DependencyGraph,
+    ///     workspace::Workspace,
+    /// };
+    ///
+    /// let mut filter = ProjectFilter::new();
+    /// filter.add_path(Matcher::parse("src/*")?);
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?.filter(&filter);
+    /// let graph = DependencyGraph::from_workspace(&workspace, true);
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn filter(&self, filter: &ProjectFilter) -> Self {
+        Self {
+            root: self.root.clone(),
+            projects: Arc::new(filter.apply(self)),
+            path_mappings: self.path_mappings.clone(),
+            reader: self.reader.clone(),
+            assemblies: OnceLock::new(),
+        }
+    }
+
+    /// Returns a workspace with only the projects of a solution, so the graphs and reports built
+    /// from it are scoped to that solution instead of the whole repository.
+    ///
+    /// The projects referenced by the solution's projects without being part of the solution are
+    /// left out, and so are the projects of the solution missing from the workspace.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{graph::DependencyGraph, solution::Solution, workspace::Workspace};
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    /// let solution = Solution::load("path/to/repository/Payments.sln")?;
+    ///
+    /// let payments = workspace.solution_slice(&solution);
+    /// let graph = DependencyGraph::from_workspace(&payments, true);
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn solution_slice(&self, solution: &Solution) -> Self {
+        let projects = self
+            .projects
+            .iter()
+            .filter(|project| solution.find(project.path()).is_some())
+            .cloned()
+            .collect();
+
+        Self {
+            root: self.root.clone(),
+            projects: Arc::new(projects),
+            path_mappings: self.path_mappings.clone(),
+            reader: self.reader.clone(),
+            assemblies: OnceLock::new(),
+        }
+    }
+
+    /// Returns the projects matching the query, in the order of the workspace.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::workspace::Workspace;
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    ///
+    /// for project in workspace.query(&"kind == test && tfm startswith 'net4'".parse()?) {
+    ///     println!("{}", project.name());
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&self, query: &Query) -> Vec<&Project> {
+        self.projects
+            .iter()
+            .filter(|project| query.matches(project))
+            .collect()
+    }
+
+    /// Returns the projects by tag (see [`Project::tags`]), grouping the tags that only differ by
+    /// case under the first spelling found.
+    ///
+    /// Projects without tags are not included, and a project with several tags is in several
+    /// groups.
+    pub fn tags(&self) -> BTreeMap<&str, Vec<&Project>> {
+        let mut tags: BTreeMap<&str, Vec<&Project>> = BTreeMap::new();
+
+        for project in self.projects.iter() {
+            for tag in project.tags() {
+                let key = tags
+                    .keys()
+                    .find(|key| key.eq_ignore_ascii_case(tag))
+                    .copied()
+                    .unwrap_or(tag.as_str());
+
+                tags.entry(key).or_default().push(project);
+            }
+        }
+
+        tags
+    }
+
+    /// Returns the projects by the lowercase name of the assembly they build (see
+    /// [`Project::assembly_name`]), so the assemblies mentioned by logs, binding redirects or
+    /// `deps.json` files can be traced back to their projects.
+    ///
+    /// Several projects may build an assembly with the same name, ignoring case. The index is
+    /// built once and kept until the projects change.
+    pub fn assemblies(&self) -> BTreeMap<&str, Vec<&Project>> {
+        self.assembly_index()
+            .iter()
+            .map(|(name, indexes)| {
+                let projects = indexes.iter().map(|index| &self.projects[*index]).collect();
+
+                (name.as_str(), projects)
+            })
+            .collect()
+    }
+
+    fn assembly_index(&self) -> &BTreeMap<String, Vec<usize>> {
+        self.assemblies.get_or_init(|| {
+            let mut assemblies: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+
+            for (index, project) in self.projects.iter().enumerate() {
+                assemblies
+                    .entry(project.assembly_name().to_ascii_lowercase())
+                    .or_default()
+                    .push(index);
+            }
+
+            assemblies
+        })
+    }
+
+    /// Returns the first project building the assembly with the given name, ignoring case and
+    /// the `.dll` or `.exe` extension.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dotnet_lens::workspace::WorkspaceBuilder;
+    ///
+    /// let mut builder = WorkspaceBuilder::new("/repo");
+    /// builder.add_project(
+    ///     "src/Api/Api.csproj",
+    ///     "<Project><PropertyGroup><AssemblyName>Contoso.Api</AssemblyName></PropertyGroup></Project>",
+    /// );
+    ///
+    /// let workspace = builder.build()?;
+    ///
+    /// let project = workspace.project_by_assembly("contoso.api.dll").unwrap();
+    ///
+    /// assert_eq!(project.name(), "Api");
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn project_by_assembly(&self, name: &str) -> Option<&Project> {
+        let name = [".dll", ".exe"]
+            .iter()
+            .find_map(|extension| strip_suffix_ignore_case(name, extension))
+            .unwrap_or(name);
+
+        self.assembly_index()
+            .get(&name.to_ascii_lowercase())
+            .map(|indexes| &self.projects[indexes[0]])
+    }
+
+    /// Returns the project with the given path.
+    pub fn project_by_path<P>(&self, path: P) -> Option<&Project>
+    where
+        P: AsRef<Path>,
+    {
+        let path = normalize_path(path.as_ref());
+
+        self.projects
+            .iter()
+            .find(|project| normalize_path(project.path()) == path)
+    }
+
+    /// Returns the project targeted by a project reference of `project`.
+    ///
+    /// The reference path is resolved relative to the directory of the referencing project. If
+    /// no project is found at that path, the path mappings are applied, then the project is
+    /// looked up by name.
+    pub fn resolve_reference(
+        &self,
+        project: &Project,
+        reference: &ProjectReference,
+    ) -> Option<&Project> {
+        let directory = project.path().parent().unwrap_or(Path::new(""));
+        let path = normalize_path(&directory.join(reference.path()));
+
+        self.project_by_path(&path)
+            .or_else(|| {
+                self.path_mappings
+                    .iter()
+                    .filter_map(|mapping| mapping.apply(&path))
+                    .find_map(|path| self.project_by_path(path))
+            })
+            .or_else(|| self.project(reference.name()))
+    }
+
+    /// Re-parses the projects at the given paths, typically reported by a file watcher.
+    ///
+    /// The projects are parsed the way the workspace was loaded, with the same properties (see
+    /// [`Workspace::load_with_properties`]), parsers (see [`Workspace::load_with_parsers`]) or
+    /// cache (see [`crate::cache::ParseCache::load`]).
+    ///
+    /// Paths that are not project files, or that are excluded by the `.dotnetlensignore` file of
+    /// the root, are ignored, so every changed path can be passed without filtering. Projects that
+    /// no longer exist are removed and new projects are added.
+    ///
+    /// Returns the paths of the projects that were added, updated or removed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if one of the projects could not be parsed. The
+    /// projects parsed before the error are kept up to date.
+    pub fn refresh<P>(&mut self, paths: &[P]) -> Result<Vec<PathBuf>, WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        self.refresh_with_policy(paths, LoadPolicy::FailFast)
+            .map(|(changed, _)| changed)
+    }
+
+    /// Re-parses the projects at the given paths like [`Workspace::refresh`], with a policy
+    /// deciding what happens when a project could not be parsed.
+    ///
+    /// With [`LoadPolicy::CollectErrors`], a project that could not be parsed keeps its previous
+    /// version, the other paths are still refreshed, and the failures are returned with the
+    /// changed paths.
+    pub(crate) fn refresh_with_policy<P>(
+        &mut self,
+        paths: &[P],
+        policy: LoadPolicy,
+    ) -> Result<(Vec<PathBuf>, Vec<WorkspaceError>), WorkspaceError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut changed: Vec<PathBuf> = vec![];
+        let mut failures = vec![];
+        let ignore = IgnoreFile::load(&self.root)?;
+        let strings = Interner::new();
+
+        for path in paths {
+            let path = path.as_ref();
+            let relative = path.strip_prefix(&self.root).unwrap_or(path);
+
+            if !self.reader.is_project_file(relative)
+                || ignore.is_ignored(relative)
+                || changed.iter().any(|p| p == path)
+            {
+                continue;
+            }
+
+            let normalized = normalize_path(path);
+            let position = self
+                .projects
+                .iter()
+                .position(|project| normalize_path(project.path()) == normalized);
+
+            if !path.is_file() {
+                if let Some(position) = position {
+                    self.projects_mut().remove(position);
+                    changed.push(path.to_path_buf());
+                }
+
+                continue;
+            }
+
+            let project = match self.reader.read(path, &strings) {
+                Ok(project) => project,
+                Err(source) => {
+                    let error = WorkspaceError::ParseError {
+                        path: path.to_path_buf(),
+                        source,
+                    };
+
+                    if policy == LoadPolicy::FailFast {
+                        return Err(error);
+                    }
+
+                    failures.push(error);
+                    continue;
+                }
+            };
+
+            match position {
+                Some(position) => self.projects_mut()[position] = project,
+                None => {
+                    let projects = self.projects_mut();
+                    projects.push(project);
+                    projects.sort_by(|a, b| a.path().cmp(b.path()));
+                }
+            }
+
+            changed.push(path.to_path_buf());
+        }
+
+        Ok((changed, failures))
+    }
+
+    /// Returns the projects impacted by changed files, sorted by path.
+    ///
+    /// A file belongs to the project with the deepest directory containing it. `Directory.*`
+    /// files (ex: `Directory.Build.props`, `Directory.Packages.props`) impact every project below
+    /// their directory. The result includes every project that depends on an impacted project,
+    /// directly or transitively. Relative paths are resolved against the workspace root.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::PathBuf;
+    ///
+    /// use dotnet_lens::workspace::Workspace;
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    ///
+    /// for project in workspace.affected_by(&[PathBuf::from("src/Domain/Order.cs")]) {
+    ///     println!("{}", project.name());
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn affected_by(&self, paths: &[PathBuf]) -> Vec<&Project> {
+        let graph = DependencyGraph::from_workspace(self, false);
+        let index_of = |project: &Project| graph.find(&self.relative_path(project.path()));
+
+        let mut stack = vec![];
+
+        for path in paths {
+            let file = normalize_path(&self.root.join(path));
+
+            let is_directory_file = file
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("Directory."));
+
+            if is_directory_file {
+                let directory = file.parent().unwrap_or(Path::new(""));
+
+                stack.extend(
+                    self.projects
+                        .iter()
+                        .filter(|project| normalize_path(project.path()).starts_with(directory))
+                        .filter_map(index_of),
+                );
+
+                continue;
+            }
+
+            let owner = self
+                .projects
+                .iter()
+                .filter(|project| {
+                    normalize_path(project.path())
+                        .parent()
+                        .is_some_and(|directory| file.starts_with(directory))
+                })
+                .max_by_key(|project| project.path().components().count());
+
+            stack.extend(owner.and_then(index_of));
+        }
+
+        let mut affected = HashSet::new();
+
+        while let Some(index) = stack.pop() {
+            if affected.insert(index) {
+                stack.extend(graph.dependents(index));
+            }
+        }
+
+        let mut affected: Vec<&Project> = self
+            .projects
+            .iter()
+            .filter(|project| index_of(project).is_some_and(|index| affected.contains(&index)))
+            .collect();
+        affected.sort_by(|a, b| a.path().cmp(b.path()));
+
+        affected
+    }
+
+    /// Returns the projects that use a package, sorted by path.
+    ///
+    /// A project uses the package directly when it references it, and transitively when one of
+    /// the projects it references, directly or transitively, references it. When `range` is
+    /// given, only the versions inside the range are considered; versions that are not valid
+    /// NuGet versions (ex: MSBuild properties) never match a range.
+    ///
+    /// See [`Workspace::projects_using_package_in`] to also find the packages that depend on
+    /// the package.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::workspace::Workspace;
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    /// let range = "[2.0,3.0)".parse()?;
+    ///
+    /// for usage in workspace.projects_using_package("Serilog", Some(&range)) {
+    ///     println!("{:?}: {} ({:?})", usage.project(), usage.version(), usage.kind());
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn projects_using_package(
+        &self,
+        name: &str,
+        range: Option<&VersionRange>,
+    ) -> Vec<PackageUsage> {
+        self.package_usages(name, range, |_| Ok(vec![]))
+            .unwrap_or_default()
+    }
+
+    /// Returns the projects that use a package, like [`Workspace::projects_using_package`],
+    /// also resolving the dependencies of the referenced packages from a global packages folder.
+    ///
+    /// A project whose packages depend on the package, directly or transitively, uses it
+    /// transitively.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a `.nuspec` of the folder could not be parsed.
+    pub fn projects_using_package_in(
+        &self,
+        name: &str,
+        range: Option<&VersionRange>,
+        folder: &GlobalPackagesFolder,
+    ) -> Result<Vec<PackageUsage>, ParseError> {
+        let through_packages = |project: &Project| -> Result<Vec<String>, ParseError> {
+            Ok(folder
+                .project_transitive_dependencies(project)?
+                .into_iter()
+                .filter(|package| package.name().eq_ignore_ascii_case(name))
+                .map(|package| package.version().clone())
+                .collect())
+        };
+
+        self.package_usages(name, range, through_packages)
+    }
+
+    fn package_usages<T>(
+        &self,
+        name: &str,
+        range: Option<&VersionRange>,
+        through_packages: T,
+    ) -> Result<Vec<PackageUsage>, ParseError>
+    where
+        T: Fn(&Project) -> Result<Vec<String>, ParseError>,
+    {
+        let in_range = |version: &String| match range {
+            Some(range) => version
+                .parse()
+                .is_ok_and(|version| range.satisfies(&version)),
+            None => true,
+        };
+
+        let graph = DependencyGraph::from_workspace(self, false);
+        let index_of = |project: &Project| graph.find(&self.relative_path(project.path()));
+
+        // the versions each project brings by itself, directly or through its packages
+        let mut own: Vec<(Vec<String>, Vec<String>)> = vec![(vec![], vec![]); graph.nodes().len()];
+
+        for project in self.projects.iter() {
+            if let Some(index) = index_of(project) {
+                let direct = project
+                    .package_references()
+                    .iter()
+                    .filter(|package| package.name().eq_ignore_ascii_case(name))
+                    .map(|package| package.version().to_string())
+                    .collect();
+
+                own[index] = (direct, through_packages(project)?);
+            }
+        }
+
+        let mut usages = vec![];
+
+        for project in self.projects.iter() {
+            let Some(index) = index_of(project) else {
+                continue;
+            };
+
+            let (direct, through_packages) = &own[index];
+
+            if !direct.is_empty() {
+                for version in direct.iter().filter(|version| in_range(version)) {
+                    usages.push(PackageUsage::new(
+                        project.path().clone(),
+                        version.clone(),
+                        DependencyKind::Direct,
+                    ));
+                }
+
+                // NuGet uses the direct reference instead of the transitive versions
+                continue;
+            }
+
+            let mut versions: Vec<&String> = through_packages.iter().collect();
+            let mut visited = HashSet::from([index]);
+            let mut stack: Vec<usize> = graph.dependencies(index).collect();
+
+            while let Some(current) = stack.pop() {
+                if !visited.insert(current) {
+                    continue;
+                }
+
+                versions.extend(own[current].0.iter().chain(own[current].1.iter()));
+                stack.extend(graph.dependencies(current));
+            }
+
+            versions.sort();
+            versions.dedup();
+
+            for version in versions.into_iter().filter(|version| in_range(version)) {
+                usages.push(PackageUsage::new(
+                    project.path().clone(),
+                    version.clone(),
+                    DependencyKind::Transitive,
+                ));
+            }
+        }
+
+        usages.sort_by(|a, b| a.project.cmp(&b.project));
+
+        Ok(usages)
+    }
+
+    /// Serializes the workspace into a versioned, deterministic JSON snapshot.
+    ///
+    /// Project paths are stored relative to the root, with forward slashes, and the projects
+    /// and their references are sorted, so the same workspace always produces the same
+    /// snapshot regardless of where it is checked out. Snapshots can be stored as pipeline
+    /// artifacts and compared later with [`crate::diff::WorkspaceDiff`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the workspace could not be serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::{diff::WorkspaceDiff, workspace::Workspace};
+    ///
+    /// let stored = std::fs::read_to_string("dependencies.snapshot.json")?;
+    /// let previous = Workspace::from_snapshot(&stored)?;
+    /// let current = Workspace::load("path/to/repository")?;
+    ///
+    /// println!("{}", WorkspaceDiff::between(&previous, &current).to_markdown());
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_snapshot(&self) -> Result<String, serde_json::Error> {
+        let mut projects = (*self.projects).clone();
+
+        for project in &mut projects {
+            project.path = PathBuf::from(self.relative_path(&project.path));
+            project
+                .project_references
+                .sort_by(|a, b| a.path.cmp(&b.path));
+            project.package_references.sort_by(|a, b| {
+                (a.name.to_lowercase(), &a.version).cmp(&(b.name.to_lowercase(), &b.version))
+            });
+        }
+
+        projects.sort_by(|a, b| a.path.cmp(&b.path));
+
+        serde_json::to_string_pretty(&Snapshot {
+            version: SNAPSHOT_VERSION,
+            projects,
+        })
+    }
+
+    /// Restores a workspace from a snapshot created by [`Workspace::to_snapshot`].
+    ///
+    /// The workspace root is `.` and the project paths are relative to it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the snapshot is not valid JSON or was created
+    /// with an unsupported snapshot version.
+    #[cfg(feature = "serde")]
+    pub fn from_snapshot(snapshot: &str) -> Result<Self, SnapshotError> {
+        let value: serde_json::Value = serde_json::from_str(snapshot)?;
+
+        let version = value
+            .get("version")
+            .and_then(|version| version.as_u64())
+            .ok_or(SnapshotError::MissingVersion)?;
+
+        if version != u64::from(SNAPSHOT_VERSION) {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let snapshot: Snapshot = serde_json::from_value(value)?;
+
+        Ok(Self::from_projects(".", snapshot.projects))
+    }
+
+    /// Returns every package referenced by the workspace, grouped by name (ignoring case), with
+    /// the versions in use and the projects referencing each version.
+    ///
+    /// The packages are sorted by the number of projects referencing them, most used first, then
+    /// by name. The versions are sorted from the lowest to the highest.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::workspace::Workspace;
+    ///
+    /// let workspace = Workspace::load("path/to/repository")?;
+    ///
+    /// for package in workspace.package_usage_report() {
+    ///     println!("{} ({} projects)", package.name(), package.usage_count());
+    ///
+    ///     for version in package.versions() {
+    ///         println!("  {}: {:?}", version.version(), version.projects());
+    ///     }
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn package_usage_report(&self) -> Vec<PackageVersions> {
+        let mut packages: Vec<PackageVersions> = vec![];
+
+        for project in self.projects.iter() {
+            for reference in project.package_references() {
+                let index = match packages
+                    .iter()
+                    .position(|package| package.name.eq_ignore_ascii_case(reference.name()))
+                {
+                    Some(index) => index,
+                    None => {
+                        packages.push(PackageVersions {
+                            name: reference.name().to_string(),
+                            versions: vec![],
+                            usage_count: 0,
+                        });
+                        packages.len() - 1
+                    }
+                };
+
+                let versions = &mut packages[index].versions;
+
+                match versions
+                    .iter_mut()
+                    .find(|usage| &usage.version == reference.version())
+                {
+                    Some(usage) => usage.projects.push(project.path().clone()),
+                    None => versions.push(VersionUsage {
+                        version: reference.version().to_string(),
+                        projects: vec![project.path().clone()],
+                    }),
+                }
+            }
+        }
+
+        for package in &mut packages {
+            let projects: HashSet<&PathBuf> = package
+                .versions
+                .iter()
+                .flat_map(|usage| usage.projects.iter())
+                .collect();
+            package.usage_count = projects.len();
+
+            // a total order: the valid versions first, by precedence, then by how they are written
+            package.versions.sort_by_cached_key(|usage| {
+                let parsed = usage.version.parse::<NuGetVersion>().ok();
+
+                (parsed.is_none(), parsed, usage.version.clone())
+            });
+        }
+
+        packages.sort_by(|left, right| {
+            right
+                .usage_count
+                .cmp(&left.usage_count)
+                .then_with(|| left.name.to_lowercase().cmp(&right.name.to_lowercase()))
+        });
+
+        packages
+    }
+
+    /// Returns the assembly versions stamped by the projects, to check that they are consistent
+    /// across the workspace.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::workspace::Workspace;
+    ///
+    /// let report = Workspace::load("path/to/repository")?.version_stamping_report();
+    ///
+    /// if !report.is_consistent() {
+    ///     for (version, projects) in report.assembly_versions() {
+    ///         println!("{}: {:?}", version, projects);
+    ///     }
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn version_stamping_report(&self) -> VersionStamping {
+        VersionStamping::new(&self.projects)
+    }
+
+    /// Returns statistics about the projects and references of the workspace.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotnet_lens::workspace::Workspace;
+    ///
+    /// let stats = Workspace::load("path/to/repository")?.stats();
+    ///
+    /// println!("{} projects, {} packages", stats.projects(), stats.unique_packages());
+    ///
+    /// for (target_framework, count) in stats.by_target_framework() {
+    ///     println!("{}: {}", target_framework, count);
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn stats(&self) -> WorkspaceStats {
+        let mut stats = WorkspaceStats {
+            projects: self.projects.len(),
+            ..WorkspaceStats::default()
+        };
+
+        let mut packages = HashSet::new();
+        let mut package_versions = HashSet::new();
+
+        for project in self.projects.iter() {
+            *stats.by_language.entry(project.language()).or_default() += 1;
+
+            if let Some(target_framework) = project.target_framework() {
+                *stats
+                    .by_target_framework
+                    .entry(target_framework.clone())
+                    .or_default() += 1;
+            }
+
+            stats.project_references += project.project_references().len();
+            stats.package_references += project.package_references().len();
+
+            for package in project.package_references() {
+                packages.insert(package.name().to_lowercase());
+                package_versions.insert((package.name().to_lowercase(), package.version()));
+            }
+        }
+
+        stats.unique_packages = packages.len();
+        stats.unique_package_versions = package_versions.len();
+        stats.average_project_references = average(stats.project_references, stats.projects);
+        stats.average_package_references = average(stats.package_references, stats.projects);
+
+        stats
+    }
+
+    /// Returns the path of a project relative to the workspace root, with forward slashes.
+    ///
+    /// Paths outside of the root are returned as they are.
+    pub fn relative_path<P>(&self, path: P) -> String
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+
+        relative.to_string_lossy().replace('\\', "/")
+    }
+}
+
+/// Builds a [`Workspace`] from project contents declared in memory, so analysis code can be
+/// tested without writing a repository to disk.
+///
+/// The projects are parsed like the files of [`Workspace::load`], at paths relative to the
+/// root. The other files read while parsing (ex: `Directory.Packages.props`,
+/// `docker-compose.yml`) can be declared with [`WorkspaceBuilder::add_file`]. No file is read
+/// from or written to the disk.
+///
+/// # Examples
+///
+/// ```rust
+/// use dotnet_lens::workspace::WorkspaceBuilder;
+///
+/// let mut builder = WorkspaceBuilder::new("/repo");
+/// builder.add_project(
+///     "src/Api/Api.csproj",
+///     r#"<Project Sdk="Microsoft.NET.Sdk">
+///   <ItemGroup>
+///     <ProjectReference Include="..\Domain\Domain.csproj" />
+///   </ItemGroup>
+/// </Project>"#,
+/// );
+/// builder.add_project("src/Domain/Domain.csproj", "<Project />");
+///
+/// let workspace = builder.build()?;
+/// let api = workspace.project("Api").unwrap();
+///
+/// assert!(workspace
+///     .resolve_reference(api, &api.project_references()[0])
+///     .is_some());
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceBuilder {
+    root: PathBuf,
+    projects: Vec<PathBuf>,
+    files: MemoryFileSystem,
+}
+
+impl WorkspaceBuilder {
+    /// Creates a new `WorkspaceBuilder` for a workspace at `root`. The root does not need to
+    /// exist.
+    pub fn new<P>(root: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            projects: vec![],
+            files: MemoryFileSystem::new(),
+        }
+    }
+
+    /// Adds a project file with its content, at a path relative to the root (ex:
+    /// `src/Api/Api.csproj`). A project added twice at the same path is replaced.
+    pub fn add_project<P>(&mut self, path: P, content: &str)
+    where
+        P: AsRef<Path>,
+    {
+        let path = normalize_path(&self.root.join(path));
+
+        self.files.add_file(&path, content);
+
+        if !self.projects.contains(&path) {
+            self.projects.push(path);
+        }
+    }
+
+    /// Adds a file that is not a project but is read while parsing the projects (ex:
+    /// `Directory.Packages.props`, `nuget.config`), at a path relative to the root.
+    pub fn add_file<P>(&mut self, path: P, content: &str)
+    where
+        P: AsRef<Path>,
+    {
+        self.files.add_file(self.root.join(path), content);
+    }
+
+    /// Parses the projects and returns the workspace, with the projects sorted by path.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if one of the projects could not be parsed.
+    pub fn build(&self) -> Result<Workspace, WorkspaceError> {
+        let strings = Interner::new();
+        let mut projects = self
+            .projects
+            .iter()
+            .map(|path| {
+                self.files
+                    .read(path)
+                    .map_err(ParseError::from)
+                    .and_then(|content| {
+                        parser::parse_in(
+                            content.as_slice(),
+                            path,
+                            &Properties::new(),
+                            &self.files,
+                            &strings,
+                        )
+                    })
+                    .map_err(|source| WorkspaceError::ParseError {
+                        path: path.clone(),
+                        source,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        projects.sort_by(|a, b| a.path().cmp(b.path()));
+
+        Ok(Workspace::from_projects(&self.root, projects))
+    }
+}
+
+/// Represents a mapping redirecting the project references pointing under a directory to
+/// another directory, used to resolve the references between the roots of a merged workspace.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathMapping {
+    from: PathBuf,
+    to: PathBuf,
+}
+
+impl PathMapping {
+    /// Creates a new `PathMapping` redirecting the paths under `from` to `to`.
+    pub fn new<P, Q>(from: P, to: Q) -> Self
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        Self {
+            from: normalize_path(from.as_ref()),
+            to: normalize_path(to.as_ref()),
+        }
+    }
+
+    /// Returns the directory the references point to.
+    pub fn from(&self) -> &PathBuf {
+        &self.from
+    }
+
+    /// Returns the directory the references are redirected to.
+    pub fn to(&self) -> &PathBuf {
+        &self.to
+    }
+
+    /// Returns `path` redirected to the target directory, or `None` if it is not under the
+    /// source directory.
+    pub fn apply(&self, path: &Path) -> Option<PathBuf> {
+        normalize_path(path)
+            .strip_prefix(&self.from)
+            .ok()
+            .map(|relative| self.to.join(relative))
+    }
+}
+
+/// Callbacks notified while [`Workspace::load_with_observer`] loads a workspace, to collect
+/// custom data or apply custom policies without post-processing the workspace.
+///
+/// Every callback has a default implementation, so observers only implement the ones they need.
+/// The projects are discovered and parsed in path order, and the references are resolved once
+/// every project is parsed.
+pub trait WorkspaceObserver {
+    /// Called when a project file is found, before it is parsed. Returns `false` to leave the
+    /// project out of the workspace.
+    fn on_project_discovered(&mut self, path: &Path) -> bool {
+        let _ = path;
+        true
+    }
+
+    /// Called when a project is parsed, before it is added to the workspace.
+    fn on_project_parsed(&mut self, project: &Project) {
+        let _ = project;
+    }
+
+    /// Called for every project reference, with the project it resolves to, if any (see
+    /// [`Workspace::resolve_reference`]).
+    fn on_reference_resolved(
+        &mut self,
+        project: &Project,
+        reference: &ProjectReference,
+        target: Option<&Project>,
+    ) {
+        let _ = (project, reference, target);
+    }
+
+    /// Called when the projects could not be searched or a project could not be parsed. Returns
+    /// whether a project that could not be parsed stops the loading, which it does by default
+    /// like [`Workspace::load`]; search errors always stop it.
+    fn on_error(&mut self, error: &WorkspaceError) -> ErrorAction {
+        let _ = error;
+        ErrorAction::Abort
+    }
+}
+
+/// Represents what [`Workspace::load_with_observer`] does after an error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorAction {
+    /// Stops the loading and returns the error.
+    Abort,
+    /// Leaves the project out of the workspace and continues the loading.
+    Skip,
+}
+
+/// Represents what [`Workspace::load_with_policy`] does when a project could not be parsed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum LoadPolicy {
+    /// Stops the loading and returns the error.
+    #[default]
+    FailFast,
+    /// Leaves the project out of the workspace, collects the error and continues the loading.
+    CollectErrors,
+}
+
+/// Represents a workspace loaded by [`Workspace::load_with_policy`], with the projects that
+/// could not be parsed.
+#[derive(Debug)]
+pub struct LoadedWorkspace {
+    workspace: Workspace,
+    failures: Vec<WorkspaceError>,
+}
+
+impl LoadedWorkspace {
+    /// Returns the workspace with every project that was parsed.
+    pub fn workspace(&self) -> &Workspace {
+        &self.workspace
+    }
+
+    /// Returns the errors of the projects that could not be parsed, in path order.
+    pub fn failures(&self) -> &Vec<WorkspaceError> {
+        &self.failures
+    }
+
+    /// Returns `true` if every project was parsed.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the workspace, dropping the failures.
+    pub fn into_workspace(self) -> Workspace {
+        self.workspace
+    }
+}
+
+fn strip_suffix_ignore_case<'a>(value: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = value.len().checked_sub(suffix.len())?;
+
+    value
+        .get(start..)
+        .filter(|end| end.eq_ignore_ascii_case(suffix))
+        .map(|_| &value[..start])
+}
+
+/// Returns the deepest directory containing both paths.
+fn common_ancestor(left: &Path, right: &Path) -> PathBuf {
+    left.components()
+        .zip(right.components())
+        .take_while(|(left, right)| left == right)
+        .map(|(component, _)| component)
+        .collect()
+}
+
+/// Represents how a project depends on a package.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DependencyKind {
+    /// The project references the package.
+    Direct,
+    /// The project depends on the package through a referenced project or package.
+    Transitive,
+}
+
+/// Represents a project using a package, returned by [`Workspace::projects_using_package`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackageUsage {
+    project: PathBuf,
+    version: String,
+    kind: DependencyKind,
+}
+
+impl PackageUsage {
+    /// Creates a new `PackageUsage`.
+    pub fn new(project: PathBuf, version: String, kind: DependencyKind) -> Self {
+        Self {
+            project,
+            version,
+            kind,
+        }
+    }
+
+    /// Returns the path of the project.
+    pub fn project(&self) -> &PathBuf {
+        &self.project
+    }
+
+    /// Returns the version of the package used by the project.
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+
+    /// Returns whether the project references the package directly.
+    pub fn kind(&self) -> DependencyKind {
+        self.kind
+    }
+}
+
+/// Represents the versions of a package used in a workspace, returned by
+/// [`Workspace::package_usage_report`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackageVersions {
+    name: String,
+    versions: Vec<VersionUsage>,
+    usage_count: usize,
+}
+
+impl PackageVersions {
+    /// Returns the name of the package, as written in the first project referencing it.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the versions of the package in use.
+    pub fn versions(&self) -> &Vec<VersionUsage> {
+        &self.versions
+    }
+
+    /// Returns the number of projects referencing the package, in any version.
+    pub fn usage_count(&self) -> usize {
+        self.usage_count
+    }
+}
+
+/// Represents a version of a package and the projects referencing it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionUsage {
+    version: String,
+    projects: Vec<PathBuf>,
+}
+
+impl VersionUsage {
+    /// Returns the version of the package.
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+
+    /// Returns the paths of the projects referencing this version.
+    pub fn projects(&self) -> &Vec<PathBuf> {
+        &self.projects
+    }
+}
+
+/// Represents statistics about a workspace, returned by [`Workspace::stats`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkspaceStats {
+    projects: usize,
+    by_language: BTreeMap<ProjectLanguage, usize>,
+    by_target_framework: BTreeMap<String, usize>,
+    project_references: usize,
+    package_references: usize,
+    unique_packages: usize,
+    unique_package_versions: usize,
+    average_project_references: f64,
+    average_package_references: f64,
+}
+
+impl WorkspaceStats {
+    /// Returns the number of projects.
+    pub fn projects(&self) -> usize {
+        self.projects
+    }
+
+    /// Returns the number of projects of each language.
+    pub fn by_language(&self) -> &BTreeMap<ProjectLanguage, usize> {
+        &self.by_language
+    }
+
+    /// Returns the number of projects of each target framework (ex: `net8.0`). Projects without
+    /// a target framework are not counted.
+    pub fn by_target_framework(&self) -> &BTreeMap<String, usize> {
+        &self.by_target_framework
+    }
+
+    /// Returns the total number of project references.
+    pub fn project_references(&self) -> usize {
+        self.project_references
+    }
+
+    /// Returns the total number of package references.
+    pub fn package_references(&self) -> usize {
+        self.package_references
+    }
+
+    /// Returns the number of distinct packages referenced, ignoring case and versions.
+    pub fn unique_packages(&self) -> usize {
+        self.unique_packages
+    }
+
+    /// Returns the number of distinct package versions referenced.
+    pub fn unique_package_versions(&self) -> usize {
+        self.unique_package_versions
+    }
+
+    /// Returns the average number of project references per project.
+    pub fn average_project_references(&self) -> f64 {
+        self.average_project_references
+    }
+
+    /// Returns the average number of package references per project.
+    pub fn average_package_references(&self) -> f64 {
+        self.average_package_references
+    }
+}
+
+fn average(total: usize, count: usize) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        total as f64 / count as f64
+    }
+}
+
+/// The version of the snapshot format written by [`Workspace::to_snapshot`].
+#[cfg(feature = "serde")]
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Snapshot {
+    version: u32,
+    projects: Vec<Project>,
+}
+
+/// Normalizes a path lexically, removing `.` components and resolving `..` components.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                let ends_with_parent = matches!(
+                    normalized.components().next_back(),
+                    Some(Component::ParentDir)
+                );
+
+                if ends_with_parent || !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// Represents errors that can occur while loading a workspace.
+#[derive(Debug, Error)]
+pub enum WorkspaceError {
+    /// An I/O error occurred while searching for projects.
+    #[error("there was an error while searching for projects")]
+    SearchError(#[from] io::Error),
+    /// A project could not be parsed.
+    #[error("the project {path:?} could not be parsed")]
+    ParseError {
+        path: PathBuf,
+        #[source]
+        source: ParseError,
+    },
+}
+
+/// Represents errors that can occur while restoring a workspace from a snapshot.
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    /// The snapshot is not valid JSON or does not have the expected shape.
+    #[error("the snapshot could not be deserialized")]
+    JsonError(#[from] serde_json::Error),
+    /// The snapshot does not have a version.
+    #[error("the snapshot does not have a version")]
+    MissingVersion,
+    /// The snapshot was created with a version this library cannot read.
+    #[error("the snapshot version {0} is not supported")]
+    UnsupportedVersion(u64),
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+        thread,
+    };
+
+    use crate::{
+        export::Clusters,
+        graph::{test::project, DependencyGraph},
+        solution::Solution,
+    };
+
+    use crate::ProjectLanguage;
+
+    use super::{
+        normalize_path, DependencyKind, LoadPolicy, PackageUsage, PathMapping, Workspace,
+        WorkspaceBuilder, WorkspaceError,
+    };
+
+    #[test]
+    pub fn affected_by_changed_files() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/src/Api/Api.csproj",
+                    &["../Domain/Domain.csproj"],
+                    &[],
+                ),
+                project("/repo/src/Domain/Domain.csproj", &[], &[]),
+                project("/repo/tools/Cli/Cli.csproj", &[], &[]),
+            ],
+        );
+
+        // when
+        let from_source = workspace.affected_by(&[PathBuf::from("src/Domain/Models/Order.cs")]);
+        let from_props =
+            workspace.affected_by(&[PathBuf::from("/repo/tools/Directory.Build.props")]);
+        let from_readme = workspace.affected_by(&[PathBuf::from("README.md")]);
+
+        // then
+        let names = |projects: Vec<&crate::Project>| -> Vec<String> {
+            projects
+                .iter()
+                .map(|project| project.name().clone())
+                .collect()
+        };
+
+        assert_eq!(names(from_source), vec!["Api", "Domain"]);
+        assert_eq!(names(from_props), vec!["Cli"]);
+        assert!(from_readme.is_empty());
+    }
+
+    #[test]
+    pub fn slice_workspace_by_solution() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/payments/Api/Api.csproj",
+                    &[
+                        "../Domain/Domain.csproj",
+                        "../../shared/Common/Common.csproj",
+                    ],
+                    &[],
+                ),
+                project("/repo/payments/Domain/Domain.csproj", &[], &[]),
+                project("/repo/shared/Common/Common.csproj", &[], &[]),
+                project("/repo/shipping/Api/Api.csproj", &[], &[]),
+            ],
+        );
+        let solution = Solution::parse(
+            r#"
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Api", "Api\Api.csproj", "{C0000000-0000-0000-0000-000000000001}"
+EndProject
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Domain", "Domain\Domain.csproj", "{C0000000-0000-0000-0000-000000000002}"
+EndProject
+"#
+            .as_bytes(),
+            "/repo/payments/Payments.sln",
+        )
+        .unwrap();
+
+        // when
+        let slice = workspace.solution_slice(&solution);
+        let graph = DependencyGraph::from_workspace(&slice, false);
+
+        // then
+        let paths: Vec<String> = slice
+            .projects()
+            .iter()
+            .map(|project| slice.relative_path(project.path()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["payments/Api/Api.csproj", "payments/Domain/Domain.csproj"]
+        );
+        assert_eq!(graph.edges().len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn snapshot_round_trip() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/src/Domain/Domain.csproj",
+                    &[],
+                    &[("Serilog", "3.1.1"), ("Dapper", "2.1.35")],
+                ),
+                project(
+                    "/repo/src/Api/Api.csproj",
+                    &["../Domain/Domain.csproj"],
+                    &[],
+                ),
+            ],
+        );
+        let moved = Workspace::from_projects(
+            "/elsewhere",
+            workspace
+                .projects()
+                .iter()
+                .rev()
+                .map(|p| {
+                    let mut p = p.clone();
+                    p.path = PathBuf::from("/elsewhere").join(workspace.relative_path(&p.path));
+                    p
+                })
+                .collect(),
+        );
+
+        // when
+        let snapshot = workspace.to_snapshot().unwrap();
+        let restored = Workspace::from_snapshot(&snapshot).unwrap();
+
+        // then
+        assert_eq!(snapshot, moved.to_snapshot().unwrap());
+        assert_eq!(
+            restored.projects()[0].path(),
+            Path::new("src/Api/Api.csproj")
+        );
+        assert_eq!(
+            restored.projects()[1].package_references()[0].name(),
+            "Dapper"
+        );
+        assert!(crate::diff::WorkspaceDiff::between(&workspace, &restored).is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn reject_unsupported_snapshot_version() {
+        let result = Workspace::from_snapshot(r#"{"version":99,"projects":[]}"#);
+
+        assert!(matches!(
+            result,
+            Err(super::SnapshotError::UnsupportedVersion(99))
+        ));
+    }
+
+    #[test]
+    pub fn projects_using_package_directly_and_transitively() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/src/Api/Api.csproj",
+                    &["../Domain/Domain.csproj"],
+                    &[],
+                ),
+                project(
+                    "/repo/src/Domain/Domain.csproj",
+                    &[],
+                    &[("Serilog", "3.1.1")],
+                ),
+                project(
+                    "/repo/src/Worker/Worker.csproj",
+                    &[],
+                    &[("serilog", "2.12.0")],
+                ),
+            ],
+        );
+        let range = "[3.0,4.0)".parse().unwrap();
+
+        // when
+        let all = workspace.projects_using_package("Serilog", None);
+        let in_range = workspace.projects_using_package("Serilog", Some(&range));
+
+        // then
+        assert_eq!(
+            all,
+            vec![
+                PackageUsage::new(
+                    PathBuf::from("/repo/src/Api/Api.csproj"),
+                    "3.1.1".to_string(),
+                    DependencyKind::Transitive
+                ),
+                PackageUsage::new(
+                    PathBuf::from("/repo/src/Domain/Domain.csproj"),
+                    "3.1.1".to_string(),
+                    DependencyKind::Direct
+                ),
+                PackageUsage::new(
+                    PathBuf::from("/repo/src/Worker/Worker.csproj"),
+                    "2.12.0".to_string(),
+                    DependencyKind::Direct
+                ),
+            ]
+        );
+        assert_eq!(in_range.len(), 2);
+    }
+
+    #[test]
+    pub fn index_projects_by_assembly_name() {
+        // given
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project(
+            "src/Api/Api.csproj",
+            "<Project><PropertyGroup><AssemblyName>Contoso.Api</AssemblyName></PropertyGroup></Project>",
+        );
+        builder.add_project("src/Core/Core.csproj", "<Project />");
+        builder.add_project(
+            "tools/Core/Core.csproj",
+            "<Project><PropertyGroup><TargetFramework>net8.0</TargetFramework></PropertyGroup></Project>",
+        );
+
+        let workspace = builder.build().unwrap();
+
+        // when
+        let assemblies = workspace.assemblies();
+
+        // then
+        assert_eq!(
+            assemblies.keys().collect::<Vec<_>>(),
+            vec![&"contoso.api", &"core"]
+        );
+        assert_eq!(assemblies["core"].len(), 2);
+
+        assert_eq!(
+            workspace
+                .project_by_assembly("CONTOSO.API.DLL")
+                .unwrap()
+                .name(),
+            "Api"
+        );
+        assert_eq!(
+            workspace.project_by_assembly("Core.exe").unwrap().path(),
+            &PathBuf::from("/repo/src/Core/Core.csproj")
+        );
+        assert!(workspace.project_by_assembly("Api").is_none());
+
+        // the index is rebuilt when the projects change
+        let mut workspace = workspace;
+        workspace.projects_mut().pop();
+
+        assert_eq!(workspace.assemblies()["core"].len(), 1);
+    }
+
+    #[test]
+    pub fn group_projects_by_tag() {
+        // given
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project(
+            "src/Api/Api.csproj",
+            "<Project><PropertyGroup><ProjectTags>payments;backend</ProjectTags></PropertyGroup></Project>",
+        );
+        builder.add_project(
+            "src/Web/Web.csproj",
+            "<Project><PropertyGroup><ProjectTags>Payments</ProjectTags></PropertyGroup></Project>",
+        );
+        builder.add_project("src/Core/Core.csproj", "<Project />");
+
+        let workspace = builder.build().unwrap();
+
+        // when
+        let tags = workspace.tags();
+        let graph = DependencyGraph::from_workspace(&workspace, false);
+        let clusters = Clusters::by_tag(&graph, &workspace);
+
+        // then
+        let names = |projects: &Vec<&crate::Project>| -> Vec<String> {
+            projects.iter().map(|p| p.name().clone()).collect()
+        };
+
+        assert_eq!(
+            tags.keys().collect::<Vec<_>>(),
+            vec![&"backend", &"payments"]
+        );
+        assert_eq!(names(&tags["payments"]), vec!["Api", "Web"]);
+
+        let clustered: Vec<Option<&String>> = (0..graph.nodes().len())
+            .map(|index| clusters.cluster(index))
+            .collect();
+        assert_eq!(
+            clustered,
+            vec![
+                Some(&"payments".to_string()),
+                None,
+                Some(&"Payments".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    pub fn build_workspace_in_memory() {
+        // given
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project("src/Web/Web.fsproj", "<Project />");
+        builder.add_project(
+            "src/Api/Api.csproj",
+            r#"<Project><ItemGroup><PackageReference Include="Serilog" Version="3.1.1" /></ItemGroup></Project>"#,
+        );
+
+        // when
+        let workspace = builder.build().unwrap();
+
+        builder.add_project("src/Api/Api.csproj", "<Project>");
+        let broken = builder.build();
+
+        // then
+        let names: Vec<&String> = workspace.projects().iter().map(|p| p.name()).collect();
+
+        assert_eq!(names, vec!["Api", "Web"]);
+        assert_eq!(workspace.projects()[1].language(), ProjectLanguage::FSharp);
+        assert_eq!(
+            workspace.relative_path(workspace.projects()[0].path()),
+            "src/Api/Api.csproj"
+        );
+        assert!(matches!(broken, Err(WorkspaceError::ParseError { .. })));
+    }
+
+    #[test]
+    pub fn build_workspace_with_declared_files() {
+        // given
+        let mut builder = WorkspaceBuilder::new("/repo");
+        builder.add_project("src/Api/Api.csproj", "<Project />");
+        builder.add_project(
+            "docker-compose.dcproj",
+            r#"<Project Sdk="Microsoft.Docker.Sdk" />"#,
+        );
+        builder.add_file(
+            "docker-compose.yml",
+            "services:\n  api:\n    build:\n      context: .\n      dockerfile: src/Api/Dockerfile\n",
+        );
+
+        // when
+        let workspace = builder.build().unwrap();
+
+        // then
+        let compose = workspace.project("docker-compose").unwrap();
+
+        assert_eq!(
+            compose.project_references()[0].path(),
+            &PathBuf::from("src/Api/Api.csproj")
+        );
+        assert!(workspace
+            .resolve_reference(compose, &compose.project_references()[0])
+            .is_some());
+    }
+
+    #[test]
+    pub fn merge_roots_with_path_mappings() {
+        // given
+        let mut workspace = Workspace::from_projects(
+            "/repos/shop",
+            vec![project(
+                "/repos/shop/src/Shop/Shop.csproj",
+                &["../../external/platform/src/Core/Core.csproj"],
+                &[],
+            )],
+        );
+        let platform = Workspace::from_projects(
+            "/repos/platform",
+            vec![project("/repos/platform/src/Core/Core.csproj", &[], &[])],
+        );
+        let archive = Workspace::from_projects(
+            "/repos/archive",
+            vec![project("/repos/archive/Core/Core.csproj", &[], &[])],
+        );
+
+        // when
+        workspace.merge(platform);
+        workspace.merge(archive);
+        workspace.add_path_mapping(PathMapping::new(
+            "/repos/shop/external/platform",
+            "/repos/platform",
+        ));
+
+        // then
+        let shop = workspace.project("Shop").unwrap();
+        let core = workspace
+            .resolve_reference(shop, &shop.project_references()[0])
+            .unwrap();
+
+        assert_eq!(workspace.root(), &PathBuf::from("/repos"));
+        assert_eq!(
+            workspace.relative_path(core.path()),
+            "platform/src/Core/Core.csproj"
+        );
+        assert_eq!(workspace.projects().len(), 3);
+    }
+
+    #[test]
+    pub fn report_package_usage() {
+        // given
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project("/repo/A/A.csproj", &[], &[("Serilog", "3.1.1")]),
+                project(
+                    "/repo/B/B.csproj",
+                    &[],
+                    &[("serilog", "2.12.0"), ("Dapper", "2.1.35")],
+                ),
+                project("/repo/C/C.csproj", &[], &[("Serilog", "3.1.1")]),
+                project("/repo/D/D.csproj", &[], &[("Serilog", "$(SerilogVersion)")]),
+                project("/repo/E/E.csproj", &[], &[("Serilog", "10.0.0")]),
+            ],
+        );
+
+        // when
+        let report = workspace.package_usage_report();
+
+        // then
+        let names: Vec<&String> = report.iter().map(|package| package.name()).collect();
+        let versions: Vec<&String> = report[0]
+            .versions()
+            .iter()
+            .map(|usage| usage.version())
+            .collect();
+
+        assert_eq!(names, vec!["Serilog", "Dapper"]);
+        assert_eq!(report[0].usage_count(), 5);
+        assert_eq!(
+            versions,
+            vec!["2.12.0", "3.1.1", "10.0.0", "$(SerilogVersion)"]
+        );
+        assert_eq!(
+            report[0].versions()[1].projects(),
+            &vec![
+                PathBuf::from("/repo/A/A.csproj"),
+                PathBuf::from("/repo/C/C.csproj")
+            ]
+        );
+    }
+
+    #[test]
+    pub fn compute_workspace_stats() {
+        // given
+        let mut tests = project(
+            "/repo/tests/Api.Tests/Api.Tests.csproj",
+            &["../../src/Api/Api.csproj"],
+            &[("xunit", "2.9.0"), ("serilog", "3.1.1")],
+        );
+        tests.target_framework = None;
+
+        let workspace = Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/src/Api/Api.csproj",
+                    &[],
+                    &[("Serilog", "3.1.1"), ("Dapper", "2.1.35")],
+                ),
+                tests,
+            ],
+        );
+
+        // when
+        let stats = workspace.stats();
+
+        // then
+        assert_eq!(stats.projects(), 2);
+        assert_eq!(stats.by_language().get(&ProjectLanguage::CSharp), Some(&2));
+        assert_eq!(stats.by_target_framework().get("net8.0"), Some(&1));
+        assert_eq!(stats.package_references(), 4);
+        assert_eq!(stats.unique_packages(), 3);
+        assert_eq!(stats.unique_package_versions(), 3);
+        assert_eq!(stats.average_project_references(), 0.5);
+        assert_eq!(stats.average_package_references(), 2.0);
+    }
+
+    #[test]
+    pub fn normalize_parent_components() {
+        assert_eq!(
+            normalize_path(Path::new("src/Api/../Domain/./Domain.csproj")),
+            PathBuf::from("src/Domain/Domain.csproj")
+        );
+        assert_eq!(
+            normalize_path(Path::new("../Domain/Domain.csproj")),
+            PathBuf::from("../Domain/Domain.csproj")
+        );
+    }
+
+    #[test]
+    pub fn share_workspace_between_threads() {
+        // given
+        let workspace = Arc::new(Workspace::from_projects(
+            "/repo",
+            vec![
+                project(
+                    "/repo/src/Api/Api.csproj",
+                    &["../Domain/Domain.csproj"],
+                    &[],
+                ),
+                project("/repo/src/Domain/Domain.csproj", &[], &[]),
+            ],
+        ));
+
+        // when
+        let found: Vec<bool> = ["api", "domain", "web"]
+            .into_iter()
+            .map(|name| {
+                let workspace = Arc::clone(&workspace);
+                thread::spawn(move || workspace.project(name).is_some())
+            })
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        let mut snapshot = (*workspace).clone();
+        snapshot
+            .projects_mut()
+            .push(project("/repo/src/Web/Web.csproj", &[], &[]));
+
+        // then
+        assert_eq!(found, vec![true, true, false]);
+        assert_eq!(workspace.projects().len(), 2);
+        assert_eq!(snapshot.projects().len(), 3);
+    }
+
+    #[test]
+    pub fn refresh_other_projects_after_a_parse_error() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        std::fs::write(root.join("Api.csproj"), "<Project></Project>").unwrap();
+        std::fs::write(root.join("Jobs.csproj"), "<Project></Project>").unwrap();
+
+        let mut workspace = Workspace::load(root).unwrap();
+
+        std::fs::write(root.join("Api.csproj"), "<Project>").unwrap();
+        std::fs::write(
+            root.join("Jobs.csproj"),
+            "<Project><PropertyGroup><TargetFramework>net8.0</TargetFramework></PropertyGroup></Project>",
+        )
+        .unwrap();
+
+        // when
+        let (changed, failures) = workspace
+            .refresh_with_policy(
+                &[root.join("Api.csproj"), root.join("Jobs.csproj")],
+                LoadPolicy::CollectErrors,
+            )
+            .unwrap();
+
+        // then
+        assert_eq!(changed, vec![root.join("Jobs.csproj")]);
+        assert_eq!(failures.len(), 1);
+        assert!(workspace.project("Api").is_some());
+        assert_eq!(
+            workspace.project("Jobs").unwrap().target_framework(),
+            Some(&"net8.0".to_string())
+        );
+
+        dir.close().unwrap();
+    }
+}
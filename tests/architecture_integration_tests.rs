@@ -0,0 +1,47 @@
+use std::fs;
+
+use tempfile::tempdir;
+
+extern crate dotnet_lens;
+
+use dotnet_lens::{
+    architecture::{assert_no_cycles, assert_no_dependency, assert_rule, ArchitectureRule},
+    workspace::Workspace,
+};
+
+#[test]
+fn test_architecture_assertions() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::create_dir_all(dir_path.join("src/Shop.Web")).unwrap();
+    fs::create_dir_all(dir_path.join("src/Shop.Domain")).unwrap();
+    fs::write(
+        dir_path.join("src/Shop.Web/Shop.Web.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="..\Shop.Domain\Shop.Domain.csproj" />
+  </ItemGroup>
+</Project>"#,
+    )
+    .unwrap();
+    fs::write(
+        dir_path.join("src/Shop.Domain/Shop.Domain.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk" />"#,
+    )
+    .unwrap();
+
+    // when
+    let workspace = Workspace::load(dir_path).unwrap();
+
+    // then
+    let mut rule = ArchitectureRule::new();
+    rule.add_layers(&["*.Web", "*.Domain"]);
+
+    assert_no_dependency(&workspace, "*.Domain", "*.Web");
+    assert_no_cycles(&workspace);
+    assert_rule(&workspace, &rule);
+
+    dir.close().unwrap();
+}
@@ -0,0 +1,383 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use tempfile::tempdir;
+
+extern crate dotnet_lens;
+
+use dotnet_lens::{
+    condition::Properties,
+    graph::DependencyGraph,
+    workspace::{ErrorAction, LoadPolicy, Workspace, WorkspaceError, WorkspaceObserver},
+    Project, ProjectReference,
+};
+
+#[test]
+fn test_load_workspace_and_resolve_references() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::create_dir_all(dir_path.join("src/Api")).unwrap();
+    fs::create_dir_all(dir_path.join("src/Domain")).unwrap();
+    fs::write(
+        dir_path.join("src/Api/Api.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="..\Domain\Domain.csproj" />
+  </ItemGroup>
+</Project>"#,
+    )
+    .unwrap();
+    fs::write(
+        dir_path.join("src/Domain/Domain.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+    )
+    .unwrap();
+
+    // when
+    let workspace = Workspace::load(dir_path).unwrap();
+
+    // then
+    let names: Vec<&String> = workspace.projects().iter().map(|p| p.name()).collect();
+    assert_eq!(names, vec!["Api", "Domain"]);
+
+    let api = workspace.project("api").unwrap();
+    let domain = workspace
+        .resolve_reference(api, &api.project_references()[0])
+        .unwrap();
+    assert_eq!(
+        workspace.relative_path(domain.path()),
+        "src/Domain/Domain.csproj"
+    );
+
+    let graph = DependencyGraph::from_workspace(&workspace, false);
+    assert_eq!(graph.edges().len(), 1);
+
+    dir.close().unwrap();
+}
+
+#[test]
+fn test_load_workspace_with_central_package_management() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::create_dir_all(dir_path.join("src/Api")).unwrap();
+    fs::write(
+        dir_path.join("Directory.Packages.props"),
+        r#"<Project>
+  <PropertyGroup>
+    <ManagePackageVersionsCentrally>true</ManagePackageVersionsCentrally>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageVersion Include="Serilog" Version="3.1.1" />
+    <PackageVersion Include="Dapper" Version="2.1.35" />
+  </ItemGroup>
+</Project>"#,
+    )
+    .unwrap();
+    fs::write(
+        dir_path.join("src/Api/Api.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="serilog" />
+    <PackageReference Include="Dapper" VersionOverride="2.0.151" />
+    <PackageReference Include="Polly" />
+  </ItemGroup>
+</Project>"#,
+    )
+    .unwrap();
+
+    // when
+    let workspace = Workspace::load(dir_path).unwrap();
+
+    // then
+    let versions: Vec<(&String, &String)> = workspace.projects()[0]
+        .package_references()
+        .iter()
+        .map(|reference| (reference.name(), reference.version()))
+        .collect();
+
+    assert_eq!(
+        versions,
+        vec![
+            (&"serilog".to_string(), &"3.1.1".to_string()),
+            (&"Dapper".to_string(), &"2.0.151".to_string()),
+            (&"Polly".to_string(), &String::new()),
+        ]
+    );
+
+    dir.close().unwrap();
+}
+
+#[test]
+fn test_load_workspace_with_invalid_project() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::write(dir_path.join("Broken.csproj"), "<Project>").unwrap();
+
+    // when
+    let result = Workspace::load(dir_path);
+
+    // then
+    assert!(result.is_err());
+
+    dir.close().unwrap();
+}
+
+#[test]
+fn test_refresh_changed_projects() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::create_dir_all(dir_path.join("src/Api")).unwrap();
+    fs::write(dir_path.join("src/Api/Api.csproj"), "<Project></Project>").unwrap();
+
+    let mut workspace = Workspace::load(dir_path).unwrap();
+
+    fs::write(
+        dir_path.join("src/Api/Api.csproj"),
+        r#"<Project>
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+    )
+    .unwrap();
+    fs::write(dir_path.join("Tools.csproj"), "<Project></Project>").unwrap();
+    fs::write(dir_path.join("src/Api/Program.cs"), "").unwrap();
+
+    // when
+    let changed = workspace
+        .refresh(&[
+            dir_path.join("src/Api/Api.csproj"),
+            dir_path.join("Tools.csproj"),
+            dir_path.join("src/Api/Program.cs"),
+        ])
+        .unwrap();
+
+    // then
+    assert_eq!(changed.len(), 2);
+    assert_eq!(workspace.projects().len(), 2);
+    assert_eq!(
+        workspace.project("Api").unwrap().target_framework(),
+        Some(&"net8.0".to_string())
+    );
+
+    fs::remove_file(dir_path.join("Tools.csproj")).unwrap();
+
+    let changed = workspace.refresh(&[dir_path.join("Tools.csproj")]).unwrap();
+
+    assert_eq!(changed, vec![dir_path.join("Tools.csproj")]);
+    assert!(workspace.project("Tools").is_none());
+
+    dir.close().unwrap();
+}
+
+#[test]
+fn test_refresh_with_load_properties() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::write(dir_path.join("Api.csproj"), "<Project></Project>").unwrap();
+
+    let mut properties = Properties::new();
+    properties.set("Configuration", "Release");
+
+    let mut workspace = Workspace::load_with_properties(dir_path, &properties).unwrap();
+
+    fs::write(
+        dir_path.join("Api.csproj"),
+        r#"<Project>
+  <ItemGroup Condition="'$(Configuration)' == 'Release'">
+    <PackageReference Include="Serilog" Version="3.1.1" />
+  </ItemGroup>
+</Project>"#,
+    )
+    .unwrap();
+
+    // when
+    workspace.refresh(&[dir_path.join("Api.csproj")]).unwrap();
+
+    // then
+    let api = workspace.project("Api").unwrap();
+    assert_eq!(api.package_references().len(), 1);
+
+    dir.close().unwrap();
+}
+
+#[test]
+fn test_stream_continues_after_invalid_project() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::write(dir_path.join("A.csproj"), "<Project>").unwrap();
+    fs::write(dir_path.join("B.csproj"), "<Project></Project>").unwrap();
+
+    // when
+    let results: Vec<_> = Workspace::stream(dir_path).unwrap().collect();
+
+    // then
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_err());
+    assert_eq!(results[1].as_ref().unwrap().name(), "B");
+
+    dir.close().unwrap();
+}
+
+#[test]
+fn test_load_workspace_with_policy() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::write(dir_path.join("A.csproj"), "<Project>").unwrap();
+    fs::write(dir_path.join("B.csproj"), "<Project></Project>").unwrap();
+    fs::write(dir_path.join("C.csproj"), "<Project").unwrap();
+
+    // when
+    let fail_fast = Workspace::load_with_policy(dir_path, LoadPolicy::FailFast);
+    let loaded = Workspace::load_with_policy(dir_path, LoadPolicy::CollectErrors).unwrap();
+
+    // then
+    assert!(fail_fast.is_err());
+    assert!(!loaded.is_complete());
+    assert_eq!(loaded.workspace().projects().len(), 1);
+    assert_eq!(loaded.workspace().projects()[0].name(), "B");
+
+    let failed: Vec<PathBuf> = loaded
+        .failures()
+        .iter()
+        .filter_map(|failure| match failure {
+            WorkspaceError::ParseError { path, .. } => Some(path.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        failed,
+        vec![dir_path.join("A.csproj"), dir_path.join("C.csproj")]
+    );
+
+    dir.close().unwrap();
+}
+
+#[derive(Default)]
+struct RecordingObserver {
+    parsed: Vec<String>,
+    unresolved: Vec<String>,
+    errors: usize,
+}
+
+impl WorkspaceObserver for RecordingObserver {
+    fn on_project_discovered(&mut self, path: &Path) -> bool {
+        !path.to_string_lossy().contains("samples")
+    }
+
+    fn on_project_parsed(&mut self, project: &Project) {
+        self.parsed.push(project.name().clone());
+    }
+
+    fn on_reference_resolved(
+        &mut self,
+        project: &Project,
+        reference: &ProjectReference,
+        target: Option<&Project>,
+    ) {
+        if target.is_none() {
+            self.unresolved
+                .push(format!("{} -> {}", project.name(), reference.name()));
+        }
+    }
+
+    fn on_error(&mut self, _: &WorkspaceError) -> ErrorAction {
+        self.errors += 1;
+        ErrorAction::Skip
+    }
+}
+
+#[test]
+fn test_load_workspace_with_observer() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::create_dir_all(dir_path.join("samples")).unwrap();
+    fs::write(
+        dir_path.join("Api.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="Domain.csproj" />
+    <ProjectReference Include="Missing.csproj" />
+  </ItemGroup>
+</Project>"#,
+    )
+    .unwrap();
+    fs::write(dir_path.join("Domain.csproj"), "<Project></Project>").unwrap();
+    fs::write(dir_path.join("Broken.csproj"), "<Project>").unwrap();
+    fs::write(dir_path.join("samples/Sample.csproj"), "<Project>").unwrap();
+
+    let mut observer = RecordingObserver::default();
+
+    // when
+    let workspace = Workspace::load_with_observer(dir_path, &mut observer).unwrap();
+
+    // then
+    let paths: Vec<&PathBuf> = workspace.projects().iter().map(|p| p.path()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            &dir_path.join("Api.csproj"),
+            &dir_path.join("Domain.csproj")
+        ]
+    );
+    assert_eq!(observer.parsed, vec!["Api", "Domain"]);
+    assert_eq!(observer.unresolved, vec!["Api -> Missing"]);
+    assert_eq!(observer.errors, 1);
+
+    dir.close().unwrap();
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_load_mapped_workspace() {
+    // given
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+
+    fs::write(
+        dir_path.join("Api.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="3.1.1" />
+  </ItemGroup>
+</Project>"#,
+    )
+    .unwrap();
+    fs::write(dir_path.join("Empty.csproj"), "").unwrap();
+
+    // when
+    // SAFETY: the project files are only written between the loads
+    let mapped = unsafe { Workspace::load_mapped(dir_path) };
+
+    fs::write(dir_path.join("Empty.csproj"), "<Project></Project>").unwrap();
+    let buffered = Workspace::load(dir_path).unwrap();
+    let mapped_again = unsafe { Workspace::load_mapped(dir_path) }.unwrap();
+
+    // then
+    assert!(mapped.is_err());
+    assert_eq!(mapped_again, buffered);
+
+    dir.close().unwrap();
+}